@@ -0,0 +1,96 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use faest::*;
+use rand::SeedableRng;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Allocator counting the total number of allocated bytes
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const MAX_POOLED_BYTES: usize = 4 << 20;
+
+/// Bytes allocated by `f`
+fn allocated_bytes(f: impl FnOnce()) -> usize {
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    f();
+    ALLOCATED.load(Ordering::Relaxed) - allocated
+}
+
+macro_rules! benchmark {
+    ($c:expr, $pool:expr, $sk:ty, $name:expr) => {{
+        let mut rng = rand_chacha::ChaCha8Rng::from_seed([0; 32]);
+        let sk = <$sk>::generate(&mut rng);
+        let pool = $pool;
+        let sign = |options: SignOptions<'_>| {
+            black_box(sk.sign_with_options(b"message", options).unwrap());
+        };
+
+        // warm up the pool
+        sign(SignOptions::new().buffer_pool(pool));
+        println!(
+            "{}: {} bytes allocated per signature, {} bytes with buffer pool",
+            $name,
+            allocated_bytes(|| sign(SignOptions::new())),
+            allocated_bytes(|| sign(SignOptions::new().buffer_pool(pool)))
+        );
+
+        let vk = sk.verifying_key();
+        let signature = sk.sign(b"message");
+        let verify = |options: VerifyOptions<'_>| {
+            vk.verify_with_options(black_box(b"message"), &signature, options)
+                .unwrap();
+        };
+
+        // warm up the pool
+        verify(VerifyOptions::new().buffer_pool(pool));
+        println!(
+            "{}: {} bytes allocated per verification, {} bytes with buffer pool",
+            $name,
+            allocated_bytes(|| verify(VerifyOptions::new())),
+            allocated_bytes(|| verify(VerifyOptions::new().buffer_pool(pool)))
+        );
+
+        let mut c = $c.benchmark_group($name);
+        c.bench_function("sign", |b| b.iter(|| sign(SignOptions::new())));
+        c.bench_function("sign with buffer pool", |b| {
+            b.iter(|| sign(SignOptions::new().buffer_pool(pool)))
+        });
+        c.bench_function("verify", |b| b.iter(|| verify(VerifyOptions::new())));
+        c.bench_function("verify with buffer pool", |b| {
+            b.iter(|| verify(VerifyOptions::new().buffer_pool(pool)))
+        });
+    }};
+}
+
+fn buffer_pool_benchmark(c: &mut Criterion) {
+    // one pool shared by all parameter sets
+    let pool = &BufferPool::new(MAX_POOLED_BYTES);
+    benchmark!(c, pool, FAEST128fSigningKey, "FAEST-128f");
+    benchmark!(c, pool, FAEST128sSigningKey, "FAEST-128s");
+    benchmark!(c, pool, FAEST192fSigningKey, "FAEST-192f");
+    benchmark!(c, pool, FAEST256fSigningKey, "FAEST-256f");
+    benchmark!(c, pool, FAESTEM128fSigningKey, "FAEST-EM-128f");
+    benchmark!(c, pool, FAESTEM256fSigningKey, "FAEST-EM-256f");
+}
+
+criterion_group!(benches, buffer_pool_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use faest::*;
+use rand::SeedableRng;
+
+const BATCH_SIZES: [usize; 2] = [64, 1024];
+
+macro_rules! benchmark {
+    ($c:expr, $kp:ty, $name:expr) => {{
+        let mut rng = rand_chacha::ChaCha8Rng::from_seed([0; 32]);
+        let mut c = $c.benchmark_group($name);
+
+        for n in BATCH_SIZES {
+            c.throughput(Throughput::Elements(n as u64));
+            c.bench_with_input(BenchmarkId::new("serial", n), &n, |b, &n| {
+                b.iter(|| {
+                    black_box(
+                        (0..n)
+                            .map(|_| <$kp>::generate(&mut rng))
+                            .collect::<Vec<_>>(),
+                    )
+                })
+            });
+            c.bench_with_input(BenchmarkId::new("batch", n), &n, |b, &n| {
+                b.iter(|| black_box(<$kp>::generate_batch(n, &mut rng)))
+            });
+        }
+    }};
+}
+
+fn keygen_batch_benchmark(c: &mut Criterion) {
+    benchmark!(c, FAEST128fSigningKey, "FAEST-128f");
+    benchmark!(c, FAEST192fSigningKey, "FAEST-192f");
+    benchmark!(c, FAEST256fSigningKey, "FAEST-256f");
+    benchmark!(c, FAESTEM128fSigningKey, "FAEST-EM-128f");
+    benchmark!(c, FAESTEM192fSigningKey, "FAEST-EM-192f");
+    benchmark!(c, FAESTEM256fSigningKey, "FAEST-EM-256f");
+}
+
+criterion_group!(benches, keygen_batch_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use faest::owf::{self, Aes128, Input, Key, Output};
+use generic_array::GenericArray;
+use rand::{RngCore, SeedableRng};
+
+const INPUTS: usize = 32;
+
+fn owf_benchmark(c: &mut Criterion) {
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed([0; 32]);
+    let mut c = c.benchmark_group("OWF AES-128");
+
+    let mut key = Key::<Aes128>::default();
+    rng.fill_bytes(&mut key);
+    let inputs: Vec<Input<Aes128>> = (0..INPUTS)
+        .map(|_| {
+            let mut input = GenericArray::default();
+            rng.fill_bytes(&mut input);
+            input
+        })
+        .collect();
+    let mut outputs = vec![Output::<Aes128>::default(); INPUTS];
+
+    c.bench_function("evaluate", |b| {
+        b.iter(|| {
+            for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+                *output = owf::evaluate::<Aes128>(black_box(&key), black_box(input));
+            }
+        })
+    });
+    c.bench_function("evaluate_multi", |b| {
+        b.iter(|| owf::evaluate_multi::<Aes128>(black_box(&key), black_box(&inputs), &mut outputs))
+    });
+}
+
+criterion_group!(benches, owf_benchmark);
+criterion_main!(benches);
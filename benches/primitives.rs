@@ -0,0 +1,74 @@
+use criterion::{black_box, criterion_group, Criterion};
+use faest::{
+    bench_internals::{GF128, GF192, GF256},
+    prg::IV,
+    vole::{
+        self, Seed, Vole128f, Vole128s, Vole192f, Vole192s, Vole256f, Vole256s, VoleParameters,
+    },
+};
+use generic_array::{
+    typenum::{U234, U458, U566},
+    ArrayLength,
+};
+use rand::{RngCore, SeedableRng};
+
+mod summary;
+
+fn field_benchmark<F>(c: &mut Criterion, name: &str, bytes: usize)
+where
+    F: for<'a> From<&'a [u8]> + std::ops::Mul<Output = F> + Copy,
+{
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed([0; 32]);
+    let mut buffer = vec![0; 2 * bytes];
+    rng.fill_bytes(&mut buffer);
+    let lhs = F::from(&buffer[..bytes]);
+    let rhs = F::from(&buffer[bytes..]);
+
+    c.benchmark_group(name)
+        .bench_function("mul", |b| b.iter(|| black_box(lhs) * black_box(rhs)));
+}
+
+fn fields_benchmark(c: &mut Criterion) {
+    field_benchmark::<GF128>(c, "GF128", 16);
+    field_benchmark::<GF192>(c, "GF192", 24);
+    field_benchmark::<GF256>(c, "GF256", 32);
+}
+
+/// Benchmark the VOLE commitment for correlations of `LH` bytes, i.e.,
+/// `ℓ̂ / 8` of the corresponding parameter set
+fn vole_benchmark<V, LH>(c: &mut Criterion, name: &str)
+where
+    V: VoleParameters,
+    LH: ArrayLength,
+{
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed([0; 32]);
+    let mut seed = Seed::<V>::default();
+    rng.fill_bytes(&mut seed);
+    let mut iv = IV::default();
+    rng.fill_bytes(&mut iv);
+
+    let mut c = c.benchmark_group(name);
+    c.bench_function("GGM expansion", |b| {
+        b.iter(|| V::expand_ggm(black_box(&seed), black_box(&iv)))
+    });
+    c.bench_function("volecommit", |b| {
+        b.iter(|| vole::commit::<V, LH>(black_box(&seed), black_box(&iv)))
+    });
+}
+
+fn voles_benchmark(c: &mut Criterion) {
+    vole_benchmark::<Vole128s, U234>(c, "VOLE-128s");
+    vole_benchmark::<Vole128f, U234>(c, "VOLE-128f");
+    vole_benchmark::<Vole192s, U458>(c, "VOLE-192s");
+    vole_benchmark::<Vole192f, U458>(c, "VOLE-192f");
+    vole_benchmark::<Vole256s, U566>(c, "VOLE-256s");
+    vole_benchmark::<Vole256f, U566>(c, "VOLE-256f");
+}
+
+criterion_group!(benches, fields_benchmark, voles_benchmark);
+
+fn main() {
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    summary::write();
+}
@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use faest::*;
+use rand::{RngCore, SeedableRng};
+use signature::{Signer, Verifier};
+
+type Message = [u8; 32];
+
+fn random_message(mut rng: impl RngCore) -> Message {
+    let mut ret = Message::default();
+    rng.fill_bytes(&mut ret);
+    ret
+}
+
+fn benchmark<KP, S>(c: &mut Criterion, name: &str)
+where
+    KP: KeypairGenerator + Signer<S>,
+    KP::VerifyingKey: Verifier<S>,
+{
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed([0; 32]);
+    let mut c = c.benchmark_group(name);
+
+    let kp = KP::generate(&mut rng);
+    c.bench_function("sign", |b| {
+        let message = random_message(&mut rng);
+        b.iter(|| black_box(kp.sign(&message)));
+    });
+    let vk = kp.verifying_key();
+    c.bench_function("verify", |b| {
+        let message = random_message(&mut rng);
+        let signature = kp.sign(&message);
+        b.iter(|| black_box(vk.verify(&message, &signature)))
+    });
+}
+
+fn random_oracle_benchmark(c: &mut Criterion) {
+    benchmark::<FAEST128fSigningKey, FAEST128fSignature>(c, "FAEST-128f (SHAKE128)");
+    benchmark::<FAEST128fBlake3SigningKey, FAEST128fBlake3Signature>(c, "FAEST-128f (BLAKE3)");
+    benchmark::<FAEST128sSigningKey, FAEST128sSignature>(c, "FAEST-128s (SHAKE128)");
+    benchmark::<FAEST128sBlake3SigningKey, FAEST128sBlake3Signature>(c, "FAEST-128s (BLAKE3)");
+}
+
+criterion_group!(benches, random_oracle_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use faest::{signature::RandomizedSigner, *};
+use rand::SeedableRng;
+
+const BATCH_SIZES: [usize; 2] = [16, 64];
+
+macro_rules! benchmark {
+    ($c:expr, $kp:ty, $sig:ty, $name:expr) => {{
+        let mut rng = rand_chacha::ChaCha8Rng::from_seed([0; 32]);
+        let sk = <$kp>::generate(&mut rng);
+        let mut c = $c.benchmark_group($name);
+        c.sample_size(10);
+
+        for n in BATCH_SIZES {
+            let msgs: Vec<Vec<u8>> = (0..n)
+                .map(|i| format!("message {i}").into_bytes())
+                .collect();
+            let msgs: Vec<&[u8]> = msgs.iter().map(Vec::as_slice).collect();
+
+            c.throughput(Throughput::Elements(n as u64));
+            c.bench_with_input(BenchmarkId::new("serial", n), &msgs, |b, msgs| {
+                b.iter(|| {
+                    black_box(
+                        msgs.iter()
+                            .map(|msg| -> $sig { sk.sign_with_rng(&mut rng, msg) })
+                            .collect::<Vec<_>>(),
+                    )
+                })
+            });
+            c.bench_with_input(BenchmarkId::new("batch", n), &msgs, |b, msgs| {
+                b.iter(|| black_box(sk.sign_batch(msgs, &mut rng)))
+            });
+        }
+    }};
+}
+
+fn sign_batch_benchmark(c: &mut Criterion) {
+    benchmark!(c, FAEST128fSigningKey, FAEST128fSignature, "FAEST-128f");
+    benchmark!(c, FAEST192fSigningKey, FAEST192fSignature, "FAEST-192f");
+    benchmark!(c, FAEST256fSigningKey, FAEST256fSignature, "FAEST-256f");
+    benchmark!(
+        c,
+        FAESTEM128fSigningKey,
+        FAESTEM128fSignature,
+        "FAEST-EM-128f"
+    );
+    benchmark!(
+        c,
+        FAESTEM192fSigningKey,
+        FAESTEM192fSignature,
+        "FAEST-EM-192f"
+    );
+    benchmark!(
+        c,
+        FAESTEM256fSigningKey,
+        FAESTEM256fSignature,
+        "FAEST-EM-256f"
+    );
+}
+
+criterion_group!(benches, sign_batch_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+//! Machine-readable summary of the benchmark results
+//!
+//! Criterion stores the estimates of each benchmark in its output directory.
+//! After all benchmarks ran, the latest estimates are collected into
+//! `summary.json` in the same directory, so that regressions can be tracked
+//! without parsing the human-readable output.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+
+/// Output directory of criterion
+fn criterion_directory() -> Option<PathBuf> {
+    if let Some(home) = env::var_os("CRITERION_HOME") {
+        return Some(home.into());
+    }
+    if let Some(target) = env::var_os("CARGO_TARGET_DIR") {
+        return Some(PathBuf::from(target).join("criterion"));
+    }
+    // benchmarks are run from target/<profile>/deps
+    env::current_exe()
+        .ok()?
+        .ancestors()
+        .nth(3)
+        .map(|target| target.join("criterion"))
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+fn collect(directory: &Path, results: &mut Vec<Value>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().is_some_and(|name| name == "new") {
+            let (Some(benchmark), Some(estimates)) = (
+                read_json(&path.join("benchmark.json")),
+                read_json(&path.join("estimates.json")),
+            ) else {
+                continue;
+            };
+            results.push(json!({
+                "id": benchmark["full_id"],
+                "mean_ns": estimates["mean"]["point_estimate"],
+                "median_ns": estimates["median"]["point_estimate"],
+                "std_dev_ns": estimates["std_dev"]["point_estimate"],
+                "throughput": benchmark["throughput"],
+            }));
+        } else if path.file_name().is_some_and(|name| name != "report") {
+            collect(&path, results);
+        }
+    }
+}
+
+/// Write the latest estimates of all benchmarks to `summary.json`
+pub fn write() {
+    let Some(directory) = criterion_directory() else {
+        return;
+    };
+    let mut results = Vec::new();
+    collect(&directory, &mut results);
+    if results.is_empty() {
+        return;
+    }
+    results.sort_by(|lhs, rhs| lhs["id"].as_str().cmp(&rhs["id"].as_str()));
+
+    let path = directory.join("summary.json");
+    let written =
+        serde_json::to_vec_pretty(&results).is_ok_and(|summary| fs::write(&path, summary).is_ok());
+    if written {
+        println!("Summary written to {}", path.display());
+    } else {
+        eprintln!("Failed to write summary to {}", path.display());
+    }
+}
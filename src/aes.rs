@@ -1,4 +1,4 @@
-use std::{array, mem::size_of};
+use std::{array, iter::zip, mem::size_of};
 
 use generic_array::{
     typenum::{Unsigned, U3, U4},
@@ -6,23 +6,38 @@ use generic_array::{
 };
 use itertools::iproduct;
 
+#[cfg(feature = "protocol-analysis")]
+use crate::owf::AESRoundState;
 use crate::{
-    fields::{ByteCombine, ByteCombineConstants, Field as _, SumPoly},
-    internal_keys::PublicKey,
-    parameter::{BaseParameters, OWFParameters, QSProof, TauParameters},
+    fields::{ByteCombine, ByteCombineConstants},
+    parameter::OWFParameters,
     rijndael_32::{
         bitslice, convert_from_batchblocks, inv_bitslice, mix_columns_0, rijndael_add_round_key,
         rijndael_key_schedule, rijndael_shift_rows_1, sub_bytes, sub_bytes_nots, State, RCON_TABLE,
     },
-    universal_hashing::{ZKHasherInit, ZKProofHasher, ZKVerifyHasher},
-    utils::{bit_combine_with_delta, contains_zeros, convert_gq, transpose_and_into_field, Field},
+    utils::{contains_zeros, Field},
+};
+#[cfg(feature = "_variant")]
+use crate::{
+    fields::{Double, Field as _},
+    internal_keys::PublicKey,
+    parameter::{BaseParameters, FAESTParameters},
+    universal_hashing::{evaluate_at, ZKHasherInit, ZKVerifyHasher},
+    utils::{bit_combine_with_delta, convert_gq, witness_bit_at},
+};
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use crate::{
+    owf::KeyRejected, parameter::QSProof, universal_hashing::ZKProofHasher,
+    utils::transpose_and_into_field,
 };
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 type KeyCstrnts<O> = (
     Box<GenericArray<u8, <O as OWFParameters>::PRODRUN128Bytes>>,
     Box<GenericArray<Field<O>, <O as OWFParameters>::PRODRUN128>>,
 );
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 type CstrntsVal<'a, O> = &'a GenericArray<
     GenericArray<u8, <O as OWFParameters>::LAMBDALBYTES>,
     <O as OWFParameters>::LAMBDA,
@@ -62,6 +77,48 @@ where
         witness[index..index + size_of::<u32>()].copy_from_slice(&i);
         index += size_of::<u32>();
     }
+    if O::NK::USIZE == 4 {
+        // AES-128: exactly one word of each of the R round keys is part of the
+        // witness. The number of round keys is known, so the loop is unrolled
+        // and the bounds checks on the witness are dropped.
+        const ROUND_KEYS: usize = 10;
+        debug_assert_eq!(O::R::USIZE, ROUND_KEYS);
+
+        let words: [[u8; 4]; ROUND_KEYS] = array::from_fn(|j| key_schedule_word(&kb, j + 1));
+        for (dst, word) in witness[index..index + ROUND_KEYS * size_of::<u32>()]
+            .chunks_exact_mut(size_of::<u32>())
+            .zip(words)
+        {
+            dst.copy_from_slice(&word);
+        }
+        index += ROUND_KEYS * size_of::<u32>();
+    } else {
+        extend_key_schedule_witness::<O>(&kb, &mut witness, &mut index);
+    }
+    // Step 5
+    zeros |= round_with_save(&input[..16], &kb, O::R::U8, &mut witness, &mut index);
+    if O::LAMBDA::USIZE > 128 {
+        zeros |= round_with_save(&input[16..], &kb, O::R::U8, &mut witness, &mut index);
+    }
+    if zeros {
+        None
+    } else {
+        Some(witness)
+    }
+}
+
+/// First word of the `j`-th round key in the fixsliced key schedule `kb`
+#[inline(always)]
+fn key_schedule_word(kb: &[u32], j: usize) -> [u8; 4] {
+    let block = inv_bitslice(&kb[8 * j..8 * (j + 1)]);
+    [block[0][0], block[0][1], block[0][2], block[0][3]]
+}
+
+fn extend_key_schedule_witness<O>(kb: &[u32], witness: &mut [u8], index: &mut usize)
+where
+    O: OWFParameters,
+{
+    let mut idx = *index;
     for j in 1 + (O::NK::USIZE / 8)
         ..1 + (O::NK::USIZE / 8)
             + (O::SKE::USIZE * ((2 - (O::NK::USIZE % 4)) * 2 + (O::NK::USIZE % 4) * 3)) / 16
@@ -71,27 +128,18 @@ where
         );
         if O::NK::USIZE == 6 {
             if j % 3 == 1 {
-                witness[index..index + size_of::<u32>()].copy_from_slice(&inside[2]);
-                index += size_of::<u32>();
+                witness[idx..idx + size_of::<u32>()].copy_from_slice(&inside[2]);
+                idx += size_of::<u32>();
             } else if j % 3 == 0 {
-                witness[index..index + size_of::<u32>()].copy_from_slice(&inside[0]);
-                index += size_of::<u32>();
+                witness[idx..idx + size_of::<u32>()].copy_from_slice(&inside[0]);
+                idx += size_of::<u32>();
             }
         } else {
-            witness[index..index + size_of::<u32>()].copy_from_slice(&inside[0]);
-            index += size_of::<u32>();
+            witness[idx..idx + size_of::<u32>()].copy_from_slice(&inside[0]);
+            idx += size_of::<u32>();
         }
     }
-    // Step 5
-    zeros |= round_with_save(&input[..16], &kb, O::R::U8, &mut witness, &mut index);
-    if O::LAMBDA::USIZE > 128 {
-        zeros |= round_with_save(&input[16..], &kb, O::R::U8, &mut witness, &mut index);
-    }
-    if zeros {
-        None
-    } else {
-        Some(witness)
-    }
+    *index = idx;
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -121,6 +169,100 @@ fn round_with_save(
     zeros | contains_zeros(&inv_bitslice(&state)[0])
 }
 
+/// Locate the first zero S-box input of the OWF
+///
+/// Checks the same S-box inputs as [aes_extendedwitness]: those of the key
+/// schedule followed by those of the encryption rounds of each block.
+#[cfg(all(feature = "sign", feature = "_variant"))]
+pub(crate) fn aes_find_zero_sbox_input<O>(
+    owf_key: &GenericArray<u8, O::LAMBDABYTES>,
+    owf_input: &GenericArray<u8, O::InputSize>,
+) -> Option<KeyRejected>
+where
+    O: OWFParameters,
+{
+    let (kb, _) = rijndael_key_schedule::<U4, O::NK, O::R>(owf_key, O::SKE::USIZE);
+    let nk = O::NK::USIZE;
+    let word = |i: usize| -> [u8; 4] {
+        let block = inv_bitslice(&kb[8 * (i / 4)..8 * (i / 4 + 1)]);
+        array::from_fn(|j| block[0][4 * (i % 4) + j])
+    };
+    // the S-boxes of the key schedule are applied to the word preceding each
+    // of these words
+    if let Some(i) = (nk..4 * (O::R::USIZE + 1))
+        .filter(|i| i % nk == 0 || (nk == 8 && i % nk == 4))
+        .take(O::SKE::USIZE / 4)
+        .find(|i| contains_zeros(&word(i - 1)))
+    {
+        return Some(KeyRejected::KeySchedule { round: i / 4 });
+    }
+
+    for (block, input) in owf_input.chunks_exact(16).enumerate() {
+        let mut state = State::default();
+        bitslice(&mut state, input, &[]);
+        rijndael_add_round_key(&mut state, &kb[..8]);
+        for round in 1..=O::R::USIZE {
+            if contains_zeros(&inv_bitslice(&state)[0]) {
+                return Some(KeyRejected::Encryption { block, round });
+            }
+            if round == O::R::USIZE {
+                break;
+            }
+
+            sub_bytes(&mut state);
+            sub_bytes_nots(&mut state);
+            rijndael_shift_rows_1::<U4>(&mut state);
+            mix_columns_0(&mut state);
+            rijndael_add_round_key(&mut state, &kb[8 * round..8 * (round + 1)]);
+        }
+    }
+    None
+}
+
+/// Evaluate the OWF round by round and record the states of each round
+///
+/// Follows the computation of [aes_extendedwitness], i.e., the S-box outputs
+/// of rounds `1` to `R - 1` are stored in the witness after the key schedule.
+#[cfg(feature = "protocol-analysis")]
+pub(crate) fn aes_round_states<O>(
+    owf_key: &GenericArray<u8, O::LAMBDABYTES>,
+    owf_input: &GenericArray<u8, O::InputSize>,
+) -> Vec<AESRoundState>
+where
+    O: OWFParameters,
+{
+    let (kb, _) = rijndael_key_schedule::<U4, O::NK, O::R>(owf_key, O::SKE::USIZE);
+    let rounds = O::R::USIZE;
+    let mut states = Vec::with_capacity(owf_input.len() / 16 * rounds);
+    for (block, input) in owf_input.chunks_exact(16).enumerate() {
+        let mut state = State::default();
+        bitslice(&mut state, input, &[]);
+        rijndael_add_round_key(&mut state, &kb[..8]);
+        for round in 1..=rounds {
+            sub_bytes(&mut state);
+            sub_bytes_nots(&mut state);
+            rijndael_shift_rows_1::<U4>(&mut state);
+            let sbox_output = inv_bitslice(&state)[0].into();
+            if round != rounds {
+                mix_columns_0(&mut state);
+            }
+            let round_key = &kb[8 * round..8 * (round + 1)];
+            rijndael_add_round_key(&mut state, round_key);
+
+            states.push(AESRoundState {
+                block,
+                round,
+                sbox_output,
+                round_key: inv_bitslice(round_key)[0].into(),
+                state: inv_bitslice(&state)[0].into(),
+                witness_offset: (round != rounds)
+                    .then(|| O::LKEBytes::USIZE + 16 * ((rounds - 1) * block + round - 1)),
+            });
+        }
+    }
+    states
+}
+
 fn aes_key_exp_fwd_1<O>(
     x: &GenericArray<u8, O::LKEBytes>,
 ) -> Box<GenericArray<u8, O::PRODRUN128Bytes>>
@@ -146,6 +288,7 @@ where
     out
 }
 
+#[cfg(feature = "_variant")]
 fn aes_key_exp_fwd<O>(
     x: &GenericArray<Field<O>, O::LKE>,
 ) -> Box<GenericArray<Field<O>, O::PRODRUN128>>
@@ -215,6 +358,7 @@ where
     })
 }
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn aes_key_exp_bwd_mtag1_mkey0<'a, O>(
     x: &'a [Field<O>],
     xk: &'a GenericArray<Field<O>, O::PRODRUN128>,
@@ -249,6 +393,7 @@ where
     })
 }
 
+#[cfg(feature = "_variant")]
 fn aes_key_exp_bwd_mtag0_mkey1<'a, O>(
     x: &'a GenericArray<Field<O>, O::LKE>,
     xk: &'a GenericArray<Field<O>, O::PRODRUN128>,
@@ -297,6 +442,22 @@ where
     })
 }
 
+/// Inputs of the S-boxes of the key schedule computed from the expanded key
+/// `k`
+fn aes_key_exp_sbox_inputs<O>(
+    k: &GenericArray<u8, O::PRODRUN128Bytes>,
+) -> impl Iterator<Item = Field<O>> + '_
+where
+    O: OWFParameters,
+{
+    iproduct!(0..O::SKE::USIZE / 4, 0..4).map(|(j, r)| {
+        let iwd = 32 * (O::NK::USIZE - 1) + j * if O::LAMBDA::USIZE == 192 { 192 } else { 128 };
+        let dorotword = !(O::LAMBDA::USIZE == 256 && j % 2 == 1);
+        Field::<O>::byte_combine_bits(k[iwd / 8 + inverse_rotate_word(r, dorotword)])
+    })
+}
+
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn aes_key_exp_cstrnts_mkey0<O>(
     zk_hasher: &mut ZKProofHasher<Field<O>>,
     w: &GenericArray<u8, O::LKEBytes>,
@@ -311,11 +472,7 @@ where
     let v_w_b = aes_key_exp_bwd_mtag1_mkey0::<O>(&v[O::LAMBDA::USIZE..], &vk);
 
     zk_hasher.process(
-        iproduct!(0..O::SKE::USIZE / 4, 0..4).map(|(j, r)| {
-            let iwd = 32 * (O::NK::USIZE - 1) + j * if O::LAMBDA::USIZE == 192 { 192 } else { 128 };
-            let dorotword = !(O::LAMBDA::USIZE == 256 && j % 2 == 1);
-            Field::<O>::byte_combine_bits(k[iwd / 8 + inverse_rotate_word(r, dorotword)])
-        }),
+        aes_key_exp_sbox_inputs::<O>(&k),
         iproduct!(0..O::SKE::USIZE / 4, 0..4).map(|(j, r)| {
             let iwd = 32 * (O::NK::USIZE - 1) + j * if O::LAMBDA::USIZE == 192 { 192 } else { 128 };
             let dorotword = !(O::LAMBDA::USIZE == 256 && j % 2 == 1);
@@ -329,6 +486,7 @@ where
     (k, vk)
 }
 
+#[cfg(feature = "_variant")]
 fn aes_key_exp_cstrnts_mkey1<O>(
     zk_hasher: &mut ZKVerifyHasher<Field<O>>,
     q: &GenericArray<Field<O>, O::LKE>,
@@ -400,6 +558,7 @@ where
         )
 }
 
+#[cfg(feature = "_variant")]
 fn aes_enc_fwd_mkey1_mtag0<'a, O>(
     x: &'a GenericArray<Field<O>, O::LENC>,
     xk: &'a GenericArray<Field<O>, O::PRODRUN128>,
@@ -451,6 +610,7 @@ where
         )
 }
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn aes_enc_fwd_mkey0_mtag1<'a, O>(
     x: &'a GenericArray<Field<O>, O::LENC>,
     xk: &'a GenericArray<Field<O>, O::PRODRUN128>,
@@ -522,6 +682,7 @@ where
     })
 }
 
+#[cfg(feature = "_variant")]
 fn aes_enc_bkwd_mkey1_mtag0<'a, O>(
     x: &'a GenericArray<Field<O>, O::LENC>,
     xk: &'a GenericArray<Field<O>, O::PRODRUN128>,
@@ -538,10 +699,7 @@ where
         let x_t: [_; 8] = if j < O::R::USIZE - 1 {
             array::from_fn(|i| x[ird + i])
         } else {
-            array::from_fn(|i| {
-                *delta * ((out[(ird - 128 * j + i) / 8] >> ((ird - 128 * j + i) % 8)) & 1)
-                    + xk[128 + ird + i]
-            })
+            array::from_fn(|i| *delta * witness_bit_at(out, ird - 128 * j + i) + xk[128 + ird + i])
         };
         let mut y_t = array::from_fn(|i| x_t[(i + 7) % 8] + x_t[(i + 5) % 8] + x_t[(i + 2) % 8]);
         y_t[0] += delta;
@@ -550,6 +708,7 @@ where
     })
 }
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn aes_enc_bkwd_mkey0_mtag1<'a, O>(
     x: &'a GenericArray<Field<O>, O::LENC>,
     xk: &'a GenericArray<Field<O>, O::PRODRUN128>,
@@ -571,6 +730,7 @@ where
     })
 }
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn aes_enc_cstrnts_mkey0<O>(
     zk_hasher: &mut ZKProofHasher<Field<O>>,
     input: &[u8; 16],
@@ -589,6 +749,7 @@ fn aes_enc_cstrnts_mkey0<O>(
     zk_hasher.process(s, vs, s_b, v_s_b);
 }
 
+#[cfg(feature = "_variant")]
 fn aes_enc_cstrnts_mkey1<O>(
     zk_hasher: &mut ZKVerifyHasher<Field<O>>,
     input: &[u8; 16],
@@ -604,7 +765,47 @@ fn aes_enc_cstrnts_mkey1<O>(
     zk_hasher.process(qs, q_s_b);
 }
 
+/// Evaluate both sides of all constraints `s·s_b = 1` on the witness `w`
+///
+/// The sides are computed as by the prover, i.e., as affine functions of the
+/// bits of `w`. The constraints of the key schedule are followed by those of
+/// the encryption of each block.
+pub(crate) fn aes_constraint_values<O>(
+    w: &GenericArray<u8, O::LBYTES>,
+    owf_input: &GenericArray<u8, O::InputSize>,
+    owf_output: &GenericArray<u8, O::InputSize>,
+) -> Vec<(Field<O>, Field<O>)>
+where
+    O: OWFParameters,
+{
+    let w_k = GenericArray::from_slice(&w[..O::LKE::USIZE / 8]);
+    let k = aes_key_exp_fwd_1::<O>(w_k);
+    let mut values: Vec<_> = zip(
+        aes_key_exp_sbox_inputs::<O>(&k),
+        aes_key_exp_bwd_mtag0_mkey0::<O>(w_k, &k),
+    )
+    .collect();
+
+    let blocks = if O::LAMBDA::USIZE > 128 { 2 } else { 1 };
+    for block in 0..blocks {
+        let input = array::from_fn(|i| owf_input[16 * block + i]);
+        let output = array::from_fn(|i| owf_output[16 * block + i]);
+        let start = (O::LKE::USIZE + block * O::LENC::USIZE) / 8;
+        let w_enc = GenericArray::from_slice(&w[start..start + O::LENC::USIZE / 8]);
+        values.extend(zip(
+            aes_enc_fwd_mkey0_mtag0::<O>(w_enc, &k, &input),
+            aes_enc_bkwd_mkey0_mtag0::<O>(w_enc, &k, &output),
+        ));
+    }
+    values
+}
+
 // Bits are represented as bytes : each times we manipulate bit data, we divide length by 8
+//
+// The constraints are not split by repetition, so there is no batch of
+// independent MACs to vectorize here. With `opt-simd`, the field arithmetic
+// already uses PCLMULQDQ; AVX2 provides no 256-bit carry-less multiplication.
+#[cfg(all(feature = "sign", feature = "_variant"))]
 pub(crate) fn aes_prove<O>(
     w: &GenericArray<u8, O::LBYTES>,
     u: &GenericArray<u8, O::LAMBDALBYTES>,
@@ -628,8 +829,8 @@ where
 
     aes_enc_cstrnts_mkey0::<O>(
         &mut zk_hasher,
-        pk.owf_input[..16].try_into().unwrap(),
-        pk.owf_output[..16].try_into().unwrap(),
+        &array::from_fn(|i| pk.owf_input()[i]),
+        &array::from_fn(|i| pk.owf_output()[i]),
         GenericArray::from_slice(&w[O::LKE::USIZE / 8..(O::LKE::USIZE + O::LENC::USIZE) / 8]),
         GenericArray::from_slice(&new_v[O::LKE::USIZE..O::LKE::USIZE + O::LENC::USIZE]),
         &k,
@@ -639,8 +840,8 @@ where
     if O::LAMBDA::USIZE > 128 {
         aes_enc_cstrnts_mkey0::<O>(
             &mut zk_hasher,
-            pk.owf_input[16..].try_into().unwrap(),
-            pk.owf_output[16..].try_into().unwrap(),
+            &array::from_fn(|i| pk.owf_input()[16 + i]),
+            &array::from_fn(|i| pk.owf_output()[16 + i]),
             GenericArray::from_slice(&w[(O::LKE::USIZE + O::LENC::USIZE) / 8..O::LBYTES::USIZE]),
             GenericArray::from_slice(&new_v[(O::LKE::USIZE + O::LENC::USIZE)..O::L::USIZE]),
             &k,
@@ -649,17 +850,18 @@ where
     }
 
     let u_s = Field::<O>::from(&u[O::LBYTES::USIZE..]);
-    let v_s = Field::<O>::sum_poly(&new_v[O::L::USIZE..O::L::USIZE + O::LAMBDA::USIZE]);
+    let v_s = evaluate_at::<O>(&new_v, &Field::<O>::ONE.double());
     let (a_t, b_t) = zk_hasher.finalize(&u_s, &v_s);
 
     (a_t.as_bytes(), b_t.as_bytes())
 }
 
 // Bits are represented as bytes : each times we manipulate bit data, we divide length by 8
+#[cfg(feature = "_variant")]
 #[allow(clippy::too_many_arguments)]
-pub(crate) fn aes_verify<O, Tau>(
+pub(crate) fn aes_verify<O, P>(
     d: &GenericArray<u8, O::LBYTES>,
-    gq: Box<GenericArray<GenericArray<u8, O::LAMBDALBYTES>, O::LAMBDA>>,
+    gq: &mut GenericArray<GenericArray<u8, O::LAMBDALBYTES>, O::LAMBDA>,
     a_t: &GenericArray<u8, O::LAMBDABYTES>,
     chall2: &GenericArray<u8, <<O as OWFParameters>::BaseParams as BaseParameters>::Chall>,
     chall3: &GenericArray<u8, O::LAMBDABYTES>,
@@ -667,10 +869,10 @@ pub(crate) fn aes_verify<O, Tau>(
 ) -> GenericArray<u8, O::LAMBDABYTES>
 where
     O: OWFParameters,
-    Tau: TauParameters,
+    P: FAESTParameters<OWF = O>,
 {
     let delta = Field::<O>::from(chall3);
-    let new_q = convert_gq::<O, Tau>(d, gq, chall3);
+    let new_q = convert_gq::<O, P::Tau>(d, gq, chall3);
     let mut zk_hasher =
         <<O as OWFParameters>::BaseParams as BaseParameters>::ZKHasher::new_zk_verify_hasher(
             chall2, delta,
@@ -684,8 +886,8 @@ where
 
     aes_enc_cstrnts_mkey1::<O>(
         &mut zk_hasher,
-        pk.owf_input[..16].try_into().unwrap(),
-        pk.owf_output[..16].try_into().unwrap(),
+        &array::from_fn(|i| pk.owf_input()[i]),
+        &array::from_fn(|i| pk.owf_output()[i]),
         GenericArray::from_slice(&new_q[O::LKE::USIZE..(O::LKE::USIZE + O::LENC::USIZE)]),
         &qk,
         &delta,
@@ -693,15 +895,15 @@ where
     if O::LAMBDA::USIZE > 128 {
         aes_enc_cstrnts_mkey1::<O>(
             &mut zk_hasher,
-            pk.owf_input[16..].try_into().unwrap(),
-            pk.owf_output[16..].try_into().unwrap(),
+            &array::from_fn(|i| pk.owf_input()[16 + i]),
+            &array::from_fn(|i| pk.owf_output()[16 + i]),
             GenericArray::from_slice(&new_q[O::LKE::USIZE + O::LENC::USIZE..O::L::USIZE]),
             &qk,
             &delta,
         );
     }
 
-    let q_s = Field::<O>::sum_poly(&new_q[O::L::USIZE..O::L::USIZE + O::LAMBDA::USIZE]);
+    let q_s = evaluate_at::<O>(&new_q, &Field::<O>::ONE.double());
     (zk_hasher.finalize(&q_s) + Field::<O>::from(a_t) * delta).as_bytes()
 }
 
@@ -711,16 +913,21 @@ mod test {
 
     use super::*;
 
+    #[cfg(feature = "_variant")]
     use crate::{
         fields::{GF128, GF192, GF256},
         parameter::{
             FAEST128sParameters, FAEST192sParameters, FAEST256sParameters, FAESTParameters,
-            OWFParameters, OWF128, OWF192, OWF256,
         },
+    };
+    use crate::{
+        parameter::{OWFParameters, OWF128, OWF192, OWF256},
         utils::test::read_test_data,
     };
 
-    use generic_array::{sequence::GenericSequence, ArrayLength, GenericArray};
+    use generic_array::GenericArray;
+    #[cfg(feature = "_variant")]
+    use generic_array::{sequence::GenericSequence, ArrayLength};
     use serde::Deserialize;
 
     #[derive(Debug, Deserialize)]
@@ -764,6 +971,7 @@ mod test {
         }
     }
 
+    #[cfg(feature = "_variant")]
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "camelCase")]
     struct AesProve {
@@ -775,18 +983,20 @@ mod test {
         bt: Vec<u8>,
     }
 
+    #[cfg(feature = "_variant")]
     impl AesProve {
         fn as_pk<O>(&self) -> PublicKey<O>
         where
             O: OWFParameters,
         {
-            PublicKey {
-                owf_input: GenericArray::from_slice(&self.input).clone(),
-                owf_output: GenericArray::from_slice(&self.output).clone(),
-            }
+            PublicKey::new(
+                GenericArray::from_slice(&self.input),
+                GenericArray::from_slice(&self.output),
+            )
         }
     }
 
+    #[cfg(feature = "_variant")]
     #[test]
     fn aes_prove_test() {
         let database: Vec<AesProve> = read_test_data("AesProve.json");
@@ -825,6 +1035,7 @@ mod test {
         }
     }
 
+    #[cfg(feature = "_variant")]
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "camelCase")]
     struct AesVerify {
@@ -839,6 +1050,7 @@ mod test {
         res: Vec<u64>,
     }
 
+    #[cfg(feature = "_variant")]
     impl AesVerify {
         fn res_as_u8(&self) -> Vec<u8> {
             self.res.iter().flat_map(|x| x.to_le_bytes()).collect()
@@ -848,10 +1060,10 @@ mod test {
         where
             O: OWFParameters,
         {
-            PublicKey {
-                owf_input: GenericArray::from_slice(&self.input).clone(),
-                owf_output: GenericArray::from_slice(&self.output).clone(),
-            }
+            PublicKey::new(
+                GenericArray::from_slice(&self.input),
+                GenericArray::from_slice(&self.output),
+            )
         }
 
         fn as_gq<LHI, LHO>(&self) -> GenericArray<GenericArray<u8, LHI>, LHO>
@@ -866,7 +1078,8 @@ mod test {
         }
     }
 
-    fn aes_verify<O, Tau>(
+    #[cfg(feature = "_variant")]
+    fn aes_verify<O, P>(
         d: &GenericArray<u8, O::LBYTES>,
         gq: &GenericArray<GenericArray<u8, O::LAMBDALBYTES>, O::LAMBDA>,
         a_t: &GenericArray<u8, O::LAMBDABYTES>,
@@ -876,24 +1089,18 @@ mod test {
     ) -> GenericArray<u8, O::LAMBDABYTES>
     where
         O: OWFParameters,
-        Tau: TauParameters,
+        P: FAESTParameters<OWF = O>,
     {
-        super::aes_verify::<O, Tau>(
-            d,
-            Box::<GenericArray<_, _>>::from_iter(gq.iter().cloned()),
-            a_t,
-            chall2,
-            chall3,
-            pk,
-        )
+        super::aes_verify::<O, P>(d, &mut gq.clone(), a_t, chall2, chall3, pk)
     }
 
+    #[cfg(feature = "_variant")]
     #[test]
     fn aes_verify_test() {
         let database: Vec<AesVerify> = read_test_data("AesVerify.json");
         for data in database {
             if data.lambda == 128 {
-                let out = aes_verify::<OWF128, <FAEST128sParameters as FAESTParameters>::Tau>(
+                let out = aes_verify::<OWF128, FAEST128sParameters>(
                     GenericArray::from_slice(&data.d[..]),
                     &data.as_gq(),
                     GenericArray::from_slice(&data.at),
@@ -906,7 +1113,7 @@ mod test {
                     GF128::from(out.as_slice())
                 );
             } else if data.lambda == 192 {
-                let out = aes_verify::<OWF192, <FAEST192sParameters as FAESTParameters>::Tau>(
+                let out = aes_verify::<OWF192, FAEST192sParameters>(
                     GenericArray::from_slice(&data.d[..]),
                     &data.as_gq(),
                     GenericArray::from_slice(&data.at),
@@ -919,7 +1126,7 @@ mod test {
                     GF192::from(out.as_slice())
                 );
             } else {
-                let out = aes_verify::<OWF256, <FAEST256sParameters as FAESTParameters>::Tau>(
+                let out = aes_verify::<OWF256, FAEST256sParameters>(
                     GenericArray::from_slice(&data.d[..]),
                     &data.as_gq(),
                     GenericArray::from_slice(&data.at),
@@ -0,0 +1,18 @@
+//! Selection of the AES implementation
+//!
+//! The OWFs of the AES-based variants, the PRGs and the NIST DRBG use the
+//! types of this module. Per default, they resolve to the `aes` crate, which
+//! uses AES-NI (or the ARMv8 instructions with `--cfg aes_armv8`) if available
+//! at runtime and its fixsliced software implementation otherwise. The
+//! software implementation can be forced with `--cfg aes_force_soft`. If the
+//! `soft-aes-only` feature is enabled, the types resolve to the bitsliced
+//! implementation in [`rijndael_32`](crate::rijndael_32) instead.
+
+#[cfg(not(feature = "soft-aes-only"))]
+pub(crate) use aes::{Aes128, Aes128Enc, Aes192, Aes192Enc, Aes256, Aes256Enc};
+
+#[cfg(feature = "soft-aes-only")]
+pub(crate) use crate::rijndael_32::{
+    Aes192, Aes192 as Aes192Enc, Aes256, Aes256 as Aes256Enc, Rijndael128 as Aes128,
+    Rijndael128 as Aes128Enc,
+};
@@ -0,0 +1,215 @@
+//! Command line tool to generate FAEST keys, and to sign and verify files
+//!
+//! Keys are stored PEM-encoded with the variant in the label, e.g.,
+//! `FAEST-128S SIGNING KEY`. Signatures are stored in their raw byte
+//! encoding.
+//!
+//! The exit code is 0 on success, 1 if a signature is invalid, and 2 for
+//! usage errors, I/O errors, and malformed keys or signatures.
+
+use std::{fs, process::ExitCode};
+
+use clap::{Parser, Subcommand};
+use faest::{ByteEncoding, FaestVariant, Keypair, KeypairGenerator, RandomizedSigner, Verifier};
+use rand_core::OsRng;
+
+const SIGNING_KEY_LABEL: &str = "SIGNING KEY";
+const VERIFICATION_KEY_LABEL: &str = "VERIFICATION KEY";
+
+/// Exit code for invalid signatures
+const INVALID_SIGNATURE: u8 = 1;
+/// Exit code for all other errors
+const USAGE_ERROR: u8 = 2;
+
+#[derive(Parser)]
+#[command(version, about = "Generate FAEST keys, and sign and verify files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a new key pair
+    Keygen {
+        /// Variant, e.g., faest_128s or FAEST-EM-256f
+        #[arg(long, value_parser = parse_variant)]
+        variant: FaestVariant,
+        /// Output file of the signing key
+        #[arg(long)]
+        out_sk: String,
+        /// Output file of the verification key
+        #[arg(long)]
+        out_pk: String,
+    },
+    /// Sign a file
+    Sign {
+        /// Signing key
+        #[arg(long)]
+        sk: String,
+        /// File to sign
+        #[arg(long = "in")]
+        input: String,
+        /// Output file of the signature
+        #[arg(long)]
+        out: String,
+    },
+    /// Verify the signature of a file
+    Verify {
+        /// Verification key
+        #[arg(long)]
+        pk: String,
+        /// Signed file
+        #[arg(long = "in")]
+        input: String,
+        /// Signature
+        #[arg(long)]
+        sig: String,
+    },
+}
+
+/// Parse the name of a variant
+///
+/// The comparison is case-insensitive and `_` may be used instead of `-`.
+fn parse_variant(name: &str) -> Result<FaestVariant, String> {
+    let normalized = name.replace('_', "-");
+    FaestVariant::ALL
+        .iter()
+        .copied()
+        .find(|variant| variant.name().eq_ignore_ascii_case(&normalized))
+        .ok_or_else(|| format!("unknown variant: {name}"))
+}
+
+/// Error of a command with the exit code to report
+struct Failure {
+    code: u8,
+    message: String,
+}
+
+impl Failure {
+    fn usage(message: impl Into<String>) -> Self {
+        Self {
+            code: USAGE_ERROR,
+            message: message.into(),
+        }
+    }
+}
+
+fn read(path: &str) -> Result<Vec<u8>, Failure> {
+    fs::read(path).map_err(|err| Failure::usage(format!("failed to read {path}: {err}")))
+}
+
+fn write(path: &str, contents: impl AsRef<[u8]>) -> Result<(), Failure> {
+    fs::write(path, contents)
+        .map_err(|err| Failure::usage(format!("failed to write {path}: {err}")))
+}
+
+fn write_key(path: &str, variant: FaestVariant, label: &str, key: &[u8]) -> Result<(), Failure> {
+    let tag = format!("{} {label}", variant.name().to_ascii_uppercase());
+    write(path, pem::encode(&pem::Pem::new(tag, key)))
+}
+
+/// Read a PEM-encoded key and return its variant and encoding
+fn read_key(path: &str, label: &str) -> Result<(FaestVariant, Vec<u8>), Failure> {
+    let contents = read(path)?;
+    let key = pem::parse(contents)
+        .map_err(|err| Failure::usage(format!("failed to parse {path}: {err}")))?;
+    let variant = key
+        .tag()
+        .strip_suffix(label)
+        .and_then(|name| parse_variant(name.trim_end()).ok())
+        .ok_or_else(|| Failure::usage(format!("{path} does not contain a FAEST {label}")))?;
+    Ok((variant, key.into_contents()))
+}
+
+/// Run `$f` with the signing key type of `$variant`
+macro_rules! with_signing_key {
+    ($variant:expr, $f:ident $(, $arg:expr)*) => {
+        match $variant {
+            FaestVariant::FAEST128f => $f::<faest::FAEST128fSigningKey, faest::FAEST128fSignature>($($arg),*),
+            FaestVariant::FAEST128s => $f::<faest::FAEST128sSigningKey, faest::FAEST128sSignature>($($arg),*),
+            FaestVariant::FAEST192f => $f::<faest::FAEST192fSigningKey, faest::FAEST192fSignature>($($arg),*),
+            FaestVariant::FAEST192s => $f::<faest::FAEST192sSigningKey, faest::FAEST192sSignature>($($arg),*),
+            FaestVariant::FAEST256f => $f::<faest::FAEST256fSigningKey, faest::FAEST256fSignature>($($arg),*),
+            FaestVariant::FAEST256s => $f::<faest::FAEST256sSigningKey, faest::FAEST256sSignature>($($arg),*),
+            FaestVariant::FAESTEM128f => $f::<faest::FAESTEM128fSigningKey, faest::FAESTEM128fSignature>($($arg),*),
+            FaestVariant::FAESTEM128s => $f::<faest::FAESTEM128sSigningKey, faest::FAESTEM128sSignature>($($arg),*),
+            FaestVariant::FAESTEM192f => $f::<faest::FAESTEM192fSigningKey, faest::FAESTEM192fSignature>($($arg),*),
+            FaestVariant::FAESTEM192s => $f::<faest::FAESTEM192sSigningKey, faest::FAESTEM192sSignature>($($arg),*),
+            FaestVariant::FAESTEM256f => $f::<faest::FAESTEM256fSigningKey, faest::FAESTEM256fSignature>($($arg),*),
+            FaestVariant::FAESTEM256s => $f::<faest::FAESTEM256sSigningKey, faest::FAESTEM256sSignature>($($arg),*),
+            variant => Err(Failure::usage(format!("{variant} is not supported"))),
+        }
+    };
+}
+
+fn keygen<K, S>(variant: FaestVariant, out_sk: &str, out_pk: &str) -> Result<(), Failure>
+where
+    K: KeypairGenerator + ByteEncoding + RandomizedSigner<S>,
+    K::VerifyingKey: ByteEncoding,
+{
+    let sk = K::generate(OsRng);
+    write_key(out_sk, variant, SIGNING_KEY_LABEL, sk.to_bytes().as_ref())?;
+    write_key(
+        out_pk,
+        variant,
+        VERIFICATION_KEY_LABEL,
+        sk.verifying_key().to_bytes().as_ref(),
+    )
+}
+
+fn sign<K, S>(sk: &[u8], msg: &[u8], out: &str) -> Result<(), Failure>
+where
+    K: for<'a> TryFrom<&'a [u8]> + RandomizedSigner<S>,
+    S: AsRef<[u8]>,
+{
+    let sk = K::try_from(sk).map_err(|_| Failure::usage("invalid signing key"))?;
+    write(out, sk.sign_with_rng(&mut OsRng, msg))
+}
+
+fn verify<K, S>(pk: &[u8], msg: &[u8], signature: &[u8]) -> Result<(), Failure>
+where
+    K: Keypair,
+    K::VerifyingKey: for<'a> TryFrom<&'a [u8]> + Verifier<S>,
+    S: for<'a> TryFrom<&'a [u8]>,
+{
+    let pk =
+        K::VerifyingKey::try_from(pk).map_err(|_| Failure::usage("invalid verification key"))?;
+    let signature = S::try_from(signature).map_err(|_| Failure::usage("malformed signature"))?;
+    pk.verify(msg, &signature).map_err(|_| Failure {
+        code: INVALID_SIGNATURE,
+        message: "invalid signature".into(),
+    })
+}
+
+fn run(command: Command) -> Result<(), Failure> {
+    match command {
+        Command::Keygen {
+            variant,
+            out_sk,
+            out_pk,
+        } => with_signing_key!(variant, keygen, variant, &out_sk, &out_pk),
+        Command::Sign { sk, input, out } => {
+            let (variant, sk) = read_key(&sk, SIGNING_KEY_LABEL)?;
+            let msg = read(&input)?;
+            with_signing_key!(variant, sign, &sk, &msg, &out)
+        }
+        Command::Verify { pk, input, sig } => {
+            let (variant, pk) = read_key(&pk, VERIFICATION_KEY_LABEL)?;
+            let msg = read(&input)?;
+            let signature = read(&sig)?;
+            with_signing_key!(variant, verify, &pk, &msg, &signature)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("{}", failure.message);
+            ExitCode::from(failure.code)
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! Reusable scratch buffers for signing and verification
+//!
+//! Signing allocates the VOLE correlations of the prover, a matrix of
+//! `λ` rows with a few hundred bytes to a few KB each, and a copy of it
+//! reshaped for the QuickSilver proof. Verification allocates the same
+//! matrices for the VOLE correlations of the verifier. Services producing or
+//! verifying many signatures can pass a [`BufferPool`] via
+//! [`SignOptions::buffer_pool`](crate::SignOptions::buffer_pool) or
+//! [`VerifyOptions::buffer_pool`](crate::VerifyOptions::buffer_pool) to reuse
+//! these buffers across operations instead of allocating them anew.
+//!
+//! Buffers are zeroized before they are returned to the pool. A pool is not
+//! tied to a parameter set: a request is served by any pooled buffer that is
+//! large enough, so the buffers grow to the largest requirement of the
+//! parameter sets that are used with the pool. The pool never holds more than
+//! its configured number of bytes; buffers exceeding the limit are released.
+//! Using a pool does not change the produced signatures or the result of a
+//! verification.
+//!
+//! ```
+//! # #[cfg(feature = "faest-128f")] {
+//! use faest::{BufferPool, FAEST128fSigningKey, KeypairGenerator, SignOptions};
+//!
+//! let pool = BufferPool::new(1 << 20);
+//! let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+//! for msg in [b"first message", b"other message"] {
+//!     let options = SignOptions::new().buffer_pool(&pool);
+//!     let signature = sk.sign_with_options(msg, options).expect("valid options");
+//! }
+//! assert!(pool.pooled_bytes() > 0);
+//! # }
+//! ```
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+#[cfg(feature = "_variant")]
+use generic_array::{ArrayLength, GenericArray};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Bounded pool of zeroized scratch buffers
+///
+/// The pool is cheap to clone: clones share the same buffers. It can be
+/// shared by all threads of a service, e.g., the blocking thread pool of
+/// Tokio.
+#[derive(Clone)]
+pub struct BufferPool {
+    inner: Arc<Mutex<PoolState>>,
+}
+
+struct PoolState {
+    buffers: Vec<Vec<u8>>,
+    pooled_bytes: usize,
+    max_bytes: usize,
+}
+
+impl BufferPool {
+    /// Create an empty pool holding at most `max_bytes` bytes of buffers
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(PoolState {
+                buffers: Vec::new(),
+                pooled_bytes: 0,
+                max_bytes,
+            })),
+        }
+    }
+
+    /// Maximal number of bytes held by the pool
+    pub fn max_bytes(&self) -> usize {
+        self.state().max_bytes
+    }
+
+    /// Number of bytes currently held by the pool
+    ///
+    /// Buffers that are in use by a signing operation or a verification are
+    /// not included.
+    pub fn pooled_bytes(&self) -> usize {
+        self.state().pooled_bytes
+    }
+
+    /// Release all pooled buffers
+    pub fn clear(&self) {
+        let mut state = self.state();
+        state.buffers.clear();
+        state.pooled_bytes = 0;
+    }
+
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        // the state is consistent after every operation, so a panic of
+        // another thread does not invalidate it
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Take a zeroized buffer with a capacity of at least `len` bytes
+    ///
+    /// Prefers the smallest sufficiently large buffer. Allocates a new buffer
+    /// if none is available.
+    fn take(&self, len: usize) -> Vec<u8> {
+        let mut state = self.state();
+        let best = state
+            .buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, buffer)| buffer.capacity() >= len)
+            .min_by_key(|(_, buffer)| buffer.capacity())
+            .map(|(idx, _)| idx);
+        match best {
+            Some(idx) => {
+                let buffer = state.buffers.swap_remove(idx);
+                state.pooled_bytes -= buffer.capacity();
+                buffer
+            }
+            None => {
+                drop(state);
+                Vec::with_capacity(len)
+            }
+        }
+    }
+
+    /// Return a zeroized buffer to the pool or release it if the pool is full
+    fn put(&self, buffer: Vec<u8>) {
+        let mut state = self.state();
+        if state.pooled_bytes + buffer.capacity() <= state.max_bytes {
+            state.pooled_bytes += buffer.capacity();
+            state.buffers.push(buffer);
+        }
+    }
+}
+
+impl fmt::Debug for BufferPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state();
+        f.debug_struct("BufferPool")
+            .field("buffers", &state.buffers.len())
+            .field("pooled_bytes", &state.pooled_bytes)
+            .field("max_bytes", &state.max_bytes)
+            .finish()
+    }
+}
+
+/// Zero-initialized byte buffer, optionally taken from a [`BufferPool`]
+///
+/// The buffer is zeroized on drop and then returned to its pool.
+pub(crate) struct ScratchBuffer {
+    buffer: Vec<u8>,
+    pool: Option<BufferPool>,
+}
+
+impl ScratchBuffer {
+    /// Create a buffer of `len` bytes, taken from `pool` if given
+    pub(crate) fn new(len: usize, pool: Option<&BufferPool>) -> Self {
+        let mut buffer = pool.map_or_else(|| Vec::with_capacity(len), |pool| pool.take(len));
+        // pooled buffers are empty, hence this zero-fills all `len` bytes and
+        // the pool only saves the allocation
+        buffer.resize(len, 0);
+        Self {
+            buffer,
+            pool: pool.cloned(),
+        }
+    }
+
+    /// Pool of the buffer
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    pub(crate) fn pool(&self) -> Option<&BufferPool> {
+        self.pool.as_ref()
+    }
+
+    /// View the buffer as matrix with `R` rows of `C` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the buffer is not `R * C`.
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    pub(crate) fn as_matrix<R, C>(&self) -> &GenericArray<GenericArray<u8, C>, R>
+    where
+        R: ArrayLength,
+        C: ArrayLength,
+    {
+        let (rows, rest) = GenericArray::<u8, C>::chunks_from_slice(&self.buffer);
+        assert!(rest.is_empty());
+        GenericArray::from_slice(rows)
+    }
+
+    /// View the buffer as mutable matrix with `R` rows of `C` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the buffer is not `R * C`.
+    #[cfg(feature = "_variant")]
+    pub(crate) fn as_matrix_mut<R, C>(&mut self) -> &mut GenericArray<GenericArray<u8, C>, R>
+    where
+        R: ArrayLength,
+        C: ArrayLength,
+    {
+        let (rows, rest) = GenericArray::<u8, C>::chunks_from_slice_mut(&mut self.buffer);
+        assert!(rest.is_empty());
+        GenericArray::from_mut_slice(rows)
+    }
+}
+
+impl Deref for ScratchBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+impl DerefMut for ScratchBuffer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buffer
+    }
+}
+
+impl Drop for ScratchBuffer {
+    fn drop(&mut self) {
+        #[cfg(feature = "zeroize")]
+        self.buffer.zeroize();
+        if let Some(pool) = self.pool.take() {
+            #[cfg(not(feature = "zeroize"))]
+            self.buffer.fill(0);
+            // keep the capacity; the zeroization above only wipes the secret
+            // data while the buffer is pooled, `new` zero-fills it again
+            self.buffer.clear();
+            pool.put(std::mem::take(&mut self.buffer));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reuse_zeroized() {
+        let pool = BufferPool::new(1024);
+        let mut buffer = ScratchBuffer::new(100, Some(&pool));
+        buffer.fill(0xff);
+        let ptr = buffer.as_ptr();
+        drop(buffer);
+        assert_eq!(pool.pooled_bytes(), 100);
+
+        // a smaller request is served by the same buffer
+        let buffer = ScratchBuffer::new(50, Some(&pool));
+        assert_eq!(buffer.as_ptr(), ptr);
+        assert_eq!(buffer.len(), 50);
+        assert!(buffer.iter().all(|b| *b == 0));
+        assert_eq!(pool.pooled_bytes(), 0);
+        drop(buffer);
+
+        // a larger request allocates a new buffer
+        let buffer = ScratchBuffer::new(200, Some(&pool));
+        assert!(buffer.iter().all(|b| *b == 0));
+        assert_eq!(pool.pooled_bytes(), 100);
+    }
+
+    #[test]
+    fn bounded() {
+        let pool = BufferPool::new(150);
+        let first = ScratchBuffer::new(100, Some(&pool));
+        let second = ScratchBuffer::new(100, Some(&pool));
+        drop(first);
+        drop(second);
+        assert_eq!(pool.pooled_bytes(), 100);
+
+        pool.clear();
+        assert_eq!(pool.pooled_bytes(), 0);
+        assert_eq!(pool.max_bytes(), 150);
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn without_pool() {
+        let buffer = ScratchBuffer::new(10, None);
+        assert!(buffer.pool().is_none());
+        assert_eq!(&*buffer, &[0; 10]);
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn matrix() {
+        use generic_array::typenum::{U2, U3};
+
+        let mut buffer = ScratchBuffer::new(6, None);
+        buffer.as_matrix_mut::<U2, U3>()[1][0] = 1;
+        assert_eq!(&*buffer, &[0, 0, 0, 1, 0, 0]);
+        assert_eq!(buffer.as_matrix::<U2, U3>()[1][0], 1);
+    }
+}
@@ -0,0 +1,484 @@
+//! CBOR encoding of keys and signatures
+//!
+//! Keys and signatures are encoded with a fixed and versioned schema.
+//! Verification keys are encoded as CBOR map with integer keys:
+//!
+//! | key | value                                            |
+//! |-----|--------------------------------------------------|
+//! | 0   | schema version, currently 1                      |
+//! | 1   | OWF input (byte string)                          |
+//! | 2   | OWF output (byte string)                         |
+//! | 3   | algorithm identifier (see [`algorithm_id`])      |
+//!
+//! Signing keys are encoded in the same way, but contain the OWF key (byte
+//! string) with key 4 instead of the OWF output.
+//!
+//! Signatures are encoded as byte string with the tag returned by
+//! [`signature_tag`]. The tag encodes the schema version and the algorithm,
+//! e.g., `0x46410101` for FAEST-128f. These tags are not registered in the
+//! IANA CBOR tags registry. They lie in the first come first served range and
+//! may therefore collide with tags used by other applications.
+//!
+//! Decoders ignore unknown integer keys in maps. Hence, later versions of the
+//! schema may add fields without breaking existing decoders. Encodings with a
+//! different schema version are rejected.
+//!
+//! ```
+//! # #[cfg(feature = "faest-128f")] {
+//! use faest::{cbor::CborEncoding, FAEST128fSigningKey, FAEST128fSignature};
+//! use faest::{signature::{Signer, Verifier, Keypair}, KeypairGenerator};
+//!
+//! let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+//! let vk = sk.verifying_key();
+//! let signature: FAEST128fSignature = sk.sign(b"message");
+//!
+//! let vk = faest::FAEST128fVerificationKey::from_cbor(&vk.to_cbor()).unwrap();
+//! let signature = FAEST128fSignature::from_cbor(&signature.to_cbor()).unwrap();
+//! assert!(vk.verify(b"message", &signature).is_ok());
+//! # }
+//! ```
+
+use ciborium::Value;
+use generic_array::typenum::Unsigned;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+    internal_keys::{PublicKey, SecretKey},
+    parameter::OWFParameters,
+    Error, FaestVariant,
+};
+
+/// Version of the schema
+pub const SCHEMA_VERSION: u64 = 1;
+
+const KEY_VERSION: u64 = 0;
+const KEY_OWF_INPUT: u64 = 1;
+const KEY_OWF_OUTPUT: u64 = 2;
+const KEY_ALGORITHM: u64 = 3;
+const KEY_OWF_KEY: u64 = 4;
+
+/// Base of the signature tags, i.e., `FA` followed by the version and the algorithm
+const SIGNATURE_TAG_BASE: u64 = 0x4641_0000;
+
+/// Byte string decoded from a map, zeroized on drop
+#[cfg(feature = "zeroize")]
+type Bytes = Zeroizing<Vec<u8>>;
+#[cfg(not(feature = "zeroize"))]
+type Bytes = Vec<u8>;
+
+/// Encoding of keys and signatures as CBOR
+pub trait CborEncoding: Sized {
+    /// Encode as CBOR
+    fn to_cbor(&self) -> Vec<u8>;
+
+    /// Decode from CBOR
+    fn from_cbor(bytes: &[u8]) -> Result<Self, Error>;
+}
+
+/// Identifier of the algorithm used in the CBOR encoding
+///
+/// This is [`FaestVariant::id`], hence the identifiers are stable and do not
+/// depend on the enabled features.
+pub const fn algorithm_id(variant: FaestVariant) -> u64 {
+    variant.id() as u64
+}
+
+/// CBOR tag of signatures
+pub const fn signature_tag(variant: FaestVariant) -> u64 {
+    SIGNATURE_TAG_BASE | (SCHEMA_VERSION << 8) | algorithm_id(variant)
+}
+
+fn encode(value: &Value) -> Vec<u8> {
+    let mut out = Vec::new();
+    #[allow(clippy::expect_used)]
+    ciborium::into_writer(value, &mut out).expect("writing to a Vec does not fail");
+    out
+}
+
+fn decode(mut bytes: &[u8]) -> Result<Value, Error> {
+    // short byte strings are read via the scratch buffer
+    let mut scratch = [0; 4096];
+    let value = ciborium::de::from_reader_with_buffer(&mut bytes, &mut scratch);
+    #[cfg(feature = "zeroize")]
+    scratch.zeroize();
+    match value {
+        Ok(value) if bytes.is_empty() => Ok(value),
+        Ok(mut value) => {
+            wipe(&mut value);
+            Err(Error::new())
+        }
+        Err(_) => Err(Error::new()),
+    }
+}
+
+/// Zeroize all byte and text strings contained in `value`
+#[cfg(feature = "zeroize")]
+fn wipe(value: &mut Value) {
+    match value {
+        Value::Bytes(bytes) => bytes.zeroize(),
+        Value::Text(text) => text.zeroize(),
+        Value::Array(values) => values.iter_mut().for_each(wipe),
+        Value::Map(entries) => entries.iter_mut().for_each(|(key, value)| {
+            wipe(key);
+            wipe(value);
+        }),
+        Value::Tag(_, value) => wipe(value),
+        _ => {}
+    }
+}
+
+#[cfg(not(feature = "zeroize"))]
+fn wipe(_value: &mut Value) {}
+
+fn encode_map(variant: FaestVariant, fields: [(u64, &[u8]); 2]) -> Vec<u8> {
+    let mut entries = vec![
+        (KEY_VERSION, SCHEMA_VERSION.into()),
+        (KEY_ALGORITHM, algorithm_id(variant).into()),
+    ];
+    entries.extend(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key, Value::Bytes(value.to_vec()))),
+    );
+    entries.sort_by_key(|(key, _)| *key);
+
+    let mut map = Value::Map(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.into(), value))
+            .collect(),
+    );
+    let out = encode(&map);
+    wipe(&mut map);
+    out
+}
+
+/// Decode a map and return the byte strings stored with the given keys
+///
+/// All byte strings of the decoded map are zeroized, including those of
+/// unknown keys and those of rejected encodings.
+fn decode_map(variant: FaestVariant, bytes: &[u8], keys: [u64; 2]) -> Result<[Bytes; 2], Error> {
+    let mut value = decode(bytes)?;
+    let fields = match &mut value {
+        Value::Map(entries) => take_fields(variant, entries, keys),
+        _ => Err(Error::new()),
+    };
+    wipe(&mut value);
+    fields
+}
+
+fn take_fields(
+    variant: FaestVariant,
+    entries: &mut [(Value, Value)],
+    keys: [u64; 2],
+) -> Result<[Bytes; 2], Error> {
+    let mut seen = Vec::with_capacity(entries.len());
+    let mut version = None;
+    let mut algorithm = None;
+    let mut fields = [None, None];
+    for (key, value) in entries {
+        let key = key
+            .as_integer()
+            .and_then(|key| u64::try_from(key).ok())
+            .ok_or_else(Error::new)?;
+        if seen.contains(&key) {
+            return Err(Error::new());
+        }
+        seen.push(key);
+
+        match key {
+            KEY_VERSION => version = value.as_integer().and_then(|v| u64::try_from(v).ok()),
+            KEY_ALGORITHM => algorithm = value.as_integer().and_then(|v| u64::try_from(v).ok()),
+            _ => {
+                if let Some(index) = keys.iter().position(|k| *k == key) {
+                    let Value::Bytes(value) = value else {
+                        return Err(Error::new());
+                    };
+                    fields[index] = Some(Bytes::from(core::mem::take(value)));
+                }
+            }
+        }
+    }
+
+    if version != Some(SCHEMA_VERSION) || algorithm != Some(algorithm_id(variant)) {
+        return Err(Error::new());
+    }
+    match fields {
+        [Some(first), Some(second)] => Ok([first, second]),
+        _ => Err(Error::new()),
+    }
+}
+
+pub(crate) fn encode_verification_key<O>(variant: FaestVariant, pk: &PublicKey<O>) -> Vec<u8>
+where
+    O: OWFParameters,
+{
+    encode_map(
+        variant,
+        [
+            (KEY_OWF_INPUT, pk.owf_input().as_slice()),
+            (KEY_OWF_OUTPUT, pk.owf_output().as_slice()),
+        ],
+    )
+}
+
+pub(crate) fn decode_verification_key<O>(
+    variant: FaestVariant,
+    bytes: &[u8],
+) -> Result<PublicKey<O>, Error>
+where
+    O: OWFParameters,
+{
+    let [owf_input, owf_output] = decode_map(variant, bytes, [KEY_OWF_INPUT, KEY_OWF_OUTPUT])?;
+    if owf_input.len() != O::InputSize::USIZE {
+        return Err(Error::new());
+    }
+    PublicKey::try_from(
+        [owf_input.as_slice(), owf_output.as_slice()]
+            .concat()
+            .as_slice(),
+    )
+}
+
+pub(crate) fn encode_signing_key<O>(variant: FaestVariant, sk: &SecretKey<O>) -> Vec<u8>
+where
+    O: OWFParameters,
+{
+    encode_map(
+        variant,
+        [
+            (KEY_OWF_INPUT, sk.pk.owf_input().as_slice()),
+            (KEY_OWF_KEY, sk.owf_key.as_slice()),
+        ],
+    )
+}
+
+pub(crate) fn decode_signing_key<O>(
+    variant: FaestVariant,
+    bytes: &[u8],
+) -> Result<SecretKey<O>, Error>
+where
+    O: OWFParameters,
+{
+    let [owf_input, owf_key] = decode_map(variant, bytes, [KEY_OWF_INPUT, KEY_OWF_KEY])?;
+    if owf_input.len() != O::InputSize::USIZE {
+        return Err(Error::new());
+    }
+    let sk_bytes = Bytes::from([owf_input.as_slice(), owf_key.as_slice()].concat());
+    SecretKey::try_from(sk_bytes.as_slice())
+}
+
+pub(crate) fn encode_signature(variant: FaestVariant, signature: &[u8]) -> Vec<u8> {
+    encode(&Value::Tag(
+        signature_tag(variant),
+        Box::new(Value::Bytes(signature.to_vec())),
+    ))
+}
+
+pub(crate) fn decode_signature(variant: FaestVariant, bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    match decode(bytes)? {
+        Value::Tag(tag, value) if tag == signature_tag(variant) => match *value {
+            Value::Bytes(signature) => Ok(signature),
+            _ => Err(Error::new()),
+        },
+        _ => Err(Error::new()),
+    }
+}
+
+#[cfg(all(test, feature = "faest-128f", feature = "faest-128s"))]
+mod schema_test {
+    use super::*;
+
+    use crate::{
+        ByteEncoding, FAEST128fSignature, FAEST128fSigningKey, FAEST128fVerificationKey,
+        FAEST128sVerificationKey, KeypairGenerator, Signer,
+    };
+
+    /// Key pair from the NIST KATs of FAEST-128f
+    const OWF_INPUT: &str = "8626ed79d451140800e03b59b956f821";
+    const OWF_KEY: &str = "0e556067407d13dc90fa9e8b872bfb8f";
+    const OWF_OUTPUT: &str = "138c295fd8d4ae73b3c54649db1904f2";
+
+    /// Decode CBOR given as hex with whitespace between the items
+    fn cbor(encoded: &str) -> Vec<u8> {
+        hex::decode(encoded.replace(' ', "")).unwrap()
+    }
+
+    #[test]
+    fn encoding() {
+        let vk =
+            FAEST128fVerificationKey::try_from(cbor(&[OWF_INPUT, OWF_OUTPUT].concat()).as_slice())
+                .unwrap();
+        assert_eq!(
+            vk.to_cbor(),
+            cbor(&format!(
+                "a4 00 01 01 50 {OWF_INPUT} 02 50 {OWF_OUTPUT} 03 01"
+            ))
+        );
+
+        let sk =
+            FAEST128fSigningKey::try_from(cbor(&[OWF_INPUT, OWF_KEY].concat()).as_slice()).unwrap();
+        assert_eq!(
+            sk.to_cbor(),
+            cbor(&format!("a4 00 01 01 50 {OWF_INPUT} 03 01 04 50 {OWF_KEY}"))
+        );
+
+        let signature: FAEST128fSignature =
+            FAEST128fSigningKey::generate(rand::thread_rng()).sign(b"message");
+        let encoded = signature.to_cbor();
+        // tag 0x46410101 and a byte string of 6336 bytes
+        assert_eq!(encoded[..8], cbor("da 46410101 59 18c0"));
+        assert_eq!(&encoded[8..], signature.as_ref());
+    }
+
+    #[test]
+    fn forward_compatibility() {
+        // version 1 with an additional text field using key 5
+        let encoded = cbor(&format!(
+            "a5 00 01 01 50 {OWF_INPUT} 02 50 {OWF_OUTPUT} 03 01 05 63 6e6577"
+        ));
+        let vk = FAEST128fVerificationKey::from_cbor(&encoded).expect("unknown keys are ignored");
+        assert_eq!(
+            vk.to_bytes().as_slice(),
+            cbor(&[OWF_INPUT, OWF_OUTPUT].concat())
+        );
+        // FAEST-128f and FAEST-128s share the OWF, but not the algorithm
+        assert!(FAEST128sVerificationKey::from_cbor(&encoded).is_err());
+
+        // version 2
+        let encoded = cbor(&format!(
+            "a4 00 02 01 50 {OWF_INPUT} 02 50 {OWF_OUTPUT} 03 01"
+        ));
+        assert!(FAEST128fVerificationKey::from_cbor(&encoded).is_err());
+    }
+
+    #[test]
+    fn reject_malformed() {
+        for encoded in [
+            // empty map
+            "a0".to_owned(),
+            // missing OWF output
+            format!("a3 00 01 01 50 {OWF_INPUT} 03 01"),
+            // duplicate key
+            format!("a5 00 01 01 50 {OWF_INPUT} 02 50 {OWF_OUTPUT} 03 01 03 01"),
+            // OWF input of wrong length
+            format!("a4 00 01 01 41 00 02 50 {OWF_OUTPUT} 03 01"),
+            // OWF output as text
+            "a4 00 01 01 41 00 02 61 61 03 01".to_owned(),
+            // text key
+            format!("a5 00 01 01 50 {OWF_INPUT} 02 50 {OWF_OUTPUT} 61 61 00 03 01"),
+        ] {
+            assert!(
+                FAEST128fVerificationKey::from_cbor(&cbor(&encoded)).is_err(),
+                "{encoded} is rejected"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod test {
+    use super::*;
+
+    use std::fmt::Debug;
+
+    use crate::*;
+
+    const TEST_MESSAGE: &[u8] = b"test message";
+
+    #[test]
+    fn round_trip<KP, S>()
+    where
+        KP: KeypairGenerator + Signer<S> + CborEncoding + Eq + Debug,
+        KP::VerifyingKey: Verifier<S> + CborEncoding + Eq + Debug,
+        S: CborEncoding + Eq + Debug,
+    {
+        let sk = KP::generate(rand::thread_rng());
+        let vk = sk.verifying_key();
+        let signature = sk.sign(TEST_MESSAGE);
+
+        let sk2 = KP::from_cbor(&sk.to_cbor()).expect("signing key decodes");
+        let vk2 = KP::VerifyingKey::from_cbor(&vk.to_cbor()).expect("verification key decodes");
+        let signature2 = S::from_cbor(&signature.to_cbor()).expect("signature decodes");
+        assert_eq!(sk, sk2);
+        assert_eq!(vk, vk2);
+        assert_eq!(signature, signature2);
+        vk2.verify(TEST_MESSAGE, &signature2)
+            .expect("signature verifies");
+
+        // encodings are not interchangeable
+        assert!(KP::from_cbor(&vk.to_cbor()).is_err());
+        assert!(KP::VerifyingKey::from_cbor(&sk.to_cbor()).is_err());
+        assert!(S::from_cbor(&vk.to_cbor()).is_err());
+
+        // trailing data is rejected
+        let mut encoded = signature.to_cbor();
+        encoded.push(0);
+        assert!(S::from_cbor(&encoded).is_err());
+        let mut encoded = vk.to_cbor();
+        encoded.push(0);
+        assert!(KP::VerifyingKey::from_cbor(&encoded).is_err());
+    }
+
+    #[cfg(feature = "faest-128f")]
+    #[instantiate_tests(<FAEST128fSigningKey, FAEST128fSignature>)]
+    mod faest_128f {}
+
+    #[cfg(feature = "faest-128s")]
+    #[instantiate_tests(<FAEST128sSigningKey, FAEST128sSignature>)]
+    mod faest_128s {}
+
+    #[cfg(feature = "faest-192f")]
+    #[instantiate_tests(<FAEST192fSigningKey, FAEST192fSignature>)]
+    mod faest_192f {}
+
+    #[cfg(feature = "faest-192s")]
+    #[instantiate_tests(<FAEST192sSigningKey, FAEST192sSignature>)]
+    mod faest_192s {}
+
+    #[cfg(feature = "faest-256f")]
+    #[instantiate_tests(<FAEST256fSigningKey, FAEST256fSignature>)]
+    mod faest_256f {}
+
+    #[cfg(feature = "faest-256s")]
+    #[instantiate_tests(<FAEST256sSigningKey, FAEST256sSignature>)]
+    mod faest_256s {}
+
+    #[cfg(feature = "faest-em-128f")]
+    #[instantiate_tests(<FAESTEM128fSigningKey, FAESTEM128fSignature>)]
+    mod faest_em_128f {}
+
+    #[cfg(feature = "faest-em-128s")]
+    #[instantiate_tests(<FAESTEM128sSigningKey, FAESTEM128sSignature>)]
+    mod faest_em_128s {}
+
+    #[cfg(feature = "faest-em-192f")]
+    #[instantiate_tests(<FAESTEM192fSigningKey, FAESTEM192fSignature>)]
+    mod faest_em_192f {}
+
+    #[cfg(feature = "faest-em-192s")]
+    #[instantiate_tests(<FAESTEM192sSigningKey, FAESTEM192sSignature>)]
+    mod faest_em_192s {}
+
+    #[cfg(feature = "faest-em-256f")]
+    #[instantiate_tests(<FAESTEM256fSigningKey, FAESTEM256fSignature>)]
+    mod faest_em_256f {}
+
+    #[cfg(feature = "faest-em-256s")]
+    #[instantiate_tests(<FAESTEM256sSigningKey, FAESTEM256sSignature>)]
+    mod faest_em_256s {}
+
+    #[cfg(feature = "blake3-oracle")]
+    #[instantiate_tests(<crate::FAEST128fBlake3SigningKey, crate::FAEST128fBlake3Signature>)]
+    mod faest_128f_blake3 {}
+
+    #[cfg(feature = "blake3-oracle")]
+    #[instantiate_tests(<crate::FAEST128sBlake3SigningKey, crate::FAEST128sBlake3Signature>)]
+    mod faest_128s_blake3 {}
+
+    #[cfg(feature = "experimental")]
+    #[instantiate_tests(<crate::FAEST128usSigningKey, crate::FAEST128usSignature>)]
+    mod faest_128us {}
+}
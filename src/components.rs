@@ -0,0 +1,244 @@
+//! Structured access to the components of signatures
+
+#[cfg(feature = "_variant")]
+use generic_array::{typenum::Unsigned, GenericArray};
+
+#[cfg(feature = "_variant")]
+use crate::{
+    parameter::{BaseParameters, FAESTParameters, OWFParameters, TauParameters},
+    prg::IVSize,
+    Error,
+};
+
+#[cfg(feature = "_variant")]
+type LambdaBytes<P> = <<P as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES;
+#[cfg(feature = "_variant")]
+type LHatBytes<P> = <<P as FAESTParameters>::OWF as OWFParameters>::LHATBYTES;
+#[cfg(feature = "_variant")]
+type LBytes<P> = <<P as FAESTParameters>::OWF as OWFParameters>::LBYTES;
+#[cfg(feature = "_variant")]
+type BaseParams<P> = <<P as FAESTParameters>::OWF as OWFParameters>::BaseParams;
+#[cfg(feature = "_variant")]
+type Tau<P> = <P as FAESTParameters>::Tau;
+
+/// Components of a signature
+///
+/// The components are sub-slices of the signature in the order in which they
+/// are encoded. Signatures can be reassembled from their components with
+/// `from_components` of the signature types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureComponents<'a> {
+    /// Corrections `c_1, ..., c_{τ-1}` of the VOLE correlations
+    pub corrections: Vec<&'a [u8]>,
+    /// Masked hash `ũ` of the VOLE correlations
+    pub u_tilde: &'a [u8],
+    /// Correction `d` of the witness
+    pub d: &'a [u8],
+    /// QuickSilver proof `ã`
+    pub a_t: &'a [u8],
+    /// Partial decommitment of the vector commitment of each repetition
+    pub partial_decommitments: Vec<&'a [u8]>,
+    /// Third challenge
+    pub chall3: &'a [u8],
+    /// IV of the vector commitments
+    pub iv: &'a [u8],
+}
+
+/// Size of the partial decommitment of repetition `i`
+#[cfg(feature = "_variant")]
+fn partial_decommitment_size<P>(i: usize) -> usize
+where
+    P: FAESTParameters,
+{
+    let k = if i < <Tau<P> as TauParameters>::Tau0::USIZE {
+        <Tau<P> as TauParameters>::K0::USIZE
+    } else {
+        <Tau<P> as TauParameters>::K1::USIZE
+    };
+    (k + 2) * LambdaBytes::<P>::USIZE
+}
+
+/// Split a signature into its components
+#[cfg(feature = "_variant")]
+pub(crate) fn split<P>(signature: &GenericArray<u8, P::SignatureSize>) -> SignatureComponents<'_>
+where
+    P: FAESTParameters,
+{
+    let tau = <Tau<P> as TauParameters>::Tau::USIZE;
+
+    let (corrections, signature) = signature.split_at(LHatBytes::<P>::USIZE * (tau - 1));
+    let (u_tilde, signature) = signature.split_at(BaseParams::<P>::vole_output_length_bytes());
+    let (d, signature) = signature.split_at(LBytes::<P>::USIZE);
+    let (a_t, mut signature) = signature.split_at(LambdaBytes::<P>::USIZE);
+    let partial_decommitments = (0..tau)
+        .map(|i| {
+            let (pdecom, rest) = signature.split_at(partial_decommitment_size::<P>(i));
+            signature = rest;
+            pdecom
+        })
+        .collect();
+    let (chall3, iv) = signature.split_at(LambdaBytes::<P>::USIZE);
+    debug_assert_eq!(iv.len(), IVSize::USIZE);
+
+    SignatureComponents {
+        corrections: corrections.chunks_exact(LHatBytes::<P>::USIZE).collect(),
+        u_tilde,
+        d,
+        a_t,
+        partial_decommitments,
+        chall3,
+        iv,
+    }
+}
+
+/// Reassemble a signature from its components
+///
+/// Fails if the number of corrections or partial decommitments, or the size
+/// of any component does not match the parameters.
+#[cfg(feature = "_variant")]
+pub(crate) fn join<P>(
+    components: &SignatureComponents<'_>,
+) -> Result<GenericArray<u8, P::SignatureSize>, Error>
+where
+    P: FAESTParameters,
+{
+    let tau = <Tau<P> as TauParameters>::Tau::USIZE;
+    let valid = components.corrections.len() == tau - 1
+        && components
+            .corrections
+            .iter()
+            .all(|c| c.len() == LHatBytes::<P>::USIZE)
+        && components.u_tilde.len() == BaseParams::<P>::vole_output_length_bytes()
+        && components.d.len() == LBytes::<P>::USIZE
+        && components.a_t.len() == LambdaBytes::<P>::USIZE
+        && components.partial_decommitments.len() == tau
+        && components
+            .partial_decommitments
+            .iter()
+            .enumerate()
+            .all(|(i, pdecom)| pdecom.len() == partial_decommitment_size::<P>(i))
+        && components.chall3.len() == LambdaBytes::<P>::USIZE
+        && components.iv.len() == IVSize::USIZE;
+    if !valid {
+        return Err(Error::new());
+    }
+
+    let parts = components
+        .corrections
+        .iter()
+        .chain([&components.u_tilde, &components.d, &components.a_t])
+        .chain(components.partial_decommitments.iter())
+        .chain([&components.chall3, &components.iv]);
+    let mut signature = GenericArray::default();
+    let mut offset = 0;
+    for part in parts {
+        signature[offset..offset + part.len()].copy_from_slice(part);
+        offset += part.len();
+    }
+    debug_assert_eq!(offset, P::SIGNATURE_SIZE);
+    Ok(signature)
+}
+
+#[cfg(all(test, feature = "_variant"))]
+#[generic_tests::define]
+mod test {
+    use super::*;
+
+    use rand::RngCore;
+
+    use crate::parameter::{
+        FAEST128fParameters, FAEST128sParameters, FAEST192fParameters, FAEST192sParameters,
+        FAEST256fParameters, FAEST256sParameters, FAESTEM128fParameters, FAESTEM128sParameters,
+        FAESTEM192fParameters, FAESTEM192sParameters, FAESTEM256fParameters, FAESTEM256sParameters,
+    };
+
+    fn random_signature<P: FAESTParameters>() -> GenericArray<u8, P::SignatureSize> {
+        let mut signature = GenericArray::default();
+        rand::thread_rng().fill_bytes(&mut signature);
+        signature
+    }
+
+    #[test]
+    fn component_sizes<P: FAESTParameters>() {
+        let signature = random_signature::<P>();
+        let components = split::<P>(&signature);
+
+        let size: usize = components
+            .corrections
+            .iter()
+            .chain([&components.u_tilde, &components.d, &components.a_t])
+            .chain(components.partial_decommitments.iter())
+            .chain([&components.chall3, &components.iv])
+            .map(|part| part.len())
+            .sum();
+        assert_eq!(size, P::SignatureSize::USIZE);
+        assert_eq!(
+            components.corrections.len() + 1,
+            <P::Tau as TauParameters>::Tau::USIZE
+        );
+        assert_eq!(
+            components.partial_decommitments.len(),
+            <P::Tau as TauParameters>::Tau::USIZE
+        );
+    }
+
+    #[test]
+    fn reassemble<P: FAESTParameters>() {
+        let signature = random_signature::<P>();
+        let components = split::<P>(&signature);
+        assert_eq!(join::<P>(&components).expect("valid components"), signature);
+
+        let mut invalid = components.clone();
+        invalid.corrections.pop();
+        assert!(join::<P>(&invalid).is_err());
+
+        let mut invalid = components.clone();
+        invalid.iv = &invalid.iv[1..];
+        assert!(join::<P>(&invalid).is_err());
+
+        // sizes of the partial decommitments differ if K0 != K1
+        let mut invalid = components;
+        invalid.partial_decommitments.reverse();
+        assert_eq!(
+            join::<P>(&invalid).is_ok(),
+            <P::Tau as TauParameters>::K0::USIZE == <P::Tau as TauParameters>::K1::USIZE
+                || <P::Tau as TauParameters>::Tau1::USIZE == 0
+        );
+    }
+
+    #[instantiate_tests(<FAEST128fParameters>)]
+    mod faest_128f {}
+
+    #[instantiate_tests(<FAEST128sParameters>)]
+    mod faest_128s {}
+
+    #[instantiate_tests(<FAEST192fParameters>)]
+    mod faest_192f {}
+
+    #[instantiate_tests(<FAEST192sParameters>)]
+    mod faest_192s {}
+
+    #[instantiate_tests(<FAEST256fParameters>)]
+    mod faest_256f {}
+
+    #[instantiate_tests(<FAEST256sParameters>)]
+    mod faest_256s {}
+
+    #[instantiate_tests(<FAESTEM128fParameters>)]
+    mod faest_em_128f {}
+
+    #[instantiate_tests(<FAESTEM128sParameters>)]
+    mod faest_em_128s {}
+
+    #[instantiate_tests(<FAESTEM192fParameters>)]
+    mod faest_em_192f {}
+
+    #[instantiate_tests(<FAESTEM192sParameters>)]
+    mod faest_em_192s {}
+
+    #[instantiate_tests(<FAESTEM256fParameters>)]
+    mod faest_em_256f {}
+
+    #[instantiate_tests(<FAESTEM256sParameters>)]
+    mod faest_em_256s {}
+}
@@ -0,0 +1,9 @@
+//! Constants of the FAEST specification
+
+/// Number of additional bits `B` of the VOLE hash
+///
+/// The VOLE hash `VOLEHash` compresses the VOLE correlations to `λ + B` bits.
+/// The `B` bits beyond the security parameter absorb the collision
+/// probability of the universal hash, so that `ũ` binds the prover to the
+/// committed correlations with soundness error `2^-λ`.
+pub const VOLE_REDUNDANCY_BITS: usize = 16;
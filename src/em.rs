@@ -1,19 +1,50 @@
 use std::{array, iter::zip, mem::size_of};
 
-use generic_array::{typenum::Unsigned, GenericArray};
+use aes::cipher::{BlockEncrypt, KeyInit};
+use generic_array::{typenum::Unsigned, ArrayLength, GenericArray};
 use itertools::{chain, iproduct};
 
 use crate::{
-    fields::{ByteCombine, ByteCombineConstants, Field as _, SumPoly},
-    internal_keys::PublicKey,
-    parameter::{BaseParameters, OWFParameters, QSProof, TauParameters},
+    fields::{ByteCombine, ByteCombineConstants},
+    parameter::OWFParameters,
     rijndael_32::{
         bitslice, convert_from_batchblocks, inv_bitslice, mix_columns_0, rijndael_add_round_key,
         rijndael_key_schedule, rijndael_shift_rows_1, sub_bytes, sub_bytes_nots, State,
     },
-    universal_hashing::{ZKHasherInit, ZKProofHasher, ZKVerifyHasher},
-    utils::{bit_combine_with_delta, convert_gq, transpose_and_into_field, Field},
+    utils::Field,
+};
+#[cfg(feature = "_variant")]
+use crate::{
+    fields::{Double, Field as _},
+    internal_keys::PublicKey,
+    parameter::{BaseParameters, FAESTParameters},
+    universal_hashing::{evaluate_at, ZKHasherInit, ZKVerifyHasher},
+    utils::{bit_combine_with_delta, convert_gq, witness_bit_at},
 };
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use crate::{
+    owf::KeyRejected, parameter::QSProof, universal_hashing::ZKProofHasher,
+    utils::transpose_and_into_field,
+};
+
+/// The Even-Mansour OWF `E_input(key) ⊕ key`
+///
+/// The cipher `C` is keyed with the public `input` and encrypts the secret
+/// `key`, whose feed-forward XOR gives the output.
+pub(crate) fn em_evaluate<C, N>(
+    key: &GenericArray<u8, N>,
+    input: &GenericArray<u8, N>,
+    output: &mut GenericArray<u8, N>,
+) where
+    C: KeyInit<KeySize = N> + BlockEncrypt<BlockSize = N>,
+    N: ArrayLength + aes::cipher::ArrayLength<u8>,
+{
+    let cipher = C::new(input.as_0_14());
+    cipher.encrypt_block_b2b(key.as_0_14(), output.as_0_14_mut());
+    for (output, key) in zip(output.iter_mut(), key) {
+        *output ^= key;
+    }
+}
 
 pub(crate) fn em_extendedwitness<O>(
     owf_key: &GenericArray<u8, O::LAMBDABYTES>,
@@ -61,6 +92,46 @@ where
     }
 }
 
+/// Locate the first zero S-box input of the OWF
+///
+/// Checks the same S-box inputs as [em_extendedwitness], i.e., the inputs of
+/// all rounds of the encryption of the key.
+#[cfg(all(feature = "sign", feature = "_variant"))]
+pub(crate) fn em_find_zero_sbox_input<O>(
+    owf_key: &GenericArray<u8, O::LAMBDABYTES>,
+    owf_input: &GenericArray<u8, O::InputSize>,
+) -> Option<KeyRejected>
+where
+    O: OWFParameters,
+{
+    let (kb, _) = rijndael_key_schedule::<O::NST, O::NK, O::R>(
+        owf_input,
+        4 * (((O::R::USIZE + 1) * O::NST::USIZE) / O::NK::USIZE),
+    );
+    let mut state = State::default();
+    bitslice(&mut state, &owf_key[..16], &owf_key[16..]);
+    rijndael_add_round_key(&mut state, &kb[..8]);
+    for round in 1..=O::R::USIZE {
+        let inv_state = inv_bitslice(&state);
+        if chain(inv_state[0], inv_state[1])
+            .take(O::NST::USIZE * 4)
+            .any(|i| i == 0)
+        {
+            return Some(KeyRejected::Encryption { block: 0, round });
+        }
+        if round == O::R::USIZE {
+            break;
+        }
+
+        sub_bytes(&mut state);
+        sub_bytes_nots(&mut state);
+        rijndael_shift_rows_1::<O::NST>(&mut state);
+        mix_columns_0(&mut state);
+        rijndael_add_round_key(&mut state, &kb[8 * round..8 * (round + 1)]);
+    }
+    None
+}
+
 /// Implementation of `EncFwd` with `GF(2)`
 fn em_enc_fwd_1<'a, O>(z: &'a [u8], x: &'a [u8]) -> impl Iterator<Item = Field<O>> + 'a
 where
@@ -105,6 +176,7 @@ where
 }
 
 /// Implementation of `EncFwd` for `GF(\lambda)` for signing
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn em_enc_fwd_proof<O>(z: &[Field<O>]) -> impl Iterator<Item = Field<O>> + '_
 where
     O: OWFParameters,
@@ -147,6 +219,7 @@ where
         )
 }
 
+#[cfg(feature = "_variant")]
 fn em_enc_fwd_verify<'a, O>(
     z: &'a [Field<O>],
     x: &'a [u8],
@@ -222,6 +295,7 @@ where
     })
 }
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn em_enc_bkwd_mkey0_mtag1<O>(
     z: &GenericArray<Field<O>, O::L>,
 ) -> impl Iterator<Item = Field<O>> + '_
@@ -246,6 +320,7 @@ where
     })
 }
 
+#[cfg(feature = "_variant")]
 fn em_enc_bkwd_mkey1_mtag0<'a, O>(
     x: &'a GenericArray<u8, O::LAMBDAR1BYTE>,
     z: &'a GenericArray<Field<O>, O::L>,
@@ -277,6 +352,7 @@ where
     })
 }
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn em_enc_cstrnts_mkey0<O>(
     zk_hasher: &mut ZKProofHasher<Field<O>>,
     output: &GenericArray<u8, O::InputSize>,
@@ -294,6 +370,7 @@ fn em_enc_cstrnts_mkey0<O>(
     zk_hasher.process(s, vs, s_b, v_s_b);
 }
 
+#[cfg(feature = "_variant")]
 fn em_enc_cstrnts_mkey1<O>(
     zk_hasher: &mut ZKVerifyHasher<Field<O>>,
     output: &GenericArray<u8, O::InputSize>,
@@ -304,13 +381,55 @@ fn em_enc_cstrnts_mkey1<O>(
     O: OWFParameters,
 {
     let q_out = Box::<GenericArray<Field<O>, O::LAMBDA>>::from_iter(
-        (0..O::LAMBDA::USIZE).map(|idx| *delta * ((output[idx / 8] >> (idx % 8)) & 1) + q[idx]),
+        (0..O::LAMBDA::USIZE).map(|idx| *delta * witness_bit_at(output, idx) + q[idx]),
     );
     let qs = em_enc_fwd_verify::<O>(q, x, delta);
     let qs_b = em_enc_bkwd_mkey1_mtag0::<O>(x, q, &q_out, delta);
     zk_hasher.process(qs, qs_b);
 }
 
+/// Round keys of the Rijndael key schedule keyed with `owf_input`
+fn em_round_keys<O>(owf_input: &GenericArray<u8, O::InputSize>) -> GenericArray<u8, O::LAMBDAR1BYTE>
+where
+    O: OWFParameters,
+{
+    let (x, _) = rijndael_key_schedule::<O::NST, O::NK, O::R>(
+        owf_input,
+        4 * (((O::R::USIZE + 1) * O::NST::USIZE) / O::NK::USIZE),
+    );
+    x.chunks(8)
+        .flat_map(|x| {
+            convert_from_batchblocks(inv_bitslice(x))
+                .flatten()
+                .take(O::LAMBDABYTES::USIZE)
+        })
+        .take(O::LAMBDABYTES::USIZE * (O::R::USIZE + 1))
+        .collect()
+}
+
+/// Evaluate both sides of all constraints `s·s_b = 1` on the witness `w`
+///
+/// The sides are computed as by the prover, i.e., as affine functions of the
+/// bits of `w`.
+pub(crate) fn em_constraint_values<O>(
+    w: &GenericArray<u8, O::LBYTES>,
+    owf_input: &GenericArray<u8, O::InputSize>,
+    owf_output: &GenericArray<u8, O::InputSize>,
+) -> Vec<(Field<O>, Field<O>)>
+where
+    O: OWFParameters,
+{
+    let x = em_round_keys::<O>(owf_input);
+    let w_out =
+        GenericArray::from_iter(zip(&w[..O::InputSize::USIZE], owf_output).map(|(l, r)| l ^ r));
+    zip(
+        em_enc_fwd_1::<O>(w, &x),
+        em_enc_bkwd_mkey0_mtag0::<O>(&x, w, &w_out),
+    )
+    .collect()
+}
+
+#[cfg(all(feature = "sign", feature = "_variant"))]
 pub(crate) fn em_prove<O>(
     w: &GenericArray<u8, O::LBYTES>,
     u: &GenericArray<u8, O::LAMBDALBYTES>,
@@ -326,34 +445,25 @@ where
     let mut zk_hasher =
         <<O as OWFParameters>::BaseParams as BaseParameters>::ZKHasher::new_zk_proof_hasher(chall);
 
-    let (x, _) = rijndael_key_schedule::<O::NST, O::NK, O::R>(
-        &pk.owf_input,
-        4 * (((O::R::USIZE + 1) * O::NST::USIZE) / O::NK::USIZE),
-    );
+    let x = em_round_keys::<O>(pk.owf_input());
     em_enc_cstrnts_mkey0::<O>(
         &mut zk_hasher,
-        &pk.owf_output,
-        &x.chunks(8)
-            .flat_map(|x| {
-                convert_from_batchblocks(inv_bitslice(x))
-                    .flatten()
-                    .take(O::LAMBDABYTES::USIZE)
-            })
-            .take(O::LAMBDABYTES::USIZE * (O::R::USIZE + 1))
-            .collect::<GenericArray<u8, _>>(),
+        pk.owf_output(),
+        &x,
         w,
         GenericArray::from_slice(&new_v[..O::L::USIZE]),
     );
     let u_s = Field::<O>::from(&u[O::LBYTES::USIZE..]);
-    let v_s = Field::<O>::sum_poly(&new_v[O::L::USIZE..O::L::USIZE + O::LAMBDA::USIZE]);
+    let v_s = evaluate_at::<O>(&new_v, &Field::<O>::ONE.double());
     let (a_t, b_t) = zk_hasher.finalize(&u_s, &v_s);
     (a_t.as_bytes(), b_t.as_bytes())
 }
 
+#[cfg(feature = "_variant")]
 #[allow(clippy::too_many_arguments)]
-pub(crate) fn em_verify<O, Tau>(
+pub(crate) fn em_verify<O, P>(
     d: &GenericArray<u8, O::LBYTES>,
-    gq: Box<GenericArray<GenericArray<u8, O::LAMBDALBYTES>, O::LAMBDA>>,
+    gq: &mut GenericArray<GenericArray<u8, O::LAMBDALBYTES>, O::LAMBDA>,
     a_t: &GenericArray<u8, O::LAMBDABYTES>,
     chall2: &GenericArray<u8, <<O as OWFParameters>::BaseParams as BaseParameters>::Chall>,
     chall3: &GenericArray<u8, O::LAMBDABYTES>,
@@ -361,35 +471,25 @@ pub(crate) fn em_verify<O, Tau>(
 ) -> GenericArray<u8, O::LAMBDABYTES>
 where
     O: OWFParameters,
-    Tau: TauParameters,
+    P: FAESTParameters<OWF = O>,
 {
     let delta = Field::<O>::from(chall3);
 
-    let new_q = convert_gq::<O, Tau>(d, gq, chall3);
+    let new_q = convert_gq::<O, P::Tau>(d, gq, chall3);
     let mut zk_hasher =
         <<O as OWFParameters>::BaseParams as BaseParameters>::ZKHasher::new_zk_verify_hasher(
             chall2, delta,
         );
-    let (x, _) = rijndael_key_schedule::<O::NST, O::NK, O::R>(
-        &pk.owf_input,
-        4 * (((O::R::USIZE + 1) * O::NST::USIZE) / O::NK::USIZE),
-    );
+    let x = em_round_keys::<O>(pk.owf_input());
     em_enc_cstrnts_mkey1::<O>(
         &mut zk_hasher,
-        &pk.owf_output,
-        &x.chunks(8)
-            .flat_map(|x| {
-                convert_from_batchblocks(inv_bitslice(x))
-                    .flatten()
-                    .take(O::LAMBDABYTES::USIZE)
-            })
-            .take(O::LAMBDABYTES::USIZE * (O::R::USIZE + 1))
-            .collect::<GenericArray<u8, _>>(),
+        pk.owf_output(),
+        &x,
         GenericArray::from_slice(&new_q[..O::L::USIZE]),
         &delta,
     );
 
-    let q_s = Field::<O>::sum_poly(&new_q[O::L::USIZE..O::L::USIZE + O::LAMBDA::USIZE]);
+    let q_s = evaluate_at::<O>(&new_q, &Field::<O>::ONE.double());
     (zk_hasher.finalize(&q_s) + Field::<O>::from(a_t) * delta).as_bytes()
 }
 
@@ -397,16 +497,19 @@ where
 mod test {
     use super::*;
 
+    #[cfg(feature = "_variant")]
+    use crate::parameter::{
+        FAESTEM128fParameters, FAESTEM128sParameters, FAESTEM192fParameters, FAESTEM192sParameters,
+        FAESTEM256fParameters, FAESTEM256sParameters, FAESTParameters,
+    };
     use crate::{
-        parameter::{
-            FAESTEM128fParameters, FAESTEM128sParameters, FAESTEM192fParameters,
-            FAESTEM192sParameters, FAESTEM256fParameters, FAESTEM256sParameters, FAESTParameters,
-            OWF128EM, OWF192EM, OWF256EM,
-        },
+        parameter::{OWF128EM, OWF192EM, OWF256EM},
         utils::test::read_test_data,
     };
 
-    use generic_array::{ArrayLength, GenericArray};
+    #[cfg(feature = "_variant")]
+    use generic_array::ArrayLength;
+    use generic_array::GenericArray;
     use serde::Deserialize;
 
     #[derive(Debug, Deserialize)]
@@ -448,6 +551,7 @@ mod test {
         }
     }
 
+    #[cfg(feature = "_variant")]
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "camelCase")]
     struct EmProve {
@@ -462,15 +566,16 @@ mod test {
         bt: Vec<u8>,
     }
 
+    #[cfg(feature = "_variant")]
     impl EmProve {
         fn as_pk<O>(&self) -> PublicKey<O>
         where
             O: OWFParameters,
         {
-            PublicKey {
-                owf_input: GenericArray::from_slice(&self.input).clone(),
-                owf_output: GenericArray::from_slice(&self.output).clone(),
-            }
+            PublicKey::new(
+                GenericArray::from_slice(&self.input),
+                GenericArray::from_slice(&self.output),
+            )
         }
 
         fn as_gv<LHI, LHO>(&self) -> GenericArray<GenericArray<u8, LHI>, LHO>
@@ -485,6 +590,7 @@ mod test {
         }
     }
 
+    #[cfg(feature = "_variant")]
     #[test]
     fn em_prove_test() {
         let database: Vec<EmProve> = read_test_data("EmProve.json");
@@ -525,6 +631,7 @@ mod test {
         }
     }
 
+    #[cfg(feature = "_variant")]
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "camelCase")]
     struct EmVerify {
@@ -540,15 +647,16 @@ mod test {
         qt: Vec<u8>,
     }
 
+    #[cfg(feature = "_variant")]
     impl EmVerify {
         fn as_pk<O>(&self) -> PublicKey<O>
         where
             O: OWFParameters,
         {
-            PublicKey {
-                owf_input: GenericArray::from_slice(&self.input).clone(),
-                owf_output: GenericArray::from_slice(&self.output).clone(),
-            }
+            PublicKey::new(
+                GenericArray::from_slice(&self.input),
+                GenericArray::from_slice(&self.output),
+            )
         }
 
         fn as_gq<LHI, LHO>(&self) -> Box<GenericArray<GenericArray<u8, LHI>, LHO>>
@@ -563,14 +671,15 @@ mod test {
         }
     }
 
-    fn em_verify<O, Tau>(data: &EmVerify) -> GenericArray<u8, O::LAMBDABYTES>
+    #[cfg(feature = "_variant")]
+    fn em_verify<O, P>(data: &EmVerify) -> GenericArray<u8, O::LAMBDABYTES>
     where
         O: OWFParameters,
-        Tau: TauParameters,
+        P: FAESTParameters<OWF = O>,
     {
-        super::em_verify::<O, Tau>(
+        super::em_verify::<O, P>(
             GenericArray::from_slice(&data.d),
-            data.as_gq(),
+            &mut data.as_gq(),
             GenericArray::from_slice(&data.at),
             GenericArray::from_slice(&data.chall2),
             GenericArray::from_slice(&data.chall3),
@@ -578,29 +687,30 @@ mod test {
         )
     }
 
+    #[cfg(feature = "_variant")]
     #[test]
     fn em_verify_test() {
         let database: Vec<EmVerify> = read_test_data("EmVerify.json");
         for data in database {
             if data.lambda == 128 {
                 let res = if data.tau == 11 {
-                    em_verify::<OWF128EM, <FAESTEM128sParameters as FAESTParameters>::Tau>(&data)
+                    em_verify::<OWF128EM, FAESTEM128sParameters>(&data)
                 } else {
-                    em_verify::<OWF128EM, <FAESTEM128fParameters as FAESTParameters>::Tau>(&data)
+                    em_verify::<OWF128EM, FAESTEM128fParameters>(&data)
                 };
                 assert_eq!(res.as_slice(), data.qt.as_slice());
             } else if data.lambda == 192 {
                 let res = if data.tau == 16 {
-                    em_verify::<OWF192EM, <FAESTEM192sParameters as FAESTParameters>::Tau>(&data)
+                    em_verify::<OWF192EM, FAESTEM192sParameters>(&data)
                 } else {
-                    em_verify::<OWF192EM, <FAESTEM192fParameters as FAESTParameters>::Tau>(&data)
+                    em_verify::<OWF192EM, FAESTEM192fParameters>(&data)
                 };
                 assert_eq!(res.as_slice(), data.qt.as_slice());
             } else {
                 let res = if data.tau == 22 {
-                    em_verify::<OWF256EM, <FAESTEM256sParameters as FAESTParameters>::Tau>(&data)
+                    em_verify::<OWF256EM, FAESTEM256sParameters>(&data)
                 } else {
-                    em_verify::<OWF256EM, <FAESTEM256fParameters as FAESTParameters>::Tau>(&data)
+                    em_verify::<OWF256EM, FAESTEM256fParameters>(&data)
                 };
                 assert_eq!(res.as_slice(), data.qt.as_slice());
             }
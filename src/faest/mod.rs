@@ -0,0 +1,756 @@
+//! Signing and verification of FAEST
+//!
+//! The prover side, i.e., key generation and signing, lives in `sign` and is
+//! only available with the `sign` feature. The verifier side lives in
+//! `verify`. Both share the hashes defined here.
+
+use crate::{
+    hash_values::{Challenge1, Challenge2, Challenge3, MessageHash},
+    parameter::{BaseParameters, FAESTParameters, OWFParameters},
+    prg::IV,
+    random_oracles::{Hasher, RandomOracle},
+    utils::Reader,
+};
+
+/// Mode byte of [`Domain::Context`]
+const CONTEXT_MODE: u8 = 1;
+/// Mode byte of [`Domain::Prehash`]
+#[cfg(feature = "prehash")]
+const PREHASH_MODE: u8 = 2;
+/// Mode byte of [`Domain::ExternalPrehash`]
+#[cfg(feature = "prehash")]
+const EXTERNAL_PREHASH_MODE: u8 = 3;
+
+/// Domain of a signed message
+///
+/// Plain messages are hashed into `µ` as specified by FAEST. For all other
+/// domains, `µ` is computed with [`RandomOracle::h1_domain_init`] on
+///
+/// `input || output || mode || encoding of the domain || msg`
+///
+/// where `mode` is a single byte identifying the domain. As the separator of
+/// the hasher differs from the one of `H1`, a signature in one domain is
+/// never valid in another domain, independent of the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Domain<'a> {
+    /// Plain message as in the FAEST specification
+    Plain,
+    /// Message bound to a non-empty context of at most 255 bytes
+    Context(&'a [u8]),
+    /// Digest of the message under the hash function with the given name of
+    /// at most 255 bytes
+    ///
+    /// Only the names of the sealed `PrehashDigest` implementations are used.
+    #[cfg(feature = "prehash")]
+    Prehash(&'a [u8]),
+    /// Digest of the message under a hash function chosen by the caller
+    #[cfg(feature = "prehash")]
+    ExternalPrehash,
+}
+
+impl Domain<'_> {
+    /// Absorb the mode and the encoding of the domain
+    fn absorb(self, hasher: &mut impl Hasher) {
+        match self {
+            Domain::Plain => {}
+            Domain::Context(context) => {
+                debug_assert!(!context.is_empty() && context.len() <= u8::MAX as usize);
+                hasher.update(&[CONTEXT_MODE, context.len() as u8]);
+                hasher.update(context);
+            }
+            #[cfg(feature = "prehash")]
+            Domain::Prehash(name) => {
+                debug_assert!(name.len() <= u8::MAX as usize);
+                hasher.update(&[PREHASH_MODE, name.len() as u8]);
+                hasher.update(name);
+            }
+            #[cfg(feature = "prehash")]
+            Domain::ExternalPrehash => hasher.update(&[EXTERNAL_PREHASH_MODE]),
+        }
+    }
+
+    /// Number of bytes absorbed by [`Self::absorb`]
+    #[cfg(feature = "transcript")]
+    fn encoded_len(self) -> usize {
+        match self {
+            Domain::Plain => 0,
+            Domain::Context(context) => 2 + context.len(),
+            #[cfg(feature = "prehash")]
+            Domain::Prehash(name) => 2 + name.len(),
+            #[cfg(feature = "prehash")]
+            Domain::ExternalPrehash => 1,
+        }
+    }
+}
+
+type RO<P> =
+    <<<P as FAESTParameters>::OWF as OWFParameters>::BaseParams as BaseParameters>::RandomOracle;
+type VoleHasher<P> =
+    <<<P as FAESTParameters>::OWF as OWFParameters>::BaseParams as BaseParameters>::VoleHasher;
+
+/// Hashes required for FAEST implementation
+///
+/// The challenges are chained rather than forked: each challenge hash absorbs
+/// the previous challenge first, so no two hashes share a prefix and every
+/// hash starts from a fresh state.
+trait FaestHash {
+    /// Generate `µ` from the full public key, i.e., the OWF input and output,
+    /// and the message in `domain`
+    fn hash_mu<O: OWFParameters>(
+        input: &[u8],
+        output: &[u8],
+        domain: Domain<'_>,
+        msg: &[&[u8]],
+    ) -> MessageHash<O>;
+    /// Generate `r` and `iv`
+    #[cfg(feature = "sign")]
+    fn hash_r_iv<O: OWFParameters>(
+        r: &mut [u8],
+        iv: &mut IV,
+        key: &[u8],
+        mu: &MessageHash<O>,
+        rho: &[u8],
+    );
+    /// Generate first challange
+    fn hash_challenge_1<O: OWFParameters>(
+        mu: &MessageHash<O>,
+        hcom: &[u8],
+        c: &[u8],
+        iv: &IV,
+    ) -> Challenge1<O>;
+    /// Generate second challenge
+    fn hash_challenge_2<O: OWFParameters>(
+        chall1: &Challenge1<O>,
+        u_t: &[u8],
+        hv: &[u8],
+        d: &[u8],
+    ) -> Challenge2<O>;
+    /// Generate third challenge
+    fn hash_challenge_3<O: OWFParameters>(
+        chall2: &Challenge2<O>,
+        a_t: &[u8],
+        b_t: &[u8],
+    ) -> Challenge3<O>;
+}
+
+impl<RO> FaestHash for RO
+where
+    RO: RandomOracle,
+{
+    fn hash_mu<O: OWFParameters>(
+        input: &[u8],
+        output: &[u8],
+        domain: Domain<'_>,
+        msg: &[&[u8]],
+    ) -> MessageHash<O> {
+        fn hash<O: OWFParameters>(
+            mut hasher: impl Hasher,
+            input: &[u8],
+            output: &[u8],
+            domain: Domain<'_>,
+            msg: &[&[u8]],
+        ) -> MessageHash<O> {
+            hasher.update(input);
+            hasher.update(output);
+            domain.absorb(&mut hasher);
+            for part in msg {
+                hasher.update(part);
+            }
+            let mu = MessageHash::from(hasher.finish().read_into());
+            #[cfg(feature = "transcript")]
+            crate::transcript::record(
+                "H1",
+                "mu",
+                input.len()
+                    + output.len()
+                    + domain.encoded_len()
+                    + msg.iter().map(|part| part.len()).sum::<usize>(),
+                mu.as_ref(),
+            );
+            mu
+        }
+
+        match domain {
+            Domain::Plain => hash(Self::h1_init(), input, output, domain, msg),
+            _ => hash(Self::h1_domain_init(), input, output, domain, msg),
+        }
+    }
+
+    #[cfg(feature = "sign")]
+    fn hash_r_iv<O: OWFParameters>(
+        r: &mut [u8],
+        iv: &mut IV,
+        key: &[u8],
+        mu: &MessageHash<O>,
+        rho: &[u8],
+    ) {
+        let mut h3_hasher = Self::h3_init();
+        h3_hasher.update(key);
+        h3_hasher.update(mu.as_ref());
+        h3_hasher.update(rho);
+
+        let mut h3_reader = h3_hasher.finish();
+        h3_reader.read(r);
+        h3_reader.read(iv);
+        // r is not recorded as it reveals the witness
+        #[cfg(feature = "transcript")]
+        crate::transcript::record("H3", "iv", key.len() + mu.len() + rho.len(), iv);
+    }
+
+    fn hash_challenge_1<O: OWFParameters>(
+        mu: &MessageHash<O>,
+        hcom: &[u8],
+        c: &[u8],
+        iv: &IV,
+    ) -> Challenge1<O> {
+        let mut h2_hasher = Self::h2_init();
+        h2_hasher.update(mu.as_ref());
+        h2_hasher.update(hcom);
+        h2_hasher.update(c);
+        h2_hasher.update(iv);
+        let chall1 = Challenge1::from(h2_hasher.finish().read_into());
+        #[cfg(feature = "transcript")]
+        crate::transcript::record(
+            "H2",
+            "chall1",
+            mu.len() + hcom.len() + c.len() + iv.len(),
+            chall1.as_ref(),
+        );
+        chall1
+    }
+
+    fn hash_challenge_2<O: OWFParameters>(
+        chall1: &Challenge1<O>,
+        u_t: &[u8],
+        hv: &[u8],
+        d: &[u8],
+    ) -> Challenge2<O> {
+        let mut h2_hasher = Self::h2_init();
+        h2_hasher.update(chall1.as_ref());
+        h2_hasher.update(u_t);
+        h2_hasher.update(hv);
+        h2_hasher.update(d);
+        let chall2 = Challenge2::from(h2_hasher.finish().read_into());
+        #[cfg(feature = "transcript")]
+        crate::transcript::record(
+            "H2",
+            "chall2",
+            chall1.len() + u_t.len() + hv.len() + d.len(),
+            chall2.as_ref(),
+        );
+        chall2
+    }
+
+    fn hash_challenge_3<O: OWFParameters>(
+        chall2: &Challenge2<O>,
+        a_t: &[u8],
+        b_t: &[u8],
+    ) -> Challenge3<O> {
+        let mut h2_hasher = Self::h2_init();
+        h2_hasher.update(chall2.as_ref());
+        h2_hasher.update(a_t);
+        h2_hasher.update(b_t);
+        let chall3 = Challenge3::from(h2_hasher.finish().read_into());
+        #[cfg(feature = "transcript")]
+        crate::transcript::record(
+            "H2",
+            "chall3",
+            chall2.len() + a_t.len() + b_t.len(),
+            chall3.as_ref(),
+        );
+        chall3
+    }
+}
+
+/// Record `h_com`, the hash of the `τ` commitments to the GGM trees
+#[cfg(feature = "transcript")]
+fn record_hcom<P: FAESTParameters>(hcom: &[u8]) {
+    use crate::parameter::TauParameters;
+    use generic_array::typenum::Unsigned;
+
+    crate::transcript::record(
+        "H1",
+        "hcom",
+        <P::Tau as TauParameters>::Tau::USIZE * hcom.len(),
+        hcom,
+    );
+}
+
+#[cfg(feature = "sign")]
+mod sign;
+mod verify;
+
+#[cfg(feature = "sign")]
+pub(crate) use sign::*;
+#[cfg(feature = "diagnostics")]
+pub use verify::RecomputedChallenges;
+pub(crate) use verify::*;
+
+#[cfg(test)]
+#[generic_tests::define]
+mod test {
+    use super::{sign::*, verify::*, *};
+
+    use generic_array::{typenum::Unsigned, GenericArray};
+    use rand::RngCore;
+
+    use crate::{
+        internal_keys::{PublicKey, SecretKey},
+        parameter::TauParameters,
+        prg::IVSize,
+        progress::{Phase, Progress},
+        ByteEncoding,
+    };
+    #[cfg(feature = "serde")]
+    use serde::{Deserialize, Serialize};
+
+    use crate::parameter::{
+        FAEST128fParameters, FAEST128sParameters, FAEST192fParameters, FAEST192sParameters,
+        FAEST256fParameters, FAEST256sParameters, FAESTEM128fParameters, FAESTEM128sParameters,
+        FAESTEM192fParameters, FAESTEM192sParameters, FAESTEM256fParameters, FAESTEM256sParameters,
+        FAESTParameters,
+    };
+
+    const RUNS: usize = 3;
+
+    fn random_message(mut rng: impl RngCore) -> Vec<u8> {
+        let mut length = [0];
+        while length[0] == 0 {
+            rng.fill_bytes(&mut length);
+        }
+        let mut ret = vec![0; length[0] as usize];
+        rng.fill_bytes(&mut ret);
+        ret
+    }
+
+    #[test]
+    fn sign_and_verify<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        for _i in 0..RUNS {
+            let sk = P::OWF::keygen_with_rng(&mut rng);
+            let msg = random_message(&mut rng);
+            let mut sigma = GenericArray::default_boxed();
+            faest_sign::<P>(&msg, &sk, &[], &mut sigma);
+            let pk = sk.as_public_key();
+            let res = faest_verify::<P>(&msg, &pk, &sigma);
+            assert!(res.is_ok());
+        }
+    }
+
+    #[test]
+    fn keygen_fast_sign_and_verify<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        for _i in 0..RUNS {
+            let sk = faest_keygen_fast::<P, _>(&mut rng);
+            // same validity predicate as for keys from the standard loop
+            assert_eq!(
+                SecretKey::<P::OWF>::try_from(sk.to_bytes().as_slice()).expect("valid key"),
+                sk
+            );
+
+            let msg = random_message(&mut rng);
+            let mut sigma = GenericArray::default_boxed();
+            faest_sign::<P>(&msg, &sk, &[], &mut sigma);
+            assert!(faest_verify::<P>(&msg, &sk.as_public_key(), &sigma).is_ok());
+        }
+    }
+
+    #[test]
+    fn precompute_sign_and_verify<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+        let pk = sk.as_public_key();
+        for _i in 0..RUNS {
+            let precomputation = faest_precompute::<P, _>(&sk, &mut rng);
+            assert!(precomputation.verify_witness());
+            let msg = random_message(&mut rng);
+            let mut sigma = GenericArray::default_boxed();
+            faest_finish::<P>(precomputation, &msg, &mut sigma);
+            assert!(faest_verify::<P>(&msg, &pk, &sigma).is_ok());
+            assert!(faest_verify::<P>(&[msg.as_slice(), &[0]].concat(), &pk, &sigma).is_err());
+        }
+    }
+
+    #[test]
+    fn precomputations_are_independent<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+        let msg = random_message(&mut rng);
+
+        let mut sigma_1 = GenericArray::default_boxed();
+        faest_finish::<P>(faest_precompute::<P, _>(&sk, &mut rng), &msg, &mut sigma_1);
+        let mut sigma_2 = GenericArray::default_boxed();
+        faest_finish::<P>(faest_precompute::<P, _>(&sk, &mut rng), &msg, &mut sigma_2);
+        // fresh randomness for every precomputation, hence fresh IVs
+        assert_ne!(
+            sigma_1[P::SIGNATURE_SIZE - IVSize::USIZE..],
+            sigma_2[P::SIGNATURE_SIZE - IVSize::USIZE..]
+        );
+    }
+
+    #[test]
+    fn vectored_sign_and_verify<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+        let pk = sk.as_public_key();
+        let msg = [random_message(&mut rng), random_message(&mut rng)].concat();
+        let mut rho = GenericArray::<u8, <P::OWF as OWFParameters>::LAMBDABYTES>::default();
+        rng.fill_bytes(&mut rho);
+
+        let mut expected_sigma = GenericArray::default_boxed();
+        faest_sign::<P>(&msg, &sk, &rho, &mut expected_sigma);
+
+        let mid = msg.len() / 2;
+        for parts in [
+            vec![msg.as_slice()],
+            vec![&msg[..mid], &msg[mid..]],
+            vec![&[], &msg[..mid], &[], &msg[mid..], &[]],
+            msg.chunks(7).collect(),
+            msg.chunks(1).collect(),
+        ] {
+            let mut sigma = GenericArray::default_boxed();
+            faest_sign_vectored::<P>(&parts, &sk, &rho, &mut sigma);
+            // the boundaries of the parts do not change the signature
+            assert_eq!(sigma, expected_sigma);
+            assert!(faest_verify_vectored::<P>(Domain::Plain, &parts, &pk, &sigma).is_ok());
+        }
+
+        // but their order does
+        let parts = [&msg[mid..], &msg[..mid]];
+        if parts.concat() != msg {
+            assert!(
+                faest_verify_vectored::<P>(Domain::Plain, &parts, &pk, &expected_sigma).is_err()
+            );
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn keygen_batch<P: FAESTParameters>() {
+        use rand::SeedableRng;
+
+        const N: usize = 8;
+
+        let keys = faest_keygen_batch::<P, _>(N, rand_chacha::ChaCha8Rng::seed_from_u64(42));
+        assert_eq!(keys.len(), N);
+
+        // same seed and serial key generation with the derived RNGs
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(42);
+        let mut seed = GenericArray::default();
+        rng.fill_bytes(&mut seed);
+        for (i, sk) in keys.iter().enumerate() {
+            assert_eq!(
+                sk,
+                &P::OWF::keygen_with_rng(keygen_rng::<P::OWF>(&seed, i as u64))
+            );
+        }
+
+        // keys are distinct
+        assert!(keys
+            .iter()
+            .enumerate()
+            .all(|(i, sk)| keys[i + 1..].iter().all(|sk2| sk != sk2)));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn sign_batch<P: FAESTParameters>() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+        let pk = sk.as_public_key();
+        let msgs: [&[u8]; 4] = [b"message", b"message", b"", b"another message"];
+
+        let signatures =
+            faest_sign_batch::<P, _>(&msgs, &sk, rand_chacha::ChaCha8Rng::seed_from_u64(42));
+        assert_eq!(signatures.len(), msgs.len());
+
+        // same randomness and serial signing
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(42);
+        for (msg, sigma) in msgs.iter().zip(&signatures) {
+            let mut rho = GenericArray::<u8, <P::OWF as OWFParameters>::LAMBDABYTES>::default();
+            rng.fill_bytes(&mut rho);
+            let mut expected_sigma = GenericArray::default_boxed();
+            faest_sign::<P>(msg, &sk, &rho, &mut expected_sigma);
+            assert_eq!(sigma, expected_sigma.as_ref());
+            assert!(faest_verify::<P>(msg, &pk, sigma).is_ok());
+        }
+
+        // signatures are distinct, also on equal messages
+        assert!(signatures
+            .iter()
+            .enumerate()
+            .all(|(i, sigma)| signatures[i + 1..].iter().all(|sigma2| sigma != sigma2)));
+        assert!(faest_sign_batch::<P, _>(&[], &sk, &mut rng).is_empty());
+    }
+
+    /// Check that progress is monotonic, ends at 1 and returns the phases
+    fn check_progress(records: &[(Phase, f32)]) -> Vec<Phase> {
+        assert!(records.windows(2).all(|window| window[0].1 <= window[1].1));
+        assert!(records
+            .iter()
+            .all(|(_, progress)| (0.0..=1.0).contains(progress)));
+        assert_eq!(records.last().unwrap().1, 1.0);
+
+        let mut phases: Vec<_> = records.iter().map(|(phase, _)| *phase).collect();
+        phases.dedup();
+        phases
+    }
+
+    #[test]
+    fn sign_and_verify_with_progress<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+        let pk = sk.as_public_key();
+        let msg = random_message(&mut rng);
+        let mut rho = GenericArray::<u8, <P::OWF as OWFParameters>::LAMBDABYTES>::default();
+        rng.fill_bytes(&mut rho);
+
+        let mut records = Vec::new();
+        let mut sigma = GenericArray::default_boxed();
+        faest_sign_with_progress::<P>(&msg, &sk, &rho, &mut sigma, &mut |phase, progress| {
+            records.push((phase, progress))
+        });
+        assert_eq!(
+            check_progress(&records),
+            [
+                Phase::WitnessExpansion,
+                Phase::Commitment,
+                Phase::Hashing,
+                Phase::Proof,
+                Phase::Openings
+            ]
+        );
+        assert!(
+            records
+                .iter()
+                .filter(|(phase, _)| *phase == Phase::Commitment)
+                .count()
+                > <P::Tau as TauParameters>::Tau::USIZE
+        );
+
+        // the callback does not influence the signature
+        let mut expected_sigma = GenericArray::default_boxed();
+        faest_sign::<P>(&msg, &sk, &rho, &mut expected_sigma);
+        assert_eq!(sigma, expected_sigma);
+
+        for (msg, expected) in [(msg.as_slice(), true), (b"other message", false)] {
+            let mut records = Vec::new();
+            let res = faest_verify_with_progress::<P>(msg, &pk, &sigma, &mut |phase, progress| {
+                records.push((phase, progress))
+            });
+            assert_eq!(res.is_ok(), expected);
+            assert_eq!(
+                check_progress(&records),
+                [Phase::Reconstruction, Phase::Hashing, Phase::Recompute]
+            );
+        }
+    }
+
+    #[test]
+    fn verify_low_memory<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+        let pk = sk.as_public_key();
+        let msg = random_message(&mut rng);
+        let mut sigma = GenericArray::default_boxed();
+        faest_sign::<P>(&msg, &sk, &[], &mut sigma);
+
+        assert!(faest_verify_low_memory::<P>(&msg, &pk, &sigma).is_ok());
+        assert!(faest_verify_low_memory::<P>(b"other message", &pk, &sigma).is_err());
+        for low_memory in [false, true] {
+            assert_eq!(
+                recompute_challenges::<P, P::OWF>(
+                    Domain::Plain,
+                    &[&msg],
+                    &pk,
+                    &sigma,
+                    low_memory,
+                    &mut Progress::none(),
+                    None,
+                )
+                .chall3,
+                signature_chall3::<P, P::OWF>(&sigma)
+            );
+        }
+
+        // same recomputed challenge for a modified signature
+        sigma[0] ^= 1;
+        let challenges = recompute_challenges::<P, P::OWF>(
+            Domain::Plain,
+            &[&msg],
+            &pk,
+            &sigma,
+            false,
+            &mut Progress::none(),
+            None,
+        );
+        let challenges_low_memory = recompute_challenges::<P, P::OWF>(
+            Domain::Plain,
+            &[&msg],
+            &pk,
+            &sigma,
+            true,
+            &mut Progress::none(),
+            None,
+        );
+        assert_eq!(challenges.chall3, challenges_low_memory.chall3);
+        assert_ne!(challenges.chall3, signature_chall3::<P, P::OWF>(&sigma));
+        assert!(faest_verify_low_memory::<P>(&msg, &pk, &sigma).is_err());
+    }
+
+    #[test]
+    fn verify_with_mixed_public_key<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+        let pk = sk.as_public_key();
+        let other_pk = P::OWF::keygen_with_rng(&mut rng).as_public_key();
+        let msg = random_message(&mut rng);
+        let mut sigma = GenericArray::default_boxed();
+        faest_sign::<P>(&msg, &sk, &[], &mut sigma);
+        assert!(faest_verify::<P>(&msg, &pk, &sigma).is_ok());
+
+        for mixed_pk in [
+            PublicKey::new(other_pk.owf_input(), pk.owf_output()),
+            PublicKey::new(pk.owf_input(), other_pk.owf_output()),
+        ] {
+            assert!(faest_verify::<P>(&msg, &mixed_pk, &sigma).is_err());
+            assert!(faest_verify_low_memory::<P>(&msg, &mixed_pk, &sigma).is_err());
+        }
+    }
+
+    #[test]
+    fn verify_extreme_challenges<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+        let pk = sk.as_public_key();
+        let msg = random_message(&mut rng);
+        let mut sigma = GenericArray::default_boxed();
+        faest_sign::<P>(&msg, &sk, &[], &mut sigma);
+
+        // chall3 selects the largest (and smallest) leaf of every repetition
+        let chall3 = P::SIGNATURE_SIZE
+            - (IVSize::USIZE + <P::OWF as OWFParameters>::LAMBDABYTES::USIZE)
+            ..P::SIGNATURE_SIZE - IVSize::USIZE;
+        for byte in [0xff, 0x00] {
+            sigma[chall3.clone()].fill(byte);
+            assert!(faest_verify::<P>(&msg, &pk, &sigma).is_err());
+            assert!(faest_verify_low_memory::<P>(&msg, &pk, &sigma).is_err());
+        }
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn verify_with_recomputed_challenges<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+        let pk = sk.as_public_key();
+        let msg = random_message(&mut rng);
+        let mut sigma = GenericArray::default_boxed();
+        faest_sign::<P>(&msg, &sk, &[], &mut sigma);
+        assert!(faest_verify_with_recomputed_challenges::<P>(&msg, &pk, &sigma).is_ok());
+
+        let d_offset = <P::OWF as OWFParameters>::LHATBYTES::USIZE
+            * (<P::Tau as TauParameters>::Tau::USIZE - 1)
+            + <P::OWF as OWFParameters>::LAMBDABYTES::USIZE
+            + 2;
+        let chall3_offset =
+            P::SIGNATURE_SIZE - (IVSize::USIZE + <P::OWF as OWFParameters>::LAMBDABYTES::USIZE);
+        // modify c, d, the last byte of the decommitments and the last challenge
+        for index in [0, d_offset, chall3_offset - 1, chall3_offset] {
+            let mut modified = sigma.clone();
+            modified[index] ^= 1;
+            let challenges = faest_verify_with_recomputed_challenges::<P>(&msg, &pk, &modified)
+                .expect_err("modification is detected");
+            assert_ne!(
+                challenges.chall3,
+                signature_chall3::<P, P::OWF>(&modified).as_ref()
+            );
+        }
+
+        let challenges =
+            faest_verify_with_recomputed_challenges::<P>(b"other message", &pk, &sigma)
+                .expect_err("other message is rejected");
+        assert_ne!(
+            challenges.chall3,
+            signature_chall3::<P, P::OWF>(&sigma).as_ref()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+
+        let mut out = vec![];
+        let mut ser = serde_json::Serializer::new(&mut out);
+
+        sk.serialize(&mut ser).expect("serialize key pair");
+        let serialized = String::from_utf8(out).expect("serialize to string");
+
+        let mut de = serde_json::Deserializer::from_str(&serialized);
+        let sk2 = SecretKey::<P::OWF>::deserialize(&mut de).expect("deserialize secret key");
+        assert_eq!(sk, sk2);
+
+        let pk = sk.as_public_key();
+        let mut out = vec![];
+        let mut ser = serde_json::Serializer::new(&mut out);
+
+        pk.serialize(&mut ser).expect("serialize key pair");
+        let serialized = String::from_utf8(out).expect("serialize to string");
+
+        let mut de = serde_json::Deserializer::from_str(&serialized);
+        let pk2 = PublicKey::<P::OWF>::deserialize(&mut de).expect("deserialize public key");
+        assert_eq!(pk, pk2);
+    }
+
+    #[instantiate_tests(<FAEST128fParameters>)]
+    mod faest_128f {}
+
+    #[instantiate_tests(<FAEST128sParameters>)]
+    mod faest_128s {}
+
+    #[instantiate_tests(<FAEST192fParameters>)]
+    mod faest_192f {}
+
+    #[instantiate_tests(<FAEST192sParameters>)]
+    mod faest_192s {}
+
+    #[instantiate_tests(<FAEST256fParameters>)]
+    mod faest_256f {}
+
+    #[instantiate_tests(<FAEST256sParameters>)]
+    mod faest_256s {}
+
+    #[instantiate_tests(<FAESTEM128fParameters>)]
+    mod faest_em_128f {}
+
+    #[instantiate_tests(<FAESTEM128sParameters>)]
+    mod faest_em_128s {}
+
+    #[instantiate_tests(<FAESTEM192fParameters>)]
+    mod faest_em_192f {}
+
+    #[instantiate_tests(<FAESTEM192sParameters>)]
+    mod faest_em_192s {}
+
+    #[instantiate_tests(<FAESTEM256fParameters>)]
+    mod faest_em_256f {}
+
+    #[instantiate_tests(<FAESTEM256sParameters>)]
+    mod faest_em_256s {}
+
+    #[cfg(feature = "blake3-oracle")]
+    #[instantiate_tests(<crate::parameter::FAEST128fBlake3Parameters>)]
+    mod faest_128f_blake3 {}
+
+    #[cfg(feature = "blake3-oracle")]
+    #[instantiate_tests(<crate::parameter::FAEST128sBlake3Parameters>)]
+    mod faest_128s_blake3 {}
+
+    #[cfg(feature = "experimental")]
+    #[instantiate_tests(<crate::parameter::FAEST128usParameters>)]
+    mod faest_128us {}
+}
@@ -0,0 +1,814 @@
+use std::{
+    iter::{self, zip},
+    mem,
+};
+
+use crate::{
+    buffer_pool::{BufferPool, ScratchBuffer},
+    hash_values::{Challenge1, Challenge2, Challenge3, MessageHash},
+    internal_keys::{PublicKey, SecretKey},
+    owf::RngSuspect,
+    parameter::{BaseParameters, FAESTParameters, OWFParameters, QSProof, TauParameters},
+    prg::{IVSize, IV},
+    progress::{Phase, Progress},
+    random_oracles::{Hasher, RandomOracle},
+    universal_hashing::{VoleHasherInit, VoleHasherProcess},
+    utils::Reader,
+    vc::VectorCommitment,
+    vole::{volecommit_with_progress, VoleCommitmentCRef},
+};
+
+#[cfg(feature = "parallel")]
+use crate::random_oracles::KEYGEN_SEP;
+use generic_array::{typenum::Unsigned, GenericArray};
+use itertools::izip;
+use rand_core::CryptoRngCore;
+#[cfg(feature = "parallel")]
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+#[cfg(feature = "transcript")]
+use super::record_hcom;
+use super::{Domain, FaestHash, VoleHasher, RO};
+
+#[inline]
+pub(crate) fn faest_keygen<P, R>(rng: R) -> SecretKey<P::OWF>
+where
+    P: FAESTParameters,
+    R: CryptoRngCore,
+{
+    P::OWF::keygen_with_rng(rng)
+}
+
+/// Generate a key and report the number of attempts
+#[inline]
+pub(crate) fn faest_keygen_detailed<P, R>(rng: R) -> Result<(SecretKey<P::OWF>, u32), RngSuspect>
+where
+    P: FAESTParameters,
+    R: CryptoRngCore,
+{
+    P::OWF::keygen_with_rng_detailed(rng)
+}
+
+#[inline]
+pub(crate) fn faest_keygen_fast<P, R>(rng: R) -> SecretKey<P::OWF>
+where
+    P: FAESTParameters,
+    R: CryptoRngCore,
+{
+    P::OWF::keygen_fast_with_rng(rng)
+}
+
+/// RNG used for batch key generation
+///
+/// The RNG produces the output of a dedicated hash of `seed || index` (see
+/// [`RandomOracle::keygen_init`]). It is only used to derive independent RNGs
+/// for each key of a batch from a single seed. With the `zeroize` feature, the
+/// state of the wrapped reader is zeroized on drop.
+#[cfg(feature = "parallel")]
+pub(super) struct KeygenRng<R>(R)
+where
+    R: Reader;
+
+#[cfg(feature = "parallel")]
+impl<R> RngCore for KeygenRng<R>
+where
+    R: Reader,
+{
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0; 4];
+        self.0.read(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0; 8];
+        self.0.read(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.read(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<R> CryptoRng for KeygenRng<R> where R: Reader {}
+
+#[cfg(feature = "parallel")]
+pub(super) fn keygen_rng<O>(
+    seed: &GenericArray<u8, O::LAMBDABYTES>,
+    index: u64,
+) -> KeygenRng<<<<O::BaseParams as BaseParameters>::RandomOracle as RandomOracle>::Hasher<KEYGEN_SEP> as Hasher>::Reader>
+where
+    O: OWFParameters,
+{
+    let mut hasher = <O::BaseParams as BaseParameters>::RandomOracle::keygen_init();
+    hasher.update(seed);
+    hasher.update(&index.to_le_bytes());
+    KeygenRng(hasher.finish())
+}
+
+/// Generate `n` keys in parallel
+///
+/// A seed is sampled from `rng` and the `i`-th key is generated from an RNG
+/// derived from the seed and `i`. Hence, the result only depends on the
+/// output of `rng` but not on the scheduling of the tasks.
+#[cfg(feature = "parallel")]
+pub(crate) fn faest_keygen_batch<P, R>(n: usize, mut rng: R) -> Vec<SecretKey<P::OWF>>
+where
+    P: FAESTParameters,
+    R: CryptoRngCore,
+{
+    let mut seed = GenericArray::<u8, <P::OWF as OWFParameters>::LAMBDABYTES>::default();
+    rng.fill_bytes(&mut seed);
+
+    let keys = (0..n)
+        .into_par_iter()
+        .map(|i| P::OWF::keygen_with_rng(keygen_rng::<P::OWF>(&seed, i as u64)))
+        .collect();
+    #[cfg(feature = "zeroize")]
+    seed.zeroize();
+    keys
+}
+
+/// Sign each of `msgs` in parallel
+///
+/// A fresh `ρ` is sampled from `rng` for every message, hence the `r`s and
+/// IVs are independent even for equal messages. Only the witness and the
+/// scratch buffers are shared: the witness is expanded once, and the VOLE
+/// correlations are stored in buffers from a pool shared by all tasks. The
+/// signatures are returned in the order of `msgs` and are identical to the
+/// ones of [`faest_sign`] for the same `ρ`.
+#[cfg(feature = "parallel")]
+pub(crate) fn faest_sign_batch<P, R>(
+    msgs: &[&[u8]],
+    sk: &SecretKey<P::OWF>,
+    mut rng: R,
+) -> Vec<GenericArray<u8, P::SignatureSize>>
+where
+    P: FAESTParameters,
+    R: CryptoRngCore,
+{
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let mut rhos: Vec<GenericArray<u8, <P::OWF as OWFParameters>::LAMBDABYTES>> = msgs
+        .iter()
+        .map(|_| {
+            let mut rho = GenericArray::default();
+            rng.fill_bytes(&mut rho);
+            rho
+        })
+        .collect();
+
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let mut w = P::OWF::witness(sk);
+    let pool = BufferPool::new(
+        rayon::current_num_threads()
+            * <P::OWF as OWFParameters>::LAMBDA::USIZE
+            * <P::OWF as OWFParameters>::LHATBYTES::USIZE,
+    );
+    let signatures = msgs
+        .par_iter()
+        .zip(&rhos)
+        .map(|(msg, rho)| {
+            let mut signature = GenericArray::default();
+            sign_with_witness::<P, P::OWF>(
+                Domain::Plain,
+                &[msg],
+                sk,
+                w.clone(),
+                rho,
+                &mut signature,
+                &mut Progress::none(),
+                Some(&pool),
+                true,
+            );
+            signature
+        })
+        .collect();
+    #[cfg(feature = "zeroize")]
+    {
+        w.zeroize();
+        rhos.iter_mut().for_each(|rho| rho.zeroize());
+    }
+    signatures
+}
+
+#[inline]
+pub(crate) fn faest_sign<P>(
+    msg: &[u8],
+    sk: &SecretKey<P::OWF>,
+    rho: &[u8],
+    signature: &mut GenericArray<u8, P::SignatureSize>,
+) where
+    P: FAESTParameters,
+{
+    sign::<P, P::OWF>(
+        Domain::Plain,
+        &[msg],
+        sk,
+        rho,
+        signature,
+        &mut Progress::none(),
+        None,
+        true,
+    );
+}
+
+/// Sign and report the progress to `callback`
+#[inline]
+pub(crate) fn faest_sign_with_progress<P>(
+    msg: &[u8],
+    sk: &SecretKey<P::OWF>,
+    rho: &[u8],
+    signature: &mut GenericArray<u8, P::SignatureSize>,
+    callback: &mut dyn FnMut(Phase, f32),
+) where
+    P: FAESTParameters,
+{
+    sign::<P, P::OWF>(
+        Domain::Plain,
+        &[msg],
+        sk,
+        rho,
+        signature,
+        &mut Progress::signing(callback),
+        None,
+        true,
+    );
+}
+
+/// Sign the concatenation of `msg`
+///
+/// The parts are absorbed in order, so the signature only depends on their
+/// concatenation and not on the boundaries of the parts.
+#[inline]
+pub(crate) fn faest_sign_vectored<P>(
+    msg: &[&[u8]],
+    sk: &SecretKey<P::OWF>,
+    rho: &[u8],
+    signature: &mut GenericArray<u8, P::SignatureSize>,
+) where
+    P: FAESTParameters,
+{
+    sign::<P, P::OWF>(
+        Domain::Plain,
+        msg,
+        sk,
+        rho,
+        signature,
+        &mut Progress::none(),
+        None,
+        true,
+    );
+}
+
+/// Sign the concatenation of `msg` in `domain` and report the progress to
+/// `callback` if given
+///
+/// The scratch buffers are taken from `pool` if given. The VOLE commitments
+/// are computed in parallel if `parallel` is set and the `parallel` feature is
+/// enabled.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn faest_sign_with_options<P>(
+    domain: Domain<'_>,
+    msg: &[&[u8]],
+    sk: &SecretKey<P::OWF>,
+    rho: &[u8],
+    signature: &mut GenericArray<u8, P::SignatureSize>,
+    callback: Option<&mut dyn FnMut(Phase, f32)>,
+    pool: Option<&BufferPool>,
+    parallel: bool,
+) where
+    P: FAESTParameters,
+{
+    let mut progress = match callback {
+        Some(callback) => Progress::signing(callback),
+        None => Progress::none(),
+    };
+    sign::<P, P::OWF>(
+        domain,
+        msg,
+        sk,
+        rho,
+        signature,
+        &mut progress,
+        pool,
+        parallel,
+    );
+}
+
+#[inline]
+pub(crate) fn faest_precompute<P, R>(sk: &SecretKey<P::OWF>, rng: R) -> SigningPrecomputation<P>
+where
+    P: FAESTParameters,
+    R: CryptoRngCore,
+{
+    SigningPrecomputation::new(sk, rng)
+}
+
+#[inline]
+pub(crate) fn faest_finish<P>(
+    precomputation: SigningPrecomputation<P>,
+    msg: &[u8],
+    signature: &mut GenericArray<u8, P::SignatureSize>,
+) where
+    P: FAESTParameters,
+{
+    precomputation.finish(msg, signature);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn sign<P, O>(
+    domain: Domain<'_>,
+    msg: &[&[u8]],
+    sk: &SecretKey<O>,
+    rho: &[u8],
+    signature: &mut GenericArray<u8, P::SignatureSize>,
+    progress: &mut Progress,
+    pool: Option<&BufferPool>,
+    parallel: bool,
+) where
+    P: FAESTParameters<OWF = O>,
+    O: OWFParameters,
+{
+    // compute witness
+    progress.report(Phase::WitnessExpansion, 0, 1);
+    let w = O::witness(sk);
+    sign_with_witness::<P, O>(domain, msg, sk, w, rho, signature, progress, pool, parallel);
+}
+
+/// Sign with the witness `w` of `sk`
+#[allow(clippy::too_many_arguments)]
+fn sign_with_witness<P, O>(
+    domain: Domain<'_>,
+    msg: &[&[u8]],
+    sk: &SecretKey<O>,
+    w: Box<GenericArray<u8, O::LBYTES>>,
+    rho: &[u8],
+    signature: &mut GenericArray<u8, P::SignatureSize>,
+    progress: &mut Progress,
+    pool: Option<&BufferPool>,
+    parallel: bool,
+) where
+    P: FAESTParameters<OWF = O>,
+    O: OWFParameters,
+{
+    let mu = RO::<P>::hash_mu::<O>(sk.pk.owf_input(), sk.pk.owf_output(), domain, msg);
+
+    let mut r = GenericArray::<u8, O::LAMBDABYTES>::default();
+    let mut iv = IV::default();
+    RO::<P>::hash_r_iv(&mut r, &mut iv, &sk.owf_key, &mu, rho);
+
+    let (volecommit_cs, signature) = split_signature::<P>(signature, &iv);
+    let commitment =
+        ProverCommitment::<P>::with_witness(w, &r, iv, volecommit_cs, progress, pool, parallel);
+    #[cfg(feature = "zeroize")]
+    r.zeroize();
+    prove_and_open::<P, O>(sk, &commitment, &mu, volecommit_cs, signature, progress);
+}
+
+/// Write `iv` to the signature and split off the storage area of the `c`s
+fn split_signature<'a, P>(
+    signature: &'a mut GenericArray<u8, P::SignatureSize>,
+    iv: &IV,
+) -> (&'a mut [u8], &'a mut [u8])
+where
+    P: FAESTParameters,
+{
+    let (signature, iv_d) = signature.split_at_mut(P::SIGNATURE_SIZE - IVSize::USIZE);
+    iv_d.copy_from_slice(iv);
+    signature.split_at_mut(
+        <P::OWF as OWFParameters>::LHATBYTES::USIZE * (<P::Tau as TauParameters>::Tau::USIZE - 1),
+    )
+}
+
+/// Commitment of the prover to the VOLE correlations
+///
+/// Holds the VOLE commitment (GGM trees, leaf commitments and VOLE
+/// correlations) together with the witness. The state is zeroized on drop.
+/// The VOLE correlations `V` are stored in a [`ScratchBuffer`] as matrix with
+/// `λ` rows of `LHATBYTES` bytes.
+pub(crate) struct ProverCommitment<P>
+where
+    P: FAESTParameters,
+{
+    w: Box<GenericArray<u8, <P::OWF as OWFParameters>::LBYTES>>,
+    iv: IV,
+    hcom: GenericArray<
+        u8,
+        <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::LambdaBytesTimes2,
+    >,
+    decom: Box<GenericArray<Decom<P>, <P::Tau as TauParameters>::Tau>>,
+    u: Box<GenericArray<u8, <P::OWF as OWFParameters>::LHATBYTES>>,
+    gv: ScratchBuffer,
+}
+
+type Decom<P> = (
+    Vec<GenericArray<u8, <<P as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES>>,
+    Vec<
+        GenericArray<
+            u8,
+            <<<P as FAESTParameters>::OWF as OWFParameters>::BaseParams as BaseParameters>::LambdaBytesTimes2,
+        >,
+    >,
+);
+
+impl<P> ProverCommitment<P>
+where
+    P: FAESTParameters,
+{
+    /// Commit to the VOLE correlations and write the `c`s to `volecommit_cs`
+    ///
+    /// The VOLE correlations are stored in a buffer taken from `pool` if
+    /// given.
+    pub(crate) fn new(
+        sk: &SecretKey<P::OWF>,
+        r: &GenericArray<u8, <P::OWF as OWFParameters>::LAMBDABYTES>,
+        iv: IV,
+        volecommit_cs: &mut [u8],
+        progress: &mut Progress,
+        pool: Option<&BufferPool>,
+    ) -> Self {
+        // compute witness
+        progress.report(Phase::WitnessExpansion, 0, 1);
+        Self::with_witness(
+            P::OWF::witness(sk),
+            r,
+            iv,
+            volecommit_cs,
+            progress,
+            pool,
+            true,
+        )
+    }
+
+    /// Commit to the VOLE correlations for the witness `w`
+    ///
+    /// The repetitions are committed to in parallel if `parallel` is set and
+    /// the `parallel` feature is enabled.
+    fn with_witness(
+        w: Box<GenericArray<u8, <P::OWF as OWFParameters>::LBYTES>>,
+        r: &GenericArray<u8, <P::OWF as OWFParameters>::LAMBDABYTES>,
+        iv: IV,
+        volecommit_cs: &mut [u8],
+        progress: &mut Progress,
+        pool: Option<&BufferPool>,
+        parallel: bool,
+    ) -> Self {
+        progress.report(Phase::Commitment, 0, 1);
+        let mut gv = ScratchBuffer::new(
+            <P::OWF as OWFParameters>::LAMBDA::USIZE * <P::OWF as OWFParameters>::LHATBYTES::USIZE,
+            pool,
+        );
+        let (hcom, decom, u) = volecommit_with_progress::<
+            <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::VC,
+            P::Tau,
+            <P::OWF as OWFParameters>::LHATBYTES,
+        >(
+            VoleCommitmentCRef::new(volecommit_cs),
+            r,
+            &iv,
+            gv.as_matrix_mut(),
+            pool,
+            parallel,
+            |done| {
+                progress.report(
+                    Phase::Commitment,
+                    done,
+                    <P::Tau as TauParameters>::Tau::USIZE,
+                )
+            },
+        );
+        #[cfg(feature = "transcript")]
+        record_hcom::<P>(&hcom);
+
+        Self {
+            w,
+            iv,
+            hcom,
+            decom,
+            u,
+            gv,
+        }
+    }
+
+    /// Hash of the commitments to the GGM trees
+    pub(crate) fn hcom(
+        &self,
+    ) -> &GenericArray<
+        u8,
+        <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::LambdaBytesTimes2,
+    > {
+        &self.hcom
+    }
+
+    /// IV used for the VOLE commitment
+    pub(crate) fn iv(&self) -> &IV {
+        &self.iv
+    }
+
+    /// VOLE correlations `V`
+    fn gv(
+        &self,
+    ) -> &GenericArray<
+        GenericArray<u8, <P::OWF as OWFParameters>::LHATBYTES>,
+        <P::OWF as OWFParameters>::LAMBDA,
+    > {
+        self.gv.as_matrix()
+    }
+
+    /// Hash the VOLE correlations with `chall1`
+    ///
+    /// Returns the hash `u~` of `u` and the hash `h_V` of the hashes of the
+    /// `V`s.
+    pub(crate) fn hash_correlations(
+        &self,
+        chall1: &Challenge1<P::OWF>,
+        progress: &mut Progress,
+    ) -> (
+        GenericArray<
+            u8,
+            <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::VoleHasherOutputLength,
+        >,
+        GenericArray<
+            u8,
+            <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::LambdaBytesTimes2,
+        >,
+    ) {
+        let vole_hasher = VoleHasher::<P>::new_vole_hasher(chall1);
+        let u_t = vole_hasher.process(&self.u);
+
+        let mut h1_hasher = RO::<P>::h1_init();
+        let hs = vole_hasher.process_batch(self.gv().iter().map(|v| v.as_slice()));
+        for (i, h) in hs.iter().enumerate() {
+            h1_hasher.update(h);
+            progress.report(Phase::Hashing, i + 1, hs.len());
+        }
+        let hv = h1_hasher.finish().read_into();
+        #[cfg(feature = "transcript")]
+        crate::transcript::record("H1", "hv", hs.len() * u_t.len(), &hv);
+        (u_t, hv)
+    }
+
+    /// Write the masked witness `d = w + u` to `d`
+    pub(crate) fn mask_witness(&self, d: &mut [u8]) {
+        for (dj, wj, uj) in izip!(
+            d.iter_mut(),
+            self.w.iter(),
+            &self.u[..<P::OWF as OWFParameters>::LBYTES::USIZE]
+        ) {
+            *dj = wj ^ *uj;
+        }
+    }
+
+    /// Compute the QuickSilver proof `(a~, b~)` for `chall2`
+    pub(crate) fn prove(
+        &self,
+        pk: &PublicKey<P::OWF>,
+        chall2: &Challenge2<P::OWF>,
+    ) -> QSProof<P::OWF> {
+        // FIXME: this is only re-shapping gv
+        let mut gv = ScratchBuffer::new(
+            <P::OWF as OWFParameters>::LAMBDA::USIZE
+                * <P::OWF as OWFParameters>::LAMBDALBYTES::USIZE,
+            self.gv.pool(),
+        );
+        for (dst, src) in zip(
+            gv.as_matrix_mut::<
+                <P::OWF as OWFParameters>::LAMBDA,
+                <P::OWF as OWFParameters>::LAMBDALBYTES,
+            >(),
+            self.gv(),
+        ) {
+            dst.copy_from_slice(&src[..<P::OWF as OWFParameters>::LAMBDALBYTES::USIZE]);
+        }
+
+        P::OWF::prove(
+            &self.w,
+            GenericArray::from_slice(
+                &self.u[..<P::OWF as OWFParameters>::LBYTES::USIZE
+                    + <P::OWF as OWFParameters>::LAMBDABYTES::USIZE],
+            ),
+            gv.as_matrix(),
+            pk,
+            chall2,
+        )
+    }
+
+    /// Open the vector commitments at the leaves selected by `chall3` and
+    /// write the openings to `pdecom`
+    pub(crate) fn open(
+        &self,
+        chall3: &Challenge3<P::OWF>,
+        pdecom: &mut [u8],
+        progress: &mut Progress,
+    ) {
+        opening_to_signature(
+            (0..<P::Tau as TauParameters>::Tau::USIZE).map(|i| {
+                let s = P::Tau::decode_challenge(chall3.as_ref(), i);
+                // the decoded index has exactly k_i bits and hence always
+                // selects one of the N_i = 2^k_i leaves
+                debug_assert!(
+                    s.iter()
+                        .enumerate()
+                        .fold(0, |a, (j, d)| a ^ (usize::from(*d) << j))
+                        < if i < <P::Tau as TauParameters>::Tau0::USIZE {
+                            P::N0::USIZE
+                        } else {
+                            P::N1::USIZE
+                        }
+                );
+                if i < <P::Tau as TauParameters>::Tau0::USIZE {
+                    <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::VC::open::<
+                        P::POWK0,
+                        <P::Tau as TauParameters>::K0,
+                        P::N0,
+                    >(&self.decom[i], GenericArray::from_slice(&s))
+                } else {
+                    <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::VC::open::<
+                        P::POWK1,
+                        <P::Tau as TauParameters>::K1,
+                        P::N1,
+                    >(&self.decom[i], GenericArray::from_slice(&s))
+                }
+            }),
+            pdecom,
+            progress,
+        );
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<P> Drop for ProverCommitment<P>
+where
+    P: FAESTParameters,
+{
+    fn drop(&mut self) {
+        self.w.zeroize();
+        self.iv.zeroize();
+        self.u.zeroize();
+        self.decom.iter_mut().for_each(|(keys, coms)| {
+            keys.iter_mut().for_each(|k| k.zeroize());
+            coms.iter_mut().for_each(|com| com.zeroize());
+        });
+    }
+}
+
+/// Message-independent part of the signing operation
+///
+/// Holds the secret key, the [`ProverCommitment`] and the `c`s. The state is
+/// consumed by [`SigningPrecomputation::finish`] and zeroized on drop.
+pub(crate) struct SigningPrecomputation<P>
+where
+    P: FAESTParameters,
+{
+    sk: SecretKey<P::OWF>,
+    commitment: ProverCommitment<P>,
+    c: Vec<u8>,
+}
+
+impl<P> SigningPrecomputation<P>
+where
+    P: FAESTParameters,
+{
+    /// Commit to the VOLE correlations with fresh randomness
+    ///
+    /// In contrast to the regular signing operation, `r` and `iv` are derived
+    /// without `µ` from the secret key and a fresh `ρ` sampled from `rng`. `ρ`
+    /// is absorbed length-prefixed to separate this derivation from `H3` as
+    /// used by [`sign`].
+    pub(crate) fn new(sk: &SecretKey<P::OWF>, mut rng: impl CryptoRngCore) -> Self {
+        let mut rho = GenericArray::<u8, <P::OWF as OWFParameters>::LAMBDABYTES>::default();
+        rng.fill_bytes(&mut rho);
+
+        let mut h3_hasher = RO::<P>::h3_init();
+        h3_hasher.update(&sk.owf_key);
+        h3_hasher.absorb_length_prefixed(&rho);
+        #[cfg(feature = "zeroize")]
+        rho.zeroize();
+
+        let mut r = GenericArray::<u8, <P::OWF as OWFParameters>::LAMBDABYTES>::default();
+        let mut iv = IV::default();
+        let mut h3_reader = h3_hasher.finish();
+        h3_reader.read(&mut r);
+        h3_reader.read(&mut iv);
+        #[cfg(feature = "transcript")]
+        crate::transcript::record(
+            "H3",
+            "iv",
+            sk.owf_key.len() + mem::size_of::<u64>() + rho.len(),
+            &iv,
+        );
+
+        let mut c = vec![
+            0;
+            <P::OWF as OWFParameters>::LHATBYTES::USIZE
+                * (<P::Tau as TauParameters>::Tau::USIZE - 1)
+        ];
+        let commitment = ProverCommitment::new(sk, &r, iv, &mut c, &mut Progress::none(), None);
+        #[cfg(feature = "zeroize")]
+        r.zeroize();
+
+        Self {
+            sk: sk.clone(),
+            commitment,
+            c,
+        }
+    }
+
+    /// Check that the witness of the commitment belongs to the key
+    pub(crate) fn verify_witness(&self) -> bool {
+        P::OWF::verify_witness(&self.sk.pk, &self.commitment.w)
+    }
+
+    /// Absorb the message and complete the signature
+    pub(crate) fn finish(self, msg: &[u8], signature: &mut GenericArray<u8, P::SignatureSize>) {
+        let mu = RO::<P>::hash_mu(
+            self.sk.pk.owf_input(),
+            self.sk.pk.owf_output(),
+            Domain::Plain,
+            &[msg],
+        );
+
+        let (volecommit_cs, signature) = split_signature::<P>(signature, &self.commitment.iv);
+        volecommit_cs.copy_from_slice(&self.c);
+        prove_and_open::<P, P::OWF>(
+            &self.sk,
+            &self.commitment,
+            &mu,
+            volecommit_cs,
+            signature,
+            &mut Progress::none(),
+        );
+    }
+}
+
+/// Compute the proof and the openings and write them to `signature`
+///
+/// `signature` is the part of the signature following the `c`s and
+/// preceding the `iv`.
+fn prove_and_open<P, O>(
+    sk: &SecretKey<O>,
+    commitment: &ProverCommitment<P>,
+    mu: &MessageHash<O>,
+    c: &[u8],
+    signature: &mut [u8],
+    progress: &mut Progress,
+) where
+    P: FAESTParameters<OWF = O>,
+    O: OWFParameters,
+{
+    debug_assert!(O::verify_witness(&sk.pk, &commitment.w));
+
+    progress.report(Phase::Hashing, 0, 1);
+    let chall1 = RO::<P>::hash_challenge_1(mu, &commitment.hcom, c, &commitment.iv);
+
+    // compute and write u_t to signature
+    let (u_t, hv) = commitment.hash_correlations(&chall1, progress);
+    let (u_t_d, signature) = signature.split_at_mut(u_t.len());
+    u_t_d.copy_from_slice(&u_t);
+
+    // compute and write d to signature
+    let (d, signature) = signature.split_at_mut(O::LBYTES::USIZE);
+    commitment.mask_witness(d);
+
+    let chall2 = RO::<P>::hash_challenge_2(&chall1, &u_t, &hv, d);
+
+    progress.report(Phase::Proof, 0, 1);
+    let (a_t, b_t) = commitment.prove(&sk.pk, &chall2);
+    let (a_t_d, signature) = signature.split_at_mut(O::LAMBDABYTES::USIZE);
+    a_t_d.copy_from_slice(&a_t);
+
+    let (pdecom, chall3_d) = signature.split_at_mut(signature.len() - O::LAMBDABYTES::USIZE);
+    let chall3 = RO::<P>::hash_challenge_3(&chall2, &a_t, &b_t);
+    chall3_d.copy_from_slice(chall3.as_ref());
+
+    progress.report(Phase::Openings, 0, 1);
+    commitment.open(&chall3, pdecom, progress);
+}
+
+fn opening_to_signature<'a>(
+    pdecom: impl ExactSizeIterator<Item = (Vec<&'a [u8]>, &'a [u8])>,
+    mut signature: &mut [u8],
+    progress: &mut Progress,
+) {
+    let total = pdecom.len();
+    pdecom.enumerate().for_each(|(i, x)| {
+        for v in x.0.iter().copied().chain(iter::once(x.1)) {
+            let (dst, rest) = mem::take(&mut signature).split_at_mut(v.len());
+            dst.copy_from_slice(v);
+            signature = rest;
+        }
+        progress.report(Phase::Openings, i + 1, total);
+    });
+}
@@ -0,0 +1,414 @@
+use std::iter::zip;
+
+use crate::{
+    buffer_pool::{BufferPool, ScratchBuffer},
+    hash_values::{Challenge1, Challenge2, Challenge3},
+    internal_keys::PublicKey,
+    parameter::{BaseParameters, FAESTParameters, OWFParameters, TauParameters},
+    prg::{IVSize, IV},
+    progress::{Phase, Progress},
+    random_oracles::{Hasher, RandomOracle},
+    universal_hashing::{VoleHasherInit, VoleHasherProcess},
+    utils::{conditional_xor_into, Reader},
+    vole::{volereconstruct_low_memory_with_progress, volereconstruct_with_progress},
+    Error,
+};
+
+use generic_array::{typenum::Unsigned, GenericArray};
+use subtle::Choice;
+
+#[cfg(feature = "transcript")]
+use super::record_hcom;
+use super::{Domain, FaestHash, VoleHasher, RO};
+
+#[inline]
+pub(crate) fn faest_verify<P>(
+    msg: &[u8],
+    pk: &PublicKey<P::OWF>,
+    sigma: &GenericArray<u8, P::SignatureSize>,
+) -> Result<(), Error>
+where
+    P: FAESTParameters,
+{
+    verify::<P, P::OWF>(
+        Domain::Plain,
+        &[msg],
+        pk,
+        sigma,
+        false,
+        &mut Progress::none(),
+        None,
+    )
+}
+
+/// Verify and report the progress to `callback`
+#[inline]
+pub(crate) fn faest_verify_with_progress<P>(
+    msg: &[u8],
+    pk: &PublicKey<P::OWF>,
+    sigma: &GenericArray<u8, P::SignatureSize>,
+    callback: &mut dyn FnMut(Phase, f32),
+) -> Result<(), Error>
+where
+    P: FAESTParameters,
+{
+    verify::<P, P::OWF>(
+        Domain::Plain,
+        &[msg],
+        pk,
+        sigma,
+        false,
+        &mut Progress::verification(callback),
+        None,
+    )
+}
+
+/// Verify a signature on the concatenation of `msg` in `domain`
+#[inline]
+pub(crate) fn faest_verify_vectored<P>(
+    domain: Domain<'_>,
+    msg: &[&[u8]],
+    pk: &PublicKey<P::OWF>,
+    sigma: &GenericArray<u8, P::SignatureSize>,
+) -> Result<(), Error>
+where
+    P: FAESTParameters,
+{
+    verify::<P, P::OWF>(domain, msg, pk, sigma, false, &mut Progress::none(), None)
+}
+
+/// Verify a signature on the concatenation of `msg` in `domain` with the
+/// given options
+///
+/// The scratch buffers are taken from `pool` if given.
+#[inline]
+pub(crate) fn faest_verify_with_options<P>(
+    domain: Domain<'_>,
+    msg: &[&[u8]],
+    pk: &PublicKey<P::OWF>,
+    sigma: &GenericArray<u8, P::SignatureSize>,
+    low_memory: bool,
+    progress: Option<&mut dyn FnMut(Phase, f32)>,
+    pool: Option<&BufferPool>,
+) -> Result<(), Error>
+where
+    P: FAESTParameters,
+{
+    let mut progress = progress.map_or_else(Progress::none, Progress::verification);
+    verify::<P, P::OWF>(domain, msg, pk, sigma, low_memory, &mut progress, pool)
+}
+
+/// Verify with a small memory footprint
+///
+/// Accepts exactly the same signatures as [`faest_verify`], but reconstructs
+/// the VOLE correlations without storing the GGM trees.
+#[inline]
+pub(crate) fn faest_verify_low_memory<P>(
+    msg: &[u8],
+    pk: &PublicKey<P::OWF>,
+    sigma: &GenericArray<u8, P::SignatureSize>,
+) -> Result<(), Error>
+where
+    P: FAESTParameters,
+{
+    verify::<P, P::OWF>(
+        Domain::Plain,
+        &[msg],
+        pk,
+        sigma,
+        true,
+        &mut Progress::none(),
+        None,
+    )
+}
+
+/// Challenges recomputed by the verifier
+pub(super) struct Challenges<O>
+where
+    O: OWFParameters,
+{
+    #[cfg_attr(not(feature = "diagnostics"), allow(dead_code))]
+    chall1: Challenge1<O>,
+    #[cfg_attr(not(feature = "diagnostics"), allow(dead_code))]
+    chall2: Challenge2<O>,
+    pub(super) chall3: Challenge3<O>,
+}
+
+pub(super) fn signature_chall3<P, O>(sigma: &GenericArray<u8, P::SignatureSize>) -> Challenge3<O>
+where
+    P: FAESTParameters<OWF = O>,
+    O: OWFParameters,
+{
+    Challenge3::from(
+        GenericArray::from_slice(
+            &sigma[P::SIGNATURE_SIZE - (IVSize::USIZE + O::LAMBDABYTES::USIZE)
+                ..P::SIGNATURE_SIZE - IVSize::USIZE],
+        )
+        .clone(),
+    )
+}
+
+fn verify<P, O>(
+    domain: Domain<'_>,
+    msg: &[&[u8]],
+    pk: &PublicKey<O>,
+    sigma: &GenericArray<u8, P::SignatureSize>,
+    low_memory: bool,
+    progress: &mut Progress,
+    pool: Option<&BufferPool>,
+) -> Result<(), Error>
+where
+    P: FAESTParameters<OWF = O>,
+    O: OWFParameters,
+{
+    if signature_chall3::<P, O>(sigma)
+        == recompute_challenges::<P, O>(domain, msg, pk, sigma, low_memory, progress, pool).chall3
+    {
+        Ok(())
+    } else {
+        Err(Error::new())
+    }
+}
+
+pub(super) fn recompute_challenges<P, O>(
+    domain: Domain<'_>,
+    msg: &[&[u8]],
+    pk: &PublicKey<O>,
+    sigma: &GenericArray<u8, P::SignatureSize>,
+    low_memory: bool,
+    progress: &mut Progress,
+    pool: Option<&BufferPool>,
+) -> Challenges<O>
+where
+    P: FAESTParameters<OWF = O>,
+    O: OWFParameters,
+{
+    let chall3 = signature_chall3::<P, O>(sigma);
+    let iv = IV::from_slice(&sigma[P::SIGNATURE_SIZE - IVSize::USIZE..]);
+
+    let mu = RO::<P>::hash_mu::<O>(pk.owf_input(), pk.owf_output(), domain, msg);
+
+    let pdecom = &sigma[(O::LHATBYTES::USIZE * (<P::Tau as TauParameters>::Tau::USIZE - 1))
+        + (2 * O::LAMBDABYTES::USIZE)
+        + O::LBYTES::USIZE
+        + 2..P::SIGNATURE_SIZE - (16 + O::LAMBDABYTES::USIZE)];
+    progress.report(Phase::Reconstruction, 0, 1);
+    let (hcom, mut gq) = reconstruct::<P, O>(&chall3, pdecom, iv, low_memory, progress, pool);
+    let gq = gq.as_matrix_mut::<O::LAMBDA, O::LHATBYTES>();
+
+    progress.report(Phase::Hashing, 0, 1);
+
+    let c = &sigma[..O::LHATBYTES::USIZE * (<P::Tau as TauParameters>::Tau::USIZE - 1)];
+    let chall1 = RO::<P>::hash_challenge_1(&mu, &hcom, c, iv);
+
+    let u_t = &sigma[O::LHATBYTES::USIZE * (<P::Tau as TauParameters>::Tau::USIZE - 1)
+        ..O::LHATBYTES::USIZE * (<P::Tau as TauParameters>::Tau::USIZE - 1)
+            + O::LAMBDABYTES::USIZE
+            + 2];
+    let hv = correct_and_hash_correlations::<P, O>(gq, c, &chall1, &chall3, u_t, progress);
+
+    let d = &sigma[O::LHATBYTES::USIZE * (<P::Tau as TauParameters>::Tau::USIZE - 1)
+        + O::LAMBDABYTES::USIZE
+        + 2
+        ..O::LHATBYTES::USIZE * (<P::Tau as TauParameters>::Tau::USIZE - 1)
+            + O::LAMBDABYTES::USIZE
+            + 2
+            + O::LBYTES::USIZE];
+    let chall2 = RO::<P>::hash_challenge_2(&chall1, u_t, &hv, d);
+
+    let a_t = &sigma[O::LHATBYTES::USIZE * (<P::Tau as TauParameters>::Tau::USIZE - 1)
+        + O::LAMBDABYTES::USIZE
+        + 2
+        + O::LBYTES::USIZE
+        ..O::LHATBYTES::USIZE * (<P::Tau as TauParameters>::Tau::USIZE - 1)
+            + 2 * O::LAMBDABYTES::USIZE
+            + 2
+            + O::LBYTES::USIZE];
+    progress.report(Phase::Recompute, 0, 1);
+    let b_t = recompute_proof::<P, O>(
+        GenericArray::from_slice(d),
+        gq,
+        GenericArray::from_slice(a_t),
+        &chall2,
+        &chall3,
+        pk,
+        pool,
+    );
+
+    let chall3_p = RO::<P>::hash_challenge_3(&chall2, a_t, &b_t);
+    progress.report(Phase::Recompute, 1, 1);
+    Challenges {
+        chall1,
+        chall2,
+        chall3: chall3_p,
+    }
+}
+
+/// Reconstruct the VOLE correlations of the verifier from the openings
+///
+/// Returns the recomputed hash of the commitments to the GGM trees and the
+/// uncorrected `Q`s as matrix with `λ` rows of `ℓ̂ / 8` bytes. The buffers are
+/// taken from `pool` if given.
+pub(crate) fn reconstruct<P, O>(
+    chall3: &Challenge3<O>,
+    pdecom: &[u8],
+    iv: &IV,
+    low_memory: bool,
+    progress: &mut Progress,
+    pool: Option<&BufferPool>,
+) -> (
+    GenericArray<u8, <O::BaseParams as BaseParameters>::LambdaBytesTimes2>,
+    ScratchBuffer,
+)
+where
+    P: FAESTParameters<OWF = O>,
+    O: OWFParameters,
+{
+    let on_repetition = |done| {
+        progress.report(
+            Phase::Reconstruction,
+            done,
+            <P::Tau as TauParameters>::Tau::USIZE,
+        )
+    };
+    let mut gq = ScratchBuffer::new(O::LAMBDA::USIZE * O::LHATBYTES::USIZE, pool);
+    let q = gq.as_matrix_mut::<O::LAMBDA, O::LHATBYTES>();
+    let hcom =
+        if low_memory {
+            volereconstruct_low_memory_with_progress::<
+                <O::BaseParams as BaseParameters>::VC,
+                P::Tau,
+                O::LHATBYTES,
+            >(chall3.as_ref(), pdecom, iv, q, on_repetition)
+        } else {
+            volereconstruct_with_progress::<
+                <O::BaseParams as BaseParameters>::VC,
+                P::Tau,
+                O::LHATBYTES,
+            >(chall3.as_ref(), pdecom, iv, q, pool, on_repetition)
+        };
+    #[cfg(feature = "transcript")]
+    record_hcom::<P>(&hcom);
+    (hcom, gq)
+}
+
+/// Apply the corrections `c` to the `Q`s and hash them with `chall1`
+///
+/// Returns the hash `h_V` as recomputed by the verifier.
+pub(crate) fn correct_and_hash_correlations<P, O>(
+    gq: &mut GenericArray<GenericArray<u8, O::LHATBYTES>, O::LAMBDA>,
+    c: &[u8],
+    chall1: &Challenge1<O>,
+    chall3: &Challenge3<O>,
+    u_t: &[u8],
+    progress: &mut Progress,
+) -> GenericArray<u8, <O::BaseParams as BaseParameters>::LambdaBytesTimes2>
+where
+    P: FAESTParameters<OWF = O>,
+    O: OWFParameters,
+{
+    for (i, c_chunk) in c.chunks(O::LHATBYTES::USIZE).enumerate() {
+        let (index, size) = <P::Tau as TauParameters>::convert_index_and_size(i + 1);
+        for (gq_i, d) in zip(
+            &mut gq[index..index + size],
+            P::Tau::decode_challenge_as_iter(chall3.as_ref(), i + 1),
+        ) {
+            conditional_xor_into(gq_i, c_chunk, Choice::from(d));
+        }
+    }
+
+    let mut h1_hasher = RO::<P>::h1_init();
+    let vole_hasher = VoleHasher::<P>::new_vole_hasher(chall1);
+    let hs = vole_hasher.process_batch(gq.iter().map(|q| q.as_slice()));
+    for (i, (mut q, d)) in zip(
+        hs,
+        (0..<P::Tau as TauParameters>::Tau::USIZE)
+            .flat_map(|i| P::Tau::decode_challenge_as_iter(chall3.as_ref(), i)),
+    )
+    .enumerate()
+    {
+        conditional_xor_into(&mut q, u_t, Choice::from(d));
+        h1_hasher.update(&q);
+        progress.report(Phase::Hashing, i + 1, gq.len());
+    }
+    let hv = h1_hasher.finish().read_into();
+    #[cfg(feature = "transcript")]
+    crate::transcript::record("H1", "hv", gq.len() * u_t.len(), &hv);
+    hv
+}
+
+/// Recompute `b~` of the QuickSilver proof from the corrected `Q`s
+///
+/// The buffer of the truncated `Q`s is taken from `pool` if given.
+pub(crate) fn recompute_proof<P, O>(
+    d: &GenericArray<u8, O::LBYTES>,
+    gq: &GenericArray<GenericArray<u8, O::LHATBYTES>, O::LAMBDA>,
+    a_t: &GenericArray<u8, O::LAMBDABYTES>,
+    chall2: &Challenge2<O>,
+    chall3: &Challenge3<O>,
+    pk: &PublicKey<O>,
+    pool: Option<&BufferPool>,
+) -> GenericArray<u8, O::LAMBDABYTES>
+where
+    P: FAESTParameters<OWF = O>,
+    O: OWFParameters,
+{
+    let mut truncated = ScratchBuffer::new(O::LAMBDA::USIZE * O::LAMBDALBYTES::USIZE, pool);
+    let truncated_gq = truncated.as_matrix_mut::<O::LAMBDA, O::LAMBDALBYTES>();
+    for (dst, src) in zip(truncated_gq.iter_mut(), gq) {
+        dst.copy_from_slice(&src[..O::LAMBDALBYTES::USIZE]);
+    }
+    P::OWF::verify::<P>(d, truncated_gq, a_t, chall2, chall3, pk)
+}
+
+/// Challenges recomputed by a failed verification
+///
+/// FAEST verification recomputes the last challenge from the signature and
+/// compares it to the one contained in the signature. This comparison is the
+/// only check: the QuickSilver proof and the vector commitments are only
+/// verified implicitly by it, and the reconstruction of the vector commitments
+/// can not fail for signatures of the correct size. Hence, it is not possible
+/// to attribute a failure to a specific part of the signature or repetition.
+///
+/// Instead, the recomputed challenges are reported to compare them with the
+/// intermediate values of other implementations.
+#[cfg(feature = "diagnostics")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecomputedChallenges {
+    /// Recomputed first challenge
+    pub chall1: Vec<u8>,
+    /// Recomputed second challenge
+    pub chall2: Vec<u8>,
+    /// Recomputed third challenge
+    pub chall3: Vec<u8>,
+}
+
+/// Verify a signature and report the recomputed challenges on failure
+#[cfg(feature = "diagnostics")]
+pub(crate) fn faest_verify_with_recomputed_challenges<P>(
+    msg: &[u8],
+    pk: &PublicKey<P::OWF>,
+    sigma: &GenericArray<u8, P::SignatureSize>,
+) -> Result<(), RecomputedChallenges>
+where
+    P: FAESTParameters,
+{
+    let challenges = recompute_challenges::<P, P::OWF>(
+        Domain::Plain,
+        &[msg],
+        pk,
+        sigma,
+        false,
+        &mut Progress::none(),
+        None,
+    );
+    if signature_chall3::<P, P::OWF>(sigma) == challenges.chall3 {
+        Ok(())
+    } else {
+        Err(RecomputedChallenges {
+            chall1: challenges.chall1.as_ref().to_vec(),
+            chall2: challenges.chall2.as_ref().to_vec(),
+            chall3: challenges.chall3.as_ref().to_vec(),
+        })
+    }
+}
@@ -1,5 +1,7 @@
 use std::{
-    array, mem,
+    array,
+    iter::{Product, Sum},
+    mem,
     num::Wrapping,
     ops::{
         Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitXor, BitXorAssign, Mul, MulAssign, Neg,
@@ -9,6 +11,8 @@ use std::{
 
 use super::{Double, Field, Square, GF64};
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use generic_array::typenum::Unsigned;
 use generic_array::{
     typenum::{U16, U24, U32},
     GenericArray,
@@ -18,6 +22,7 @@ use rand::{
     distributions::{Distribution, Standard},
     Rng,
 };
+use subtle::{Choice, ConditionallySelectable};
 
 /// Helper trait that define "alphas" for calculating embedings as part of [`ByteCombine`]
 pub(crate) trait Alphas: Sized {
@@ -38,9 +43,13 @@ pub trait BigGaloisField:
     + Square<Output = Self>
     + ByteCombine
     + ByteCombineConstants
-    + SumPoly
+    + Sum
+    + Product
+    + ConditionallySelectable
 where
     Self: for<'a> From<&'a [u8]>,
+    Self: for<'a> Sum<&'a Self>,
+    Self: for<'a> Product<&'a Self>,
     Self: for<'a> AddAssign<&'a Self>,
     Self: for<'a> Add<&'a Self, Output = Self>,
     Self: for<'a> SubAssign<&'a Self>,
@@ -48,6 +57,37 @@ where
     Self: for<'a> MulAssign<&'a Self>,
     Self: for<'a> Mul<&'a Self, Output = Self>,
 {
+    /// Write the byte representation of the element to `out`
+    ///
+    /// Panics if `out` is not of the length of the byte representation.
+    fn to_bytes_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.as_bytes());
+    }
+
+    /// Obtain a field element from its byte representation
+    ///
+    /// Returns `None` if `bytes` is not of the length of the byte
+    /// representation. All bit patterns of that length are canonical, i.e.,
+    /// there are no unused high bits to reject.
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    fn from_bytes_checked(bytes: &[u8]) -> Option<Self> {
+        (bytes.len() == Self::Length::USIZE).then(|| Self::from(bytes))
+    }
+
+    /// Raise the element to the power of `exponent`
+    ///
+    /// The computation performs square-and-multiply over all 64 bits of the
+    /// exponent and does not branch on its value.
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    fn pow(self, exponent: u64) -> Self {
+        let mut result = Self::ONE;
+        for idx in (0..64).rev() {
+            result = result.square();
+            let bit = Choice::from(((exponent >> idx) & 1) as u8);
+            result *= Self::conditional_select(&Self::ONE, &self, bit);
+        }
+        result
+    }
 }
 
 /// Trait providing methods for "byte combination"
@@ -59,6 +99,7 @@ pub trait ByteCombine: Field {
     ///
     /// This is the same as [`Self::byte_combine`] but takes a slice instead. It
     /// panics if the slice has less than `8` elements.
+    #[cfg(feature = "_variant")]
     fn byte_combine_slice(x: &[Self]) -> Self;
 
     /// "Combine" bits
@@ -77,23 +118,6 @@ pub trait ByteCombineConstants: Field {
 }
 
 /// Trait providing a polynomial sum
-pub trait SumPoly: Field {
-    /// Compute polynomial sum
-    fn sum_poly(v: &[Self]) -> Self;
-}
-
-impl<T> SumPoly for T
-where
-    T: Copy + Field + Double<Output = Self> + for<'a> Add<&'a Self, Output = Self>,
-{
-    fn sum_poly(v: &[Self]) -> Self {
-        v.iter()
-            .rev()
-            .skip(1)
-            .fold(v[v.len() - 1], |sum, val| sum.double() + val)
-    }
-}
-
 /// Binary galois field for larger sizes (e.g., 128 bits and above)
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub struct BigGF<T, const N: usize, const LENGTH: usize>(pub(crate) [T; N]);
@@ -122,6 +146,7 @@ where
             .fold(x[0], |sum, (xi, alphai)| sum + (alphai * xi))
     }
 
+    #[cfg(feature = "_variant")]
     fn byte_combine_slice(x: &[Self]) -> Self {
         debug_assert_eq!(x.len(), 8);
         let (x0, x) = x.split_at(1);
@@ -266,6 +291,57 @@ impl<T, const N: usize, const LENGTH: usize> Neg for BigGF<T, N, LENGTH> {
     }
 }
 
+// generic implementations of Sum and Product
+
+impl<T, const N: usize, const LENGTH: usize> Sum for BigGF<T, N, LENGTH>
+where
+    Self: Field,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, value| sum + value)
+    }
+}
+
+impl<'a, T, const N: usize, const LENGTH: usize> Sum<&'a Self> for BigGF<T, N, LENGTH>
+where
+    Self: Field + for<'b> Add<&'b Self, Output = Self>,
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, value| sum + value)
+    }
+}
+
+impl<T, const N: usize, const LENGTH: usize> Product for BigGF<T, N, LENGTH>
+where
+    Self: Field,
+{
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |product, value| product * value)
+    }
+}
+
+impl<'a, T, const N: usize, const LENGTH: usize> Product<&'a Self> for BigGF<T, N, LENGTH>
+where
+    Self: Field + for<'b> Mul<&'b Self, Output = Self>,
+{
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |product, value| product * value)
+    }
+}
+
+// generic implementation of ConditionallySelectable
+
+impl<T, const N: usize, const LENGTH: usize> ConditionallySelectable for BigGF<T, N, LENGTH>
+where
+    T: ConditionallySelectable,
+{
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(array::from_fn(|idx| {
+            T::conditional_select(&a.0[idx], &b.0[idx], choice)
+        }))
+    }
+}
+
 // generic implementations of Mul and MulAssign
 
 /// Modulus of a binary Galois field
@@ -562,6 +638,7 @@ where
 {
     type Output = Self;
 
+    #[cfg(all(feature = "sign", feature = "_variant"))]
     fn square(self) -> Self::Output {
         let mut other = self;
         let mut result = other.copy_apply_mask(self.to_mask_bit(0));
@@ -678,7 +755,11 @@ impl From<&[u8]> for BigGF<u128, 1, 128> {
     }
 }
 
-impl BigGaloisField for BigGF<u128, 1, 128> {}
+impl BigGaloisField for BigGF<u128, 1, 128> {
+    fn to_bytes_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.0[0].to_le_bytes());
+    }
+}
 
 #[cfg(test)]
 impl serde::Serialize for BigGF<u128, 1, 128> {
@@ -788,7 +869,13 @@ impl From<&[u8]> for BigGF<u128, 2, 192> {
     }
 }
 
-impl BigGaloisField for BigGF<u128, 2, 192> {}
+impl BigGaloisField for BigGF<u128, 2, 192> {
+    fn to_bytes_into(&self, out: &mut [u8]) {
+        let (out_0, out_1) = out.split_at_mut(16);
+        out_0.copy_from_slice(&self.0[0].to_le_bytes());
+        out_1.copy_from_slice(&self.0[1].to_le_bytes()[..8]);
+    }
+}
 
 #[cfg(test)]
 impl serde::Serialize for BigGF<u128, 2, 192> {
@@ -903,7 +990,13 @@ impl From<&[u8]> for BigGF<u128, 2, 256> {
     }
 }
 
-impl BigGaloisField for BigGF<u128, 2, 256> {}
+impl BigGaloisField for BigGF<u128, 2, 256> {
+    fn to_bytes_into(&self, out: &mut [u8]) {
+        let (out_0, out_1) = out.split_at_mut(16);
+        out_0.copy_from_slice(&self.0[0].to_le_bytes());
+        out_1.copy_from_slice(&self.0[1].to_le_bytes());
+    }
+}
 
 #[cfg(test)]
 impl serde::Serialize for BigGF<u128, 2, 256> {
@@ -952,6 +1045,7 @@ mod test {
 
         use std::iter::zip;
 
+        #[cfg(feature = "_variant")]
         use generic_array::typenum::Unsigned;
         use rand::{rngs::SmallRng, RngCore, SeedableRng};
 
@@ -1027,18 +1121,6 @@ mod test {
             }
         }
 
-        #[test]
-        fn sum_poly<F: BigGaloisField + Debug + Eq>() {
-            let all_zeroes = vec![F::ZERO; F::Length::USIZE * 8];
-            assert_eq!(F::sum_poly(&all_zeroes), F::ZERO);
-
-            let all_ones = vec![F::ONE; F::Length::USIZE * 8];
-            assert_eq!(
-                F::sum_poly(&all_ones),
-                F::from(vec![0xff; F::Length::USIZE].as_slice())
-            );
-        }
-
         #[test]
         fn byte_combine_constants<F: BigGaloisField + Debug + Eq>() {
             assert_eq!(F::ZERO, F::byte_combine(&[F::ZERO; 8]));
@@ -1072,6 +1154,7 @@ mod test {
             assert_eq!(F::ZERO, F::byte_combine_bits(0));
         }
 
+        #[cfg(feature = "_variant")]
         #[test]
         fn byte_combine_slice<F: BigGaloisField + Debug + Eq>()
         where
@@ -1096,6 +1179,27 @@ mod test {
             assert_eq!(element, F::from(bytes.as_slice()));
         }
 
+        #[cfg(feature = "_variant")]
+        #[test]
+        fn bytes_into_and_checked<F: BigGaloisField + Debug + Eq>()
+        where
+            Standard: Distribution<F>,
+        {
+            let mut rng = SmallRng::from_entropy();
+
+            let element: F = rng.gen();
+            let mut bytes = vec![0; F::Length::USIZE];
+            element.to_bytes_into(&mut bytes);
+            assert_eq!(bytes.as_slice(), element.as_bytes().as_slice());
+            assert_eq!(F::from_bytes_checked(&bytes), Some(element));
+
+            assert_eq!(F::from_bytes_checked(&bytes[1..]), None);
+            bytes.push(0);
+            assert_eq!(F::from_bytes_checked(&bytes), None);
+            assert_eq!(F::from_bytes_checked(&[]), None);
+        }
+
+        #[cfg(feature = "_variant")]
         #[test]
         fn square<F: BigGaloisField + Debug + Eq>()
         where
@@ -1107,6 +1211,73 @@ mod test {
             assert_eq!(element * element, element.square());
         }
 
+        #[test]
+        fn sum_and_product<F: BigGaloisField + Debug + Eq>()
+        where
+            Standard: Distribution<F>,
+        {
+            let mut rng = SmallRng::from_entropy();
+
+            assert_eq!(std::iter::empty::<F>().sum::<F>(), F::ZERO);
+            assert_eq!(std::iter::empty::<F>().product::<F>(), F::ONE);
+
+            let elements: Vec<F> = (0..RUNS).map(|_| rng.gen()).collect();
+            let sum = elements.iter().fold(F::ZERO, |sum, value| sum + value);
+            let product = elements
+                .iter()
+                .fold(F::ONE, |product, value| product * value);
+            assert_eq!(elements.iter().sum::<F>(), sum);
+            assert_eq!(elements.iter().copied().sum::<F>(), sum);
+            assert_eq!(elements.iter().product::<F>(), product);
+            assert_eq!(elements.iter().copied().product::<F>(), product);
+        }
+
+        #[test]
+        fn conditional_select<F: BigGaloisField + Debug + Eq>()
+        where
+            Standard: Distribution<F>,
+        {
+            let mut rng = SmallRng::from_entropy();
+
+            let a: F = rng.gen();
+            let b: F = rng.gen();
+            for bit in [false, true] {
+                let expected = if bit { b } else { a };
+                assert_eq!(
+                    F::conditional_select(&a, &b, Choice::from(bit as u8)),
+                    expected
+                );
+
+                let mut c = a;
+                c.conditional_assign(&b, Choice::from(bit as u8));
+                assert_eq!(c, expected);
+            }
+        }
+
+        #[cfg(feature = "_variant")]
+        #[test]
+        fn pow<F: BigGaloisField + Debug + Eq>()
+        where
+            Standard: Distribution<F>,
+        {
+            let mut rng = SmallRng::from_entropy();
+
+            let element: F = rng.gen();
+            assert_eq!(element.pow(0), F::ONE);
+            assert_eq!(F::ZERO.pow(0), F::ONE);
+            assert_eq!(F::ZERO.pow(5), F::ZERO);
+
+            let mut expected = F::ONE;
+            for exponent in 0..RUNS as u64 {
+                assert_eq!(element.pow(exponent), expected);
+                expected *= element;
+            }
+
+            let exponent = rng.next_u64();
+            let lhs = element.pow(exponent / 2).square() * element.pow(exponent % 2);
+            assert_eq!(element.pow(exponent), lhs);
+        }
+
         #[instantiate_tests(<GF128>)]
         mod gf128 {}
 
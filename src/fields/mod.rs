@@ -8,24 +8,27 @@ pub(crate) mod small_fields;
 ))]
 pub(crate) mod x86_simd_large_fields;
 
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
 use generic_array::{ArrayLength, GenericArray};
 
-pub(crate) use large_fields::{BigGaloisField, ByteCombine, ByteCombineConstants, SumPoly};
+pub(crate) use large_fields::{BigGaloisField, ByteCombine, ByteCombineConstants};
 #[cfg(not(all(
     feature = "opt-simd",
     target_feature = "avx2",
     target_feature = "pclmulqdq"
 )))]
-pub(crate) use large_fields::{GF128, GF192, GF256};
+pub use large_fields::{GF128, GF192, GF256};
 pub(crate) use small_fields::GF64;
 #[cfg(all(
     feature = "opt-simd",
     target_feature = "avx2",
     target_feature = "pclmulqdq"
 ))]
-pub(crate) use x86_simd_large_fields::{GF128, GF192, GF256};
+pub use x86_simd_large_fields::{GF128, GF192, GF256};
 
 /// Trait covering the basic functionality of a field
 ///
@@ -73,5 +76,83 @@ pub(crate) trait Square {
     type Output;
 
     /// Square an element
+    #[cfg(all(feature = "sign", feature = "_variant"))]
     fn square(self) -> Self::Output;
 }
+
+/// Write the byte representation of `value` in reversed order as hex
+fn fmt_hex<F: Field>(value: &F, f: &mut fmt::Formatter<'_>, upper: bool) -> fmt::Result {
+    if f.alternate() {
+        f.write_str("0x")?;
+    }
+    for byte in value.as_bytes().iter().rev() {
+        if upper {
+            write!(f, "{byte:02X}")?;
+        } else {
+            write!(f, "{byte:02x}")?;
+        }
+    }
+    Ok(())
+}
+
+macro_rules! impl_hex {
+    ($($field:ident),*) => {
+        $(
+            /// Formats the element as big-endian integer whose bit `i` is the
+            /// coefficient of `x^i`, i.e., the byte representation in reversed
+            /// order, padded to the full length of the field
+            impl fmt::LowerHex for $field {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt_hex(self, f, false)
+                }
+            }
+
+            /// Formats the element as big-endian integer whose bit `i` is the
+            /// coefficient of `x^i`, i.e., the byte representation in reversed
+            /// order, padded to the full length of the field
+            impl fmt::UpperHex for $field {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt_hex(self, f, true)
+                }
+            }
+        )*
+    };
+}
+
+impl_hex!(GF128, GF192, GF256);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hex() {
+        assert_eq!(
+            format!("{:x}", GF128::ONE),
+            "00000000000000000000000000000001"
+        );
+        assert_eq!(format!("{:x}", GF192::ONE), format!("{:048x}", 1));
+        assert_eq!(format!("{:x}", GF256::ONE), format!("{:064x}", 1));
+        assert_eq!(format!("{:x}", GF256::ZERO), "0".repeat(64));
+
+        let bytes: [u8; 16] = core::array::from_fn(|i| 0xa0 + i as u8);
+        let element = GF128::from(bytes.as_slice());
+        assert_eq!(
+            format!("{element:x}"),
+            format!("{:032x}", u128::from_le_bytes(bytes))
+        );
+        assert_eq!(
+            format!("{element:X}"),
+            format!("{:032X}", u128::from_le_bytes(bytes))
+        );
+        assert_eq!(
+            format!("{element:#x}"),
+            format!("{:#034x}", u128::from_le_bytes(bytes))
+        );
+
+        let bytes: [u8; 24] = core::array::from_fn(|i| i as u8);
+        let element = GF192::from(bytes.as_slice());
+        let expected: String = bytes.iter().rev().map(|b| format!("{b:02x}")).collect();
+        assert_eq!(format!("{element:x}"), expected);
+    }
+}
@@ -4,7 +4,10 @@
 use std::arch::x86 as x86_64;
 #[cfg(target_arch = "x86_64")]
 use std::arch::x86_64;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::{
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 use x86_64::{
     __m128i, __m256i, _mm256_and_si256, _mm256_blend_epi32, _mm256_blendv_epi8, _mm256_cmpeq_epi32,
     _mm256_extracti128_si256, _mm256_loadu_si256, _mm256_maskload_epi64, _mm256_maskstore_epi64,
@@ -23,6 +26,7 @@ use generic_array::{
     typenum::{Unsigned, U16, U24, U32},
     GenericArray,
 };
+use subtle::{Choice, ConditionallySelectable};
 
 use super::{
     large_fields::{
@@ -267,7 +271,7 @@ const fn gfu256_as_m256(v: UnoptimizedGF256) -> __m256i {
 /// Optimized implementation of the 128 bit Galois field
 #[derive(Debug, Clone, Copy)]
 #[repr(transparent)]
-pub(crate) struct GF128(__m128i);
+pub struct GF128(__m128i);
 
 impl Default for GF128 {
     #[inline(always)]
@@ -422,6 +426,7 @@ fn mul_gf128(lhs: __m128i, rhs: __m128i) -> __m128i {
     }
 }
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn square_gf128(lhs: __m128i) -> __m128i {
     unsafe {
         let mask = _mm_setr_epi32(-1, 0x0, 0x0, 0x0);
@@ -588,6 +593,37 @@ unsafe fn m128_apply_mask_msb(v: __m128i, m: __m128i) -> __m128i {
 
 const GF128_MODULUS: __m128i = u128_as_m128(UnoptimizedGF128::MODULUS);
 
+impl Sum for GF128 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, value| sum + value)
+    }
+}
+
+impl<'a> Sum<&'a Self> for GF128 {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, value| sum + value)
+    }
+}
+
+impl Product for GF128 {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |product, value| product * value)
+    }
+}
+
+impl<'a> Product<&'a Self> for GF128 {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |product, value| product * value)
+    }
+}
+
+impl ConditionallySelectable for GF128 {
+    #[inline(always)]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        *a + (*a + *b) * choice.unwrap_u8()
+    }
+}
+
 impl Double for GF128 {
     type Output = Self;
 
@@ -608,6 +644,7 @@ impl Double for GF128 {
 impl Square for GF128 {
     type Output = Self;
 
+    #[cfg(all(feature = "sign", feature = "_variant"))]
     #[inline]
     fn square(self) -> Self::Output {
         Self(square_gf128(self.0))
@@ -705,7 +742,7 @@ impl<'de> serde::Deserialize<'de> for GF128 {
 /// Optimized implementation of the 192 bit Galois field
 #[derive(Debug, Clone, Copy)]
 #[repr(transparent)]
-pub(crate) struct GF192(__m256i);
+pub struct GF192(__m256i);
 
 impl Default for GF192 {
     #[inline]
@@ -887,6 +924,7 @@ fn mul_gf192(lhs: __m256i, rhs: __m256i) -> __m256i {
     }
 }
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn square_gf192(lhs: __m256i) -> __m256i {
     unsafe {
         let x0 = _mm256_extracti128_si256(lhs, 0);
@@ -1009,6 +1047,37 @@ unsafe fn m192_apply_mask_msb(v: __m256i, m: __m256i) -> __m256i {
 
 const GF192_MODULUS: __m256i = u128_as_m256([UnoptimizedGF192::MODULUS, 0]);
 
+impl Sum for GF192 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, value| sum + value)
+    }
+}
+
+impl<'a> Sum<&'a Self> for GF192 {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, value| sum + value)
+    }
+}
+
+impl Product for GF192 {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |product, value| product * value)
+    }
+}
+
+impl<'a> Product<&'a Self> for GF192 {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |product, value| product * value)
+    }
+}
+
+impl ConditionallySelectable for GF192 {
+    #[inline(always)]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        *a + (*a + *b) * choice.unwrap_u8()
+    }
+}
+
 impl Double for GF192 {
     type Output = Self;
 
@@ -1029,6 +1098,7 @@ impl Double for GF192 {
 impl Square for GF192 {
     type Output = Self;
 
+    #[cfg(all(feature = "sign", feature = "_variant"))]
     #[inline]
     fn square(self) -> Self::Output {
         Self(square_gf192(self.0))
@@ -1137,7 +1207,7 @@ impl<'de> serde::Deserialize<'de> for GF192 {
 /// Optimized implementation of the 256 bit Galois field
 #[derive(Debug, Clone, Copy)]
 #[repr(transparent)]
-pub(crate) struct GF256(__m256i);
+pub struct GF256(__m256i);
 
 impl Default for GF256 {
     #[inline]
@@ -1307,6 +1377,7 @@ fn mul_gf256(lhs: __m256i, rhs: __m256i) -> __m256i {
     }
 }
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 fn square_gf256(lhs: __m256i) -> __m256i {
     unsafe {
         let x0 = _mm256_extracti128_si256(lhs, 0);
@@ -1419,6 +1490,37 @@ unsafe fn m256_apply_mask_msb(v: __m256i, m: __m256i) -> __m256i {
 
 const GF256_MODULUS: __m256i = u128_as_m256([UnoptimizedGF256::MODULUS, 0]);
 
+impl Sum for GF256 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, value| sum + value)
+    }
+}
+
+impl<'a> Sum<&'a Self> for GF256 {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |sum, value| sum + value)
+    }
+}
+
+impl Product for GF256 {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |product, value| product * value)
+    }
+}
+
+impl<'a> Product<&'a Self> for GF256 {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |product, value| product * value)
+    }
+}
+
+impl ConditionallySelectable for GF256 {
+    #[inline(always)]
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        *a + (*a + *b) * choice.unwrap_u8()
+    }
+}
+
 impl Double for GF256 {
     type Output = Self;
 
@@ -1436,6 +1538,7 @@ impl Double for GF256 {
 impl Square for GF256 {
     type Output = Self;
 
+    #[cfg(all(feature = "sign", feature = "_variant"))]
     #[inline]
     fn square(self) -> Self::Output {
         Self(square_gf256(self.0))
@@ -1655,6 +1758,28 @@ mod test {
             }
         }
 
+        #[test]
+        fn conditional_select<Fu, F: BigGaloisField + Debug + Eq>()
+        where
+            Standard: Distribution<Fu>,
+            Fu: BigGaloisField<Length = F::Length> + Debug + Eq,
+        {
+            let mut rng = SmallRng::from_entropy();
+
+            for _ in 0..RUNS {
+                let r1: Fu = rng.gen();
+                let r2: Fu = rng.gen();
+                let choice = Choice::from(rng.gen::<u8>() & 1);
+                let r3 = Fu::conditional_select(&r1, &r2, choice);
+
+                let v1 = F::from(r1.as_bytes().as_slice());
+                let v2 = F::from(r2.as_bytes().as_slice());
+                let v3 = F::conditional_select(&v1, &v2, choice);
+
+                assert_eq!(v3.as_bytes(), r3.as_bytes());
+            }
+        }
+
         #[test]
         fn double<Fu, F: BigGaloisField + Debug + Eq>()
         where
@@ -0,0 +1,198 @@
+//! Typed outputs of the hashes of FAEST
+//!
+//! `µ` and the three challenges are byte strings whose lengths depend on the
+//! parameter set. The wrappers of this module fix the length via the OWF
+//! parameters, so that, e.g., `chall2` can not be passed where `chall3` is
+//! expected. Values received from outside, e.g., the challenges of the
+//! interactive protocol, are only accepted via the checked `TryFrom<&[u8]>`
+//! constructors.
+
+use std::{fmt, ops::Deref};
+
+use generic_array::GenericArray;
+
+use crate::{
+    parameter::{BaseParameters, OWFParameters},
+    utils::HexPrefix,
+    Error,
+};
+
+macro_rules! hash_value {
+    ($(#[$meta:meta])* $name:ident, $length:ty) => {
+        $(#[$meta])*
+        pub(crate) struct $name<O>(GenericArray<u8, $length>)
+        where
+            O: OWFParameters;
+
+        impl<O> Deref for $name<O>
+        where
+            O: OWFParameters,
+        {
+            type Target = GenericArray<u8, $length>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<O> Default for $name<O>
+        where
+            O: OWFParameters,
+        {
+            fn default() -> Self {
+                Self(GenericArray::default())
+            }
+        }
+
+        impl<O> Clone for $name<O>
+        where
+            O: OWFParameters,
+        {
+            fn clone(&self) -> Self {
+                Self(self.0.clone())
+            }
+        }
+
+        impl<O> PartialEq for $name<O>
+        where
+            O: OWFParameters,
+        {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl<O> Eq for $name<O> where O: OWFParameters {}
+
+        impl<O> fmt::Debug for $name<O>
+        where
+            O: OWFParameters,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&HexPrefix(&self.0))
+                    .finish()
+            }
+        }
+
+        impl<O> AsRef<[u8]> for $name<O>
+        where
+            O: OWFParameters,
+        {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl<O> AsMut<[u8]> for $name<O>
+        where
+            O: OWFParameters,
+        {
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.0
+            }
+        }
+
+        impl<O> From<GenericArray<u8, $length>> for $name<O>
+        where
+            O: OWFParameters,
+        {
+            fn from(value: GenericArray<u8, $length>) -> Self {
+                Self(value)
+            }
+        }
+
+        impl<O> TryFrom<&[u8]> for $name<O>
+        where
+            O: OWFParameters,
+        {
+            type Error = Error;
+
+            fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+                GenericArray::try_from_slice(value)
+                    .map(|value| Self(value.clone()))
+                    .map_err(|_| Error::new())
+            }
+        }
+    };
+}
+
+hash_value!(
+    /// `µ`, the hash of the public key and the message (`2λ` bits)
+    MessageHash,
+    <O::BaseParams as BaseParameters>::LambdaBytesTimes2
+);
+
+hash_value!(
+    /// First challenge, the key of the VOLE hash
+    Challenge1,
+    <O::BaseParams as BaseParameters>::Chall1
+);
+
+hash_value!(
+    /// Second challenge, the key of the ZK hash
+    Challenge2,
+    <O::BaseParams as BaseParameters>::Chall
+);
+
+hash_value!(
+    /// Third challenge, selecting the opened leaves of the vector commitments
+    /// (`λ` bits)
+    Challenge3,
+    O::LAMBDABYTES
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use generic_array::typenum::Unsigned;
+
+    use crate::parameter::{OWF128, OWF192EM, OWF256};
+
+    fn checked_constructors<O: OWFParameters>() {
+        let bytes: Vec<u8> = (0..=u8::MAX).collect();
+
+        let mu = MessageHash::<O>::try_from(&bytes[..2 * O::LAMBDABYTES::USIZE]).unwrap();
+        assert_eq!(mu.as_ref(), &bytes[..2 * O::LAMBDABYTES::USIZE]);
+        assert!(MessageHash::<O>::try_from(&bytes[..O::LAMBDABYTES::USIZE]).is_err());
+
+        let chall3 = Challenge3::<O>::try_from(&bytes[..O::LAMBDABYTES::USIZE]).unwrap();
+        assert_eq!(chall3.as_ref(), &bytes[..O::LAMBDABYTES::USIZE]);
+        assert_eq!(chall3.as_slice(), chall3.as_ref());
+        assert!(Challenge3::<O>::try_from(&bytes[..O::LAMBDABYTES::USIZE + 1]).is_err());
+        assert!(Challenge3::<O>::try_from(&[][..]).is_err());
+
+        let chall1_length = 5 * O::LAMBDABYTES::USIZE + 8;
+        let chall2_length = 3 * O::LAMBDABYTES::USIZE + 8;
+        for length in [chall1_length, chall2_length] {
+            assert!(
+                Challenge1::<O>::try_from(&bytes[..length]).is_ok() == (length == chall1_length)
+            );
+            assert!(
+                Challenge2::<O>::try_from(&bytes[..length]).is_ok() == (length == chall2_length)
+            );
+        }
+    }
+
+    #[test]
+    fn checked_constructors_128() {
+        checked_constructors::<OWF128>();
+    }
+
+    #[test]
+    fn checked_constructors_192_em() {
+        checked_constructors::<OWF192EM>();
+    }
+
+    #[test]
+    fn checked_constructors_256() {
+        checked_constructors::<OWF256>();
+    }
+
+    #[test]
+    fn debug() {
+        let chall3 = Challenge3::<OWF128>::try_from(&[0xab; 16][..]).unwrap();
+        assert_eq!(format!("{chall3:?}"), "Challenge3(abababababababab…)");
+    }
+}
@@ -0,0 +1,377 @@
+//! Hybrid signatures combining FAEST and Ed25519
+//!
+//! A hybrid signature consists of a FAEST signature and an Ed25519 signature
+//! on the same message. Both components sign the message prefixed with a
+//! context string, so they can not be stripped from a hybrid signature and
+//! passed off as standalone signatures.
+//!
+//! The encoding of a hybrid signature is
+//!
+//! | field                    | size (in bytes) |
+//! |--------------------------|-----------------|
+//! | version                  | 1               |
+//! | FAEST signature length   | 4 (big endian)  |
+//! | FAEST signature          | variable        |
+//! | Ed25519 signature length | 4 (big endian)  |
+//! | Ed25519 signature        | 64              |
+//!
+//! Decoding is strict: the lengths have to match the sizes of the respective
+//! signatures exactly and no trailing data is allowed.
+//!
+//! ```
+//! # #[cfg(feature = "faest-128f")] {
+//! use faest::{FAEST128fSigningKey, FAEST128fSignature};
+//! use faest::hybrid::{verify_hybrid, HybridKeyPair, HybridSignature, Policy};
+//!
+//! let kp = HybridKeyPair::<FAEST128fSigningKey>::generate(rand::thread_rng());
+//! let msg = "some message".as_bytes();
+//! let signature: HybridSignature<FAEST128fSignature> = kp.sign(msg);
+//!
+//! let encoded = signature.to_vec();
+//! let signature = HybridSignature::<FAEST128fSignature>::try_from(encoded.as_slice())
+//!     .expect("well-formed signature");
+//! verify_hybrid(Policy::Both, &kp.verifying_key(), msg, &signature)
+//!     .expect("Verification failed");
+//! # }
+//! ```
+
+use ed25519_dalek::SIGNATURE_LENGTH;
+use rand_core::CryptoRngCore;
+use signature::SignatureEncoding;
+
+use crate::{Error, Keypair, KeypairGenerator, Signer, Verifier};
+
+const VERSION: u8 = 1;
+const CONTEXT: &[u8] = b"faest-rs hybrid FAEST+Ed25519 v1";
+
+fn with_context(msg: &[u8]) -> Vec<u8> {
+    [CONTEXT, msg].concat()
+}
+
+/// Verification policy for hybrid signatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// Both component signatures need to be valid
+    Both,
+    /// At least one of the component signatures needs to be valid
+    EitherValid,
+}
+
+/// Signing key for hybrid signatures
+#[derive(Debug, Clone)]
+pub struct HybridKeyPair<K> {
+    faest: K,
+    ed25519: ed25519_dalek::SigningKey,
+}
+
+/// Verification key for hybrid signatures
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HybridVerifyingKey<V> {
+    faest: V,
+    ed25519: ed25519_dalek::VerifyingKey,
+}
+
+/// Hybrid signature consisting of a FAEST and an Ed25519 signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HybridSignature<S> {
+    faest: S,
+    ed25519: ed25519_dalek::Signature,
+}
+
+impl<K> HybridKeyPair<K> {
+    /// Combine a FAEST signing key and an Ed25519 signing key
+    pub fn new(faest: K, ed25519: ed25519_dalek::SigningKey) -> Self {
+        Self { faest, ed25519 }
+    }
+
+    /// The FAEST signing key
+    pub fn faest(&self) -> &K {
+        &self.faest
+    }
+
+    /// The Ed25519 signing key
+    pub fn ed25519(&self) -> &ed25519_dalek::SigningKey {
+        &self.ed25519
+    }
+}
+
+impl<K> HybridKeyPair<K>
+where
+    K: KeypairGenerator,
+{
+    /// Generate a new hybrid key pair
+    pub fn generate<R>(mut rng: R) -> Self
+    where
+        R: CryptoRngCore,
+    {
+        Self {
+            faest: K::generate(&mut rng),
+            ed25519: ed25519_dalek::SigningKey::generate(&mut rng),
+        }
+    }
+}
+
+impl<K> HybridKeyPair<K>
+where
+    K: Keypair,
+{
+    /// The hybrid verification key
+    pub fn verifying_key(&self) -> HybridVerifyingKey<K::VerifyingKey> {
+        HybridVerifyingKey {
+            faest: self.faest.verifying_key(),
+            ed25519: self.ed25519.verifying_key(),
+        }
+    }
+
+    /// Sign a message with both keys
+    pub fn sign<S>(&self, msg: &[u8]) -> HybridSignature<S>
+    where
+        K: Signer<S>,
+    {
+        let msg = with_context(msg);
+        HybridSignature {
+            faest: self.faest.sign(&msg),
+            ed25519: self.ed25519.sign(&msg),
+        }
+    }
+}
+
+impl<V> HybridVerifyingKey<V> {
+    /// Combine a FAEST verification key and an Ed25519 verification key
+    pub fn new(faest: V, ed25519: ed25519_dalek::VerifyingKey) -> Self {
+        Self { faest, ed25519 }
+    }
+
+    /// The FAEST verification key
+    pub fn faest(&self) -> &V {
+        &self.faest
+    }
+
+    /// The Ed25519 verification key
+    pub fn ed25519(&self) -> &ed25519_dalek::VerifyingKey {
+        &self.ed25519
+    }
+}
+
+impl<S> HybridSignature<S> {
+    /// The FAEST signature
+    pub fn faest(&self) -> &S {
+        &self.faest
+    }
+
+    /// The Ed25519 signature
+    pub fn ed25519(&self) -> &ed25519_dalek::Signature {
+        &self.ed25519
+    }
+}
+
+impl<S> HybridSignature<S>
+where
+    S: SignatureEncoding,
+{
+    /// Encode the signature
+    pub fn to_vec(&self) -> Vec<u8> {
+        let faest = self.faest.to_vec();
+        let mut ret = Vec::with_capacity(1 + 4 + faest.len() + 4 + SIGNATURE_LENGTH);
+        ret.push(VERSION);
+        ret.extend_from_slice(&(faest.len() as u32).to_be_bytes());
+        ret.extend_from_slice(&faest);
+        ret.extend_from_slice(&(SIGNATURE_LENGTH as u32).to_be_bytes());
+        ret.extend_from_slice(&self.ed25519.to_bytes());
+        ret
+    }
+}
+
+/// Split a length-prefixed component from `bytes`
+fn split_component(bytes: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+    let (len, bytes) = bytes.split_first_chunk().ok_or_else(Error::new)?;
+    let len = u32::from_be_bytes(*len) as usize;
+    if bytes.len() < len {
+        return Err(Error::new());
+    }
+    Ok(bytes.split_at(len))
+}
+
+impl<S> TryFrom<&[u8]> for HybridSignature<S>
+where
+    S: SignatureEncoding,
+{
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let (version, value) = value.split_first().ok_or_else(Error::new)?;
+        if *version != VERSION {
+            return Err(Error::new());
+        }
+
+        let (faest, value) = split_component(value)?;
+        let (ed25519, value) = split_component(value)?;
+        if !value.is_empty() {
+            return Err(Error::new());
+        }
+
+        Ok(Self {
+            faest: S::try_from(faest).map_err(|_| Error::new())?,
+            ed25519: ed25519_dalek::Signature::from_slice(ed25519)?,
+        })
+    }
+}
+
+/// Verify a hybrid signature according to `policy`
+pub fn verify_hybrid<V, S>(
+    policy: Policy,
+    verifying_key: &HybridVerifyingKey<V>,
+    msg: &[u8],
+    signature: &HybridSignature<S>,
+) -> Result<(), Error>
+where
+    V: Verifier<S>,
+{
+    let msg = with_context(msg);
+    let faest = verifying_key.faest.verify(&msg, &signature.faest);
+    let ed25519 = verifying_key
+        .ed25519
+        .verify_strict(&msg, &signature.ed25519);
+    match policy {
+        Policy::Both => faest.and(ed25519),
+        Policy::EitherValid => faest.or(ed25519),
+    }
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "faest-128f")]
+    use crate::{FAEST128fSignature, FAEST128fSigningKey};
+    #[cfg(feature = "faest-em-128s")]
+    use crate::{FAESTEM128sSignature, FAESTEM128sSigningKey};
+
+    const TEST_MESSAGE: &[u8] = "test message".as_bytes();
+
+    #[test]
+    fn policies<K, S>()
+    where
+        K: KeypairGenerator + Signer<S>,
+        K::VerifyingKey: Verifier<S>,
+        S: SignatureEncoding,
+    {
+        let kp = HybridKeyPair::<K>::generate(rand::thread_rng());
+        let other = HybridKeyPair::<K>::generate(rand::thread_rng());
+        let vk = kp.verifying_key();
+        let valid = kp.sign::<S>(TEST_MESSAGE);
+        let invalid = other.sign::<S>(TEST_MESSAGE);
+
+        for (faest_valid, ed25519_valid) in
+            [(true, true), (true, false), (false, true), (false, false)]
+        {
+            let signature = HybridSignature {
+                faest: if faest_valid { &valid } else { &invalid }.faest.clone(),
+                ed25519: if ed25519_valid { &valid } else { &invalid }.ed25519,
+            };
+            assert_eq!(
+                verify_hybrid(Policy::Both, &vk, TEST_MESSAGE, &signature).is_ok(),
+                faest_valid && ed25519_valid
+            );
+            assert_eq!(
+                verify_hybrid(Policy::EitherValid, &vk, TEST_MESSAGE, &signature).is_ok(),
+                faest_valid || ed25519_valid
+            );
+        }
+
+        assert!(verify_hybrid(Policy::EitherValid, &vk, b"other message", &valid).is_err());
+    }
+
+    #[test]
+    fn components_are_bound_to_hybrid<K, S>()
+    where
+        K: KeypairGenerator + Signer<S>,
+        K::VerifyingKey: Verifier<S>,
+        S: SignatureEncoding,
+    {
+        let kp = HybridKeyPair::<K>::generate(rand::thread_rng());
+        let signature = kp.sign::<S>(TEST_MESSAGE);
+        assert!(kp
+            .faest()
+            .verifying_key()
+            .verify(TEST_MESSAGE, signature.faest())
+            .is_err());
+        assert!(kp
+            .ed25519()
+            .verifying_key()
+            .verify_strict(TEST_MESSAGE, signature.ed25519())
+            .is_err());
+    }
+
+    #[test]
+    fn encoding<K, S>()
+    where
+        K: KeypairGenerator + Signer<S>,
+        K::VerifyingKey: Verifier<S>,
+        S: SignatureEncoding + PartialEq + std::fmt::Debug,
+    {
+        let kp = HybridKeyPair::<K>::generate(rand::thread_rng());
+        let signature = kp.sign::<S>(TEST_MESSAGE);
+        let encoded = signature.to_vec();
+        assert_eq!(
+            HybridSignature::<S>::try_from(encoded.as_slice()).unwrap(),
+            signature
+        );
+
+        let faest_len = signature.faest().to_vec().len();
+        let ed25519_offset = 1 + 4 + faest_len;
+
+        // wrong version
+        let mut modified = encoded.clone();
+        modified[0] = VERSION + 1;
+        assert!(HybridSignature::<S>::try_from(modified.as_slice()).is_err());
+
+        // truncated signature
+        for len in [0, 1, 5, ed25519_offset, encoded.len() - 1] {
+            assert!(HybridSignature::<S>::try_from(&encoded[..len]).is_err());
+        }
+
+        // trailing data
+        assert!(
+            HybridSignature::<S>::try_from([encoded.as_slice(), &[0]].concat().as_slice()).is_err()
+        );
+
+        // truncated components with consistent length prefixes
+        let truncated_faest = [
+            &[VERSION][..],
+            &(faest_len as u32 - 1).to_be_bytes(),
+            &encoded[5..ed25519_offset - 1],
+            &encoded[ed25519_offset..],
+        ]
+        .concat();
+        assert!(HybridSignature::<S>::try_from(truncated_faest.as_slice()).is_err());
+        let truncated_ed25519 = [
+            &encoded[..ed25519_offset],
+            &(SIGNATURE_LENGTH as u32 - 1).to_be_bytes(),
+            &encoded[ed25519_offset + 4..encoded.len() - 1],
+        ]
+        .concat();
+        assert!(HybridSignature::<S>::try_from(truncated_ed25519.as_slice()).is_err());
+
+        // shifting bytes between the components
+        let shifted = [
+            &[VERSION][..],
+            &(faest_len as u32 + 1).to_be_bytes(),
+            &encoded[5..ed25519_offset],
+            &encoded[ed25519_offset + 4..ed25519_offset + 5],
+            &(SIGNATURE_LENGTH as u32 - 1).to_be_bytes(),
+            &encoded[ed25519_offset + 5..],
+        ]
+        .concat();
+        assert!(HybridSignature::<S>::try_from(shifted.as_slice()).is_err());
+    }
+
+    #[cfg(feature = "faest-128f")]
+    #[instantiate_tests(<FAEST128fSigningKey, FAEST128fSignature>)]
+    mod faest_128f {}
+
+    #[cfg(feature = "faest-em-128s")]
+    #[instantiate_tests(<FAESTEM128sSigningKey, FAESTEM128sSignature>)]
+    mod faest_em_128s {}
+}
@@ -0,0 +1,429 @@
+//! Interactive variant of the FAEST proof of knowledge
+//!
+//! FAEST signatures are obtained by applying the Fiat-Shamir transform to a
+//! public-coin interactive protocol proving knowledge of the key of the OWF.
+//! This module implements the protocol without the transform, i.e., the
+//! challenges are sampled by the verifier instead of being derived from the
+//! transcript. The protocol consists of a commitment of the prover followed by
+//! three rounds of challenges and responses:
+//!
+//! 1. commitment: hash of the vector commitments `h_com`, the corrections `c`
+//!    and the IV
+//! 2. key of the VOLE hash: `u~`, the hash `h_V` and the masked witness `d`
+//! 3. key of the ZK hash: `a~` and `b~` of the QuickSilver proof
+//! 4. `Δ`: openings of the vector commitments
+//!
+//! The protocol is only honest-verifier zero-knowledge, i.e., the verifier is
+//! expected to sample the challenges uniformly at random. In particular, the
+//! prover answers each challenge only once.
+//!
+//! The prover is only available with the `sign` feature. The verifier is
+//! always available; it takes the randomness of the challenges from the
+//! caller, hence verify-only builds do not depend on an RNG interface.
+
+use generic_array::{typenum::Unsigned, GenericArray};
+#[cfg(feature = "sign")]
+use rand_core::CryptoRngCore;
+#[cfg(all(feature = "sign", feature = "zeroize"))]
+use zeroize::Zeroize;
+
+#[cfg(feature = "sign")]
+use crate::{faest::ProverCommitment, internal_keys::SecretKey};
+use crate::{
+    faest::{correct_and_hash_correlations, recompute_proof, reconstruct},
+    hash_values::{Challenge1, Challenge2, Challenge3},
+    internal_keys::PublicKey,
+    parameter::{BaseParameters, FAESTParameters, OWFParameters, TauParameters},
+    prg::{IVSize, IV},
+    progress::Progress,
+    Error,
+};
+
+/// Number of rounds of challenges and responses
+pub(crate) const ROUNDS: usize = 3;
+
+/// Size of the `c`s (in bytes)
+fn corrections_size<P>() -> usize
+where
+    P: FAESTParameters,
+{
+    <P::OWF as OWFParameters>::LHATBYTES::USIZE * (<P::Tau as TauParameters>::Tau::USIZE - 1)
+}
+
+/// Size of the commitment of the prover (in bytes)
+fn commitment_size<P>() -> usize
+where
+    P: FAESTParameters,
+{
+    <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::LambdaBytesTimes2::USIZE
+        + corrections_size::<P>()
+        + IVSize::USIZE
+}
+
+/// Size of the challenge of the given round (in bytes)
+fn challenge_size<P>(round: usize) -> usize
+where
+    P: FAESTParameters,
+{
+    match round {
+        0 => <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::Chall1::USIZE,
+        1 => <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::Chall::USIZE,
+        _ => <P::OWF as OWFParameters>::LAMBDABYTES::USIZE,
+    }
+}
+
+/// Size of the response of the given round (in bytes)
+fn response_size<P>(round: usize) -> usize
+where
+    P: FAESTParameters,
+{
+    let lambda_bytes = <P::OWF as OWFParameters>::LAMBDABYTES::USIZE;
+    let u_t_size =
+        <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::VoleHasherOutputLength::USIZE;
+    match round {
+        0 => u_t_size + 2 * lambda_bytes + <P::OWF as OWFParameters>::LBYTES::USIZE,
+        1 => 2 * lambda_bytes,
+        // the openings take up the remaining space of a signature
+        _ => {
+            P::SIGNATURE_SIZE
+                - corrections_size::<P>()
+                - u_t_size
+                - <P::OWF as OWFParameters>::LBYTES::USIZE
+                - 2 * lambda_bytes
+                - IVSize::USIZE
+        }
+    }
+}
+
+/// Prover of the interactive protocol
+///
+/// Holds the secret key and the commitment to the VOLE correlations. The
+/// state is zeroized on drop.
+#[cfg(feature = "sign")]
+pub(crate) struct Prover<P>
+where
+    P: FAESTParameters,
+{
+    sk: SecretKey<P::OWF>,
+    commitment: ProverCommitment<P>,
+    round: usize,
+}
+
+#[cfg(feature = "sign")]
+impl<P> Prover<P>
+where
+    P: FAESTParameters,
+{
+    /// Commit to the VOLE correlations with fresh randomness from `rng`
+    ///
+    /// Returns the prover and its commitment.
+    pub(crate) fn new(sk: &SecretKey<P::OWF>, mut rng: impl CryptoRngCore) -> (Self, Vec<u8>) {
+        let mut r = GenericArray::<u8, <P::OWF as OWFParameters>::LAMBDABYTES>::default();
+        let mut iv = IV::default();
+        rng.fill_bytes(&mut r);
+        rng.fill_bytes(&mut iv);
+
+        let mut c = vec![0; corrections_size::<P>()];
+        let commitment = ProverCommitment::new(sk, &r, iv, &mut c, &mut Progress::none(), None);
+        #[cfg(feature = "zeroize")]
+        r.zeroize();
+
+        let mut message = Vec::with_capacity(commitment_size::<P>());
+        message.extend_from_slice(commitment.hcom());
+        message.extend_from_slice(&c);
+        message.extend_from_slice(commitment.iv());
+        (
+            Self {
+                sk: sk.clone(),
+                commitment,
+                round: 0,
+            },
+            message,
+        )
+    }
+
+    /// Respond to the challenge of the current round
+    ///
+    /// Fails if `challenge` is of the wrong size or if all challenges have
+    /// already been answered.
+    pub(crate) fn respond(&mut self, challenge: &[u8]) -> Result<Vec<u8>, Error> {
+        if self.round >= ROUNDS || challenge.len() != challenge_size::<P>(self.round) {
+            return Err(Error::new());
+        }
+
+        let mut response = vec![0; response_size::<P>(self.round)];
+        match self.round {
+            0 => {
+                let chall1 = Challenge1::try_from(challenge)?;
+                let (u_t, hv) = self
+                    .commitment
+                    .hash_correlations(&chall1, &mut Progress::none());
+                let (u_t_d, response) = response.split_at_mut(u_t.len());
+                u_t_d.copy_from_slice(&u_t);
+                let (hv_d, d) = response.split_at_mut(hv.len());
+                hv_d.copy_from_slice(&hv);
+                self.commitment.mask_witness(d);
+            }
+            1 => {
+                let chall2 = Challenge2::try_from(challenge)?;
+                let (a_t, b_t) = self.commitment.prove(&self.sk.pk, &chall2);
+                let (a_t_d, b_t_d) = response.split_at_mut(a_t.len());
+                a_t_d.copy_from_slice(&a_t);
+                b_t_d.copy_from_slice(&b_t);
+            }
+            _ => {
+                let chall3 = Challenge3::try_from(challenge)?;
+                self.commitment
+                    .open(&chall3, &mut response, &mut Progress::none());
+            }
+        }
+        self.round += 1;
+        Ok(response)
+    }
+}
+
+/// Verifier of the interactive protocol
+pub(crate) struct Verifier<P>
+where
+    P: FAESTParameters,
+{
+    pk: PublicKey<P::OWF>,
+    commitment: Vec<u8>,
+    challenges: Vec<Vec<u8>>,
+    responses: Vec<Vec<u8>>,
+}
+
+impl<P> Verifier<P>
+where
+    P: FAESTParameters,
+{
+    /// Receive the commitment of the prover
+    ///
+    /// Fails if `commitment` is of the wrong size.
+    pub(crate) fn new(pk: &PublicKey<P::OWF>, commitment: &[u8]) -> Result<Self, Error> {
+        if commitment.len() != commitment_size::<P>() {
+            return Err(Error::new());
+        }
+
+        Ok(Self {
+            pk: pk.clone(),
+            commitment: commitment.to_vec(),
+            challenges: Vec::with_capacity(ROUNDS),
+            responses: Vec::with_capacity(ROUNDS),
+        })
+    }
+
+    /// Sample the challenge of the current round
+    ///
+    /// `fill` is expected to fill the challenge with uniformly random bytes.
+    /// Fails if the response to the previous challenge is missing or if all
+    /// challenges have already been issued.
+    pub(crate) fn challenge(&mut self, fill: impl FnOnce(&mut [u8])) -> Result<Vec<u8>, Error> {
+        let round = self.challenges.len();
+        if round >= ROUNDS || self.responses.len() != round {
+            return Err(Error::new());
+        }
+
+        let mut challenge = vec![0; challenge_size::<P>(round)];
+        fill(&mut challenge);
+        self.challenges.push(challenge.clone());
+        Ok(challenge)
+    }
+
+    /// Receive the response to the current challenge
+    ///
+    /// Fails if `response` is of the wrong size or if no challenge is
+    /// pending.
+    pub(crate) fn receive(&mut self, response: &[u8]) -> Result<(), Error> {
+        let round = self.responses.len();
+        if self.challenges.len() != round + 1 || response.len() != response_size::<P>(round) {
+            return Err(Error::new());
+        }
+
+        self.responses.push(response.to_vec());
+        Ok(())
+    }
+
+    /// Check the transcript after all responses have been received
+    ///
+    /// The verifier reconstructs the VOLE correlations from the openings and
+    /// checks `h_com`, `h_V` and `b~` against the responses of the prover.
+    pub(crate) fn finish(self) -> Result<(), Error> {
+        if self.responses.len() != ROUNDS {
+            return Err(Error::new());
+        }
+
+        let lambda_bytes = <P::OWF as OWFParameters>::LAMBDABYTES::USIZE;
+        let (hcom, commitment) = self.commitment.split_at(2 * lambda_bytes);
+        let (c, iv) = commitment.split_at(corrections_size::<P>());
+        let chall1 = Challenge1::try_from(self.challenges[0].as_slice())?;
+        let chall2 = Challenge2::try_from(self.challenges[1].as_slice())?;
+        let chall3 = Challenge3::try_from(self.challenges[2].as_slice())?;
+
+        let (hcom_p, mut gq) = reconstruct::<P, P::OWF>(
+            &chall3,
+            &self.responses[2],
+            IV::from_slice(iv),
+            false,
+            &mut Progress::none(),
+            None,
+        );
+        let gq = gq.as_matrix_mut::<
+            <P::OWF as OWFParameters>::LAMBDA,
+            <P::OWF as OWFParameters>::LHATBYTES,
+        >();
+
+        let (u_t, response) = self.responses[0].split_at(
+            <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::VoleHasherOutputLength::USIZE,
+        );
+        let (hv, d) = response.split_at(2 * lambda_bytes);
+        let hv_p = correct_and_hash_correlations::<P, P::OWF>(
+            gq,
+            c,
+            &chall1,
+            &chall3,
+            u_t,
+            &mut Progress::none(),
+        );
+
+        let (a_t, b_t) = self.responses[1].split_at(lambda_bytes);
+        let b_t_p = recompute_proof::<P, P::OWF>(
+            GenericArray::from_slice(d),
+            gq,
+            GenericArray::from_slice(a_t),
+            &chall2,
+            &chall3,
+            &self.pk,
+            None,
+        );
+
+        if hcom == hcom_p.as_slice() && hv == hv_p.as_slice() && b_t == b_t_p.as_slice() {
+            Ok(())
+        } else {
+            Err(Error::new())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sign"))]
+#[generic_tests::define]
+mod test {
+    use super::*;
+
+    use rand::RngCore;
+
+    use crate::parameter::{
+        FAEST128fParameters, FAEST128sParameters, FAEST192fParameters, FAEST192sParameters,
+        FAEST256fParameters, FAEST256sParameters, FAESTEM128fParameters, FAESTEM128sParameters,
+        FAESTEM192fParameters, FAESTEM192sParameters, FAESTEM256fParameters, FAESTEM256sParameters,
+    };
+
+    /// Run the protocol and let `tamper` modify the responses
+    fn run<P: FAESTParameters>(tamper: impl Fn(usize, &mut Vec<u8>)) -> Result<(), Error> {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+
+        let (mut prover, commitment) = Prover::<P>::new(&sk, &mut rng);
+        let mut verifier = Verifier::<P>::new(&sk.as_public_key(), &commitment)?;
+        for round in 0..ROUNDS {
+            let challenge = verifier.challenge(|challenge| rng.fill_bytes(challenge))?;
+            let mut response = prover.respond(&challenge)?;
+            tamper(round, &mut response);
+            verifier.receive(&response)?;
+        }
+        verifier.finish()
+    }
+
+    #[test]
+    fn prove_and_verify<P: FAESTParameters>() {
+        assert!(run::<P>(|_, _| {}).is_ok());
+    }
+
+    #[test]
+    fn reject_modified_responses<P: FAESTParameters>() {
+        for round in 0..ROUNDS {
+            assert!(run::<P>(|r, response| if r == round {
+                response[0] ^= 1;
+            })
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn enforce_order<P: FAESTParameters>() {
+        let mut rng = rand::thread_rng();
+        let sk = P::OWF::keygen_with_rng(&mut rng);
+
+        let (mut prover, commitment) = Prover::<P>::new(&sk, &mut rng);
+        assert!(Verifier::<P>::new(&sk.as_public_key(), &commitment[1..]).is_err());
+        let mut verifier = Verifier::<P>::new(&sk.as_public_key(), &commitment).unwrap();
+        assert!(verifier.receive(&[]).is_err());
+
+        let challenge = verifier
+            .challenge(|challenge| rng.fill_bytes(challenge))
+            .unwrap();
+        // the response to the first challenge is still missing
+        assert!(verifier
+            .challenge(|challenge| rng.fill_bytes(challenge))
+            .is_err());
+        assert!(prover.respond(&challenge[1..]).is_err());
+        verifier
+            .receive(&prover.respond(&challenge).unwrap())
+            .unwrap();
+
+        let challenge = verifier
+            .challenge(|challenge| rng.fill_bytes(challenge))
+            .unwrap();
+        verifier
+            .receive(&prover.respond(&challenge).unwrap())
+            .unwrap();
+        let challenge = verifier
+            .challenge(|challenge| rng.fill_bytes(challenge))
+            .unwrap();
+        let response = prover.respond(&challenge).unwrap();
+
+        // every challenge is answered only once
+        assert!(prover.respond(&challenge).is_err());
+        assert!(verifier
+            .challenge(|challenge| rng.fill_bytes(challenge))
+            .is_err());
+        verifier.receive(&response).unwrap();
+        verifier.finish().unwrap();
+    }
+
+    #[instantiate_tests(<FAEST128fParameters>)]
+    mod faest_128f {}
+
+    #[instantiate_tests(<FAEST128sParameters>)]
+    mod faest_128s {}
+
+    #[instantiate_tests(<FAEST192fParameters>)]
+    mod faest_192f {}
+
+    #[instantiate_tests(<FAEST192sParameters>)]
+    mod faest_192s {}
+
+    #[instantiate_tests(<FAEST256fParameters>)]
+    mod faest_256f {}
+
+    #[instantiate_tests(<FAEST256sParameters>)]
+    mod faest_256s {}
+
+    #[instantiate_tests(<FAESTEM128fParameters>)]
+    mod faest_em_128f {}
+
+    #[instantiate_tests(<FAESTEM128sParameters>)]
+    mod faest_em_128s {}
+
+    #[instantiate_tests(<FAESTEM192fParameters>)]
+    mod faest_em_192f {}
+
+    #[instantiate_tests(<FAESTEM192sParameters>)]
+    mod faest_em_192s {}
+
+    #[instantiate_tests(<FAESTEM256fParameters>)]
+    mod faest_em_256f {}
+
+    #[instantiate_tests(<FAESTEM256sParameters>)]
+    mod faest_em_256s {}
+}
@@ -2,17 +2,29 @@
 //!
 //! This module also handles serialization and deserialization of any keys.
 
-use std::fmt::{self, Debug};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+};
 
-use crate::{parameter::OWFParameters, ByteEncoding, Error};
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use crate::owf::KeyRejected;
+use crate::{parameter::OWFParameters, utils::HexPrefix, ByteEncoding, Error};
 
 use generic_array::{typenum::Unsigned, GenericArray};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use subtle::{Choice, ConstantTimeEq};
 #[cfg(feature = "zeroize")]
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// Internal representation of a secret key.
+///
+/// The key holds no state of the vector commitments. Their seed `r` and the
+/// IV are derived for every signature from `owf_key`, `µ` and `ρ`, or from
+/// `owf_key` and fresh randomness for a `SigningPrecomputation`. Hence, there
+/// is nothing to refresh without changing the key.
 #[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
 pub(crate) struct SecretKey<O>
 where
@@ -47,16 +59,9 @@ where
             let owf_key = GenericArray::from_slice(&bytes[O::InputSize::USIZE..]);
 
             O::extendwitness(owf_key, owf_input)
-                .map(|_| {
-                    let mut owf_output = GenericArray::default();
-                    O::evaluate_owf(owf_key, owf_input, &mut owf_output);
-                    Self {
-                        owf_key: owf_key.clone(),
-                        pk: PublicKey {
-                            owf_input: owf_input.clone(),
-                            owf_output,
-                        },
-                    }
+                .map(|_| Self {
+                    owf_key: owf_key.clone(),
+                    pk: PublicKey::new(owf_input, &O::evaluate(owf_key, owf_input)),
                 })
                 .ok_or_else(Error::new)
         } else {
@@ -82,14 +87,14 @@ where
 
     fn to_bytes(&self) -> Self::Repr {
         let mut buf = GenericArray::default();
-        buf[..O::InputSize::USIZE].copy_from_slice(&self.pk.owf_input);
+        buf[..O::InputSize::USIZE].copy_from_slice(self.pk.owf_input());
         buf[O::InputSize::USIZE..].copy_from_slice(&self.owf_key);
         buf
     }
 
     fn to_vec(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(O::SK::USIZE);
-        buf.extend_from_slice(&self.pk.owf_input);
+        buf.extend_from_slice(self.pk.owf_input());
         buf.extend_from_slice(&self.owf_key);
         buf
     }
@@ -99,6 +104,7 @@ where
     }
 }
 
+#[cfg(all(feature = "sign", feature = "_variant"))]
 impl<O> SecretKey<O>
 where
     O: OWFParameters,
@@ -106,6 +112,23 @@ where
     pub(crate) fn as_public_key(&self) -> PublicKey<O> {
         self.pk.clone()
     }
+
+    /// Create a secret key from an existing OWF key and input
+    ///
+    /// Fails with the location of the first zero S-box input if FAEST can not
+    /// prove knowledge of `owf_key`. The key is never modified.
+    pub(crate) fn from_owf_key_and_input(
+        owf_key: &GenericArray<u8, O::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, O::InputSize>,
+    ) -> Result<Self, KeyRejected> {
+        match O::find_zero_sbox_input(owf_key, owf_input) {
+            Some(rejected) => Err(rejected),
+            None => Ok(Self {
+                owf_key: owf_key.clone(),
+                pk: PublicKey::new(owf_input, &O::evaluate(owf_key, owf_input)),
+            }),
+        }
+    }
 }
 
 impl<O> Debug for SecretKey<O>
@@ -115,18 +138,28 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SecretKey")
             .field("owf_key", &"redacted")
-            .field("owf_input", &self.pk.owf_input.as_slice())
-            .field("owf_output", &self.pk.owf_output.as_slice())
+            .field("pk", &self.pk)
             .finish()
     }
 }
 
+impl<O> ConstantTimeEq for SecretKey<O>
+where
+    O: OWFParameters,
+{
+    fn ct_eq(&self, rhs: &Self) -> Choice {
+        // the public key is public
+        self.owf_key.as_slice().ct_eq(rhs.owf_key.as_slice())
+            & Choice::from(u8::from(self.pk == rhs.pk))
+    }
+}
+
 impl<O> PartialEq for SecretKey<O>
 where
     O: OWFParameters,
 {
     fn eq(&self, rhs: &Self) -> bool {
-        self.owf_key == rhs.owf_key && self.pk == rhs.pk
+        self.ct_eq(rhs).into()
     }
 }
 
@@ -162,12 +195,44 @@ where
 }
 
 /// Internal representation of a public key
+///
+/// The key is stored in its canonical encoding, i.e., the OWF input followed
+/// by the OWF output.
 pub(crate) struct PublicKey<O>
 where
     O: OWFParameters,
 {
-    pub(crate) owf_input: GenericArray<u8, O::InputSize>,
-    pub(crate) owf_output: GenericArray<u8, O::InputSize>,
+    bytes: GenericArray<u8, O::PK>,
+}
+
+impl<O> PublicKey<O>
+where
+    O: OWFParameters,
+{
+    pub(crate) fn new(
+        owf_input: &GenericArray<u8, O::InputSize>,
+        owf_output: &GenericArray<u8, O::InputSize>,
+    ) -> Self {
+        let mut bytes = GenericArray::default();
+        bytes[..O::InputSize::USIZE].copy_from_slice(owf_input);
+        bytes[O::InputSize::USIZE..].copy_from_slice(owf_output);
+        Self { bytes }
+    }
+
+    pub(crate) fn owf_input(&self) -> &GenericArray<u8, O::InputSize> {
+        GenericArray::from_slice(&self.bytes[..O::InputSize::USIZE])
+    }
+
+    #[cfg(feature = "_variant")]
+    pub(crate) fn owf_output(&self) -> &GenericArray<u8, O::InputSize> {
+        GenericArray::from_slice(&self.bytes[O::InputSize::USIZE..])
+    }
+
+    /// Canonical encoding of the key, i.e., the OWF input followed by the OWF
+    /// output
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
 }
 
 impl<O> Clone for PublicKey<O>
@@ -176,8 +241,7 @@ where
 {
     fn clone(&self) -> Self {
         Self {
-            owf_input: self.owf_input.clone(),
-            owf_output: self.owf_output.clone(),
+            bytes: self.bytes.clone(),
         }
     }
 }
@@ -190,11 +254,8 @@ where
 
     fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
         if bytes.len() == O::PK::USIZE {
-            let owf_input = GenericArray::from_slice(&bytes[..O::InputSize::USIZE]);
-            let owf_output = GenericArray::from_slice(&bytes[O::InputSize::USIZE..]);
             Ok(Self {
-                owf_input: owf_input.clone(),
-                owf_output: owf_output.clone(),
+                bytes: GenericArray::from_slice(bytes).clone(),
             })
         } else {
             Err(Error::new())
@@ -207,7 +268,7 @@ where
     O: OWFParameters,
 {
     fn from(value: PublicKey<O>) -> Self {
-        value.to_bytes()
+        value.bytes
     }
 }
 
@@ -218,17 +279,11 @@ where
     type Repr = GenericArray<u8, O::PK>;
 
     fn to_bytes(&self) -> Self::Repr {
-        let mut buf = GenericArray::default();
-        buf[..O::InputSize::USIZE].copy_from_slice(&self.owf_input);
-        buf[O::InputSize::USIZE..].copy_from_slice(&self.owf_output);
-        buf
+        self.bytes.clone()
     }
 
     fn to_vec(&self) -> Vec<u8> {
-        let mut buf = Vec::with_capacity(O::PK::USIZE);
-        buf.extend_from_slice(&self.owf_input);
-        buf.extend_from_slice(&self.owf_output);
-        buf
+        self.bytes.to_vec()
     }
 
     fn encoded_len(&self) -> usize {
@@ -241,9 +296,8 @@ where
     O: OWFParameters,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("PublicKey")
-            .field("owf_input", &self.owf_input.as_slice())
-            .field("owf_output", &self.owf_output.as_slice())
+        f.debug_tuple("PublicKey")
+            .field(&HexPrefix(self.as_bytes()))
             .finish()
     }
 }
@@ -253,12 +307,39 @@ where
     O: OWFParameters,
 {
     fn eq(&self, rhs: &Self) -> bool {
-        self.owf_input == rhs.owf_input && self.owf_output == rhs.owf_output
+        self.bytes == rhs.bytes
     }
 }
 
 impl<O> Eq for PublicKey<O> where O: OWFParameters {}
 
+impl<O> PartialOrd for PublicKey<O>
+where
+    O: OWFParameters,
+{
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<O> Ord for PublicKey<O>
+where
+    O: OWFParameters,
+{
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.as_bytes().cmp(rhs.as_bytes())
+    }
+}
+
+impl<O> Hash for PublicKey<O>
+where
+    O: OWFParameters,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<O> Serialize for PublicKey<O>
 where
@@ -9,6 +9,7 @@
 //!
 //! Key generation, signing and verification can be implemented as follows:
 //! ```
+//! # #[cfg(feature = "faest-128f")] {
 //! use faest::{FAEST128fSigningKey, FAEST128fSignature};
 //! use faest::{signature::{Signer, Verifier, Keypair}, KeypairGenerator};
 //!
@@ -18,10 +19,12 @@
 //!
 //! let verification_key = sk.verifying_key();
 //! verification_key.verify(msg, &signature).expect("Verification failed");
+//! # }
 //! ```
 //!
 //! Due to the size of the signatures, all variants support signing into boxed signatures:
 //! ```
+//! # #[cfg(feature = "faest-128f")] {
 //! use faest::{FAEST128fSigningKey, FAEST128fSignature};
 //! use faest::{signature::{Signer, Verifier, Keypair}, KeypairGenerator};
 //!
@@ -31,6 +34,7 @@
 //!
 //! let verification_key = sk.verifying_key();
 //! verification_key.verify(msg, &signature).expect("Verification failed");
+//! # }
 //! ```
 //!
 //! The signature generation is determinstic per default. If the
@@ -38,7 +42,7 @@
 //! trait is also implemented which allows the caller to specify an RNG to
 //! provide additional randomness:
 //! ```
-//! # #[cfg(feature="randomized-signer")] {
+//! # #[cfg(all(feature = "randomized-signer", feature = "faest-128f"))] {
 //! use faest::{FAEST128fSigningKey, FAEST128fSignature};
 //! use faest::{signature::{RandomizedSigner, Verifier, Keypair}, KeypairGenerator};
 //!
@@ -51,52 +55,214 @@
 //! verification_key.verify(msg, &signature).expect("Verification failed");
 //! # }
 //! ```
+//!
+//! If the variant is only known at runtime, signatures can be parsed into a
+//! [ParsedSignature] either for a given [FaestVariant] or by identifying the
+//! variant from the signature length:
+//! ```
+//! # #[cfg(feature = "faest-128f")] {
+//! use faest::{FAEST128fSigningKey, FAEST128fSignature, FaestVariant};
+//! use faest::{signature::{Signer, Keypair}, ByteEncoding, KeypairGenerator};
+//!
+//! let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+//! let msg = "some message".as_bytes();
+//! let signature: FAEST128fSignature = sk.sign(msg);
+//! let verification_key = sk.verifying_key().to_bytes();
+//!
+//! let (variant, signature) = faest::parse_signature_any(signature.as_ref()).unwrap();
+//! assert_eq!(variant, FaestVariant::FAEST128f);
+//! signature.verify(msg, &verification_key).expect("Verification failed");
+//! # }
+//! ```
+//!
+//! ## Selecting variants
+//!
+//! Each variant of the FAEST specification is behind a feature, e.g.,
+//! `faest-128f` or `faest-em-256s`. The `full` feature, which is enabled per
+//! default, enables all of them. To reduce the size of the compiled code,
+//! disable the default features and only enable the required variants:
+//! ```toml
+//! faest = { version = "0.1", default-features = false, features = ["sign", "zeroize", "faest-em-128f"] }
+//! ```
+//! Disabled variants are also excluded from [FaestVariant].
+//!
+//! Key generation and signing are behind the `sign` feature, which is enabled
+//! per default. Applications that only verify signatures can drop the signer
+//! and its dependency on `rand_core`:
+//! ```toml
+//! faest = { version = "0.1", default-features = false, features = ["verify", "faest-em-128f"] }
+//! ```
+//! Such builds provide the verification keys and signatures, but no signing
+//! keys.
+//!
+//! ## Bitsliced AES
+//!
+//! Per default, the OWFs of the AES-based variants and the PRGs use the `aes`
+//! crate, which selects AES-NI or a fixsliced implementation at runtime. Its
+//! software implementation can be forced with `RUSTFLAGS="--cfg
+//! aes_force_soft"`. If the `soft-aes-only` feature is enabled, all AES
+//! evaluations use the bitsliced implementation in [rijndael_32] instead.
+//! Signatures are identical in all cases.
+//!
+//! ## Non-standard parameter sets
+//!
+//! If the `blake3-oracle` feature is enabled, the variants `FAEST128fBlake3`
+//! and `FAEST128sBlake3` are available. They replace SHAKE128 and AES-CTR by
+//! BLAKE3 in the random oracles and the PRG. These variants are **not**
+//! compatible with the FAEST specification and should only be used if both
+//! signer and verifier agree on them.
+//!
+//! If the `experimental` feature is enabled, the variant `FAEST128us` is
+//! available. It uses fewer but larger VOLE rounds (τ = 8 with 2^16 leaves
+//! each) to reduce the signature size below the one of FAEST-128s at the cost
+//! of considerably slower signing and verification. **Warning:** this
+//! parameter set is not part of the FAEST specification and its security has
+//! not been analyzed by the FAEST authors.
 
 // #![forbid(unsafe_code)]
 #![warn(missing_docs)]
+// Panics on user inputs are not acceptable. Remaining `expect`s are explicitly
+// allowed together with the invariant that guarantees that they do not fail.
+#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used))]
 // TODO: fix those
 #![allow(clippy::type_complexity)]
 
+#[cfg(feature = "_variant")]
 use generic_array::{typenum::Unsigned, GenericArray};
+#[cfg(feature = "_variant")]
 use paste::paste;
+#[cfg(feature = "sign")]
 use rand_core::CryptoRngCore;
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "serde", feature = "_variant"))]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "randomized-signer")]
 pub use signature::RandomizedSigner;
+#[cfg(feature = "_variant")]
 use signature::SignatureEncoding;
 pub use signature::{self, Error, Keypair, Signer, Verifier};
-#[cfg(feature = "zeroize")]
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use subtle::{Choice, ConstantTimeEq};
+#[cfg(all(feature = "sealed-keys", feature = "_variant"))]
+use zeroize::Zeroizing;
+#[cfg(all(feature = "zeroize", feature = "_variant"))]
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+// The unit tests generate keys and sign.
+#[cfg(all(test, not(feature = "sign")))]
+compile_error!("the unit tests require the `sign` feature");
+
 mod aes;
+mod aes_backend;
+mod buffer_pool;
+#[cfg(all(feature = "cbor", feature = "_variant"))]
+pub mod cbor;
+mod components;
+pub mod constants;
 mod em;
+#[cfg(feature = "_variant")]
 mod faest;
 mod fields;
+#[cfg(feature = "_variant")]
+mod hash_values;
+#[cfg(feature = "hybrid")]
+pub mod hybrid;
+#[cfg(feature = "_variant")]
+mod interactive;
 mod internal_keys;
+// Without any variant, the enums of the variants are uninhabited, hence the
+// code after matching on them is unreachable.
+#[cfg_attr(not(feature = "_variant"), allow(unreachable_code))]
+pub mod multi;
+pub mod owf;
 pub mod parameter;
+#[cfg(feature = "_variant")]
+mod poly;
+#[cfg(feature = "prehash")]
+pub mod prehash;
 pub mod prg;
-mod random_oracles;
-mod rijndael_32;
-mod universal_hashing;
+mod progress;
+pub mod random_oracles;
+pub mod rijndael_32;
+#[cfg(all(feature = "sealed-keys", feature = "_variant"))]
+mod sealed;
+#[cfg(all(feature = "sign", feature = "_variant"))]
+mod shamir;
+mod sign_options;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+#[cfg(feature = "tokio-compat")]
+pub mod tokio_compat;
+#[cfg(feature = "transcript")]
+pub mod transcript;
+pub mod universal_hashing;
 mod utils;
+// Without any variant, the enums of the variants are uninhabited, hence their
+// methods do not use their arguments.
+#[cfg_attr(not(feature = "_variant"), allow(unused_variables, unreachable_code))]
+mod variant;
 pub mod vc;
+mod verify_options;
 pub mod vole;
+pub mod zk;
+
+/// Internals exposed for the micro-benchmarks
+///
+/// This module is not part of the public API and may change at any time.
+#[cfg(feature = "bench-internals")]
+#[doc(hidden)]
+pub mod bench_internals {
+    pub use crate::fields::{GF128, GF192, GF256};
+}
 
+pub use crate::buffer_pool::BufferPool;
+#[cfg(all(feature = "cbor", feature = "_variant"))]
+use crate::cbor::CborEncoding;
+pub use crate::components::SignatureComponents;
+#[cfg(all(feature = "diagnostics", feature = "_variant"))]
+use crate::faest::faest_verify_with_recomputed_challenges;
+#[cfg(all(feature = "diagnostics", feature = "_variant"))]
+pub use crate::faest::RecomputedChallenges;
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use crate::faest::{
+    faest_finish, faest_keygen, faest_keygen_detailed, faest_keygen_fast, faest_precompute,
+    faest_sign, faest_sign_vectored, faest_sign_with_options, faest_sign_with_progress,
+    SigningPrecomputation,
+};
+#[cfg(all(feature = "parallel", feature = "_variant"))]
+use crate::faest::{faest_keygen_batch, faest_sign_batch};
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use crate::internal_keys::SecretKey;
+pub use crate::owf::{KeyRejected, RngSuspect};
+pub use crate::progress::Phase;
+pub use crate::sign_options::MAX_CONTEXT_LENGTH;
+#[cfg(feature = "sign")]
+pub use crate::sign_options::{InvalidSignOptions, SignOptions};
+pub use crate::variant::{
+    detect_parameters, parse_signature_any, CycleCosts, FaestVariant, ParameterInfo,
+    ParsedSignature, ParsedVerificationKey,
+};
+pub use crate::verify_options::VerifyOptions;
+#[cfg(feature = "_variant")]
 use crate::{
-    faest::{faest_keygen, faest_sign, faest_verify},
-    internal_keys::{PublicKey, SecretKey},
-    parameter::{
-        FAEST128fParameters, FAEST128sParameters, FAEST192fParameters, FAEST192sParameters,
-        FAEST256fParameters, FAEST256sParameters, FAESTEM128fParameters, FAESTEM128sParameters,
-        FAESTEM192fParameters, FAESTEM192sParameters, FAESTEM256fParameters, FAESTEM256sParameters,
-        FAESTParameters, OWFParameters,
+    faest::{
+        faest_verify, faest_verify_low_memory, faest_verify_vectored, faest_verify_with_options,
+        faest_verify_with_progress, Domain,
     },
+    internal_keys::PublicKey,
+    parameter::{FAESTParameters, OWFParameters},
+    utils::HexPrefix,
 };
 
 /// Generate a key pair from a cryptographically secure RNG
+#[cfg(feature = "sign")]
 pub trait KeypairGenerator: Keypair {
     /// Generate a new keypair
+    ///
+    /// # Panics
+    ///
+    /// The implementations of this crate panic if the RNG is broken, e.g., if
+    /// it is stuck at a constant output. Use `try_generate` of the signing
+    /// keys to handle this case.
     fn generate<R>(rng: R) -> Self
     where
         R: CryptoRngCore;
@@ -106,9 +272,11 @@ pub trait KeypairGenerator: Keypair {
 ///
 /// [Verifier] requires its generic argument to be [Sized], but `[u8]` is not.
 /// Hence, this struct simply wraps a slice.
+#[cfg(feature = "_variant")]
 #[derive(Debug)]
 pub struct SignatureRef<'a>(&'a [u8]);
 
+#[cfg(feature = "_variant")]
 impl<'a, 'b> From<&'b [u8]> for SignatureRef<'a>
 where
     'b: 'a,
@@ -135,34 +303,40 @@ pub trait ByteEncoding: Clone + Sized + for<'a> TryFrom<&'a [u8]> + TryInto<Self
     fn encoded_len(&self) -> usize;
 }
 
+#[cfg(feature = "_variant")]
 macro_rules! define_impl {
     ($param:ident) => {
         paste! {
             struct $param;
 
             impl $param {
+                #[cfg(feature = "sign")]
                 #[inline(always)]
                 fn sign(
                     msg: &[u8],
-                    sk: &SecretKey<<[<$param Parameters>] as FAESTParameters>::OWF>,
+                    sk: &SecretKey<<parameter::[<$param Parameters>] as FAESTParameters>::OWF>,
                     rho: &[u8],
-                    signature: &mut GenericArray<u8, <[<$param Parameters>] as FAESTParameters>::SignatureSize>,
+                    signature: &mut GenericArray<u8, <parameter::[<$param Parameters>] as FAESTParameters>::SignatureSize>,
                 ) {
-                    faest_sign::<[<$param Parameters>]>(msg, sk, rho, signature);
+                    faest_sign::<parameter::[<$param Parameters>]>(msg, sk, rho, signature);
                 }
 
                 #[inline(always)]
                 fn verify(
                     msg: &[u8],
-                    pk: &PublicKey<<[<$param Parameters>] as FAESTParameters>::OWF>,
-                    sigma: &GenericArray<u8, <[<$param Parameters>] as FAESTParameters>::SignatureSize>,
+                    pk: &PublicKey<<parameter::[<$param Parameters>] as FAESTParameters>::OWF>,
+                    sigma: &GenericArray<u8, <parameter::[<$param Parameters>] as FAESTParameters>::SignatureSize>,
                 ) -> Result<(), Error>
                 {
-                    faest_verify::<[<$param Parameters>]>(msg, pk, sigma)
+                    faest_verify::<parameter::[<$param Parameters>]>(msg, pk, sigma)
                 }
             }
 
+            #[cfg(feature = "sign")]
             #[doc = "Signing key for " $param]
+            ///
+            /// Keys are compared in constant time and the OWF key is redacted
+            /// from the `Debug` output.
             /// ```
             #[doc = "use faest::{" $param "SigningKey as SK, " $param "Signature as Sig};"]
             /// use faest::{Signer, Verifier, Keypair, KeypairGenerator};
@@ -180,8 +354,9 @@ macro_rules! define_impl {
             #[derive(Debug, Clone, PartialEq, Eq)]
             #[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
             #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-            pub struct [<$param SigningKey>](SecretKey<<[<$param Parameters>] as FAESTParameters>::OWF>);
+            pub struct [<$param SigningKey>](SecretKey<<parameter::[<$param Parameters>] as FAESTParameters>::OWF>);
 
+            #[cfg(feature = "sign")]
             impl TryFrom<&[u8]> for [<$param SigningKey>] {
                 type Error = Error;
 
@@ -190,20 +365,30 @@ macro_rules! define_impl {
                 }
             }
 
-            impl From<&[<$param SigningKey>]> for [u8; <<[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::SK::USIZE] {
+            #[cfg(feature = "sign")]
+            impl ConstantTimeEq for [<$param SigningKey>] {
+                fn ct_eq(&self, rhs: &Self) -> Choice {
+                    self.0.ct_eq(&rhs.0)
+                }
+            }
+
+            #[cfg(feature = "sign")]
+            impl From<&[<$param SigningKey>]> for [u8; <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::SK::USIZE] {
                 fn from(value: &[<$param SigningKey>]) -> Self {
                     value.to_bytes()
                 }
             }
 
-            impl From<[<$param SigningKey>]> for [u8; <<[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::SK::USIZE] {
+            #[cfg(feature = "sign")]
+            impl From<[<$param SigningKey>]> for [u8; <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::SK::USIZE] {
                 fn from(value: [<$param SigningKey>]) -> Self {
                     value.to_bytes()
                 }
             }
 
+            #[cfg(feature = "sign")]
             impl ByteEncoding for [<$param SigningKey>] {
-                type Repr = [u8; <<[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::SK::USIZE];
+                type Repr = [u8; <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::SK::USIZE];
 
                 fn to_bytes(&self) -> Self::Repr {
                     self.0.to_bytes().into_array()
@@ -219,9 +404,21 @@ macro_rules! define_impl {
             }
 
             #[doc = "Verification key for " $param]
-            #[derive(Debug, Clone, PartialEq, Eq)]
+            ///
+            /// Keys are compared, ordered and hashed by their canonical byte
+            /// encoding as returned by
+            #[doc = "[`" $param "VerificationKey::as_bytes`]."]
+            /// Hence, the ordering is lexicographic and stable across versions.
+            ///
+            /// Signatures are bound to the full encoding, i.e., a signature
+            /// does not verify under a key that only shares the OWF input or
+            /// the OWF output.
+            ///
+            /// The `Debug` output only shows a short hex prefix of the
+            /// encoding.
+            #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
             #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-            pub struct [<$param VerificationKey>](PublicKey<<[<$param Parameters>] as FAESTParameters>::OWF>);
+            pub struct [<$param VerificationKey>](PublicKey<<parameter::[<$param Parameters>] as FAESTParameters>::OWF>);
 
             impl TryFrom<&[u8]> for [<$param VerificationKey>] {
                 type Error = Error;
@@ -231,20 +428,34 @@ macro_rules! define_impl {
                 }
             }
 
-            impl From<&[<$param VerificationKey>]> for [u8; <<[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::PK::USIZE] {
+            impl AsRef<[u8]> for [<$param VerificationKey>] {
+                fn as_ref(&self) -> &[u8] {
+                    self.as_bytes()
+                }
+            }
+
+            impl std::fmt::Debug for [<$param VerificationKey>] {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_tuple(stringify!([<$param VerificationKey>]))
+                        .field(&HexPrefix(self.as_bytes()))
+                        .finish()
+                }
+            }
+
+            impl From<&[<$param VerificationKey>]> for [u8; <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::PK::USIZE] {
                 fn from(value: &[<$param VerificationKey>]) -> Self {
                     value.to_bytes()
                 }
             }
 
-            impl From<[<$param VerificationKey>]> for [u8; <<[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::PK::USIZE] {
+            impl From<[<$param VerificationKey>]> for [u8; <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::PK::USIZE] {
                 fn from(value: [<$param VerificationKey>]) -> Self {
                     value.to_bytes()
                 }
             }
 
             impl ByteEncoding for [<$param VerificationKey>] {
-                type Repr = [u8; <<[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::PK::USIZE];
+                type Repr = [u8; <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::PK::USIZE];
 
                 fn to_bytes(&self) -> Self::Repr {
                     self.0.to_bytes().into_array()
@@ -259,6 +470,53 @@ macro_rules! define_impl {
                 }
             }
 
+            impl [<$param VerificationKey>] {
+                /// Start the verification of an interactive proof from the
+                /// commitment of the prover
+                ///
+                /// Fails if the commitment is of the wrong size.
+                pub fn interactive_verifier(&self, commitment: &[u8]) -> Result<[<$param InteractiveVerifier>], Error> {
+                    interactive::Verifier::new(&self.0, commitment).map([<$param InteractiveVerifier>])
+                }
+
+                /// Canonical encoding of the key
+                ///
+                /// The encoding consists of the OWF input followed by the OWF
+                /// output and is identical to the output of
+                /// [`ByteEncoding::to_bytes`].
+                /// ```
+                #[doc = "use faest::{" $param "SigningKey as SK, ByteEncoding, Keypair, KeypairGenerator};"]
+                ///
+                /// let vk = SK::generate(rand::thread_rng()).verifying_key();
+                /// assert_eq!(vk.as_bytes(), vk.to_bytes().as_slice());
+                /// ```
+                pub fn as_bytes(&self) -> &[u8] {
+                    self.0.as_bytes()
+                }
+
+                /// Tagged encoding of the key
+                ///
+                /// See [`ParsedVerificationKey::to_tagged_bytes`] for the
+                /// encoding. The raw encoding of [`ByteEncoding::to_bytes`]
+                /// remains the encoding of the FAEST specification.
+                pub fn to_tagged_bytes(&self) -> Vec<u8> {
+                    variant::tag(FaestVariant::$param, self.as_bytes())
+                }
+
+                /// Key from its tagged encoding
+                ///
+                /// Fails if the key is tagged with another variant, even if
+                /// the raw encodings of both variants have the same size.
+                pub fn from_tagged_bytes(bytes: &[u8]) -> Result<Self, Error> {
+                    match variant::split_tag(bytes)? {
+                        (FaestVariant::$param, verification_key) => Self::try_from(verification_key),
+                        #[allow(unreachable_patterns)]
+                        _ => Err(Error::new()),
+                    }
+                }
+            }
+
+            #[cfg(feature = "sign")]
             impl Keypair for [<$param SigningKey>] {
                 type VerifyingKey = [<$param VerificationKey>];
 
@@ -267,20 +525,504 @@ macro_rules! define_impl {
                 }
             }
 
+            #[cfg(feature = "sign")]
             impl KeypairGenerator for [<$param SigningKey>] {
                 fn generate<R>(rng: R) -> Self
                 where
                     R: CryptoRngCore,
                 {
-                    Self(faest_keygen::<<[<$param Parameters>] as FAESTParameters>::OWF, R>(rng))
+                    Self(faest_keygen::<parameter::[<$param Parameters>], R>(rng))
                 }
             }
 
-            #[doc = "Signature for " $param]
+            #[cfg(feature = "sign")]
+            impl [<$param SigningKey>] {
+                /// Generate a new key or fail if the RNG is suspected to be broken
+                ///
+                /// Key generation samples candidates until the OWF can be
+                /// evaluated without zero S-box inputs. This fails after 2^16
+                /// rejected candidates, which an honest RNG does not reach in
+                /// practice. Otherwise, the key is identical to the
+                /// one generated by [`KeypairGenerator::generate`].
+                pub fn try_generate<R>(rng: R) -> Result<Self, RngSuspect>
+                where
+                    R: CryptoRngCore,
+                {
+                    Self::try_generate_detailed(rng).map(|(sk, _)| sk)
+                }
+
+                /// Generate a new key and report the number of sampled candidates
+                ///
+                /// See [`Self::try_generate`]. The number of candidates
+                /// includes the accepted one, i.e., it is at least 1.
+                pub fn try_generate_detailed<R>(rng: R) -> Result<(Self, u32), RngSuspect>
+                where
+                    R: CryptoRngCore,
+                {
+                    faest_keygen_detailed::<parameter::[<$param Parameters>], R>(rng)
+                        .map(|(sk, attempts)| (Self(sk), attempts))
+                }
+
+                /// Generate a new key while only re-sampling the OWF key on rejection
+                ///
+                /// The keys are valid keys in the sense of the FAEST
+                /// specification, but given the same RNG they differ from the
+                /// keys generated by
+                #[doc = "[`" $param "SigningKey::generate`]"]
+                /// and hence from the keys of the NIST KATs.
+                pub fn generate_fast<R>(rng: R) -> Self
+                where
+                    R: CryptoRngCore,
+                {
+                    Self(faest_keygen_fast::<parameter::[<$param Parameters>], R>(rng))
+                }
+
+                /// Create a signing key from an existing OWF key and input
+                ///
+                /// Fails if FAEST can not prove knowledge of the key, i.e., if
+                /// one of the S-box inputs of the OWF is zero. The error
+                /// reports the first such S-box. Rejected keys are not
+                /// repaired; they can not be used with FAEST and need to be
+                /// rotated.
+                pub fn from_owf_key_and_input(
+                    key: &[u8; <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES::USIZE],
+                    input: &[u8; <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::InputSize::USIZE],
+                ) -> Result<Self, KeyRejected> {
+                    SecretKey::from_owf_key_and_input(
+                        GenericArray::from_slice(key),
+                        GenericArray::from_slice(input),
+                    )
+                    .map(Self)
+                }
+
+                /// Check whether an OWF key and input form a valid signing key
+                ///
+                /// This is cheaper than
+                #[doc = "[`" $param "SigningKey::from_owf_key_and_input`]"]
+                /// as the OWF is not evaluated.
+                pub fn is_valid_owf_key(
+                    key: &[u8; <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES::USIZE],
+                    input: &[u8; <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::InputSize::USIZE],
+                ) -> bool {
+                    <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::find_zero_sbox_input(
+                        GenericArray::from_slice(key),
+                        GenericArray::from_slice(input),
+                    )
+                    .is_none()
+                }
+
+                /// Precompute the message-independent part of a signature
+                ///
+                /// The returned precomputation can be turned into a signature
+                /// for any message with
+                #[doc = "[`" $param "SigningPrecomputation::finish`]."]
+                /// Each precomputation can be used exactly once.
+                pub fn precompute<R>(&self, rng: R) -> [<$param SigningPrecomputation>]
+                where
+                    R: CryptoRngCore,
+                {
+                    [<$param SigningPrecomputation>](faest_precompute::<parameter::[<$param Parameters>], R>(&self.0, rng))
+                }
+
+                /// Start an interactive proof of knowledge of the signing key
+                ///
+                /// Returns the prover together with its commitment. See
+                #[doc = "[`" $param "InteractiveProver`]"]
+                /// for the remaining rounds.
+                pub fn interactive_prover<R>(&self, rng: R) -> ([<$param InteractiveProver>], Vec<u8>)
+                where
+                    R: CryptoRngCore,
+                {
+                    let (prover, commitment) = interactive::Prover::new(&self.0, rng);
+                    ([<$param InteractiveProver>](prover), commitment)
+                }
+
+                /// Encrypt the signing key with a passphrase
+                ///
+                /// The key is encrypted with ChaCha20-Poly1305 under a key
+                /// derived from the passphrase with scrypt. The result records
+                /// the variant and the scrypt parameters and can be decrypted
+                /// with
+                #[doc = "[`" $param "SigningKey::unseal`]."]
+                #[cfg(feature = "sealed-keys")]
+                pub fn seal<R>(&self, passphrase: &[u8], rng: R) -> Vec<u8>
+                where
+                    R: CryptoRngCore,
+                {
+                    let sk = Zeroizing::new(self.to_bytes());
+                    sealed::seal(FaestVariant::$param, sk.as_ref(), passphrase, &Default::default(), rng)
+                }
+
+                /// Decrypt a signing key encrypted with
+                #[doc = "[`" $param "SigningKey::seal`]"]
+                ///
+                /// Fails if the passphrase is wrong, the sealed key is
+                /// malformed or was encrypted for another variant, or the
+                /// decrypted key is invalid. All cases return the same error.
+                #[cfg(feature = "sealed-keys")]
+                pub fn unseal(passphrase: &[u8], sealed: &[u8]) -> Result<Self, Error> {
+                    sealed::unseal(FaestVariant::$param, passphrase, sealed)
+                        .and_then(|sk| Self::try_from(sk.as_slice()))
+                }
+
+                /// Generate a batch of `n` keys in parallel
+                ///
+                /// A single seed is sampled from `rng`. The `i`-th key is
+                /// generated from an RNG derived from this seed and `i`, so the
+                /// result is deterministic given the output of `rng`.
+                #[cfg(feature = "parallel")]
+                pub fn generate_batch<R>(n: usize, rng: R) -> Vec<Self>
+                where
+                    R: CryptoRngCore,
+                {
+                    faest_keygen_batch::<parameter::[<$param Parameters>], R>(n, rng)
+                        .into_iter()
+                        .map(Self)
+                        .collect()
+                }
+
+                /// Sign a batch of messages in parallel
+                ///
+                /// A fresh randomness is sampled from `rng` for each message,
+                /// so the signatures are independent even if messages repeat.
+                /// The witness expansion and the scratch buffers are shared
+                /// across the batch. The signatures are returned in the order
+                /// of `msgs`.
+                #[cfg(feature = "parallel")]
+                pub fn sign_batch(
+                    &self,
+                    msgs: &[&[u8]],
+                    rng: &mut impl CryptoRngCore,
+                ) -> Vec<[<$param Signature>]> {
+                    faest_sign_batch::<parameter::[<$param Parameters>], _>(msgs, &self.0, rng)
+                        .into_iter()
+                        .map([<$param Signature>])
+                        .collect()
+                }
+
+                /// Split the key into `n` shares of which `threshold` are
+                /// required to reconstruct it
+                ///
+                /// The key is shared with Shamir's secret sharing over
+                /// GF(2^λ). Fails if `threshold` is 0 or larger than `n`, or
+                /// if `n` exceeds 255.
+                /// ```
+                #[doc = "use faest::{" $param "SigningKey as SK, KeypairGenerator};"]
+                ///
+                /// let mut rng = rand::thread_rng();
+                /// let sk = SK::generate(&mut rng);
+                /// let shares = sk.split_shares(5, 3, &mut rng).unwrap();
+                /// assert_eq!(SK::reconstruct(&shares[2..]).unwrap(), sk);
+                /// ```
+                pub fn split_shares<R>(&self, n: usize, threshold: usize, rng: R) -> Result<Vec<[<$param KeyShare>]>, Error>
+                where
+                    R: CryptoRngCore,
+                {
+                    shamir::split_shares(&self.0, n, threshold, rng)
+                        .map(|shares| shares.into_iter().map([<$param KeyShare>]).collect())
+                }
+
+                /// Reconstruct a key from at least `threshold` of its shares
+                ///
+                /// Fails if the shares have duplicate indices, belong to
+                /// different keys, or do not reconstruct the key, e.g., since
+                /// there are too few of them.
+                pub fn reconstruct(shares: &[[<$param KeyShare>]]) -> Result<Self, Error> {
+                    shamir::reconstruct_key(shares.iter().map(|share| &share.0)).map(Self)
+                }
+            }
+
+            #[cfg(feature = "sign")]
+            #[doc = "Share of a " $param " signing key"]
+            ///
+            #[doc = "See [`" $param "SigningKey::split_shares`]."]
             #[derive(Debug, Clone, PartialEq, Eq)]
+            pub struct [<$param KeyShare>](shamir::KeyShare<<parameter::[<$param Parameters>] as FAESTParameters>::OWF>);
+
+            #[cfg(feature = "sign")]
+            impl [<$param KeyShare>] {
+                /// Index of the share, starting at 1
+                pub fn index(&self) -> u8 {
+                    self.0.index()
+                }
+
+                /// Encode the share as byte vector
+                pub fn to_vec(&self) -> Vec<u8> {
+                    self.0.to_vec()
+                }
+            }
+
+            #[cfg(feature = "sign")]
+            impl TryFrom<&[u8]> for [<$param KeyShare>] {
+                type Error = Error;
+
+                fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+                    shamir::KeyShare::try_from(value).map(Self)
+                }
+            }
+
+            #[cfg(feature = "sign")]
+            #[doc = "Precomputed, message-independent signing state for " $param]
+            ///
+            /// The state is consumed when finishing a signature and hence can
+            /// not be used to sign two messages.
+            /// ```
+            #[doc = "use faest::{" $param "SigningKey as SK, " $param "Signature as Sig};"]
+            /// use faest::{Verifier, Keypair, KeypairGenerator};
+            ///
+            /// let mut rng = rand::thread_rng();
+            /// let sk = SK::generate(&mut rng);
+            /// let precomputation = sk.precompute(&mut rng);
+            /// // ... once the message is available
+            /// let msg = "some message".as_bytes();
+            /// let signature: Sig = precomputation.finish(msg);
+            /// sk.verifying_key().verify(msg, &signature).expect("Verification failed");
+            /// ```
+            ///
+            /// Reusing a precomputation is rejected by the compiler:
+            /// ```compile_fail
+            #[doc = "use faest::{" $param "SigningKey as SK, KeypairGenerator};"]
+            ///
+            /// let mut rng = rand::thread_rng();
+            /// let sk = SK::generate(&mut rng);
+            /// let precomputation = sk.precompute(&mut rng);
+            /// let signature_1 = precomputation.finish(b"message 1");
+            /// let signature_2 = precomputation.finish(b"message 2");
+            /// ```
+            pub struct [<$param SigningPrecomputation>](SigningPrecomputation<parameter::[<$param Parameters>]>);
+
+            #[cfg(feature = "sign")]
+            impl [<$param SigningPrecomputation>] {
+                /// Absorb the message and complete the signature
+                pub fn finish(self, msg: &[u8]) -> [<$param Signature>] {
+                    let mut signature = GenericArray::default();
+                    faest_finish(self.0, msg, &mut signature);
+                    [<$param Signature>](signature)
+                }
+
+                /// Check that the cached witness still belongs to the signing key
+                ///
+                /// The check recomputes the witness, which is cheap compared
+                /// to signing. A corrupted witness would otherwise produce a
+                /// signature that fails to verify.
+                pub fn verify_witness(&self) -> bool {
+                    self.0.verify_witness()
+                }
+
+                /// Absorb the message and complete the signature into a box
+                pub fn finish_boxed(self, msg: &[u8]) -> Box<[<$param Signature>]> {
+                    let mut signature = Box::new([<$param Signature>](GenericArray::default()));
+                    faest_finish(self.0, msg, &mut signature.0);
+                    signature
+                }
+            }
+
+            #[cfg(feature = "sign")]
+            impl std::fmt::Debug for [<$param SigningPrecomputation>] {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_tuple(stringify!([<$param SigningPrecomputation>]))
+                        .field(&"redacted")
+                        .finish()
+                }
+            }
+
+            #[cfg(feature = "sign")]
+            #[doc = "Prover of the interactive protocol underlying " $param]
+            ///
+            /// FAEST signatures are the non-interactive version of a
+            /// protocol consisting of a commitment and three rounds of
+            /// challenges and responses. The prover answers each challenge
+            /// exactly once. The protocol is only honest-verifier
+            /// zero-knowledge, hence challenges need to be sampled uniformly
+            /// at random.
+            /// ```
+            #[doc = "use faest::{" $param "SigningKey as SK, KeypairGenerator, Keypair};"]
+            /// use rand::RngCore;
+            ///
+            /// let mut rng = rand::thread_rng();
+            /// let sk = SK::generate(&mut rng);
+            /// let (mut prover, commitment) = sk.interactive_prover(&mut rng);
+            /// let mut verifier = sk.verifying_key().interactive_verifier(&commitment).unwrap();
+            /// for _ in 0..3 {
+            ///     let challenge = verifier.challenge(|challenge| rng.fill_bytes(challenge)).unwrap();
+            ///     let response = prover.respond(&challenge).unwrap();
+            ///     verifier.receive(&response).unwrap();
+            /// }
+            /// verifier.finish().expect("Verification failed");
+            /// ```
+            pub struct [<$param InteractiveProver>](interactive::Prover<parameter::[<$param Parameters>]>);
+
+            #[cfg(feature = "sign")]
+            impl [<$param InteractiveProver>] {
+                /// Respond to the challenge of the current round
+                ///
+                /// Fails if the challenge is of the wrong size or if all
+                /// challenges have already been answered.
+                pub fn respond(&mut self, challenge: &[u8]) -> Result<Vec<u8>, Error> {
+                    self.0.respond(challenge)
+                }
+            }
+
+            #[cfg(feature = "sign")]
+            impl std::fmt::Debug for [<$param InteractiveProver>] {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_tuple(stringify!([<$param InteractiveProver>]))
+                        .field(&"redacted")
+                        .finish()
+                }
+            }
+
+            #[doc = "Verifier of the interactive protocol underlying " $param]
+            ///
+            /// The verifier is available in verify-only builds. See
+            #[doc = "[`" $param "InteractiveProver`] for an example."]
+            pub struct [<$param InteractiveVerifier>](interactive::Verifier<parameter::[<$param Parameters>]>);
+
+            impl [<$param InteractiveVerifier>] {
+                /// Sample the challenge of the current round
+                ///
+                /// `fill` receives the challenge and needs to fill it with
+                /// uniformly random bytes, e.g., from a cryptographically
+                /// secure RNG. Fails if the response to the previous
+                /// challenge is missing or if all challenges have already
+                /// been issued.
+                pub fn challenge(&mut self, fill: impl FnOnce(&mut [u8])) -> Result<Vec<u8>, Error> {
+                    self.0.challenge(fill)
+                }
+
+                /// Receive the response to the current challenge
+                ///
+                /// Fails if the response is of the wrong size or if no
+                /// challenge is pending.
+                pub fn receive(&mut self, response: &[u8]) -> Result<(), Error> {
+                    self.0.receive(response)
+                }
+
+                /// Check the transcript after all responses have been received
+                pub fn finish(self) -> Result<(), Error> {
+                    self.0.finish()
+                }
+            }
+
+            impl std::fmt::Debug for [<$param InteractiveVerifier>] {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_tuple(stringify!([<$param InteractiveVerifier>])).finish()
+                }
+            }
+
+            #[doc = "Signature for " $param]
+            ///
+            /// The `Debug` output only shows a short hex prefix of the
+            /// encoding.
+            #[derive(Clone, PartialEq, Eq)]
             #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-            pub struct [<$param Signature>](GenericArray<u8, <[<$param Parameters>] as FAESTParameters>::SignatureSize>);
+            pub struct [<$param Signature>](GenericArray<u8, <parameter::[<$param Parameters>] as FAESTParameters>::SignatureSize>);
+
+            #[cfg(feature = "sign")]
+            impl [<$param SigningKey>] {
+                /// Sign a message and report the progress to `progress`
+                ///
+                /// The callback is invoked at the boundaries of each [`Phase`]
+                /// and after each repetition of the VOLE commitment with the
+                /// overall progress in `[0, 1]`. Like [`Signer::sign`], the
+                /// signature is deterministic, i.e., no additional randomness
+                /// `ρ` is used, and identical to the one produced by
+                /// [`Signer::sign`].
+                pub fn sign_with_progress(
+                    &self,
+                    msg: &[u8],
+                    progress: &mut dyn FnMut(Phase, f32),
+                ) -> [<$param Signature>] {
+                    let mut signature = GenericArray::default();
+                    faest_sign_with_progress::<parameter::[<$param Parameters>]>(msg, &self.0, &[], &mut signature, progress);
+                    [<$param Signature>](signature)
+                }
 
+                /// Sign a message with randomness from `rng` and report the progress to `progress`
+                ///
+                /// This is the randomized counterpart of
+                /// [`Self::sign_with_progress`] and produces the same
+                /// signatures as [`RandomizedSigner::sign_with_rng`] for the
+                /// same randomness.
+                #[cfg(feature = "randomized-signer")]
+                pub fn sign_with_rng_and_progress(
+                    &self,
+                    rng: &mut impl CryptoRngCore,
+                    msg: &[u8],
+                    progress: &mut dyn FnMut(Phase, f32),
+                ) -> [<$param Signature>] {
+                    let mut rho = GenericArray::<
+                        u8,
+                        <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES,
+                    >::default();
+                    rng.fill_bytes(&mut rho);
+                    let mut signature = GenericArray::default();
+                    faest_sign_with_progress::<parameter::[<$param Parameters>]>(msg, &self.0, &rho, &mut signature, progress);
+                    [<$param Signature>](signature)
+                }
+
+                /// Sign a message given as a sequence of parts with randomness from `rng`
+                ///
+                /// The parts are absorbed in order without concatenating them
+                /// first. The signature only depends on the concatenation of
+                /// the parts and not on their boundaries, i.e., it is identical
+                /// to the signature on the concatenated message for the same
+                /// randomness.
+                pub fn sign_vectored(
+                    &self,
+                    parts: &[&[u8]],
+                    rng: &mut impl CryptoRngCore,
+                ) -> [<$param Signature>] {
+                    let mut rho = GenericArray::<
+                        u8,
+                        <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES,
+                    >::default();
+                    rng.fill_bytes(&mut rho);
+                    let mut signature = GenericArray::default();
+                    faest_sign_vectored::<parameter::[<$param Parameters>]>(parts, &self.0, &rho, &mut signature);
+                    [<$param Signature>](signature)
+                }
+            }
+
+            #[cfg(feature = "sign")]
+            impl [<$param SigningKey>] {
+                /// Sign a message with the given [`SignOptions`]
+                ///
+                /// With the default options, the signature is identical to the
+                /// one produced by [`Signer::sign`]. Fails if the context of
+                /// the options is longer than [`MAX_CONTEXT_LENGTH`] bytes or
+                /// if the options request both deterministic and randomized
+                /// signing, see [`InvalidSignOptions`].
+                pub fn sign_with_options(
+                    &self,
+                    msg: &[u8],
+                    mut options: SignOptions<'_>,
+                ) -> Result<[<$param Signature>], InvalidSignOptions> {
+                    let rho = options.rho::<
+                        <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES,
+                    >()?;
+                    let rho = rho.as_ref().map_or(&[][..], |rho| rho.as_slice());
+                    let progress = options.take_progress();
+                    let pool = options.pool();
+                    let domain = options.domain()?;
+                    let mut signature = GenericArray::default();
+                    faest_sign_with_options::<parameter::[<$param Parameters>]>(domain, &[msg], &self.0, rho, &mut signature, progress, pool, options.is_parallel());
+                    Ok([<$param Signature>](signature))
+                }
+            }
+
+            #[cfg(feature = "sign")]
+            impl multi::ErasedSigner for [<$param SigningKey>] {
+                fn variant(&self) -> FaestVariant {
+                    FaestVariant::$param
+                }
+
+                fn try_sign_erased(&self, msg: &[u8]) -> Result<ParsedSignature, Error> {
+                    Signer::<Box<[<$param Signature>]>>::try_sign(self, msg).map(ParsedSignature::$param)
+                }
+            }
+
+            #[cfg(feature = "sign")]
             impl Signer<[<$param Signature>]> for [<$param SigningKey>] {
                 fn try_sign(&self, msg: &[u8]) -> Result<[<$param Signature>], Error> {
                     Ok(self.sign(msg))
@@ -293,6 +1035,7 @@ macro_rules! define_impl {
                 }
             }
 
+            #[cfg(feature = "sign")]
             impl Signer<Box<[<$param Signature>]>> for [<$param SigningKey>] {
                 fn try_sign(&self, msg: &[u8]) -> Result<Box<[<$param Signature>]>, Error> {
                     Ok(self.sign(msg))
@@ -325,18 +1068,271 @@ macro_rules! define_impl {
                 }
             }
 
+            #[cfg(feature = "prehash")]
+            impl prehash::private::Sealed for [<$param SigningKey>] {}
+
+            #[cfg(feature = "prehash")]
+            impl prehash::PrehashSigner<[<$param Signature>]> for [<$param SigningKey>] {}
+
+            #[cfg(feature = "prehash")]
+            impl prehash::private::SignPrehashed<[<$param Signature>]> for [<$param SigningKey>] {
+                fn sign_prehashed(
+                    &self,
+                    name: &'static [u8],
+                    digest: &[u8],
+                    rng: &mut dyn CryptoRngCore,
+                ) -> [<$param Signature>] {
+                    let mut rho = GenericArray::<
+                        u8,
+                        <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES,
+                    >::default();
+                    rng.fill_bytes(&mut rho);
+                    let mut signature = GenericArray::default();
+                    faest_sign_with_options::<parameter::[<$param Parameters>]>(Domain::Prehash(name), &[digest], &self.0, &rho, &mut signature, None, None, true);
+                    [<$param Signature>](signature)
+                }
+
+                fn sign_external_prehash(
+                    &self,
+                    digest: &[u8],
+                    rng: &mut dyn CryptoRngCore,
+                ) -> [<$param Signature>] {
+                    let mut rho = GenericArray::<
+                        u8,
+                        <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES,
+                    >::default();
+                    rng.fill_bytes(&mut rho);
+                    let mut signature = GenericArray::default();
+                    faest_sign_with_options::<parameter::[<$param Parameters>]>(Domain::ExternalPrehash, &[digest], &self.0, &rho, &mut signature, None, None, true);
+                    [<$param Signature>](signature)
+                }
+            }
+
+            #[cfg(feature = "prehash")]
+            impl prehash::PrehashSigner<Box<[<$param Signature>]>> for [<$param SigningKey>] {}
+
+            #[cfg(feature = "prehash")]
+            impl prehash::private::SignPrehashed<Box<[<$param Signature>]>> for [<$param SigningKey>] {
+                fn sign_prehashed(
+                    &self,
+                    name: &'static [u8],
+                    digest: &[u8],
+                    rng: &mut dyn CryptoRngCore,
+                ) -> Box<[<$param Signature>]> {
+                    Box::new(self.sign_prehashed(name, digest, rng))
+                }
+
+                fn sign_external_prehash(
+                    &self,
+                    digest: &[u8],
+                    rng: &mut dyn CryptoRngCore,
+                ) -> Box<[<$param Signature>]> {
+                    Box::new(self.sign_external_prehash(digest, rng))
+                }
+            }
+
+            #[cfg(feature = "prehash")]
+            impl prehash::private::Sealed for [<$param VerificationKey>] {}
+
+            #[cfg(feature = "prehash")]
+            impl prehash::PrehashVerifier<[<$param Signature>]> for [<$param VerificationKey>] {}
+
+            #[cfg(feature = "prehash")]
+            impl prehash::private::VerifyPrehashed<[<$param Signature>]> for [<$param VerificationKey>] {
+                fn verify_prehashed(
+                    &self,
+                    name: &'static [u8],
+                    digest: &[u8],
+                    signature: &[<$param Signature>],
+                ) -> Result<(), Error> {
+                    faest_verify_vectored::<parameter::[<$param Parameters>]>(Domain::Prehash(name), &[digest], &self.0, &signature.0)
+                }
+
+                fn verify_external_prehash(
+                    &self,
+                    digest: &[u8],
+                    signature: &[<$param Signature>],
+                ) -> Result<(), Error> {
+                    faest_verify_vectored::<parameter::[<$param Parameters>]>(Domain::ExternalPrehash, &[digest], &self.0, &signature.0)
+                }
+            }
+
+            #[cfg(feature = "prehash")]
+            impl prehash::PrehashVerifier<Box<[<$param Signature>]>> for [<$param VerificationKey>] {}
+
+            #[cfg(feature = "prehash")]
+            impl prehash::private::VerifyPrehashed<Box<[<$param Signature>]>> for [<$param VerificationKey>] {
+                fn verify_prehashed(
+                    &self,
+                    name: &'static [u8],
+                    digest: &[u8],
+                    signature: &Box<[<$param Signature>]>,
+                ) -> Result<(), Error> {
+                    self.verify_prehashed(name, digest, signature.as_ref())
+                }
+
+                fn verify_external_prehash(
+                    &self,
+                    digest: &[u8],
+                    signature: &Box<[<$param Signature>]>,
+                ) -> Result<(), Error> {
+                    self.verify_external_prehash(digest, signature.as_ref())
+                }
+            }
+
+            #[cfg(all(feature = "sign", feature = "cbor"))]
+            impl CborEncoding for [<$param SigningKey>] {
+                fn to_cbor(&self) -> Vec<u8> {
+                    cbor::encode_signing_key(FaestVariant::$param, &self.0)
+                }
+
+                fn from_cbor(bytes: &[u8]) -> Result<Self, Error> {
+                    cbor::decode_signing_key(FaestVariant::$param, bytes).map(Self)
+                }
+            }
+
+            #[cfg(feature = "cbor")]
+            impl CborEncoding for [<$param VerificationKey>] {
+                fn to_cbor(&self) -> Vec<u8> {
+                    cbor::encode_verification_key(FaestVariant::$param, &self.0)
+                }
+
+                fn from_cbor(bytes: &[u8]) -> Result<Self, Error> {
+                    cbor::decode_verification_key(FaestVariant::$param, bytes).map(Self)
+                }
+            }
+
+            #[cfg(feature = "cbor")]
+            impl CborEncoding for [<$param Signature>] {
+                fn to_cbor(&self) -> Vec<u8> {
+                    cbor::encode_signature(FaestVariant::$param, &self.0)
+                }
+
+                fn from_cbor(bytes: &[u8]) -> Result<Self, Error> {
+                    cbor::decode_signature(FaestVariant::$param, bytes)
+                        .and_then(|signature| Self::try_from(signature.as_slice()))
+                }
+            }
+
+            impl [<$param VerificationKey>] {
+                /// Verify a signature with a small memory footprint
+                ///
+                /// The result is identical to [`Verifier::verify`], but the
+                /// GGM trees of the vector commitments are not stored during
+                /// the reconstruction of the VOLE correlations. This
+                /// significantly reduces the peak memory usage for the `s`
+                /// variants at the cost of slightly slower verification.
+                pub fn verify_low_memory(&self, msg: &[u8], signature: &[<$param Signature>]) -> Result<(), Error> {
+                    faest_verify_low_memory::<parameter::[<$param Parameters>]>(msg, &self.0, &signature.0)
+                }
+
+                /// Verify a signature and report the progress to `progress`
+                ///
+                /// The callback is invoked at the boundaries of each [`Phase`]
+                /// and after each repetition of the VOLE reconstruction with
+                /// the overall progress in `[0, 1]`. The result is identical
+                /// to [`Verifier::verify`].
+                pub fn verify_with_progress(
+                    &self,
+                    msg: &[u8],
+                    signature: &[<$param Signature>],
+                    progress: &mut dyn FnMut(Phase, f32),
+                ) -> Result<(), Error> {
+                    faest_verify_with_progress::<parameter::[<$param Parameters>]>(msg, &self.0, &signature.0, progress)
+                }
+
+                /// Verify a signature on a message given as a sequence of parts
+                ///
+                /// The result is identical to [`Verifier::verify`] on the
+                /// concatenation of the parts. Hence, signatures produced by
+                #[doc = "[`" $param "SigningKey::sign_vectored`]"]
+                /// can be verified with any split of the message into parts.
+                pub fn verify_vectored(&self, parts: &[&[u8]], signature: &[<$param Signature>]) -> Result<(), Error> {
+                    faest_verify_vectored::<parameter::[<$param Parameters>]>(Domain::Plain, parts, &self.0, &signature.0)
+                }
+
+                /// Verify a signature bound to `context`
+                ///
+                /// Verifies signatures produced by
+                #[doc = "[`" $param "SigningKey::sign_with_options`]"]
+                /// with the same context. An empty context is identical to
+                /// [`Verifier::verify`].
+                pub fn verify_with_context(
+                    &self,
+                    msg: &[u8],
+                    context: &[u8],
+                    signature: &[<$param Signature>],
+                ) -> Result<(), Error> {
+                    let domain = sign_options::context_domain(context).ok_or_else(Error::new)?;
+                    faest_verify_vectored::<parameter::[<$param Parameters>]>(domain, &[msg], &self.0, &signature.0)
+                }
+
+                /// Verify a signature with the given [`VerifyOptions`]
+                ///
+                /// With the default options, the result is identical to
+                /// [`Verifier::verify`].
+                pub fn verify_with_options(
+                    &self,
+                    msg: &[u8],
+                    signature: &[<$param Signature>],
+                    options: VerifyOptions<'_>,
+                ) -> Result<(), Error> {
+                    self.verify_vectored_with_options(&[msg], signature, options)
+                }
+
+                /// Verify a signature on a message given as a sequence of
+                /// parts with the given [`VerifyOptions`]
+                ///
+                /// With the default options, the result is identical to
+                #[doc = "[`" $param "VerificationKey::verify_vectored`]."]
+                pub fn verify_vectored_with_options(
+                    &self,
+                    parts: &[&[u8]],
+                    signature: &[<$param Signature>],
+                    mut options: VerifyOptions<'_>,
+                ) -> Result<(), Error> {
+                    let domain = options.domain()?;
+                    faest_verify_with_options::<parameter::[<$param Parameters>]>(
+                        domain,
+                        parts,
+                        &self.0,
+                        &signature.0,
+                        options.is_low_memory(),
+                        options.take_progress(),
+                        options.pool(),
+                    )
+                }
+            }
+
+            #[cfg(feature = "diagnostics")]
+            impl [<$param VerificationKey>] {
+                /// Verify a signature and report the recomputed challenges on failure
+                ///
+                /// See [`RecomputedChallenges`] for the reported values.
+                pub fn verify_with_recomputed_challenges(
+                    &self,
+                    msg: &[u8],
+                    signature: &[<$param Signature>],
+                ) -> Result<(), RecomputedChallenges> {
+                    faest_verify_with_recomputed_challenges::<parameter::[<$param Parameters>]>(msg, &self.0, &signature.0)
+                }
+            }
+
+            #[cfg(feature = "sign")]
             impl Verifier<[<$param Signature>]> for [<$param SigningKey>] {
                 fn verify(&self, msg: &[u8], signature: &[<$param Signature>]) -> Result<(), Error> {
                     $param::verify(msg, &self.0.pk, &signature.0)
                 }
             }
 
+            #[cfg(feature = "sign")]
             impl Verifier<Box<[<$param Signature>]>> for [<$param SigningKey>] {
                 fn verify(&self, msg: &[u8], signature: &Box<[<$param Signature>]>) -> Result<(), Error> {
                     $param::verify(msg, &self.0.pk, &signature.0)
                 }
             }
 
+            #[cfg(feature = "sign")]
             impl Verifier<SignatureRef<'_>> for [<$param SigningKey>] {
                 fn verify(&self, msg: &[u8], signature: &SignatureRef<'_>) -> Result<(), Error> {
                     GenericArray::try_from_slice(signature.0)
@@ -354,7 +1350,7 @@ macro_rules! define_impl {
                 ) -> Result<[<$param Signature>], Error> {
                     let mut rho = GenericArray::<
                         u8,
-                        <<[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES,
+                        <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES,
                     >::default();
                     rng.fill_bytes(&mut rho);
                     let mut signature = GenericArray::default();
@@ -372,7 +1368,7 @@ macro_rules! define_impl {
                 ) -> Result<Box<[<$param Signature>]>, Error> {
                     let mut rho = GenericArray::<
                         u8,
-                        <<[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES,
+                        <<parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDABYTES,
                     >::default();
                     rng.fill_bytes(&mut rho);
                     let mut signature = Box::new([<$param Signature>](GenericArray::default()));
@@ -381,12 +1377,84 @@ macro_rules! define_impl {
                 }
             }
 
+            impl [<$param Signature>] {
+                /// Split the signature into its components
+                ///
+                /// The components are borrowed from the signature. See
+                /// [`SignatureComponents`] for the individual components.
+                pub fn components(&self) -> SignatureComponents<'_> {
+                    components::split::<parameter::[<$param Parameters>]>(&self.0)
+                }
+
+                /// Reassemble a signature from its components
+                ///
+                /// Fails if the number or the sizes of the components do not
+                /// match the parameters. Reassembling the components returned
+                /// by
+                #[doc = "[`" $param "Signature::components`]"]
+                /// gives back the same signature.
+                pub fn from_components(components: &SignatureComponents<'_>) -> Result<Self, Error> {
+                    components::join::<parameter::[<$param Parameters>]>(components).map(Self)
+                }
+            }
+
+            impl [<$param Signature>] {
+                /// Size of the signature (in bytes)
+                pub const LEN: usize = <parameter::[<$param Parameters>] as FAESTParameters>::SIGNATURE_SIZE;
+
+                /// Signature from its encoding
+                ///
+                /// This is a `const fn`, hence signatures can be placed in
+                /// `static`s:
+                ///
+                /// ```
+                #[doc = "use faest::" $param "Signature as Sig;"]
+                ///
+                /// static SIGNATURE: Sig = Sig::from_array([0; Sig::LEN]);
+                /// assert_eq!(SIGNATURE.to_array(), [0; Sig::LEN]);
+                /// ```
+                pub const fn from_array(bytes: [u8; Self::LEN]) -> Self {
+                    // SAFETY: `GenericArray<u8, N>` has the layout of
+                    // `[u8; N]` and the lengths agree, see the assertion below
+                    Self(unsafe {
+                        std::mem::transmute::<
+                            [u8; Self::LEN],
+                            GenericArray<u8, <parameter::[<$param Parameters>] as FAESTParameters>::SignatureSize>,
+                        >(bytes)
+                    })
+                }
+
+                /// Encoding of the signature as array
+                pub fn to_array(&self) -> [u8; Self::LEN] {
+                    self.to_bytes()
+                }
+            }
+
+            const _: () = assert!(
+                [<$param Signature>]::LEN
+                    == <<parameter::[<$param Parameters>] as FAESTParameters>::SignatureSize as Unsigned>::USIZE
+            );
+
+            impl From<[u8; [<$param Signature>]::LEN]> for [<$param Signature>] {
+                fn from(value: [u8; [<$param Signature>]::LEN]) -> Self {
+                    Self::from_array(value)
+                }
+            }
+
             impl AsRef<[u8]> for [<$param Signature>] {
                 fn as_ref(&self) -> &[u8] {
                     self.0.as_slice()
                 }
             }
 
+            impl std::fmt::Debug for [<$param Signature>] {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_tuple(stringify!([<$param Signature>]))
+                        .field(&HexPrefix(&self.0))
+                        .finish()
+                }
+            }
+
             impl TryFrom<&[u8]> for [<$param Signature>] {
                 type Error = Error;
 
@@ -397,18 +1465,18 @@ macro_rules! define_impl {
                 }
             }
 
-            impl From<[<$param Signature>]> for [u8; <[<$param Parameters>] as FAESTParameters>::SignatureSize::USIZE] {
+            impl From<[<$param Signature>]> for [u8; <parameter::[<$param Parameters>] as FAESTParameters>::SIGNATURE_SIZE] {
                 fn from(value: [<$param Signature>]) -> Self {
                     value.to_bytes()
                 }
             }
 
             impl SignatureEncoding for [<$param Signature>] {
-                type Repr = [u8; <[<$param Parameters>] as FAESTParameters>::SignatureSize::USIZE];
+                type Repr = [u8; <parameter::[<$param Parameters>] as FAESTParameters>::SIGNATURE_SIZE];
 
                 fn to_bytes(&self) -> Self::Repr {
                     // NOTE: this could be done with Into if it would be supported
-                    let mut ret = [0; <[<$param Parameters>] as FAESTParameters>::SignatureSize::USIZE];
+                    let mut ret = [0; <parameter::[<$param Parameters>] as FAESTParameters>::SIGNATURE_SIZE];
                     ret.copy_from_slice(self.0.as_slice());
                     ret
                 }
@@ -418,32 +1486,54 @@ macro_rules! define_impl {
                 }
 
                 fn encoded_len(&self) -> usize {
-                    <[<$param Parameters>] as FAESTParameters>::SignatureSize::USIZE
+                    <parameter::[<$param Parameters>] as FAESTParameters>::SIGNATURE_SIZE
                 }
             }
         }
     };
 }
 
+#[cfg(feature = "faest-128f")]
 define_impl!(FAEST128f);
+#[cfg(feature = "faest-128s")]
 define_impl!(FAEST128s);
+#[cfg(feature = "faest-192f")]
 define_impl!(FAEST192f);
+#[cfg(feature = "faest-192s")]
 define_impl!(FAEST192s);
+#[cfg(feature = "faest-256f")]
 define_impl!(FAEST256f);
+#[cfg(feature = "faest-256s")]
 define_impl!(FAEST256s);
+#[cfg(feature = "faest-em-128f")]
 define_impl!(FAESTEM128f);
+#[cfg(feature = "faest-em-128s")]
 define_impl!(FAESTEM128s);
+#[cfg(feature = "faest-em-192f")]
 define_impl!(FAESTEM192f);
+#[cfg(feature = "faest-em-192s")]
 define_impl!(FAESTEM192s);
+#[cfg(feature = "faest-em-256f")]
 define_impl!(FAESTEM256f);
+#[cfg(feature = "faest-em-256s")]
 define_impl!(FAESTEM256s);
-
-#[cfg(test)]
+#[cfg(feature = "blake3-oracle")]
+define_impl!(FAEST128fBlake3);
+#[cfg(feature = "blake3-oracle")]
+define_impl!(FAEST128sBlake3);
+#[cfg(feature = "experimental")]
+define_impl!(FAEST128us);
+
+#[cfg(all(test, feature = "_variant"))]
 #[generic_tests::define]
 mod tests {
     use super::*;
 
-    use std::fmt::Debug;
+    use std::{
+        collections::{hash_map::DefaultHasher, BTreeSet, HashSet},
+        fmt::Debug,
+        hash::{Hash, Hasher},
+    };
 
     #[cfg(feature = "serde")]
     use serde::{de::DeserializeOwned, Serialize};
@@ -503,6 +1593,48 @@ mod tests {
             .expect("signature verifies");
     }
 
+    #[test]
+    fn signature_array<KP, S>()
+    where
+        KP: KeypairGenerator + Signer<S>,
+        S: SignatureEncoding + From<<S as SignatureEncoding>::Repr> + PartialEq + Debug,
+    {
+        let kp = KP::generate(rand::thread_rng());
+        let signature = kp.sign(TEST_MESSAGE);
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes.as_ref(), signature.to_vec().as_slice());
+        assert_eq!(S::from(bytes), signature);
+    }
+
+    #[test]
+    fn detect_parameters<KP, S>()
+    where
+        KP: KeypairGenerator + Signer<S>,
+        KP::VerifyingKey: ByteEncoding,
+        S: AsRef<[u8]>,
+    {
+        let kp = KP::generate(rand::thread_rng());
+        let signature = kp.sign(TEST_MESSAGE);
+        let signature = signature.as_ref();
+        let vk = kp.verifying_key().to_vec();
+        let candidates = FaestVariant::ALL
+            .iter()
+            .filter(|variant| variant.signature_size() == signature.len())
+            .count();
+        match crate::detect_parameters(signature) {
+            Some(info) => {
+                assert_eq!(candidates, 1);
+                assert_eq!(info.verification_key_size, vk.len());
+                info.variant
+                    .parse_signature(signature)
+                    .expect("signature parses")
+                    .verify(TEST_MESSAGE, &vk)
+                    .expect("signature verifies");
+            }
+            None => assert!(candidates > 1),
+        }
+    }
+
     #[test]
     fn serialize_keys<KP, S>()
     where
@@ -528,21 +1660,128 @@ mod tests {
             .expect("signature verifies");
     }
 
-    /*
-    #[cfg(feature = "subtle")]
     #[test]
-    fn subtle_eq<P: Parameters>() {
-        use subtle::ConstantTimeEq;
+    fn key_bit_flips<KP, S>()
+    where
+        KP: KeypairGenerator + Signer<S> + ByteEncoding + Eq,
+        KP::VerifyingKey: Verifier<S> + ByteEncoding + Eq,
+        for<'a> <KP::VerifyingKey as TryFrom<&'a [u8]>>::Error: Debug,
+    {
+        let kp = KP::generate(rand::thread_rng());
+        let vk = kp.verifying_key();
+        let signature = kp.sign(TEST_MESSAGE);
+
+        // flipping any bit of a signing key either gives an invalid key or a
+        // different key
+        let sk_bytes = kp.to_vec();
+        for i in 0..sk_bytes.len() * 8 {
+            let mut bytes = sk_bytes.clone();
+            bytes[i / 8] ^= 1 << (i % 8);
+            let kp2 = KP::try_from(&bytes).ok();
+            assert!(kp2.map_or(true, |kp2| kp2 != kp), "bit {i}");
+        }
+
+        // verification keys are not validated when loading, hence any bit
+        // flip is accepted and gives a different key
+        let vk_bytes = vk.to_vec();
+        let flipped = |i: usize| {
+            let mut bytes = vk_bytes.clone();
+            bytes[i / 8] ^= 1 << (i % 8);
+            KP::VerifyingKey::try_from(&bytes).unwrap()
+        };
+        for i in 0..vk_bytes.len() * 8 {
+            assert!(flipped(i) != vk, "bit {i}");
+        }
+        // verification is slow in debug builds, so only one bit each of the
+        // OWF input and of the OWF output is checked
+        for i in [0, vk_bytes.len() * 8 - 1] {
+            assert!(flipped(i).verify(TEST_MESSAGE, &signature).is_err());
+        }
+    }
+
+    // generic_tests requires all tests of the module to take the same type
+    // parameters, hence the unused `S`
+    #[test]
+    #[allow(clippy::extra_unused_type_parameters)]
+    fn verification_key_ordering<KP, S>()
+    where
+        KP: KeypairGenerator,
+        KP::VerifyingKey: ByteEncoding + Ord + Hash + Debug,
+    {
+        let mut rng = rand::thread_rng();
+        let vks: Vec<_> = (0..8)
+            .map(|_| KP::generate(&mut rng).verifying_key())
+            .collect();
+
+        for vk1 in &vks {
+            for vk2 in &vks {
+                assert_eq!(vk1.cmp(vk2), vk1.to_vec().cmp(&vk2.to_vec()));
+                assert_eq!(vk1 == vk2, vk1.to_vec() == vk2.to_vec());
+                if vk1 == vk2 {
+                    assert_eq!(hash(vk1), hash(vk2));
+                }
+            }
+            assert_eq!(hash(vk1), hash(&vk1.clone()));
+        }
 
-        let (sk1, _vk1) = SigningKey::<P>::random().expect("keygen success");
-        let (sk2, _vk2) = SigningKey::<P>::random().expect("keygen success");
-        let sk3 = sk1.clone();
+        let ordered: BTreeSet<_> = vks.iter().cloned().collect();
+        let hashed: HashSet<_> = vks.iter().cloned().collect();
+        assert_eq!(ordered.len(), vks.len());
+        assert_eq!(hashed.len(), vks.len());
+        assert!(vks
+            .iter()
+            .all(|vk| ordered.contains(vk) && hashed.contains(vk)));
+        assert!(ordered
+            .iter()
+            .zip(ordered.iter().skip(1))
+            .all(|(vk1, vk2)| vk1.to_vec() < vk2.to_vec()));
+    }
+
+    fn hash<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
 
-        assert!(bool::from(sk1.ct_eq(&sk1)));
-        assert!(bool::from(sk1.ct_eq(&sk3)));
+    #[test]
+    fn trait_conformance<KP, S>()
+    where
+        KP: KeypairGenerator + Signer<S> + Clone + Eq + Debug + ConstantTimeEq + ByteEncoding,
+        KP::VerifyingKey: Clone + Eq + Debug + Ord + Hash + AsRef<[u8]> + ByteEncoding,
+        S: Clone + Eq + Debug + AsRef<[u8]> + SignatureEncoding,
+        for<'a> S: TryFrom<&'a [u8], Error = Error>,
+    {
+        // the bounds above are the public contract of the types
+        let mut rng = rand::thread_rng();
+        let sk1 = KP::generate(&mut rng);
+        let sk2 = KP::generate(&mut rng);
+        assert!(bool::from(sk1.ct_eq(&sk1.clone())));
         assert!(!bool::from(sk1.ct_eq(&sk2)));
+        assert_eq!(sk1, sk1.clone());
+        assert_ne!(sk1, sk2);
+
+        let vk = sk1.verifying_key();
+        assert_eq!(vk.as_ref(), vk.to_vec().as_slice());
+        let signature = sk1.sign(TEST_MESSAGE);
+        assert_eq!(signature.as_ref(), signature.to_vec().as_slice());
+
+        // public items print a short hex prefix of their encoding
+        let prefix = hex::encode(&vk.as_ref()[..HexPrefix::LENGTH]);
+        let debug = format!("{vk:?}");
+        assert!(debug.ends_with(&format!("VerificationKey({prefix}…)")));
+        let prefix = hex::encode(&signature.as_ref()[..HexPrefix::LENGTH]);
+        let debug = format!("{signature:?}");
+        assert!(debug.ends_with(&format!("Signature({prefix}…)")));
+        assert!(debug.len() < 64);
+
+        // the OWF key is redacted
+        let sk_bytes = sk1.to_vec();
+        let owf_key = &sk_bytes[vk.as_ref().len() / 2..];
+        let debug = format!("{sk1:?}");
+        assert!(debug.contains("redacted"));
+        assert!(!debug.contains(&hex::encode(&owf_key[..4])));
+        assert!(!debug.contains(&format!("{:?}", &owf_key[..4])));
     }
-    */
 
     #[cfg(feature = "serde")]
     #[test]
@@ -562,39 +1801,164 @@ mod tests {
         assert_eq!(kp, kp2);
     }
 
+    #[cfg(feature = "faest-128f")]
     #[instantiate_tests(<FAEST128fSigningKey, FAEST128fSignature>)]
     mod faest_128f {}
 
+    #[cfg(feature = "faest-128s")]
     #[instantiate_tests(<FAEST128sSigningKey, FAEST128sSignature>)]
     mod faest_128s {}
 
+    #[cfg(feature = "faest-192f")]
     #[instantiate_tests(<FAEST192fSigningKey, FAEST192fSignature>)]
     mod faest_192f {}
 
+    #[cfg(feature = "faest-192s")]
     #[instantiate_tests(<FAEST192sSigningKey, FAEST192sSignature>)]
     mod faest_192s {}
 
+    #[cfg(feature = "faest-256f")]
     #[instantiate_tests(<FAEST256fSigningKey, FAEST256fSignature>)]
     mod faest_256f {}
 
+    #[cfg(feature = "faest-256s")]
     #[instantiate_tests(<FAEST256sSigningKey, FAEST256sSignature>)]
     mod faest_256s {}
 
+    #[cfg(feature = "faest-em-128f")]
     #[instantiate_tests(<FAESTEM128fSigningKey, FAESTEM128fSignature>)]
     mod faest_em_128f {}
 
+    #[cfg(feature = "faest-em-128s")]
     #[instantiate_tests(<FAESTEM128sSigningKey, FAESTEM128sSignature>)]
     mod faest_em_128s {}
 
+    #[cfg(feature = "faest-em-192f")]
     #[instantiate_tests(<FAESTEM192fSigningKey, FAESTEM192fSignature>)]
     mod faest_em_192f {}
 
+    #[cfg(feature = "faest-em-192s")]
     #[instantiate_tests(<FAESTEM192sSigningKey, FAESTEM192sSignature>)]
     mod faest_em_192s {}
 
+    #[cfg(feature = "faest-em-256f")]
     #[instantiate_tests(<FAESTEM256fSigningKey, FAESTEM256fSignature>)]
     mod faest_em_256f {}
 
+    #[cfg(feature = "faest-em-256s")]
     #[instantiate_tests(<FAESTEM256sSigningKey, FAESTEM256sSignature>)]
     mod faest_em_256s {}
+
+    #[cfg(feature = "blake3-oracle")]
+    #[instantiate_tests(<FAEST128fBlake3SigningKey, FAEST128fBlake3Signature>)]
+    mod faest_128f_blake3 {}
+
+    #[cfg(feature = "blake3-oracle")]
+    #[instantiate_tests(<FAEST128sBlake3SigningKey, FAEST128sBlake3Signature>)]
+    mod faest_128s_blake3 {}
+
+    #[cfg(feature = "experimental")]
+    #[instantiate_tests(<FAEST128usSigningKey, FAEST128usSignature>)]
+    mod faest_128us {}
+}
+
+#[cfg(all(test, feature = "blake3-oracle", feature = "faest-128f"))]
+mod blake3_tests {
+    use super::*;
+
+    const TEST_MESSAGE: &[u8] = "test message".as_bytes();
+
+    #[test]
+    fn not_interoperable_with_shake() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let sk_blake3 = FAEST128fBlake3SigningKey::try_from(sk.to_bytes().as_slice())
+            .expect("keys are compatible");
+        assert_eq!(
+            sk.verifying_key().to_bytes(),
+            sk_blake3.verifying_key().to_bytes()
+        );
+
+        let signature: FAEST128fSignature = sk.sign(TEST_MESSAGE);
+        let signature_blake3: FAEST128fBlake3Signature = sk_blake3.sign(TEST_MESSAGE);
+        assert_ne!(signature.as_ref(), signature_blake3.as_ref());
+        assert!(sk
+            .verifying_key()
+            .verify(TEST_MESSAGE, &SignatureRef::from(signature_blake3.as_ref()))
+            .is_err());
+        assert!(sk_blake3
+            .verifying_key()
+            .verify(TEST_MESSAGE, &SignatureRef::from(signature.as_ref()))
+            .is_err());
+    }
+}
+
+#[cfg(all(test, feature = "faest-128f", feature = "faest-em-128f"))]
+mod owf_key_tests {
+    use super::*;
+
+    /// Split a signing key into OWF key and input
+    fn owf_key_and_input(bytes: &[u8]) -> ([u8; 16], [u8; 16]) {
+        let (input, key) = bytes.split_at(16);
+        (key.try_into().unwrap(), input.try_into().unwrap())
+    }
+
+    #[test]
+    fn from_owf_key_and_input() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let (key, input) = owf_key_and_input(&sk.to_bytes());
+        assert!(FAEST128fSigningKey::is_valid_owf_key(&key, &input));
+        assert_eq!(
+            FAEST128fSigningKey::from_owf_key_and_input(&key, &input),
+            Ok(sk)
+        );
+
+        let sk = FAESTEM128fSigningKey::generate(rand::thread_rng());
+        let (key, input) = owf_key_and_input(&sk.to_bytes());
+        assert!(FAESTEM128fSigningKey::is_valid_owf_key(&key, &input));
+        assert_eq!(
+            FAESTEM128fSigningKey::from_owf_key_and_input(&key, &input),
+            Ok(sk)
+        );
+    }
+
+    #[test]
+    fn rejected_aes_keys() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let (mut key, mut input) = owf_key_and_input(&sk.to_bytes());
+
+        // the first S-box of the encryption gets key[0] ^ input[0]
+        let valid_input = input;
+        input[0] = key[0];
+        assert!(!FAEST128fSigningKey::is_valid_owf_key(&key, &input));
+        let err = FAEST128fSigningKey::from_owf_key_and_input(&key, &input).unwrap_err();
+        assert_eq!(err, KeyRejected::Encryption { block: 0, round: 1 });
+        assert!(FAEST128fSigningKey::try_from([&input[..], &key].concat().as_slice()).is_err());
+
+        // the first S-box of the key schedule gets the last word of the key
+        key[15] = 0;
+        assert!(!FAEST128fSigningKey::is_valid_owf_key(&key, &valid_input));
+        let err = FAEST128fSigningKey::from_owf_key_and_input(&key, &valid_input).unwrap_err();
+        assert_eq!(err, KeyRejected::KeySchedule { round: 1 });
+        assert_eq!(
+            err.to_string(),
+            "zero S-box input in the key schedule deriving round key 1"
+        );
+    }
+
+    #[test]
+    fn rejected_em_key() {
+        let sk = FAESTEM128fSigningKey::generate(rand::thread_rng());
+        let (key, mut input) = owf_key_and_input(&sk.to_bytes());
+
+        // the first S-box gets key[0] ^ input[0], there is no key schedule on
+        // the secret
+        input[0] = key[0];
+        assert!(!FAESTEM128fSigningKey::is_valid_owf_key(&key, &input));
+        let err = FAESTEM128fSigningKey::from_owf_key_and_input(&key, &input).unwrap_err();
+        assert_eq!(err, KeyRejected::Encryption { block: 0, round: 1 });
+        assert_eq!(
+            err.to_string(),
+            "zero S-box input in round 1 of the encryption of block 0"
+        );
+    }
 }
@@ -0,0 +1,418 @@
+//! Multi-signatures signing a message under several FAEST variants
+//!
+//! A [`MultiSignature`] holds one signature per variant on the same message,
+//! e.g., to sign a document with FAEST-128s and FAEST-256s and drop the
+//! weaker variant later. The component signatures are plain signatures on the
+//! message, i.e., each of them verifies on its own.
+//!
+//! The encoding of a multi-signature is
+//!
+//! | field                        | size (in bytes) |
+//! |------------------------------|-----------------|
+//! | version                      | 1               |
+//! | variant identifier           | 1               |
+//! | signature length             | 4 (big endian)  |
+//! | signature                    | variable        |
+//! | ...                          |                 |
+//!
+//! where the last three fields are repeated for every component signature.
+//! The variant identifiers are those of [`FaestVariant::id`]. Decoding is
+//! strict: the container holds at least one signature, the identifiers are
+//! strictly increasing (hence, every variant occurs at most once), the
+//! lengths match the signature sizes of the variants exactly and no trailing
+//! data is allowed. Identifiers of variants that are unknown or not enabled in
+//! this build are rejected.
+//!
+//! ```
+//! # #[cfg(all(feature = "faest-128f", feature = "faest-em-128f"))] {
+//! use faest::{ByteEncoding, FAEST128fSigningKey, FAESTEM128fSigningKey, FaestVariant};
+//! use faest::{Keypair, KeypairGenerator};
+//! use faest::multi::{sign_multi, verify_multi, MultiSignature, Policy};
+//!
+//! let sk_1 = FAEST128fSigningKey::generate(rand::thread_rng());
+//! let sk_2 = FAESTEM128fSigningKey::generate(rand::thread_rng());
+//! let msg = "some message".as_bytes();
+//! let signature = sign_multi(&[&sk_1, &sk_2], msg).expect("distinct variants");
+//!
+//! let encoded = signature.to_vec();
+//! let signature = MultiSignature::try_from(encoded.as_slice())
+//!     .expect("well-formed signature");
+//! let vk_1 = sk_1.verifying_key().to_vec();
+//! let vk_2 = sk_2.verifying_key().to_vec();
+//! let verification_keys = [
+//!     (FaestVariant::FAEST128f, vk_1.as_slice()),
+//!     (FaestVariant::FAESTEM128f, vk_2.as_slice()),
+//! ];
+//! verify_multi(Policy::All, &verification_keys, msg, &signature)
+//!     .expect("Verification failed");
+//! # }
+//! ```
+
+use crate::{Error, FaestVariant, ParsedSignature};
+
+const VERSION: u8 = 1;
+
+/// Signing key of a variant only known at runtime
+///
+/// This is implemented by the signing keys of all variants, hence keys of
+/// different variants can be passed to [`sign_multi`] together.
+pub trait ErasedSigner {
+    /// Variant of the key
+    fn variant(&self) -> FaestVariant;
+
+    /// Sign `msg`
+    fn try_sign_erased(&self, msg: &[u8]) -> Result<ParsedSignature, Error>;
+}
+
+/// Verification policy for multi-signatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// The signatures of all verification keys need to be valid
+    All,
+    /// The signature of at least one verification key needs to be valid
+    Any,
+    /// The signatures of at least `k` verification keys need to be valid
+    ///
+    /// A threshold of `0` is rejected.
+    Threshold(usize),
+}
+
+/// Signatures on the same message under distinct variants
+///
+/// The signatures are ordered by the identifiers of their variants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiSignature {
+    signatures: Vec<ParsedSignature>,
+}
+
+impl MultiSignature {
+    /// Combine signatures of distinct variants
+    ///
+    /// Fails if `signatures` is empty or contains two signatures of the same
+    /// variant.
+    pub fn new(mut signatures: Vec<ParsedSignature>) -> Result<Self, Error> {
+        signatures.sort_by_key(|signature| signature.variant().id());
+        if signatures.is_empty()
+            || signatures
+                .windows(2)
+                .any(|pair| pair[0].variant() == pair[1].variant())
+        {
+            return Err(Error::new());
+        }
+        Ok(Self { signatures })
+    }
+
+    /// The component signatures
+    pub fn signatures(&self) -> &[ParsedSignature] {
+        &self.signatures
+    }
+
+    /// The signature of `variant`, if any
+    pub fn get(&self, variant: FaestVariant) -> Option<&ParsedSignature> {
+        self.signatures
+            .iter()
+            .find(|signature| signature.variant() == variant)
+    }
+
+    /// Encode the signature
+    pub fn to_vec(&self) -> Vec<u8> {
+        let len = 1 + self
+            .signatures
+            .iter()
+            .map(|signature| 1 + 4 + signature.as_ref().len())
+            .sum::<usize>();
+        let mut ret = Vec::with_capacity(len);
+        ret.push(VERSION);
+        for signature in &self.signatures {
+            ret.push(signature.variant().id());
+            ret.extend_from_slice(&(signature.as_ref().len() as u32).to_be_bytes());
+            ret.extend_from_slice(signature.as_ref());
+        }
+        ret
+    }
+}
+
+/// Split a component signature from `bytes`
+fn split_signature(bytes: &[u8]) -> Result<(ParsedSignature, &[u8]), Error> {
+    let (id, bytes) = bytes.split_first().ok_or_else(Error::new)?;
+    let variant = FaestVariant::from_id(*id).ok_or_else(Error::new)?;
+    let (len, bytes) = bytes.split_first_chunk().ok_or_else(Error::new)?;
+    let len = u32::from_be_bytes(*len) as usize;
+    if bytes.len() < len {
+        return Err(Error::new());
+    }
+    let (signature, bytes) = bytes.split_at(len);
+    Ok((variant.parse_signature(signature)?, bytes))
+}
+
+impl TryFrom<&[u8]> for MultiSignature {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let (version, mut value) = value.split_first().ok_or_else(Error::new)?;
+        if *version != VERSION || value.is_empty() {
+            return Err(Error::new());
+        }
+
+        let mut signatures: Vec<ParsedSignature> = Vec::new();
+        while !value.is_empty() {
+            let (signature, remaining) = split_signature(value)?;
+            // strictly increasing identifiers give a canonical encoding
+            if signatures
+                .last()
+                .is_some_and(|last| last.variant().id() >= signature.variant().id())
+            {
+                return Err(Error::new());
+            }
+            signatures.push(signature);
+            value = remaining;
+        }
+        Ok(Self { signatures })
+    }
+}
+
+/// Sign `msg` with all `signers`
+///
+/// Fails if two signers are of the same variant.
+pub fn sign_multi(signers: &[&dyn ErasedSigner], msg: &[u8]) -> Result<MultiSignature, Error> {
+    if has_duplicates(signers.iter().map(|signer| signer.variant())) {
+        return Err(Error::new());
+    }
+    signers
+        .iter()
+        .map(|signer| signer.try_sign_erased(msg))
+        .collect::<Result<_, _>>()
+        .and_then(MultiSignature::new)
+}
+
+/// Verify a multi-signature according to `policy`
+///
+/// The verification keys are given as encoded keys together with their
+/// variants. A verification key counts as valid if the multi-signature
+/// contains a valid signature of its variant. Signatures of variants without
+/// a verification key are ignored. Fails if two verification keys are of the
+/// same variant.
+pub fn verify_multi(
+    policy: Policy,
+    verification_keys: &[(FaestVariant, &[u8])],
+    msg: &[u8],
+    signature: &MultiSignature,
+) -> Result<(), Error> {
+    let required = match policy {
+        Policy::All => verification_keys.len(),
+        Policy::Any => 1,
+        Policy::Threshold(k) => k,
+    };
+    if required == 0 || has_duplicates(verification_keys.iter().map(|(variant, _)| *variant)) {
+        return Err(Error::new());
+    }
+
+    let valid = verification_keys
+        .iter()
+        .filter(|(variant, verification_key)| {
+            signature
+                .get(*variant)
+                .is_some_and(|signature| signature.verify(msg, verification_key).is_ok())
+        })
+        .take(required)
+        .count();
+    if valid == required {
+        Ok(())
+    } else {
+        Err(Error::new())
+    }
+}
+
+fn has_duplicates(variants: impl Iterator<Item = FaestVariant>) -> bool {
+    let mut ids: Vec<_> = variants.map(FaestVariant::id).collect();
+    ids.sort_unstable();
+    ids.windows(2).any(|pair| pair[0] == pair[1])
+}
+
+#[cfg(all(test, feature = "faest-128f", feature = "faest-em-128f"))]
+mod test {
+    use super::*;
+
+    use crate::{ByteEncoding, FAEST128fSigningKey, FAESTEM128fSigningKey, KeypairGenerator};
+    use signature::Keypair;
+
+    const TEST_MESSAGE: &[u8] = "test message".as_bytes();
+
+    fn keys() -> (FAEST128fSigningKey, FAESTEM128fSigningKey) {
+        (
+            FAEST128fSigningKey::generate(rand::thread_rng()),
+            FAESTEM128fSigningKey::generate(rand::thread_rng()),
+        )
+    }
+
+    fn entry(id: u8, signature: &[u8]) -> Vec<u8> {
+        [
+            &[id][..],
+            &(signature.len() as u32).to_be_bytes(),
+            signature,
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn policies() {
+        let (sk_1, sk_2) = keys();
+        let (other_1, _) = keys();
+        let vk_1 = sk_1.verifying_key().to_vec();
+        let vk_2 = sk_2.verifying_key().to_vec();
+        let other_vk_1 = other_1.verifying_key().to_vec();
+        // the order of the signers does not matter
+        let signature = sign_multi(&[&sk_2, &sk_1], TEST_MESSAGE).unwrap();
+        assert_eq!(
+            signature
+                .signatures()
+                .iter()
+                .map(ParsedSignature::variant)
+                .collect::<Vec<_>>(),
+            [FaestVariant::FAEST128f, FaestVariant::FAESTEM128f]
+        );
+
+        let both_valid = [
+            (FaestVariant::FAEST128f, vk_1.as_slice()),
+            (FaestVariant::FAESTEM128f, vk_2.as_slice()),
+        ];
+        let one_valid = [
+            (FaestVariant::FAEST128f, other_vk_1.as_slice()),
+            (FaestVariant::FAESTEM128f, vk_2.as_slice()),
+        ];
+        for (verification_keys, num_valid) in [(&both_valid[..], 2), (&one_valid[..], 1)] {
+            assert_eq!(
+                verify_multi(Policy::All, verification_keys, TEST_MESSAGE, &signature).is_ok(),
+                num_valid == 2
+            );
+            assert!(verify_multi(Policy::Any, verification_keys, TEST_MESSAGE, &signature).is_ok());
+            for k in 1..=3 {
+                assert_eq!(
+                    verify_multi(
+                        Policy::Threshold(k),
+                        verification_keys,
+                        TEST_MESSAGE,
+                        &signature
+                    )
+                    .is_ok(),
+                    k <= num_valid
+                );
+            }
+            assert!(verify_multi(
+                Policy::Threshold(0),
+                verification_keys,
+                TEST_MESSAGE,
+                &signature
+            )
+            .is_err());
+        }
+
+        // wrong message
+        assert!(verify_multi(Policy::Any, &both_valid, b"other message", &signature).is_err());
+        // no verification keys
+        assert!(verify_multi(Policy::All, &[], TEST_MESSAGE, &signature).is_err());
+        assert!(verify_multi(Policy::Any, &[], TEST_MESSAGE, &signature).is_err());
+        // subset of the verification keys
+        assert!(verify_multi(Policy::All, &both_valid[1..], TEST_MESSAGE, &signature).is_ok());
+        // missing signature
+        let signature = sign_multi(&[&sk_2], TEST_MESSAGE).unwrap();
+        assert!(verify_multi(Policy::All, &both_valid, TEST_MESSAGE, &signature).is_err());
+        assert!(verify_multi(Policy::Any, &both_valid, TEST_MESSAGE, &signature).is_ok());
+    }
+
+    #[test]
+    fn duplicate_variants() {
+        let (sk_1, sk_2) = keys();
+        let (other_1, _) = keys();
+        assert!(sign_multi(&[&sk_1, &sk_2, &other_1], TEST_MESSAGE).is_err());
+        assert!(sign_multi(&[], TEST_MESSAGE).is_err());
+
+        let signature_1 = sk_1.try_sign_erased(TEST_MESSAGE).unwrap();
+        let signature_2 = other_1.try_sign_erased(TEST_MESSAGE).unwrap();
+        assert!(MultiSignature::new(vec![signature_1.clone(), signature_2.clone()]).is_err());
+        assert!(MultiSignature::new(vec![]).is_err());
+
+        let encoded = [
+            &[VERSION][..],
+            &entry(signature_1.variant().id(), signature_1.as_ref()),
+            &entry(signature_2.variant().id(), signature_2.as_ref()),
+        ]
+        .concat();
+        assert!(MultiSignature::try_from(encoded.as_slice()).is_err());
+
+        let vk_1 = sk_1.verifying_key().to_vec();
+        let other_vk_1 = other_1.verifying_key().to_vec();
+        let signature = MultiSignature::new(vec![signature_1]).unwrap();
+        let verification_keys = [
+            (FaestVariant::FAEST128f, vk_1.as_slice()),
+            (FaestVariant::FAEST128f, other_vk_1.as_slice()),
+        ];
+        assert!(verify_multi(
+            Policy::Threshold(1),
+            &verification_keys,
+            TEST_MESSAGE,
+            &signature
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn unknown_variants() {
+        let (sk_1, _) = keys();
+        let signature = sk_1.try_sign_erased(TEST_MESSAGE).unwrap();
+        for id in [0, u8::MAX] {
+            assert!(FaestVariant::from_id(id).is_none());
+            let encoded = [&[VERSION][..], &entry(id, signature.as_ref())].concat();
+            assert!(MultiSignature::try_from(encoded.as_slice()).is_err());
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let (sk_1, sk_2) = keys();
+        let signature = sign_multi(&[&sk_1, &sk_2], TEST_MESSAGE).unwrap();
+        let encoded = signature.to_vec();
+        assert_eq!(
+            MultiSignature::try_from(encoded.as_slice()).unwrap(),
+            signature
+        );
+
+        let [signature_1, signature_2] = signature.signatures() else {
+            panic!("expected two signatures");
+        };
+        let entry_1 = entry(signature_1.variant().id(), signature_1.as_ref());
+        let entry_2 = entry(signature_2.variant().id(), signature_2.as_ref());
+        assert_eq!(encoded, [&[VERSION][..], &entry_1, &entry_2].concat());
+
+        // wrong version
+        let mut modified = encoded.clone();
+        modified[0] = VERSION + 1;
+        assert!(MultiSignature::try_from(modified.as_slice()).is_err());
+
+        // truncated signature
+        for len in [0, 1, 2, 6, 1 + entry_1.len() + 1, encoded.len() - 1] {
+            assert!(MultiSignature::try_from(&encoded[..len]).is_err());
+        }
+
+        // trailing data
+        assert!(MultiSignature::try_from([encoded.as_slice(), &[0]].concat().as_slice()).is_err());
+
+        // unordered signatures
+        let unordered = [&[VERSION][..], &entry_2, &entry_1].concat();
+        assert!(MultiSignature::try_from(unordered.as_slice()).is_err());
+
+        // lengths not matching the variant
+        let truncated = [
+            &[VERSION][..],
+            &entry(signature_1.variant().id(), &signature_1.as_ref()[1..]),
+        ]
+        .concat();
+        assert!(MultiSignature::try_from(truncated.as_slice()).is_err());
+        let swapped = [
+            &[VERSION][..],
+            &entry(signature_1.variant().id(), signature_2.as_ref()),
+        ]
+        .concat();
+        assert!(MultiSignature::try_from(swapped.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,838 @@
+//! One-way functions used by FAEST
+//!
+//! FAEST signing keys consist of a key and an input for a one-way function
+//! (OWF). The verification key consists of the same input and the output of the
+//! OWF. This module exposes the OWFs to recompute verification keys, e.g., to
+//! check that a verification key matches a secret key.
+//!
+//! ```
+//! use faest::owf::{self, Aes128};
+//! use generic_array::GenericArray;
+//!
+//! let key = GenericArray::from_array([0x42; 16]);
+//! let input = GenericArray::from_array([0x13; 16]);
+//! let output = owf::evaluate::<Aes128>(&key, &input);
+//! assert_eq!(output, owf::evaluate::<Aes128>(&key, &input));
+//! ```
+//!
+//! For the FAEST-EM variants, [`even_mansour`] restricts the evaluation to the
+//! Even-Mansour OWFs, and [`even_mansour_checked`] additionally rejects keys
+//! that are rejected by the key generation.
+//!
+//! The statement proven by FAEST signatures, i.e., knowledge of a witness for
+//! the OWF, is available as [`ConstraintSystem`] for use with other proof
+//! systems:
+//!
+//! ```
+//! use faest::owf::{self, Aes128};
+//! use generic_array::GenericArray;
+//!
+//! let key = GenericArray::from_array([0x42; 16]);
+//! let input = GenericArray::from_array([0x13; 16]);
+//! let output = owf::evaluate::<Aes128>(&key, &input);
+//!
+//! let system = owf::constraint_system::<Aes128>(&input, &output);
+//! let witness = owf::extend_witness::<Aes128>(&key, &input).unwrap();
+//! assert!(system.is_satisfied(&witness));
+//! ```
+//!
+//! [`witness_to_bits`] and [`bits_to_witness`] convert between the packed
+//! witness and its bits.
+//!
+//! With the `protocol-analysis` feature, [`aes_extendedwitness_step_by_step`]
+//! evaluates the AES based OWFs round by round and relates the states of the
+//! rounds to their position in the witness.
+
+use std::fmt;
+
+use generic_array::{
+    typenum::{U16, U24, U32},
+    ArrayLength, GenericArray,
+};
+
+#[cfg(feature = "protocol-analysis")]
+use crate::aes::aes_round_states;
+use crate::{
+    fields::Field as _,
+    parameter::{OWFParameters, OWF128, OWF128EM, OWF192, OWF192EM, OWF256, OWF256EM},
+    utils::{witness_bit_at, Field},
+    Error,
+};
+
+mod private {
+    pub trait SealedEvenMansour {}
+
+    #[cfg(feature = "protocol-analysis")]
+    pub trait SealedAes: super::OneWayFunction {
+        /// Evaluate the OWF and record the state of each round
+        fn round_states(
+            key: &super::Key<Self>,
+            input: &super::Input<Self>,
+        ) -> Vec<super::AESRoundState>;
+    }
+
+    pub trait Sealed {
+        /// Whether the product of the field elements `left` and `right` is one
+        fn is_unit_product(left: &[u8], right: &[u8]) -> bool;
+    }
+}
+
+/// Interface of the one-way functions
+///
+/// This trait is sealed and implemented for the OWFs of all FAEST parameter
+/// sets.
+pub trait OneWayFunction: Sized + private::Sealed {
+    /// Size of the key (in bytes)
+    type KeySize: ArrayLength;
+    /// Size of input and output (in bytes)
+    type InputSize: ArrayLength;
+
+    /// Evaluate the OWF
+    fn evaluate(key: &Key<Self>, input: &Input<Self>) -> Output<Self>;
+
+    /// Evaluate the OWF with the same key on multiple inputs
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` and `outputs` differ in length.
+    fn evaluate_multi(key: &Key<Self>, inputs: &[Input<Self>], outputs: &mut [Output<Self>]);
+
+    /// The constraints proven by FAEST for `input` and `output`
+    fn constraint_system(input: &Input<Self>, output: &Output<Self>) -> ConstraintSystem<Self>;
+
+    /// Extend `key` to the witness of the constraint system
+    ///
+    /// Returns `None` if one of the S-box inputs is zero. Such keys are
+    /// rejected by the key generation of FAEST.
+    fn extend_witness(key: &Key<Self>, input: &Input<Self>) -> Option<Vec<u8>>;
+}
+
+/// Key of the OWF
+pub type Key<O> = GenericArray<u8, <O as OneWayFunction>::KeySize>;
+/// Input of the OWF
+pub type Input<O> = GenericArray<u8, <O as OneWayFunction>::InputSize>;
+/// Output of the OWF
+pub type Output<O> = GenericArray<u8, <O as OneWayFunction>::InputSize>;
+
+/// Evaluate the OWF `O` on `key` and `input`
+pub fn evaluate<O>(key: &Key<O>, input: &Input<O>) -> Output<O>
+where
+    O: OneWayFunction,
+{
+    O::evaluate(key, input)
+}
+
+/// Evaluate the OWF `O` on `key` and each of the `inputs`
+///
+/// This is faster than calling [evaluate] in a loop for the AES-128 based OWF.
+///
+/// # Panics
+///
+/// Panics if `inputs` and `outputs` differ in length.
+pub fn evaluate_multi<O>(key: &Key<O>, inputs: &[Input<O>], outputs: &mut [Output<O>])
+where
+    O: OneWayFunction,
+{
+    O::evaluate_multi(key, inputs, outputs)
+}
+
+/// Even-Mansour OWFs of the FAEST-EM variants
+///
+/// The OWF is `E_input(key) ⊕ key`, i.e., the cipher is keyed with the public
+/// input and encrypts the secret key. This trait is sealed.
+pub trait EvenMansour: OneWayFunction + private::SealedEvenMansour {}
+
+impl private::SealedEvenMansour for EvenMansour128 {}
+impl EvenMansour for EvenMansour128 {}
+impl private::SealedEvenMansour for EvenMansour192 {}
+impl EvenMansour for EvenMansour192 {}
+impl private::SealedEvenMansour for EvenMansour256 {}
+impl EvenMansour for EvenMansour256 {}
+
+/// Evaluate the Even-Mansour OWF `O` on the secret `key` and `public_input`
+///
+/// The evaluation is constant time with respect to `key`.
+pub fn even_mansour<O>(key: &Key<O>, public_input: &Input<O>) -> Output<O>
+where
+    O: EvenMansour,
+{
+    O::evaluate(key, public_input)
+}
+
+/// Evaluate the Even-Mansour OWF `O` if `key` is accepted by the key
+/// generation
+///
+/// Fails if one of the S-box inputs is zero. FAEST can not prove knowledge of
+/// such a key, hence the key generation rejects it.
+pub fn even_mansour_checked<O>(key: &Key<O>, public_input: &Input<O>) -> Result<Output<O>, Error>
+where
+    O: EvenMansour,
+{
+    match O::extend_witness(key, public_input) {
+        Some(_) => Ok(even_mansour::<O>(key, public_input)),
+        None => Err(Error::new()),
+    }
+}
+
+/// AES based OWFs of the FAEST variants
+///
+/// This trait is sealed.
+#[cfg(feature = "protocol-analysis")]
+pub trait Aes: OneWayFunction + private::SealedAes {}
+
+#[cfg(feature = "protocol-analysis")]
+macro_rules! impl_aes {
+    ($name:ident, $params:ty) => {
+        impl private::SealedAes for $name {
+            fn round_states(key: &Key<Self>, input: &Input<Self>) -> Vec<AESRoundState> {
+                aes_round_states::<$params>(key, input)
+            }
+        }
+
+        impl Aes for $name {}
+    };
+}
+
+#[cfg(feature = "protocol-analysis")]
+impl_aes!(Aes128, OWF128);
+#[cfg(feature = "protocol-analysis")]
+impl_aes!(Aes192, OWF192);
+#[cfg(feature = "protocol-analysis")]
+impl_aes!(Aes256, OWF256);
+
+/// State of one AES round of the OWF
+///
+/// All values are blocks of 16 bytes in the byte order of AES, i.e., column by
+/// column.
+#[cfg(feature = "protocol-analysis")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AESRoundState {
+    /// Index of the encrypted block of the input
+    pub block: usize,
+    /// Number of the round, starting at 1
+    pub round: usize,
+    /// State after `SubBytes` and `ShiftRows`
+    pub sbox_output: [u8; 16],
+    /// Round key added at the end of the round
+    pub round_key: [u8; 16],
+    /// State after the round
+    pub state: [u8; 16],
+    /// Offset (in bytes) of `sbox_output` in the witness
+    ///
+    /// The S-box outputs of the last round are not part of the witness.
+    pub witness_offset: Option<usize>,
+}
+
+/// Evaluate the AES based OWF `O` round by round
+///
+/// Returns the states of all rounds, ordered by block and round. The state of
+/// the last round of each block is the corresponding block of the output.
+/// Every state with a `witness_offset` is found at that offset in the witness
+/// computed by [`extend_witness`].
+#[cfg(feature = "protocol-analysis")]
+pub fn aes_extendedwitness_step_by_step<O>(key: &Key<O>, input: &Input<O>) -> Vec<AESRoundState>
+where
+    O: Aes,
+{
+    O::round_states(key, input)
+}
+
+/// Reason for rejecting an OWF key: the first S-box with a zero input
+///
+/// FAEST can not prove knowledge of such a key. A rejected key can not be
+/// repaired and has to be replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyRejected {
+    /// Zero input of an S-box of the key schedule while deriving the round key
+    /// `round`
+    KeySchedule {
+        /// Number of the round key
+        round: usize,
+    },
+    /// Zero input of an S-box in round `round` of the encryption of block
+    /// `block` of the input
+    ///
+    /// For the Even-Mansour OWFs, the encrypted block is the key.
+    Encryption {
+        /// Index of the block
+        block: usize,
+        /// Number of the round, starting at 1
+        round: usize,
+    },
+}
+
+impl fmt::Display for KeyRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeySchedule { round } => write!(
+                f,
+                "zero S-box input in the key schedule deriving round key {round}"
+            ),
+            Self::Encryption { block, round } => write!(
+                f,
+                "zero S-box input in round {round} of the encryption of block {block}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KeyRejected {}
+
+impl From<KeyRejected> for Error {
+    fn from(_: KeyRejected) -> Self {
+        Error::new()
+    }
+}
+
+/// Key generation rejected all candidates, the RNG is likely broken
+///
+/// Key generation samples candidates until FAEST can prove knowledge of the
+/// OWF key, and gives up after 2^16 rejected candidates. An honest RNG reaches
+/// this bound with probability less than 2^-14000, whereas an RNG stuck at a
+/// constant output never produces a valid key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RngSuspect;
+
+impl fmt::Display for RngSuspect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("key generation rejected all candidates, the RNG is likely broken")
+    }
+}
+
+impl std::error::Error for RngSuspect {}
+
+impl From<RngSuspect> for Error {
+    fn from(_: RngSuspect) -> Self {
+        Error::new()
+    }
+}
+
+/// Element of `GF(2^λ)` in its little-endian byte representation
+pub type FieldElement<O> = GenericArray<u8, <O as OneWayFunction>::KeySize>;
+
+/// Affine form `constant + Σ coefficient · w_i` over `GF(2^λ)` in the witness
+/// bits `w_i`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffineForm<O: OneWayFunction> {
+    /// The constant term
+    pub constant: FieldElement<O>,
+    /// Indices of the witness bits and their non-zero coefficients
+    pub terms: Vec<(usize, FieldElement<O>)>,
+}
+
+impl<O: OneWayFunction> AffineForm<O> {
+    pub(crate) fn new(constant: FieldElement<O>) -> Self {
+        Self {
+            constant,
+            terms: Vec::new(),
+        }
+    }
+
+    /// Evaluate the form on `witness`, whose bits are stored LSB first
+    ///
+    /// # Panics
+    ///
+    /// Panics if `witness` is too short.
+    pub fn evaluate(&self, witness: &[u8]) -> FieldElement<O> {
+        let mut value = self.constant.clone();
+        for (i, coefficient) in &self.terms {
+            if witness_bit_at(witness, *i) == 1 {
+                for (v, c) in value.iter_mut().zip(coefficient) {
+                    *v ^= c;
+                }
+            }
+        }
+        value
+    }
+}
+
+/// Constraint `left · right = 1` over `GF(2^λ)`
+///
+/// Every S-box of the OWF contributes one constraint: its input times its
+/// output is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint<O: OneWayFunction> {
+    /// Input of the S-box
+    pub left: AffineForm<O>,
+    /// Output of the S-box
+    pub right: AffineForm<O>,
+}
+
+impl<O: OneWayFunction> Constraint<O> {
+    /// Check the constraint on `witness`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `witness` is too short.
+    pub fn is_satisfied(&self, witness: &[u8]) -> bool {
+        O::is_unit_product(&self.left.evaluate(witness), &self.right.evaluate(witness))
+    }
+}
+
+/// Constraints on the witness of an OWF for a fixed input and output
+///
+/// The witness consists of the key followed by the outputs of the S-boxes as
+/// computed by [`extend_witness`]. The constraints are ordered as proven by
+/// FAEST, i.e., for AES the key schedule precedes the encryption of each block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintSystem<O: OneWayFunction> {
+    /// Number of bits of the witness
+    pub witness_bits: usize,
+    /// The constraints
+    pub constraints: Vec<Constraint<O>>,
+}
+
+impl<O: OneWayFunction> ConstraintSystem<O> {
+    /// Check that `witness` satisfies all constraints
+    pub fn is_satisfied(&self, witness: &[u8]) -> bool {
+        witness.len() * 8 >= self.witness_bits
+            && self
+                .constraints
+                .iter()
+                .all(|constraint| constraint.is_satisfied(witness))
+    }
+}
+
+/// The constraints proven by FAEST with the OWF `O` for `input` and `output`
+pub fn constraint_system<O>(input: &Input<O>, output: &Output<O>) -> ConstraintSystem<O>
+where
+    O: OneWayFunction,
+{
+    O::constraint_system(input, output)
+}
+
+/// Extend `key` to the witness of the constraint system of the OWF `O`
+///
+/// Returns `None` if one of the S-box inputs is zero.
+pub fn extend_witness<O>(key: &Key<O>, input: &Input<O>) -> Option<Vec<u8>>
+where
+    O: OneWayFunction,
+{
+    O::extend_witness(key, input)
+}
+
+/// Unpack `witness` into its bits
+///
+/// Bits are stored LSB first per byte, i.e., bit `i` of the result is the bit
+/// `i` referred to by [`AffineForm::terms`].
+pub fn witness_to_bits(witness: &[u8]) -> Vec<bool> {
+    (0..witness.len() * 8)
+        .map(|i| witness_bit_at(witness, i) == 1)
+        .collect()
+}
+
+/// Pack `bits` into a witness, the inverse of [`witness_to_bits`]
+///
+/// If the number of bits is not a multiple of 8, the last byte is padded with
+/// zeros.
+pub fn bits_to_witness(bits: &[bool]) -> Vec<u8> {
+    let mut witness = vec![0; bits.len().div_ceil(8)];
+    for (i, bit) in bits.iter().enumerate() {
+        witness[i / 8] |= u8::from(*bit) << (i % 8);
+    }
+    witness
+}
+
+macro_rules! define_owf {
+    ($name:ident, $params:ty, $key_size:ty, $input_size:ty, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $name;
+
+        impl private::Sealed for $name {
+            fn is_unit_product(left: &[u8], right: &[u8]) -> bool {
+                Field::<$params>::from(left) * Field::<$params>::from(right)
+                    == Field::<$params>::ONE
+            }
+        }
+
+        impl OneWayFunction for $name {
+            type KeySize = $key_size;
+            type InputSize = $input_size;
+
+            fn evaluate(key: &Key<Self>, input: &Input<Self>) -> Output<Self> {
+                <$params>::evaluate(key, input)
+            }
+
+            fn evaluate_multi(
+                key: &Key<Self>,
+                inputs: &[Input<Self>],
+                outputs: &mut [Output<Self>],
+            ) {
+                <$params>::evaluate_owf_multi(key, inputs, outputs)
+            }
+
+            fn constraint_system(
+                input: &Input<Self>,
+                output: &Output<Self>,
+            ) -> ConstraintSystem<Self> {
+                <$params>::generate_constraint_system(input, output)
+            }
+
+            fn extend_witness(key: &Key<Self>, input: &Input<Self>) -> Option<Vec<u8>> {
+                <$params>::extendwitness(key, input).map(|w| w.to_vec())
+            }
+        }
+    };
+}
+
+define_owf!(
+    Aes128,
+    OWF128,
+    U16,
+    U16,
+    "OWF of FAEST-128s and FAEST-128f: AES-128 encryption of one block"
+);
+define_owf!(
+    Aes192,
+    OWF192,
+    U24,
+    U32,
+    "OWF of FAEST-192s and FAEST-192f: AES-192 encryption of two blocks"
+);
+define_owf!(
+    Aes256,
+    OWF256,
+    U32,
+    U32,
+    "OWF of FAEST-256s and FAEST-256f: AES-256 encryption of two blocks"
+);
+define_owf!(
+    EvenMansour128,
+    OWF128EM,
+    U16,
+    U16,
+    "OWF of FAEST-EM-128s and FAEST-EM-128f: AES-128 keyed with the input and applied to the key, followed by the feed-forward XOR of the key"
+);
+define_owf!(
+    EvenMansour192,
+    OWF192EM,
+    U24,
+    U24,
+    "OWF of FAEST-EM-192s and FAEST-EM-192f: Rijndael-192 keyed with the input and applied to the key, followed by the feed-forward XOR of the key"
+);
+define_owf!(
+    EvenMansour256,
+    OWF256EM,
+    U32,
+    U32,
+    "OWF of FAEST-EM-256s and FAEST-EM-256f: Rijndael-256 keyed with the input and applied to the key, followed by the feed-forward XOR of the key"
+);
+
+#[cfg(test)]
+#[generic_tests::define]
+mod test {
+    use super::*;
+
+    use generic_array::typenum::Unsigned;
+
+    /// Secret and public keys taken from the NIST KATs of the reference
+    /// implementation
+    pub(super) trait KatVectors: OneWayFunction {
+        const VECTORS: &'static [(&'static str, &'static str)];
+    }
+
+    impl KatVectors for Aes128 {
+        const VECTORS: &'static [(&'static str, &'static str)] = &[
+            (
+                "8626ED79D451140800E03B59B956F8210E556067407D13DC90FA9E8B872BFB8F",
+                "8626ED79D451140800E03B59B956F821138C295FD8D4AE73B3C54649DB1904F2",
+            ),
+            (
+                "4B622DE1350119C45A9F2E2EF3DC5DF50A759D138CDFBD64C81CC7CC2F513345",
+                "4B622DE1350119C45A9F2E2EF3DC5DF5169448C22349FDAEE5ED340AEA51C35C",
+            ),
+        ];
+    }
+
+    impl KatVectors for Aes192 {
+        const VECTORS: &'static [(&'static str, &'static str)] = &[
+            (
+                "E8FDF3CF9B7BE7413F68F3F7E7973AE4B5BFD31D2B96EAAE638F47EE550942D99C2B2549B2FEBF83FA3EBC46CF3CB848DCFBF20EB637B6B4",
+                "E8FDF3CF9B7BE7413F68F3F7E7973AE4B5BFD31D2B96EAAE638F47EE550942D903F1C0EDA28D80E75B95056DDD88C5D2258EB1CED344742BB810B9D9A160783F",
+            ),
+            (
+                "4B622DE1350119C45A9F2E2EF3DC5DF50A759D138CDFBD64C81CC7CC2F513345D5A45A4CED06403C5557E87113CB30EA3DC2F39481734DE9",
+                "4B622DE1350119C45A9F2E2EF3DC5DF50A759D138CDFBD64C81CC7CC2F5133451E0B1B6F6F22ACE1D61C3EC5AD3C590E1E94FF34C4864EED5D2933CDABE4FCCC",
+            ),
+        ];
+    }
+
+    impl KatVectors for Aes256 {
+        const VECTORS: &'static [(&'static str, &'static str)] = &[
+            (
+                "BB9A5779AC559148506CFA9C8717F576084952B5C92F32F30FAB1B14CF6C7477F703CA3DF9439BC046B5982898D63C2047765F13C17BFB6CCCABD5F37C194705",
+                "BB9A5779AC559148506CFA9C8717F576084952B5C92F32F30FAB1B14CF6C7477A4D7A773D7EC6F8D0113B73623282C28B68CFA0403431E2C820BD4FC14881F03",
+            ),
+            (
+                "47CCB4D7ADC6AEEE72621BEAA34285E8AD9DA5F732BB7FBF74D94C641130D207D22FDEF8152DBA9810586E015E645D9188FF0D05748912431102C835CB2C454A",
+                "47CCB4D7ADC6AEEE72621BEAA34285E8AD9DA5F732BB7FBF74D94C641130D2074A3B1461727CA3DE116FD18669829D4DD85CD577BA8C45D2C8CCAD9D06B1869C",
+            ),
+        ];
+    }
+
+    impl KatVectors for EvenMansour128 {
+        const VECTORS: &'static [(&'static str, &'static str)] = &[
+            (
+                "7C9935A0B07694AA0C6D10E4DB6B1ADD2FD81A25CCB148032DCD739936737F2D",
+                "7C9935A0B07694AA0C6D10E4DB6B1ADDDE10410EE4C04CFAD2A783769D9995EF",
+            ),
+            (
+                "F43F68FBD694F0A6D307297110ECD4739876489FDF07EB9B03364E2ED0FF96E9",
+                "F43F68FBD694F0A6D307297110ECD473B318EF71AF09FCB65E8D2C6B30B9DCD8",
+            ),
+        ];
+    }
+
+    impl KatVectors for EvenMansour192 {
+        const VECTORS: &'static [(&'static str, &'static str)] = &[
+            (
+                "C4731C3819F29B78474DA5EAA38B5E98B253547D766CF23D50E88731C01CA6E1186CF7504E36AE12B4E9E814C06E68EB",
+                "C4731C3819F29B78474DA5EAA38B5E98B253547D766CF23DFA1644FEA34A935AB8BFAA67AC62C2C6C3E2E8C849830E01",
+            ),
+            (
+                "08E25538484CD7F1613248FE6C9F6B4EC14BE684C6DEFDD1E41333B6E9052AC4340E314EEA2C99F7A97104D1B08AAE61",
+                "08E25538484CD7F1613248FE6C9F6B4EC14BE684C6DEFDD116403464D45D9AF8D7E3B8D0A86442CC34192FBE4FFA7AD9",
+            ),
+        ];
+    }
+
+    impl KatVectors for EvenMansour256 {
+        const VECTORS: &'static [(&'static str, &'static str)] = &[
+            (
+                "2414F61EC02292614D21F3ED8F6D486C6E4181F62C23D1DE08B49CC3C9445C1E9AAFFA4FBE370BFE1D10F40C3BFE6495FEBBA68524DD464DEB6658A5813CE1AD",
+                "2414F61EC02292614D21F3ED8F6D486C6E4181F62C23D1DE08B49CC3C9445C1E29DB01721E484657C0465777BEC1D03823EB6BA639D04BCF3AAB519CA0B7F0C5",
+            ),
+            (
+                "62C92C26FC4F75B26EA9E598312DD15784D836F65155E4DB91F9D5A7DAE762A32C0EB270AEDBA4C4B27AC881E983270AFB8442C67333D9F5EF2943597AF053D1",
+                "62C92C26FC4F75B26EA9E598312DD15784D836F65155E4DB91F9D5A7DAE762A3E359AE5C56807DFC6A46BFF00AEF5B2EF13A69A5C2BDD47F707646DB3152F3AD",
+            ),
+        ];
+    }
+
+    #[test]
+    fn kat<O: KatVectors>() {
+        for (sk, pk) in O::VECTORS {
+            let sk = hex::decode(sk).unwrap();
+            let pk = hex::decode(pk).unwrap();
+            let (input, key) = sk.split_at(O::InputSize::USIZE);
+            assert_eq!(key.len(), O::KeySize::USIZE);
+
+            let output = evaluate::<O>(
+                GenericArray::from_slice(key),
+                GenericArray::from_slice(input),
+            );
+            assert_eq!(&pk[..O::InputSize::USIZE], input);
+            assert_eq!(output.as_slice(), &pk[O::InputSize::USIZE..]);
+        }
+    }
+
+    #[test]
+    fn witness_bits<O: KatVectors>() {
+        let (sk, _) = O::VECTORS[0];
+        let sk = hex::decode(sk).unwrap();
+        let (input, key) = sk.split_at(O::InputSize::USIZE);
+        let witness = extend_witness::<O>(
+            GenericArray::from_slice(key),
+            GenericArray::from_slice(input),
+        )
+        .unwrap();
+
+        let mut bits = witness_to_bits(&witness);
+        assert_eq!(bits.len(), witness.len() * 8);
+        for (i, bit) in bits.iter().enumerate() {
+            assert_eq!(witness_bit_at(&witness, i), u8::from(*bit));
+        }
+        assert_eq!(bits_to_witness(&bits), witness);
+
+        bits[0] ^= true;
+        assert_eq!(witness_to_bits(&bits_to_witness(&bits)), bits);
+        assert_ne!(bits_to_witness(&bits), witness);
+
+        // partial bytes are padded with zeros
+        assert_eq!(
+            bits_to_witness(&bits[..9]),
+            [witness[0] ^ 1, witness[1] & 1]
+        );
+    }
+
+    #[instantiate_tests(<Aes128>)]
+    mod aes_128 {}
+
+    #[instantiate_tests(<Aes192>)]
+    mod aes_192 {}
+
+    #[instantiate_tests(<Aes256>)]
+    mod aes_256 {}
+
+    #[instantiate_tests(<EvenMansour128>)]
+    mod em_128 {}
+
+    #[instantiate_tests(<EvenMansour192>)]
+    mod em_192 {}
+
+    #[instantiate_tests(<EvenMansour256>)]
+    mod em_256 {}
+}
+
+/// The extraction evaluates the constraints once per witness bit, which is slow
+/// in debug builds. Hence, only the 128-bit variants are covered.
+#[cfg(test)]
+#[generic_tests::define]
+mod constraint_test {
+    use super::*;
+
+    use generic_array::typenum::Unsigned;
+
+    use super::test::KatVectors;
+
+    #[test]
+    fn satisfied_by_witness<O: KatVectors>() {
+        let (sk, pk) = O::VECTORS[0];
+        let sk = hex::decode(sk).unwrap();
+        let pk = hex::decode(pk).unwrap();
+        let (input, key) = sk.split_at(O::InputSize::USIZE);
+        let input = GenericArray::from_slice(input);
+        let output = GenericArray::from_slice(&pk[O::InputSize::USIZE..]);
+
+        let system = constraint_system::<O>(input, output);
+        let mut witness = extend_witness::<O>(GenericArray::from_slice(key), input).unwrap();
+        assert_eq!(witness.len() * 8, system.witness_bits);
+        assert!(system.is_satisfied(&witness));
+        assert!(!system.is_satisfied(&witness[..witness.len() - 1]));
+
+        // every bit of the witness is constrained
+        for i in [0, O::KeySize::USIZE * 8, system.witness_bits - 1] {
+            witness[i / 8] ^= 1 << (i % 8);
+            assert!(!system.is_satisfied(&witness));
+            witness[i / 8] ^= 1 << (i % 8);
+        }
+    }
+
+    #[instantiate_tests(<Aes128>)]
+    mod aes_128 {}
+
+    #[instantiate_tests(<EvenMansour128>)]
+    mod em_128 {}
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod even_mansour_test {
+    use super::*;
+
+    use generic_array::typenum::Unsigned;
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::test::KatVectors;
+
+    #[test]
+    fn kat<O: EvenMansour + KatVectors>() {
+        for (sk, pk) in O::VECTORS {
+            let sk = hex::decode(sk).unwrap();
+            let pk = hex::decode(pk).unwrap();
+            let (input, key) = sk.split_at(O::InputSize::USIZE);
+            let key = GenericArray::from_slice(key);
+            let input = GenericArray::from_slice(input);
+
+            let output = even_mansour::<O>(key, input);
+            assert_eq!(output.as_slice(), &pk[O::InputSize::USIZE..]);
+            assert_eq!(even_mansour_checked::<O>(key, input).unwrap(), output);
+
+            // swapping key and input keys the cipher with the secret
+            assert_ne!(
+                even_mansour::<O>(
+                    GenericArray::from_slice(input),
+                    GenericArray::from_slice(key)
+                ),
+                output
+            );
+        }
+    }
+
+    #[test]
+    fn screened_out_key<O: EvenMansour>() {
+        // about half of the keys are rejected, so a rejected one is found
+        // quickly
+        let mut rng = ChaCha8Rng::seed_from_u64(1234);
+        let mut key = Key::<O>::default();
+        let mut input = Input::<O>::default();
+        rng.fill_bytes(&mut input);
+        loop {
+            rng.fill_bytes(&mut key);
+            if extend_witness::<O>(&key, &input).is_none() {
+                break;
+            }
+            assert!(even_mansour_checked::<O>(&key, &input).is_ok());
+        }
+        assert!(even_mansour_checked::<O>(&key, &input).is_err());
+    }
+
+    #[instantiate_tests(<EvenMansour128>)]
+    mod em_128 {}
+
+    #[instantiate_tests(<EvenMansour192>)]
+    mod em_192 {}
+
+    #[instantiate_tests(<EvenMansour256>)]
+    mod em_256 {}
+}
+
+#[cfg(all(test, feature = "protocol-analysis"))]
+#[generic_tests::define]
+mod round_states_test {
+    use super::*;
+
+    use generic_array::typenum::Unsigned;
+
+    use super::test::KatVectors;
+
+    #[test]
+    fn match_witness_and_output<O: Aes + KatVectors>() {
+        for (sk, pk) in O::VECTORS {
+            let sk = hex::decode(sk).unwrap();
+            let pk = hex::decode(pk).unwrap();
+            let (input, key) = sk.split_at(O::InputSize::USIZE);
+            let key = GenericArray::from_slice(key);
+            let input = GenericArray::from_slice(input);
+            let output = &pk[O::InputSize::USIZE..];
+
+            let states = aes_extendedwitness_step_by_step::<O>(key, input);
+            let witness = extend_witness::<O>(key, input).unwrap();
+            let blocks = O::InputSize::USIZE / 16;
+            let rounds = states.len() / blocks;
+            assert_eq!(states.len(), blocks * rounds);
+
+            for (i, state) in states.iter().enumerate() {
+                assert_eq!((state.block, state.round), (i / rounds, i % rounds + 1));
+                match state.witness_offset {
+                    Some(offset) => {
+                        assert_eq!(&witness[offset..offset + 16], state.sbox_output)
+                    }
+                    None => {
+                        assert_eq!(state.round, rounds);
+                        assert_eq!(
+                            state.state,
+                            output[16 * state.block..16 * (state.block + 1)]
+                        );
+                    }
+                }
+            }
+            // the encryption rounds fill the witness after the key schedule
+            assert_eq!(
+                states[states.len() - 2].witness_offset,
+                Some(witness.len() - 16)
+            );
+        }
+    }
+
+    #[instantiate_tests(<Aes128>)]
+    mod aes_128 {}
+
+    #[instantiate_tests(<Aes192>)]
+    mod aes_192 {}
+
+    #[instantiate_tests(<Aes256>)]
+    mod aes_256 {}
+}
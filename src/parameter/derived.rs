@@ -0,0 +1,158 @@
+//! Quantities derived from the primary parameters
+//!
+//! The OWF parameters are determined by `λ`, `ℓ`, `R`, the lengths of the
+//! key schedule and encryption witnesses, and the size of the OWF input. The
+//! FAEST parameters add the `τ` split. All other lengths are computed from
+//! these by the aliases below, which name the formulas of the specification.
+//! [`validate_owf`] cross-checks the type-level computations against the same
+//! formulas evaluated on `usize`.
+
+use generic_array::typenum::{Diff, Prod, Quot, Sum, Unsigned, U1, U128, U2, U8};
+
+use super::{BaseParameters, FAESTParameters, OWFParameters, TauParameters};
+use crate::{prg::IVSize, universal_hashing::B};
+
+type Lambda<O> = <O as OWFParameters>::LAMBDA;
+type LambdaBytesOf<O> = <O as OWFParameters>::LAMBDABYTES;
+type LBytesOf<O> = <O as OWFParameters>::LBYTES;
+type RPlus1<O> = Sum<<O as OWFParameters>::R, U1>;
+
+/// `λ/8`, the security parameter (in bytes)
+pub(crate) type LambdaBytes<O> = Quot<Lambda<O>, U8>;
+
+/// `ℓ/8`, the size of the witness (in bytes)
+pub(crate) type LBytes<O> = Quot<<O as OWFParameters>::L, U8>;
+
+/// `λ + ℓ`, the size of the masked witness (in bits)
+pub(crate) type LambdaL<O> = Sum<Lambda<O>, <O as OWFParameters>::L>;
+
+/// `(λ + ℓ)/8`, the size of the masked witness (in bytes)
+pub(crate) type LambdaLBytes<O> = Sum<LambdaBytesOf<O>, LBytesOf<O>>;
+
+/// `λ·(λ + ℓ)/8`
+pub(crate) type LambdaLBytesLambda<O> = Prod<Lambda<O>, <O as OWFParameters>::LAMBDALBYTES>;
+
+/// `ℓ_ke/8`, the size of the key schedule witness (in bytes)
+pub(crate) type LKEBytes<O> = Quot<<O as OWFParameters>::LKE, U8>;
+
+/// `ℓ_enc/8`, the size of the encryption witness (in bytes)
+pub(crate) type LEncBytes<O> = Quot<<O as OWFParameters>::LENC, U8>;
+
+/// `ℓ̂/8 = (ℓ + 2λ + B)/8`, the length of the VOLE correlations (in bytes)
+pub(crate) type LHatBytes<O> = Sum<LBytesOf<O>, Sum<Prod<U2, LambdaBytesOf<O>>, B>>;
+
+/// `8·(R + 1)`
+pub(crate) type KBLength<O> = Prod<RPlus1<O>, U8>;
+
+/// `128·(R + 1)`, the size of the expanded key of the AES (in bits)
+pub(crate) type ProdRun128<O> = Prod<RPlus1<O>, U128>;
+
+/// `16·(R + 1)`, the size of the expanded key of the AES (in bytes)
+pub(crate) type ProdRun128Bytes<O> = Quot<<O as OWFParameters>::PRODRUN128, U8>;
+
+/// `λ·(R + 1)/8`, the size of the round keys of the EM variants (in bytes)
+pub(crate) type LambdaR1Bytes<O> = Quot<Prod<Lambda<O>, RPlus1<O>>, U8>;
+
+/// Size of the public key, i.e., the OWF input and output (in bytes)
+pub(crate) type PKSize<O> = Prod<U2, <O as OWFParameters>::InputSize>;
+
+/// Size of the secret key, i.e., the OWF input and key (in bytes)
+pub(crate) type SKSize<O> = Sum<<O as OWFParameters>::InputSize, LambdaBytesOf<O>>;
+
+type Owf<P> = <P as FAESTParameters>::OWF;
+type Tau<P> = <P as FAESTParameters>::Tau;
+
+/// Size of the `τ - 1` corrections of the VOLE commitment (in bytes)
+type CorrectionsSize<P> =
+    Prod<Diff<<Tau<P> as TauParameters>::Tau, U1>, <Owf<P> as OWFParameters>::LHATBYTES>;
+
+/// Size of the decommitment paths, `(τ0·k0 + τ1·k1)·λ/8` (in bytes)
+type DecommitmentPathSize<P> = Prod<
+    Sum<
+        Prod<<Tau<P> as TauParameters>::Tau0, <Tau<P> as TauParameters>::K0>,
+        Prod<<Tau<P> as TauParameters>::Tau1, <Tau<P> as TauParameters>::K1>,
+    >,
+    LambdaBytesOf<Owf<P>>,
+>;
+
+/// Size of the commitments to the hidden leaves, `τ·2λ/8` (in bytes)
+type CommitmentSize<P> = Prod<
+    <Tau<P> as TauParameters>::Tau,
+    <<Owf<P> as OWFParameters>::BaseParams as BaseParameters>::LambdaBytesTimes2,
+>;
+
+/// Size of the signature (in bytes)
+///
+/// The signature consists of the corrections, `ũ` of `λ/8 + B` bytes, the
+/// masked witness `d`, `ã`, the decommitment paths and hidden commitments,
+/// `chall3` and the IV (see [`super::signature_size`]).
+pub(crate) type SignatureSize<P> = Sum<
+    CorrectionsSize<P>,
+    Sum<
+        Sum<LambdaBytesOf<Owf<P>>, B>,
+        Sum<
+            LBytesOf<Owf<P>>,
+            Sum<
+                LambdaBytesOf<Owf<P>>,
+                Sum<
+                    DecommitmentPathSize<P>,
+                    Sum<CommitmentSize<P>, Sum<LambdaBytesOf<Owf<P>>, IVSize>>,
+                >,
+            >,
+        >,
+    >,
+>;
+
+/// Check the derived OWF parameters against the formulas of the specification
+///
+/// Besides the lengths defined above, this checks that the witness consists of
+/// the key schedule, encryption and state bits.
+pub(crate) const fn validate_owf<O>() -> Result<(), &'static str>
+where
+    O: OWFParameters,
+{
+    let lambda = O::LAMBDA::USIZE;
+    let l = O::L::USIZE;
+    let r = O::R::USIZE;
+
+    if lambda % 8 != 0 || O::LAMBDABYTES::USIZE != lambda / 8 {
+        return Err("LAMBDABYTES != lambda / 8");
+    }
+    if l % 8 != 0 || O::LBYTES::USIZE != l / 8 {
+        return Err("LBYTES != l / 8");
+    }
+    if O::LAMBDAL::USIZE != lambda + l || O::LAMBDALBYTES::USIZE != (lambda + l) / 8 {
+        return Err("LAMBDAL != lambda + l");
+    }
+    if O::LAMBDALBYTESLAMBDA::USIZE != lambda * (lambda + l) / 8 {
+        return Err("LAMBDALBYTESLAMBDA != lambda * (lambda + l) / 8");
+    }
+    if O::LKE::USIZE % 8 != 0 || O::LKEBytes::USIZE != O::LKE::USIZE / 8 {
+        return Err("LKEBytes != l_ke / 8");
+    }
+    if O::LENC::USIZE % 8 != 0 || O::QUOTLENC8::USIZE != O::LENC::USIZE / 8 {
+        return Err("QUOTLENC8 != l_enc / 8");
+    }
+    if O::LHATBYTES::USIZE != (l + 2 * lambda) / 8 + B::USIZE {
+        return Err("LHATBYTES != (l + 2 * lambda + B) / 8");
+    }
+    if O::KBLENGTH::USIZE != 8 * (r + 1)
+        || O::PRODRUN128::USIZE != 128 * (r + 1)
+        || O::PRODRUN128Bytes::USIZE != 16 * (r + 1)
+    {
+        return Err("key schedule lengths do not match R");
+    }
+    if O::LAMBDAR1BYTE::USIZE != lambda * (r + 1) / 8 {
+        return Err("LAMBDAR1BYTE != lambda * (R + 1) / 8");
+    }
+    if O::PK::USIZE != 2 * O::InputSize::USIZE || O::SK::USIZE != O::InputSize::USIZE + lambda / 8 {
+        return Err("key sizes do not match the OWF");
+    }
+    if O::WITNESS_KEY_SCHEDULE_BITS + O::WITNESS_ENCRYPTION_BITS + O::WITNESS_STATE_BITS != l {
+        return Err("witness layout does not cover l");
+    }
+    if O::WITNESS_BITS_PER_ROUND * (r - 1) != O::WITNESS_ENCRYPTION_BITS {
+        return Err("encryption witness is not split evenly into rounds");
+    }
+    Ok(())
+}
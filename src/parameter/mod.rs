@@ -0,0 +1,2560 @@
+use std::{iter::zip, marker::PhantomData};
+
+use aes::cipher::{generic_array::GenericArray as GenericArray_AES, BlockEncrypt, KeyInit};
+use generic_array::{
+    sequence::Split,
+    typenum::{
+        Diff, Prod, Sum, Unsigned, U0, U1, U10, U1024, U11, U12, U128, U14, U16, U192, U2048, U22,
+        U24, U256, U3, U32, U384, U4, U40, U4096, U448, U48, U5, U511, U512, U52, U576, U6, U64,
+        U640, U672, U7, U8, U8192, U832, U96,
+    },
+    ArrayLength, GenericArray,
+};
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use rand_core::RngCore;
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use subtle::{Choice, ConstantTimeEq};
+
+#[cfg(feature = "_variant")]
+use crate::{aes::aes_verify, em::em_verify, internal_keys::PublicKey, variant::CycleCosts};
+use crate::{
+    aes::{aes_constraint_values, aes_extendedwitness},
+    aes_backend::{Aes128Enc, Aes192Enc, Aes256Enc},
+    em::{em_constraint_values, em_evaluate, em_extendedwitness},
+    fields::{BigGaloisField, Field as _, GF128, GF192, GF256},
+    owf::{AffineForm, Constraint, ConstraintSystem, OneWayFunction},
+    prg::{IVSize, PseudoRandomGenerator, PRG128, PRG192, PRG256},
+    random_oracles::{RandomOracle, RandomOracleShake128, RandomOracleShake256},
+    rijndael_32::{Rijndael128, Rijndael192, Rijndael256},
+    universal_hashing::{VoleHasher, VoleHasherInit, ZKHasher, ZKHasherInit, B},
+    utils::{witness_bit_at, Field},
+    vc::{VectorCommitment, VC},
+};
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use crate::{
+    aes::{aes_find_zero_sbox_input, aes_prove},
+    em::{em_find_zero_sbox_input, em_prove},
+    internal_keys::SecretKey,
+    owf::{KeyRejected, RngSuspect},
+};
+#[cfg(feature = "blake3-oracle")]
+use crate::{prg::PRGBlake3, random_oracles::RandomOracleBlake3};
+#[cfg(feature = "experimental")]
+use generic_array::typenum::{U131072, U65536};
+
+mod derived;
+
+/// Base parameters per security level
+pub(crate) trait BaseParameters {
+    /// The field that is of size `2^λ` which is defined as [`Self::Lambda`]
+    type Field: BigGaloisField<Length = Self::LambdaBytes> + PartialEq + std::fmt::Debug;
+    /// Hasher implementation of `ZKHash`
+    type ZKHasher: ZKHasherInit<Self::Field, SDLength = Self::Chall>;
+    /// Hasher implementation of `VOLEHash`
+    type VoleHasher: VoleHasherInit<
+        Self::Field,
+        SDLength = Self::Chall1,
+        OutputLength = Self::VoleHasherOutputLength,
+    >;
+    /// Associated random oracle
+    type RandomOracle: RandomOracle;
+    /// Associated PRG
+    type PRG: PseudoRandomGenerator<KeySize = Self::LambdaBytes>;
+    type VC: VectorCommitment<
+        LambdaBytes = Self::LambdaBytes,
+        LambdaBytesTimes2 = Self::LambdaBytesTimes2,
+        Lambda = Self::Lambda,
+    >;
+
+    /// Security parameter (in bits)
+    type Lambda: ArrayLength;
+    /// Security parameter (in bytes)
+    type LambdaBytes: ArrayLength;
+    type LambdaBytesTimes2: ArrayLength;
+    type Chall: ArrayLength;
+    type Chall1: ArrayLength;
+    /// Output length of the VOLE hash (in bytes), i.e., `λ + B` bits where `B`
+    /// are the additional bits of
+    /// [`VOLE_REDUNDANCY_BITS`](crate::constants::VOLE_REDUNDANCY_BITS)
+    type VoleHasherOutputLength: ArrayLength;
+
+    /// Output length of the VOLE hash (in bytes)
+    #[cfg(feature = "_variant")]
+    fn vole_output_length_bytes() -> usize {
+        Self::VoleHasherOutputLength::USIZE
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BaseParams128;
+
+impl BaseParameters for BaseParams128 {
+    type Field = GF128;
+    type ZKHasher = ZKHasher<Self::Field>;
+    type VoleHasher = VoleHasher<Self::Field>;
+    type RandomOracle = RandomOracleShake128;
+    type PRG = PRG128;
+    type VC = VC<Self::PRG, Self::RandomOracle>;
+
+    type Lambda = U128;
+    type LambdaBytes = U16;
+    type LambdaBytesTimes2 = U32;
+
+    type Chall = Sum<U8, Prod<U3, Self::LambdaBytes>>;
+    type Chall1 = Sum<U8, Prod<U5, Self::LambdaBytes>>;
+    type VoleHasherOutputLength = Sum<Self::LambdaBytes, B>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BaseParams192;
+
+impl BaseParameters for BaseParams192 {
+    type Field = GF192;
+    type ZKHasher = ZKHasher<Self::Field>;
+    type VoleHasher = VoleHasher<Self::Field>;
+    type RandomOracle = RandomOracleShake256;
+    type PRG = PRG192;
+    type VC = VC<Self::PRG, Self::RandomOracle>;
+
+    type Lambda = U192;
+    type LambdaBytes = U24;
+    type LambdaBytesTimes2 = U48;
+
+    type Chall = Sum<U8, Prod<U3, Self::LambdaBytes>>;
+    type Chall1 = Sum<U8, Prod<U5, Self::LambdaBytes>>;
+    type VoleHasherOutputLength = Sum<Self::LambdaBytes, B>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BaseParams256;
+
+impl BaseParameters for BaseParams256 {
+    type Field = GF256;
+    type ZKHasher = ZKHasher<Self::Field>;
+    type VoleHasher = VoleHasher<Self::Field>;
+    type RandomOracle = RandomOracleShake256;
+    type PRG = PRG256;
+    type VC = VC<Self::PRG, Self::RandomOracle>;
+
+    type Lambda = U256;
+    type LambdaBytes = U32;
+    type LambdaBytesTimes2 = U64;
+
+    type Chall = Sum<U8, Prod<U3, Self::LambdaBytes>>;
+    type Chall1 = Sum<U8, Prod<U5, Self::LambdaBytes>>;
+    type VoleHasherOutputLength = Sum<Self::LambdaBytes, B>;
+}
+
+/// Base parameters for 128 bit security using BLAKE3
+///
+/// This is a non-standard extension: BLAKE3 replaces SHAKE128 as random oracle
+/// and AES-CTR as PRG. Parameter sets built from it are not compatible with
+/// the FAEST specification. As BLAKE3 targets 128 bit security, no such base
+/// parameters are provided for the higher security levels.
+#[cfg(feature = "blake3-oracle")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BaseParamsBlake3_128;
+
+#[cfg(feature = "blake3-oracle")]
+impl BaseParameters for BaseParamsBlake3_128 {
+    type Field = GF128;
+    type ZKHasher = ZKHasher<Self::Field>;
+    type VoleHasher = VoleHasher<Self::Field>;
+    type RandomOracle = RandomOracleBlake3;
+    type PRG = PRGBlake3;
+    type VC = VC<Self::PRG, Self::RandomOracle>;
+
+    type Lambda = U128;
+    type LambdaBytes = U16;
+    type LambdaBytesTimes2 = U32;
+
+    type Chall = Sum<U8, Prod<U3, Self::LambdaBytes>>;
+    type Chall1 = Sum<U8, Prod<U5, Self::LambdaBytes>>;
+    type VoleHasherOutputLength = Sum<Self::LambdaBytes, B>;
+}
+
+#[cfg(all(feature = "sign", feature = "_variant"))]
+pub(crate) type QSProof<O> = (
+    GenericArray<u8, <O as OWFParameters>::LAMBDABYTES>,
+    GenericArray<u8, <O as OWFParameters>::LAMBDABYTES>,
+);
+
+pub(crate) trait OWFParameters: Sized {
+    // Base parameters of the OWF
+    type BaseParams: BaseParameters<Lambda = Self::LAMBDA, LambdaBytes = Self::LAMBDABYTES>;
+    /// The input (= output) size of the OWF (in bytes)
+    type InputSize: ArrayLength;
+
+    type LAMBDA: ArrayLength;
+    type LAMBDABYTES: ArrayLength;
+    type L: ArrayLength;
+    type LBYTES: ArrayLength;
+    type NK: ArrayLength;
+    type R: ArrayLength;
+    type SKE: ArrayLength;
+    type LKE: ArrayLength;
+    type LKEBytes: ArrayLength;
+    type LENC: ArrayLength;
+    type QUOTLENC8: ArrayLength;
+    type NST: ArrayLength;
+    type LAMBDALBYTES: ArrayLength;
+    type LAMBDAL: ArrayLength;
+    type PK: ArrayLength;
+    type SK: ArrayLength;
+    type LHATBYTES: ArrayLength;
+    type KBLENGTH: ArrayLength;
+    type PRODRUN128: ArrayLength;
+    type PRODRUN128Bytes: ArrayLength;
+    type LAMBDALBYTESLAMBDA: ArrayLength;
+    type LAMBDAR1BYTE: ArrayLength;
+
+    /// Number of witness bits covering the key schedule (in bits)
+    ///
+    /// For AES, `w` starts with the key followed by the S-box outputs of the
+    /// key expansion, i.e., `w[..LKE]`. The key schedule of the EM variants
+    /// is evaluated on the public input and is not part of the witness.
+    const WITNESS_KEY_SCHEDULE_BITS: usize = Self::LKE::USIZE;
+
+    /// Number of witness bits covering the encryption (in bits)
+    ///
+    /// These are the S-box outputs of all but the last round, following the
+    /// key schedule or the state bits. For λ > 128, AES encrypts two blocks
+    /// and the witness contains both of them.
+    const WITNESS_ENCRYPTION_BITS: usize = Self::LENC::USIZE;
+
+    /// Number of witness bits covering the initial state (in bits)
+    ///
+    /// For EM, `w` starts with the secret key which is the input state of
+    /// the Rijndael circuit, i.e., `w[..32 * NST]`. This is empty for AES.
+    const WITNESS_STATE_BITS: usize = Self::NST::USIZE * 32;
+
+    /// Number of encryption witness bits per round (in bits)
+    const WITNESS_BITS_PER_ROUND: usize = Self::WITNESS_ENCRYPTION_BITS / (Self::R::USIZE - 1);
+
+    fn evaluate_owf(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        input: &GenericArray<u8, Self::InputSize>,
+        output: &mut GenericArray<u8, Self::InputSize>,
+    );
+
+    /// Evaluate the OWF in constant time with respect to `key`
+    ///
+    /// The key of the OWF is the secret key, whereas the input is part of the
+    /// public key. By default, this is [`Self::evaluate_owf`] which relies on
+    /// the constant-time backends of the `aes` crate (AES-NI or fixsliced) or
+    /// the fixsliced Rijndael implementation.
+    fn evaluate_owf_ct(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        input: &GenericArray<u8, Self::InputSize>,
+        output: &mut GenericArray<u8, Self::InputSize>,
+    ) {
+        Self::evaluate_owf(key, input, output);
+    }
+
+    /// Evaluate the OWF with the same key on multiple inputs
+    fn evaluate_owf_multi(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        inputs: &[GenericArray<u8, Self::InputSize>],
+        outputs: &mut [GenericArray<u8, Self::InputSize>],
+    ) {
+        assert_eq!(inputs.len(), outputs.len());
+        for (input, output) in zip(inputs, outputs) {
+            Self::evaluate_owf(key, input, output);
+        }
+    }
+
+    /// Evaluate the OWF on typed key and input
+    fn evaluate(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        input: &GenericArray<u8, Self::InputSize>,
+    ) -> GenericArray<u8, Self::InputSize> {
+        let mut output = GenericArray::default();
+        Self::evaluate_owf_ct(key, input, &mut output);
+        output
+    }
+
+    /// Number of rounds of the underlying AES or Rijndael circuit
+    #[cfg(feature = "_variant")]
+    fn circuit_depth() -> usize {
+        Self::R::USIZE
+    }
+
+    /// Number of 32-bit words of the OWF key
+    #[cfg(feature = "_variant")]
+    fn num_key_words() -> usize {
+        Self::NK::USIZE
+    }
+
+    /// Size of the witness per round (in bytes, rounded down)
+    #[cfg(feature = "_variant")]
+    fn witness_per_round_bytes() -> usize {
+        Self::LBYTES::USIZE / Self::R::USIZE
+    }
+
+    /// Maximal number of signatures per key, `None` if unlimited
+    ///
+    /// Signing is stateless: every signature derives fresh VOLE randomness
+    /// from the key, the message and `ρ`, and nothing is accumulated across
+    /// signatures. Hence, FAEST does not limit the number of signatures.
+    /// Only `owf_input` needs to be unique, but it is sampled once per key
+    /// pair and not per signature.
+    #[cfg(feature = "_variant")]
+    fn max_sign_operations() -> Option<u64> {
+        None
+    }
+
+    /// Whether signing requires a unique nonce from the caller
+    ///
+    /// The randomness `ρ` is optional and only hedges against fault attacks;
+    /// deterministic signatures without `ρ` are secure.
+    #[cfg(feature = "_variant")]
+    fn requires_unique_nonce() -> bool {
+        false
+    }
+
+    fn extendwitness(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<Box<GenericArray<u8, Self::LBYTES>>>;
+
+    /// Locate the first zero S-box input of the OWF
+    ///
+    /// Returns `None` if and only if [`Self::extendwitness`] succeeds. Unlike
+    /// the latter, this does not compute the witness.
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    fn find_zero_sbox_input(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<KeyRejected>;
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    fn witness(sk: &SecretKey<Self>) -> Box<GenericArray<u8, Self::LBYTES>> {
+        // TODO: compute once and store in SecretKey
+        // Secret keys are only constructed after checking that the witness can
+        // be extended, hence this can not fail.
+        #[allow(clippy::expect_used)]
+        Self::extendwitness(&sk.owf_key, sk.pk.owf_input()).expect("valid secret key")
+    }
+
+    /// Check that the witness `w` belongs to the public key `pk`
+    ///
+    /// Both witness layouts start with the OWF key. The key is checked to
+    /// evaluate to the output of `pk`, and the witness is recomputed from it
+    /// and compared in full, including the key schedule and the round states.
+    ///
+    /// This deliberately does more than re-deriving the output from the last
+    /// round: a corrupted intermediate round state leaves the last round
+    /// intact, but still yields a signature that fails to verify. The check
+    /// costs one OWF evaluation and one witness extension, i.e., about two
+    /// AES encryptions, which is negligible compared to the VOLE commitment
+    /// of a signature. Signing with debug assertions hence computes the
+    /// witness twice.
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    fn verify_witness(pk: &PublicKey<Self>, w: &GenericArray<u8, Self::LBYTES>) -> bool {
+        let owf_key = GenericArray::from_slice(&w[..Self::LAMBDABYTES::USIZE]);
+        let valid_output = Self::evaluate(owf_key, pk.owf_input())
+            .as_slice()
+            .ct_eq(pk.owf_output());
+        let valid_witness = Self::extendwitness(owf_key, pk.owf_input())
+            .map_or(Choice::from(0), |expected| expected.as_slice().ct_eq(w));
+        (valid_output & valid_witness).into()
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    fn prove(
+        w: &GenericArray<u8, Self::LBYTES>,
+        u: &GenericArray<u8, Self::LAMBDALBYTES>,
+        gv: &GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        pk: &PublicKey<Self>,
+        chall: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+    ) -> QSProof<Self>;
+
+    /// Recompute the QuickSilver check value from the corrected VOLE tags
+    ///
+    /// The challenge is decoded with the `τ` split of `P`. Taking the FAEST
+    /// parameters rather than the split itself rules out mismatched
+    /// combinations.
+    #[cfg(feature = "_variant")]
+    fn verify<P>(
+        d: &GenericArray<u8, Self::LBYTES>,
+        gq: &mut GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        a_t: &GenericArray<u8, Self::LAMBDABYTES>,
+        chall2: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+        chall3: &GenericArray<u8, Self::LAMBDABYTES>,
+        pk: &PublicKey<Self>,
+    ) -> GenericArray<u8, Self::LAMBDABYTES>
+    where
+        P: FAESTParameters<OWF = Self>;
+
+    /// Evaluate both sides of the constraints `left · right = 1` on the
+    /// witness `w`
+    ///
+    /// The values are the ones committed to by [`Self::prove`]. Both sides
+    /// are affine functions of the bits of `w`.
+    fn constraint_values(
+        w: &GenericArray<u8, Self::LBYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+        owf_output: &GenericArray<u8, Self::InputSize>,
+    ) -> Vec<(Field<Self>, Field<Self>)>;
+
+    /// Extract the constraints proven by [`Self::prove`] for the public key
+    /// `owf_input` and `owf_output`
+    ///
+    /// As both sides of the constraints are affine in the witness bits, the
+    /// constant terms are their values on the zero witness, and the
+    /// coefficients of bit `i` are the differences to their values on the
+    /// witness with only bit `i` set.
+    fn generate_constraint_system<O>(
+        owf_input: &GenericArray<u8, Self::InputSize>,
+        owf_output: &GenericArray<u8, Self::InputSize>,
+    ) -> ConstraintSystem<O>
+    where
+        O: OneWayFunction<KeySize = Self::LAMBDABYTES>,
+    {
+        let mut w = GenericArray::<u8, Self::LBYTES>::default();
+        let constants = Self::constraint_values(&w, owf_input, owf_output);
+        let mut constraints: Vec<Constraint<O>> = constants
+            .iter()
+            .map(|(left, right)| Constraint {
+                left: AffineForm::new(left.as_bytes()),
+                right: AffineForm::new(right.as_bytes()),
+            })
+            .collect();
+
+        for i in 0..Self::L::USIZE {
+            w[i / 8] = 1 << (i % 8);
+            let values = Self::constraint_values(&w, owf_input, owf_output);
+            for (constraint, (value, constant)) in zip(&mut constraints, zip(values, &constants)) {
+                let left = value.0 - constant.0;
+                if left != Field::<Self>::ZERO {
+                    constraint.left.terms.push((i, left.as_bytes()));
+                }
+                let right = value.1 - constant.1;
+                if right != Field::<Self>::ZERO {
+                    constraint.right.terms.push((i, right.as_bytes()));
+                }
+            }
+            w[i / 8] = 0;
+        }
+
+        ConstraintSystem {
+            witness_bits: Self::L::USIZE,
+            constraints,
+        }
+    }
+
+    /// Generate a secret key
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`MAX_KEYGEN_ATTEMPTS`] candidates are rejected, see
+    /// [`Self::keygen_with_rng_detailed`].
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    fn keygen_with_rng(rng: impl RngCore) -> SecretKey<Self> {
+        match Self::keygen_with_rng_detailed(rng) {
+            Ok((sk, _)) => sk,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Generate a secret key and report the number of attempts
+    ///
+    /// Fails if [`MAX_KEYGEN_ATTEMPTS`] candidates are rejected, which
+    /// indicates a broken RNG.
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    fn keygen_with_rng_detailed(rng: impl RngCore) -> Result<(SecretKey<Self>, u32), RngSuspect> {
+        keygen_bounded(rng, MAX_KEYGEN_ATTEMPTS)
+    }
+
+    /// Generate a secret key while only re-sampling the OWF key on rejection
+    ///
+    /// The validity predicate is the same as for [`Self::keygen_with_rng`],
+    /// but the OWF input is sampled once and kept. This reduces the
+    /// consumption of randomness, but the generated keys differ from the keys
+    /// of the NIST KATs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`MAX_KEYGEN_ATTEMPTS`] candidates are rejected.
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    fn keygen_fast_with_rng(rng: impl RngCore) -> SecretKey<Self> {
+        match keygen_fast_bounded(rng, MAX_KEYGEN_ATTEMPTS) {
+            Ok(sk) => sk,
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
+
+/// Maximal number of candidates sampled by the key generation
+///
+/// A candidate is rejected if one of the S-box inputs of the OWF is zero. With
+/// `n = LBYTES` S-boxes, this happens with probability `1 - (255/256)^n`, which
+/// is largest for AES-256 with `n = 500` and less than `0.86`. Hence, an honest
+/// RNG exhausts all attempts with probability less than `0.86^(2^16) <
+/// 2^-14000`.
+#[cfg(all(feature = "sign", feature = "_variant"))]
+pub(crate) const MAX_KEYGEN_ATTEMPTS: u32 = 1 << 16;
+
+/// Sample at most `max_attempts` candidates for a secret key
+#[cfg(all(feature = "sign", feature = "_variant"))]
+fn keygen_bounded<O>(
+    mut rng: impl RngCore,
+    max_attempts: u32,
+) -> Result<(SecretKey<O>, u32), RngSuspect>
+where
+    O: OWFParameters,
+{
+    for attempt in 1..=max_attempts {
+        // This is a quirk of the NIST PRG to generate the test vectors. The array has to be sampled at once.
+        let mut sk: GenericArray<u8, O::SK> = GenericArray::default();
+        rng.fill_bytes(&mut sk);
+
+        let owf_input = GenericArray::from_slice(&sk[..O::InputSize::USIZE]);
+        let owf_key = GenericArray::from_slice(&sk[O::InputSize::USIZE..]);
+
+        if O::extendwitness(owf_key, owf_input).is_none() {
+            continue;
+        }
+
+        let sk = SecretKey {
+            owf_key: owf_key.clone(),
+            pk: PublicKey::new(owf_input, &O::evaluate(owf_key, owf_input)),
+        };
+        return Ok((sk, attempt));
+    }
+    Err(RngSuspect)
+}
+
+/// Sample an OWF input and at most `max_attempts` OWF keys for a secret key
+#[cfg(all(feature = "sign", feature = "_variant"))]
+fn keygen_fast_bounded<O>(
+    mut rng: impl RngCore,
+    max_attempts: u32,
+) -> Result<SecretKey<O>, RngSuspect>
+where
+    O: OWFParameters,
+{
+    let mut owf_input = GenericArray::<u8, O::InputSize>::default();
+    rng.fill_bytes(&mut owf_input);
+
+    let mut owf_key = GenericArray::<u8, O::LAMBDABYTES>::default();
+    for _ in 0..max_attempts {
+        rng.fill_bytes(&mut owf_key);
+        if O::extendwitness(&owf_key, &owf_input).is_some() {
+            let owf_output = O::evaluate(&owf_key, &owf_input);
+            return Ok(SecretKey {
+                owf_key,
+                pk: PublicKey::new(&owf_input, &owf_output),
+            });
+        }
+    }
+    Err(RngSuspect)
+}
+
+/// AES-128 based OWF
+///
+/// The base parameters default to the ones defined by the FAEST specification,
+/// but may be replaced by other 128 bit base parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OWF128<B = BaseParams128>(PhantomData<B>);
+
+impl<B> OWFParameters for OWF128<B>
+where
+    B: BaseParameters<Lambda = U128, LambdaBytes = U16>,
+{
+    type BaseParams = B;
+    type InputSize = U16;
+
+    type LAMBDA = U128;
+    type LAMBDABYTES = derived::LambdaBytes<Self>;
+    type L = Sum<U1024, U576>;
+    type LBYTES = derived::LBytes<Self>;
+    type LAMBDALBYTES = derived::LambdaLBytes<Self>;
+    type NK = U4;
+    type R = U10;
+    type SKE = U40;
+    type LKE = U448;
+    type LKEBytes = derived::LKEBytes<Self>;
+    type LENC = Sum<U1024, U128>;
+    type NST = U0;
+    type PK = derived::PKSize<Self>;
+    type SK = derived::SKSize<Self>;
+    type LHATBYTES = derived::LHatBytes<Self>;
+    type KBLENGTH = derived::KBLength<Self>;
+    type PRODRUN128 = derived::ProdRun128<Self>;
+    type PRODRUN128Bytes = derived::ProdRun128Bytes<Self>;
+    type LAMBDALBYTESLAMBDA = derived::LambdaLBytesLambda<Self>;
+    type QUOTLENC8 = derived::LEncBytes<Self>;
+    type LAMBDAL = derived::LambdaL<Self>;
+    type LAMBDAR1BYTE = derived::LambdaR1Bytes<Self>;
+
+    fn evaluate_owf(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        input: &GenericArray<u8, Self::InputSize>,
+        output: &mut GenericArray<u8, Self::InputSize>,
+    ) {
+        let aes = Aes128Enc::new(key.as_0_14());
+        aes.encrypt_block_b2b(input.as_0_14(), output.as_0_14_mut());
+    }
+
+    fn evaluate_owf_multi(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        inputs: &[GenericArray<u8, Self::InputSize>],
+        outputs: &mut [GenericArray<u8, Self::InputSize>],
+    ) {
+        // number of blocks processed in parallel by AES-NI
+        const BATCH: usize = 8;
+
+        assert_eq!(inputs.len(), outputs.len());
+        let aes = Aes128Enc::new(key.as_0_14());
+        let mut blocks = [GenericArray_AES::default(); BATCH];
+        for (inputs, outputs) in zip(inputs.chunks(BATCH), outputs.chunks_mut(BATCH)) {
+            let blocks = &mut blocks[..inputs.len()];
+            for (block, input) in zip(blocks.iter_mut(), inputs) {
+                block.copy_from_slice(input);
+            }
+            aes.encrypt_blocks(blocks);
+            for (output, block) in zip(outputs.iter_mut(), blocks.iter()) {
+                output.copy_from_slice(block);
+            }
+        }
+    }
+
+    #[inline]
+    fn extendwitness(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<Box<GenericArray<u8, Self::LBYTES>>> {
+        aes_extendedwitness::<Self>(owf_key, owf_input)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn find_zero_sbox_input(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<KeyRejected> {
+        aes_find_zero_sbox_input::<Self>(owf_key, owf_input)
+    }
+
+    #[inline]
+    fn constraint_values(
+        w: &GenericArray<u8, Self::LBYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+        owf_output: &GenericArray<u8, Self::InputSize>,
+    ) -> Vec<(Field<Self>, Field<Self>)> {
+        aes_constraint_values::<Self>(w, owf_input, owf_output)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn prove(
+        w: &GenericArray<u8, Self::LBYTES>,
+        u: &GenericArray<u8, Self::LAMBDALBYTES>,
+        gv: &GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        pk: &PublicKey<Self>,
+        chall: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+    ) -> QSProof<Self> {
+        aes_prove::<Self>(w, u, gv, pk, chall)
+    }
+
+    #[cfg(feature = "_variant")]
+    #[inline]
+    fn verify<P>(
+        d: &GenericArray<u8, Self::LBYTES>,
+        gq: &mut GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        a_t: &GenericArray<u8, Self::LAMBDABYTES>,
+        chall2: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+        chall3: &GenericArray<u8, Self::LAMBDABYTES>,
+        pk: &PublicKey<Self>,
+    ) -> GenericArray<u8, Self::LAMBDABYTES>
+    where
+        P: FAESTParameters<OWF = Self>,
+    {
+        aes_verify::<Self, P>(d, gq, a_t, chall2, chall3, pk)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OWF192;
+
+impl OWFParameters for OWF192 {
+    type BaseParams = BaseParams192;
+    type InputSize = U32;
+
+    type LAMBDA = U192;
+    type LAMBDABYTES = derived::LambdaBytes<Self>;
+    type L = Diff<U4096, U832>;
+    type LBYTES = derived::LBytes<Self>;
+    type LAMBDALBYTES = derived::LambdaLBytes<Self>;
+    type NK = U6;
+    type R = U12;
+    type SKE = U32;
+    type LKE = U448;
+    type LKEBytes = derived::LKEBytes<Self>;
+    type LENC = Sum<U1024, U384>;
+    type NST = U0;
+    type PK = derived::PKSize<Self>;
+    type SK = derived::SKSize<Self>;
+    type LHATBYTES = derived::LHatBytes<Self>;
+    type KBLENGTH = derived::KBLength<Self>;
+    type PRODRUN128 = derived::ProdRun128<Self>;
+    type PRODRUN128Bytes = derived::ProdRun128Bytes<Self>;
+    type LAMBDALBYTESLAMBDA = derived::LambdaLBytesLambda<Self>;
+    type QUOTLENC8 = derived::LEncBytes<Self>;
+    type LAMBDAL = derived::LambdaL<Self>;
+    type LAMBDAR1BYTE = derived::LambdaR1Bytes<Self>;
+
+    /// Two blocks are encrypted
+    const WITNESS_ENCRYPTION_BITS: usize = 2 * Self::LENC::USIZE;
+
+    fn evaluate_owf(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        input: &GenericArray<u8, Self::InputSize>,
+        output: &mut GenericArray<u8, Self::InputSize>,
+    ) {
+        let aes = Aes192Enc::new(key.as_0_14());
+        let (input_0, input_1): (&GenericArray<_, U16>, _) = input.split();
+        let (output_0, output_1): (&mut GenericArray<_, U16>, _) = output.split();
+        aes.encrypt_block_b2b(input_0.as_0_14(), output_0.as_0_14_mut());
+        aes.encrypt_block_b2b(input_1.as_0_14(), output_1.as_0_14_mut());
+    }
+
+    #[inline]
+    fn extendwitness(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<Box<GenericArray<u8, Self::LBYTES>>> {
+        aes_extendedwitness::<Self>(owf_key, owf_input)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn find_zero_sbox_input(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<KeyRejected> {
+        aes_find_zero_sbox_input::<Self>(owf_key, owf_input)
+    }
+
+    #[inline]
+    fn constraint_values(
+        w: &GenericArray<u8, Self::LBYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+        owf_output: &GenericArray<u8, Self::InputSize>,
+    ) -> Vec<(Field<Self>, Field<Self>)> {
+        aes_constraint_values::<Self>(w, owf_input, owf_output)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn prove(
+        w: &GenericArray<u8, Self::LBYTES>,
+        u: &GenericArray<u8, Self::LAMBDALBYTES>,
+        gv: &GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        pk: &PublicKey<Self>,
+        chall: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+    ) -> QSProof<Self> {
+        aes_prove::<Self>(w, u, gv, pk, chall)
+    }
+
+    #[cfg(feature = "_variant")]
+    #[inline]
+    fn verify<P>(
+        d: &GenericArray<u8, Self::LBYTES>,
+        gq: &mut GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        a_t: &GenericArray<u8, Self::LAMBDABYTES>,
+        chall2: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+        chall3: &GenericArray<u8, Self::LAMBDABYTES>,
+        pk: &PublicKey<Self>,
+    ) -> GenericArray<u8, Self::LAMBDABYTES>
+    where
+        P: FAESTParameters<OWF = Self>,
+    {
+        aes_verify::<Self, P>(d, gq, a_t, chall2, chall3, pk)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OWF256;
+
+impl OWFParameters for OWF256 {
+    type BaseParams = BaseParams256;
+    type InputSize = U32;
+
+    type LAMBDA = U256;
+    type LAMBDABYTES = derived::LambdaBytes<Self>;
+    type L = Diff<U4096, U96>;
+    type LBYTES = derived::LBytes<Self>;
+    type LAMBDALBYTES = derived::LambdaLBytes<Self>;
+    type NK = U8;
+    type R = U14;
+    type SKE = U52;
+    type LKE = U672;
+    type LKEBytes = derived::LKEBytes<Self>;
+    type LENC = Sum<U1024, U640>;
+    type NST = U0;
+    type PK = derived::PKSize<Self>;
+    type SK = derived::SKSize<Self>;
+    type LHATBYTES = derived::LHatBytes<Self>;
+    type KBLENGTH = derived::KBLength<Self>;
+    type PRODRUN128 = derived::ProdRun128<Self>;
+    type PRODRUN128Bytes = derived::ProdRun128Bytes<Self>;
+    type LAMBDALBYTESLAMBDA = derived::LambdaLBytesLambda<Self>;
+    type QUOTLENC8 = derived::LEncBytes<Self>;
+    type LAMBDAL = derived::LambdaL<Self>;
+    type LAMBDAR1BYTE = derived::LambdaR1Bytes<Self>;
+
+    /// Two blocks are encrypted
+    const WITNESS_ENCRYPTION_BITS: usize = 2 * Self::LENC::USIZE;
+
+    fn evaluate_owf(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        input: &GenericArray<u8, Self::InputSize>,
+        output: &mut GenericArray<u8, Self::InputSize>,
+    ) {
+        let aes = Aes256Enc::new(key.as_0_14());
+        let (input_0, input_1): (&GenericArray<_, U16>, _) = input.split();
+        let (output_0, output_1): (&mut GenericArray<_, U16>, _) = output.split();
+        aes.encrypt_block_b2b(input_0.as_0_14(), output_0.as_0_14_mut());
+        aes.encrypt_block_b2b(input_1.as_0_14(), output_1.as_0_14_mut());
+    }
+
+    #[inline]
+    fn extendwitness(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<Box<GenericArray<u8, Self::LBYTES>>> {
+        aes_extendedwitness::<Self>(owf_key, owf_input)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn find_zero_sbox_input(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<KeyRejected> {
+        aes_find_zero_sbox_input::<Self>(owf_key, owf_input)
+    }
+
+    #[inline]
+    fn constraint_values(
+        w: &GenericArray<u8, Self::LBYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+        owf_output: &GenericArray<u8, Self::InputSize>,
+    ) -> Vec<(Field<Self>, Field<Self>)> {
+        aes_constraint_values::<Self>(w, owf_input, owf_output)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn prove(
+        w: &GenericArray<u8, Self::LBYTES>,
+        u: &GenericArray<u8, Self::LAMBDALBYTES>,
+        gv: &GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        pk: &PublicKey<Self>,
+        chall: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+    ) -> QSProof<Self> {
+        aes_prove::<Self>(w, u, gv, pk, chall)
+    }
+
+    #[cfg(feature = "_variant")]
+    #[inline]
+    fn verify<P>(
+        d: &GenericArray<u8, Self::LBYTES>,
+        gq: &mut GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        a_t: &GenericArray<u8, Self::LAMBDABYTES>,
+        chall2: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+        chall3: &GenericArray<u8, Self::LAMBDABYTES>,
+        pk: &PublicKey<Self>,
+    ) -> GenericArray<u8, Self::LAMBDABYTES>
+    where
+        P: FAESTParameters<OWF = Self>,
+    {
+        aes_verify::<Self, P>(d, gq, a_t, chall2, chall3, pk)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OWF128EM;
+
+impl OWFParameters for OWF128EM {
+    type BaseParams = BaseParams128;
+    type InputSize = U16;
+
+    type LAMBDA = U128;
+    type LAMBDABYTES = derived::LambdaBytes<Self>;
+    type L = Sum<U1024, U256>;
+    type LBYTES = derived::LBytes<Self>;
+    type LAMBDALBYTES = derived::LambdaLBytes<Self>;
+    type NK = U4;
+    type R = U10;
+    type SKE = U40;
+    type LKE = U448;
+    type LKEBytes = derived::LKEBytes<Self>;
+    type LENC = Sum<U1024, U128>;
+    type NST = U4;
+    type PK = derived::PKSize<Self>;
+    type SK = derived::SKSize<Self>;
+    type LHATBYTES = derived::LHatBytes<Self>;
+    type KBLENGTH = derived::KBLength<Self>;
+    type PRODRUN128 = derived::ProdRun128<Self>;
+    type PRODRUN128Bytes = derived::ProdRun128Bytes<Self>;
+    type LAMBDALBYTESLAMBDA = derived::LambdaLBytesLambda<Self>;
+    type QUOTLENC8 = derived::LEncBytes<Self>;
+    type LAMBDAL = derived::LambdaL<Self>;
+    type LAMBDAR1BYTE = derived::LambdaR1Bytes<Self>;
+
+    const WITNESS_KEY_SCHEDULE_BITS: usize = 0;
+    const WITNESS_ENCRYPTION_BITS: usize = (Self::R::USIZE - 1) * Self::NST::USIZE * 32;
+
+    fn evaluate_owf(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        input: &GenericArray<u8, Self::InputSize>,
+        output: &mut GenericArray<u8, Self::InputSize>,
+    ) {
+        em_evaluate::<Aes128Enc, _>(key, input, output);
+    }
+
+    /// Evaluate the OWF with the bitsliced AES implementation
+    ///
+    /// In the Even-Mansour construction, the AES key is the public input and
+    /// the secret key is the plaintext. Hence, the key schedule may be
+    /// computed with table lookups or branches, but the encryption of `key`
+    /// has to be constant time. The fixsliced implementation guarantees this
+    /// independently of the backend selected by the `aes` crate at runtime.
+    fn evaluate_owf_ct(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        input: &GenericArray<u8, Self::InputSize>,
+        output: &mut GenericArray<u8, Self::InputSize>,
+    ) {
+        em_evaluate::<Rijndael128, _>(key, input, output);
+    }
+
+    #[inline]
+    fn extendwitness(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<Box<GenericArray<u8, Self::LBYTES>>> {
+        em_extendedwitness::<Self>(owf_key, owf_input)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn find_zero_sbox_input(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<KeyRejected> {
+        em_find_zero_sbox_input::<Self>(owf_key, owf_input)
+    }
+
+    #[inline]
+    fn constraint_values(
+        w: &GenericArray<u8, Self::LBYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+        owf_output: &GenericArray<u8, Self::InputSize>,
+    ) -> Vec<(Field<Self>, Field<Self>)> {
+        em_constraint_values::<Self>(w, owf_input, owf_output)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn prove(
+        w: &GenericArray<u8, Self::LBYTES>,
+        u: &GenericArray<u8, Self::LAMBDALBYTES>,
+        gv: &GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        pk: &PublicKey<Self>,
+        chall: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+    ) -> QSProof<Self> {
+        em_prove::<Self>(w, u, gv, pk, chall)
+    }
+
+    #[cfg(feature = "_variant")]
+    #[inline]
+    fn verify<P>(
+        d: &GenericArray<u8, Self::LBYTES>,
+        gq: &mut GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        a_t: &GenericArray<u8, Self::LAMBDABYTES>,
+        chall2: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+        chall3: &GenericArray<u8, Self::LAMBDABYTES>,
+        pk: &PublicKey<Self>,
+    ) -> GenericArray<u8, Self::LAMBDABYTES>
+    where
+        P: FAESTParameters<OWF = Self>,
+    {
+        em_verify::<Self, P>(d, gq, a_t, chall2, chall3, pk)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OWF192EM;
+
+impl OWFParameters for OWF192EM {
+    type BaseParams = BaseParams192;
+    type InputSize = U24;
+
+    type LAMBDA = U192;
+    type LAMBDABYTES = derived::LambdaBytes<Self>;
+    type L = Sum<U2048, U256>;
+    type LBYTES = derived::LBytes<Self>;
+    type LAMBDALBYTES = derived::LambdaLBytes<Self>;
+    type NK = U6;
+    type R = U12;
+    type SKE = U32;
+    type LKE = U448;
+    type LKEBytes = derived::LKEBytes<Self>;
+    type LENC = Sum<U1024, U384>;
+    type NST = U6;
+    type PK = derived::PKSize<Self>;
+    type SK = derived::SKSize<Self>;
+    type LHATBYTES = derived::LHatBytes<Self>;
+    type KBLENGTH = derived::KBLength<Self>;
+    type PRODRUN128 = derived::ProdRun128<Self>;
+    type PRODRUN128Bytes = derived::ProdRun128Bytes<Self>;
+    type LAMBDALBYTESLAMBDA = derived::LambdaLBytesLambda<Self>;
+    type QUOTLENC8 = derived::LEncBytes<Self>;
+    type LAMBDAL = derived::LambdaL<Self>;
+    type LAMBDAR1BYTE = derived::LambdaR1Bytes<Self>;
+
+    const WITNESS_KEY_SCHEDULE_BITS: usize = 0;
+    const WITNESS_ENCRYPTION_BITS: usize = (Self::R::USIZE - 1) * Self::NST::USIZE * 32;
+
+    fn evaluate_owf(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        input: &GenericArray<u8, Self::InputSize>,
+        output: &mut GenericArray<u8, Self::InputSize>,
+    ) {
+        em_evaluate::<Rijndael192, _>(key, input, output);
+    }
+
+    #[inline]
+    fn extendwitness(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<Box<GenericArray<u8, Self::LBYTES>>> {
+        em_extendedwitness::<Self>(owf_key, owf_input)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn find_zero_sbox_input(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<KeyRejected> {
+        em_find_zero_sbox_input::<Self>(owf_key, owf_input)
+    }
+
+    #[inline]
+    fn constraint_values(
+        w: &GenericArray<u8, Self::LBYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+        owf_output: &GenericArray<u8, Self::InputSize>,
+    ) -> Vec<(Field<Self>, Field<Self>)> {
+        em_constraint_values::<Self>(w, owf_input, owf_output)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn prove(
+        w: &GenericArray<u8, Self::LBYTES>,
+        u: &GenericArray<u8, Self::LAMBDALBYTES>,
+        gv: &GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        pk: &PublicKey<Self>,
+        chall: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+    ) -> QSProof<Self> {
+        em_prove::<Self>(w, u, gv, pk, chall)
+    }
+
+    #[cfg(feature = "_variant")]
+    #[inline]
+    fn verify<P>(
+        d: &GenericArray<u8, Self::LBYTES>,
+        gq: &mut GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        a_t: &GenericArray<u8, Self::LAMBDABYTES>,
+        chall2: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+        chall3: &GenericArray<u8, Self::LAMBDABYTES>,
+        pk: &PublicKey<Self>,
+    ) -> GenericArray<u8, Self::LAMBDABYTES>
+    where
+        P: FAESTParameters<OWF = Self>,
+    {
+        em_verify::<Self, P>(d, gq, a_t, chall2, chall3, pk)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OWF256EM;
+
+impl OWFParameters for OWF256EM {
+    type BaseParams = BaseParams256;
+    type InputSize = U32;
+
+    type LAMBDA = U256;
+    type LAMBDABYTES = derived::LambdaBytes<Self>;
+    type L = Diff<U4096, U512>;
+    type LBYTES = derived::LBytes<Self>;
+    type LAMBDALBYTES = derived::LambdaLBytes<Self>;
+    type NK = U8;
+    type R = U14;
+    type SKE = U52;
+    type LKE = U672;
+    type LKEBytes = derived::LKEBytes<Self>;
+    type LENC = Sum<U1024, U640>;
+    type NST = U8;
+    type PK = derived::PKSize<Self>;
+    type SK = derived::SKSize<Self>;
+    type LHATBYTES = derived::LHatBytes<Self>;
+    type KBLENGTH = derived::KBLength<Self>;
+    type PRODRUN128 = derived::ProdRun128<Self>;
+    type PRODRUN128Bytes = derived::ProdRun128Bytes<Self>;
+    type LAMBDALBYTESLAMBDA = derived::LambdaLBytesLambda<Self>;
+    type QUOTLENC8 = derived::LEncBytes<Self>;
+    type LAMBDAL = derived::LambdaL<Self>;
+    type LAMBDAR1BYTE = derived::LambdaR1Bytes<Self>;
+
+    const WITNESS_KEY_SCHEDULE_BITS: usize = 0;
+    const WITNESS_ENCRYPTION_BITS: usize = (Self::R::USIZE - 1) * Self::NST::USIZE * 32;
+
+    fn evaluate_owf(
+        key: &GenericArray<u8, Self::LAMBDABYTES>,
+        input: &GenericArray<u8, Self::InputSize>,
+        output: &mut GenericArray<u8, Self::InputSize>,
+    ) {
+        em_evaluate::<Rijndael256, _>(key, input, output);
+    }
+
+    #[inline]
+    fn extendwitness(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<Box<GenericArray<u8, Self::LBYTES>>> {
+        em_extendedwitness::<Self>(owf_key, owf_input)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn find_zero_sbox_input(
+        owf_key: &GenericArray<u8, Self::LAMBDABYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+    ) -> Option<KeyRejected> {
+        em_find_zero_sbox_input::<Self>(owf_key, owf_input)
+    }
+
+    #[inline]
+    fn constraint_values(
+        w: &GenericArray<u8, Self::LBYTES>,
+        owf_input: &GenericArray<u8, Self::InputSize>,
+        owf_output: &GenericArray<u8, Self::InputSize>,
+    ) -> Vec<(Field<Self>, Field<Self>)> {
+        em_constraint_values::<Self>(w, owf_input, owf_output)
+    }
+
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    #[inline]
+    fn prove(
+        w: &GenericArray<u8, Self::LBYTES>,
+        u: &GenericArray<u8, Self::LAMBDALBYTES>,
+        gv: &GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        pk: &PublicKey<Self>,
+        chall: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+    ) -> QSProof<Self> {
+        em_prove::<Self>(w, u, gv, pk, chall)
+    }
+
+    #[cfg(feature = "_variant")]
+    #[inline]
+    fn verify<P>(
+        d: &GenericArray<u8, Self::LBYTES>,
+        gq: &mut GenericArray<GenericArray<u8, Self::LAMBDALBYTES>, Self::LAMBDA>,
+        a_t: &GenericArray<u8, Self::LAMBDABYTES>,
+        chall2: &GenericArray<u8, <Self::BaseParams as BaseParameters>::Chall>,
+        chall3: &GenericArray<u8, Self::LAMBDABYTES>,
+        pk: &PublicKey<Self>,
+    ) -> GenericArray<u8, Self::LAMBDABYTES>
+    where
+        P: FAESTParameters<OWF = Self>,
+    {
+        em_verify::<Self, P>(d, gq, a_t, chall2, chall3, pk)
+    }
+}
+
+pub trait TauParameters {
+    type Tau: ArrayLength;
+    type K0: ArrayLength;
+    type K1: ArrayLength;
+    type Tau0: ArrayLength;
+    type Tau1: ArrayLength;
+
+    /// Number of challenge bytes read by [`Self::decode_challenge`] for all
+    /// `τ` indices
+    ///
+    /// For consistent parameters, this is `λ / 8`.
+    const CHALLENGE_BYTES_NEEDED: usize =
+        (Self::K0::USIZE * Self::Tau0::USIZE + Self::K1::USIZE * Self::Tau1::USIZE).div_ceil(8);
+
+    fn decode_challenge(chal: &[u8], i: usize) -> Vec<u8> {
+        Self::decode_challenge_as_iter(chal, i).collect()
+    }
+
+    /// The `k_i` bits of the challenge selecting the hidden leaf of tree `i`
+    fn decode_challenge_as_iter(chal: &[u8], i: usize) -> impl ExactSizeIterator<Item = u8> + '_ {
+        debug_assert!(i < Self::Tau0::USIZE + Self::Tau1::USIZE);
+        let (lo, size) = Self::convert_index_and_size(i);
+        (lo..lo + size).map(move |j| witness_bit_at(chal, j))
+    }
+
+    /// Encode the hidden leaves `indices` of all `τ` trees into a challenge
+    ///
+    /// This is the inverse of [`Self::decode_challenge_as_iter`], where the
+    /// index of tree `i` is encoded in `k_i` bits, least significant bit
+    /// first. Returns `None` if the number of indices is not `τ` or if an
+    /// index does not fit into the `k_i` bits of its tree.
+    fn encode_challenge(indices: &[u16]) -> Option<Vec<u8>> {
+        if indices.len() != Self::Tau::USIZE {
+            return None;
+        }
+
+        let mut chal = vec![0; Self::CHALLENGE_BYTES_NEEDED];
+        for (i, index) in indices.iter().copied().enumerate() {
+            let (lo, size) = Self::convert_index_and_size(i);
+            if usize::from(index) >> size != 0 {
+                return None;
+            }
+            for j in 0..size {
+                let bit = u8::from((index >> j) & 1 == 1);
+                chal[(lo + j) / 8] |= bit << ((lo + j) % 8);
+            }
+        }
+        Some(chal)
+    }
+
+    fn convert_index(i: usize) -> usize {
+        if i < Self::Tau0::USIZE {
+            Self::K0::USIZE * i
+        } else {
+            Self::Tau0::USIZE * Self::K0::USIZE + Self::K1::USIZE * (i - Self::Tau0::USIZE)
+        }
+    }
+
+    fn convert_index_and_size(i: usize) -> (usize, usize) {
+        if i < Self::Tau0::USIZE {
+            (Self::K0::USIZE * i, Self::K0::USIZE)
+        } else {
+            (
+                Self::Tau0::USIZE * Self::K0::USIZE + Self::K1::USIZE * (i - Self::Tau0::USIZE),
+                Self::K1::USIZE,
+            )
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(
+        feature = "faest-128s",
+        feature = "faest-em-128s",
+        feature = "blake3-oracle"
+    )),
+    allow(dead_code)
+)]
+pub(crate) struct Tau128Small;
+
+impl TauParameters for Tau128Small {
+    type Tau = U11;
+    type K0 = U12;
+    type K1 = U11;
+    type Tau0 = U7;
+    type Tau1 = U4;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(
+        feature = "faest-128f",
+        feature = "faest-em-128f",
+        feature = "blake3-oracle"
+    )),
+    allow(dead_code)
+)]
+pub(crate) struct Tau128Fast;
+
+impl TauParameters for Tau128Fast {
+    type Tau = U16;
+    type K0 = U8;
+    type K1 = U8;
+    type Tau0 = U8;
+    type Tau1 = U8;
+}
+
+/// Experimental 128-bit configuration trading signing and verification time
+/// for smaller signatures
+///
+/// Each of the 8 rounds opens one of 2^16 leaves.
+#[cfg(feature = "experimental")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Tau128UltraSmall;
+
+#[cfg(feature = "experimental")]
+impl TauParameters for Tau128UltraSmall {
+    type Tau = U8;
+    type K0 = U16;
+    type K1 = U16;
+    type Tau0 = U4;
+    type Tau1 = U4;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(feature = "faest-192s", feature = "faest-em-192s")),
+    allow(dead_code)
+)]
+pub(crate) struct Tau192Small;
+
+impl TauParameters for Tau192Small {
+    type Tau = U16;
+    type K0 = U12;
+    type K1 = U12;
+    type Tau0 = U8;
+    type Tau1 = U8;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(feature = "faest-192f", feature = "faest-em-192f")),
+    allow(dead_code)
+)]
+pub(crate) struct Tau192Fast;
+
+impl TauParameters for Tau192Fast {
+    type Tau = U24;
+    type K0 = U8;
+    type K1 = U8;
+    type Tau0 = U12;
+    type Tau1 = U12;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(feature = "faest-256s", feature = "faest-em-256s")),
+    allow(dead_code)
+)]
+pub(crate) struct Tau256Small;
+
+impl TauParameters for Tau256Small {
+    type Tau = U22;
+    type K0 = U12;
+    type K1 = U11;
+    type Tau0 = U14;
+    type Tau1 = U8;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(feature = "faest-256f", feature = "faest-em-256f")),
+    allow(dead_code)
+)]
+pub(crate) struct Tau256Fast;
+
+impl TauParameters for Tau256Fast {
+    type Tau = U32;
+    type K0 = U8;
+    type K1 = U8;
+    type Tau0 = U16;
+    type Tau1 = U16;
+}
+
+// The challenges consist of λ bits.
+const _: () = assert!(<Tau128Small as TauParameters>::CHALLENGE_BYTES_NEEDED == 16);
+const _: () = assert!(<Tau128Fast as TauParameters>::CHALLENGE_BYTES_NEEDED == 16);
+#[cfg(feature = "experimental")]
+const _: () = assert!(<Tau128UltraSmall as TauParameters>::CHALLENGE_BYTES_NEEDED == 16);
+const _: () = assert!(<Tau192Small as TauParameters>::CHALLENGE_BYTES_NEEDED == 24);
+const _: () = assert!(<Tau192Fast as TauParameters>::CHALLENGE_BYTES_NEEDED == 24);
+const _: () = assert!(<Tau256Small as TauParameters>::CHALLENGE_BYTES_NEEDED == 32);
+const _: () = assert!(<Tau256Fast as TauParameters>::CHALLENGE_BYTES_NEEDED == 32);
+
+pub(crate) trait FAESTParameters {
+    type OWF: OWFParameters;
+    type Tau: TauParameters;
+
+    type N0: ArrayLength;
+    type N1: ArrayLength;
+    type POWK0: ArrayLength;
+    type POWK1: ArrayLength;
+    /// Size of the signature (in bytes)
+    type SignatureSize: ArrayLength;
+
+    /// Size of the signature (in bytes) as constant
+    const SIGNATURE_SIZE: usize = Self::SignatureSize::USIZE;
+
+    /// Compile-time check of the consistency of the parameters
+    ///
+    /// Evaluating this constant fails to compile if [`validate`] rejects the
+    /// parameters. It is evaluated for all parameter sets of the crate after
+    /// their definitions.
+    const CHECK: () = if let Err(msg) = validate::<Self>() {
+        panic!("{}", msg)
+    };
+
+    /// Estimate the cycles of signing and verification from the costs of the
+    /// basic operations
+    ///
+    /// With `n = τ0·N0 + τ1·N1` leaves in total, `b = ⌈λ/128⌉` and
+    /// `ℓ̂ = 8·LHATBYTES`, the model counts
+    ///
+    /// - `(n - τ)·2b` AES blocks to expand the inner nodes of the GGM trees
+    ///   and `⌈ℓ̂/128⌉` AES blocks per expanded leaf, where the verifier does
+    ///   not expand the `τ` hidden leaves,
+    /// - one SHAKE block per leaf commitment and `⌈n·2λ/8 / r⌉` blocks to
+    ///   hash the commitments, where `r` is the rate of SHAKE128 (`λ = 128`)
+    ///   or SHAKE256,
+    /// - `λ·⌈ℓ̂/λ⌉` field multiplications for the VOLE hash and 3 (signing)
+    ///   or 2 (verification) per S-box for QuickSilver, where the number of
+    ///   S-boxes is approximated by the witness size in bytes.
+    ///
+    /// All other operations, e.g., the OWF evaluation and the hashing of the
+    /// message, are ignored. This is a rough model, not a measurement.
+    #[cfg(feature = "_variant")]
+    fn estimate_cycles(costs: &CycleCosts) -> (u64, u64) {
+        let lambda = <Self::OWF as OWFParameters>::LAMBDA::U64;
+        let tau = <Self::Tau as TauParameters>::Tau::U64;
+        let leaves = <Self::Tau as TauParameters>::Tau0::U64 * Self::N0::U64
+            + <Self::Tau as TauParameters>::Tau1::U64 * Self::N1::U64;
+        let l_hat = 8 * <Self::OWF as OWFParameters>::LHATBYTES::U64;
+        let sboxes = <Self::OWF as OWFParameters>::LBYTES::U64;
+        let rate = if lambda == 128 { 168 } else { 136 };
+
+        let node_blocks = (leaves - tau) * 2 * lambda.div_ceil(128);
+        let leaf_blocks = l_hat.div_ceil(128);
+        let shake_blocks = leaves + (leaves * 2 * lambda / 8).div_ceil(rate);
+        let vole_hash_muls = lambda * l_hat.div_ceil(lambda);
+
+        let sign = (node_blocks + leaves * leaf_blocks) * costs.aes_block
+            + shake_blocks * costs.shake_block
+            + (vole_hash_muls + 3 * sboxes) * costs.field_mul;
+        let verify = (node_blocks + (leaves - tau) * leaf_blocks) * costs.aes_block
+            + shake_blocks * costs.shake_block
+            + (vole_hash_muls + 2 * sboxes) * costs.field_mul;
+        (sign, verify)
+    }
+}
+
+/// Check the consistency of the `τ` split with the remaining parameters
+///
+/// This checks that `τ0 + τ1 = τ`, that `k0 * τ0 + k1 * τ1 = λ`, that
+/// `N0 = 2^k0` and `N1 = 2^k1`, and that the sizes of the trees match `N0` and
+/// `N1`, and that the signature size matches the size determined by the other
+/// parameters. Note that `λ` is taken from the OWF.
+pub(crate) const fn validate<P>() -> Result<(), &'static str>
+where
+    P: FAESTParameters + ?Sized,
+{
+    let tau = <P::Tau as TauParameters>::Tau::USIZE;
+    let tau0 = <P::Tau as TauParameters>::Tau0::USIZE;
+    let tau1 = <P::Tau as TauParameters>::Tau1::USIZE;
+    let k0 = <P::Tau as TauParameters>::K0::USIZE;
+    let k1 = <P::Tau as TauParameters>::K1::USIZE;
+
+    if tau0 + tau1 != tau {
+        return Err("tau0 + tau1 != tau");
+    }
+    if k0 * tau0 + k1 * tau1 != <P::OWF as OWFParameters>::LAMBDA::USIZE {
+        return Err("k0 * tau0 + k1 * tau1 != lambda");
+    }
+    if k0 >= usize::BITS as usize || P::N0::USIZE != 1 << k0 {
+        return Err("N0 != 2^k0");
+    }
+    if k1 >= usize::BITS as usize || P::N1::USIZE != 1 << k1 {
+        return Err("N1 != 2^k1");
+    }
+    if P::POWK0::USIZE != 2 * P::N0::USIZE - 1 {
+        return Err("POWK0 != 2 * N0 - 1");
+    }
+    if P::POWK1::USIZE != 2 * P::N1::USIZE - 1 {
+        return Err("POWK1 != 2 * N1 - 1");
+    }
+    if let Err(msg) = derived::validate_owf::<P::OWF>() {
+        return Err(msg);
+    }
+    if P::SIGNATURE_SIZE != signature_size::<P>() {
+        return Err("SignatureSize does not match the parameters");
+    }
+    Ok(())
+}
+
+/// Size of the signature (in bytes) as determined by the other parameters
+///
+/// The signature consists of the `τ - 1` corrections of the VOLE commitment,
+/// the hash of `u`, the masked witness `d`, `a~`, the `τ` openings of the
+/// vector commitments, `chall3` and the IV. Each opening contains `k0` or `k1`
+/// seeds and a commitment of `2λ` bits.
+const fn signature_size<P>() -> usize
+where
+    P: FAESTParameters + ?Sized,
+{
+    let tau = <P::Tau as TauParameters>::Tau::USIZE;
+    let lambda_bytes = <P::OWF as OWFParameters>::LAMBDABYTES::USIZE;
+
+    (tau - 1) * <P::OWF as OWFParameters>::LHATBYTES::USIZE
+        + lambda_bytes
+        + B::USIZE
+        + <P::OWF as OWFParameters>::LBYTES::USIZE
+        + lambda_bytes
+        + decommitment_path_size::<P>()
+        + commitment_size::<P>()
+        + lambda_bytes
+        + IVSize::USIZE
+}
+
+/// Size of the decommitment paths of the `τ` vector commitments (in bytes)
+///
+/// The path of a tree with `N0 = 2^k0` or `N1 = 2^k1` leaves consists of the
+/// `k0` or `k1` seeds of `λ` bits that are siblings of the nodes on the path
+/// to the hidden leaf.
+pub(crate) const fn decommitment_path_size<P>() -> usize
+where
+    P: FAESTParameters + ?Sized,
+{
+    let k0 = P::N0::USIZE.trailing_zeros() as usize;
+    let k1 = P::N1::USIZE.trailing_zeros() as usize;
+
+    (<P::Tau as TauParameters>::Tau0::USIZE * k0 + <P::Tau as TauParameters>::Tau1::USIZE * k1)
+        * <P::OWF as OWFParameters>::LAMBDABYTES::USIZE
+}
+
+/// Size of the commitments to the hidden leaves of the `τ` vector commitments
+/// (in bytes)
+///
+/// Each commitment consists of `2λ` bits.
+pub(crate) const fn commitment_size<P>() -> usize
+where
+    P: FAESTParameters + ?Sized,
+{
+    <P::Tau as TauParameters>::Tau::USIZE
+        * <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::LambdaBytesTimes2::USIZE
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(feature = "faest-128s", feature = "blake3-oracle")),
+    allow(dead_code)
+)]
+pub(crate) struct FAEST128sParameters;
+
+impl FAESTParameters for FAEST128sParameters {
+    type OWF = OWF128;
+    type Tau = Tau128Small;
+
+    type N0 = U4096;
+    type POWK0 = Diff<U8192, U1>;
+    type N1 = U2048;
+    type POWK1 = Diff<U4096, U1>;
+
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(feature = "faest-128f", feature = "blake3-oracle")),
+    allow(dead_code)
+)]
+pub(crate) struct FAEST128fParameters;
+
+impl FAESTParameters for FAEST128fParameters {
+    type OWF = OWF128;
+    type Tau = Tau128Fast;
+
+    type N0 = U256;
+    type POWK0 = U511;
+    type N1 = U256;
+    type POWK1 = U511;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+/// Non-standard variant of FAEST-128s using BLAKE3
+#[cfg(feature = "blake3-oracle")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FAEST128sBlake3Parameters;
+
+#[cfg(feature = "blake3-oracle")]
+impl FAESTParameters for FAEST128sBlake3Parameters {
+    type OWF = OWF128<BaseParamsBlake3_128>;
+    type Tau = <FAEST128sParameters as FAESTParameters>::Tau;
+
+    type N0 = <FAEST128sParameters as FAESTParameters>::N0;
+    type POWK0 = <FAEST128sParameters as FAESTParameters>::POWK0;
+    type N1 = <FAEST128sParameters as FAESTParameters>::N1;
+    type POWK1 = <FAEST128sParameters as FAESTParameters>::POWK1;
+    type SignatureSize = <FAEST128sParameters as FAESTParameters>::SignatureSize;
+}
+
+/// Non-standard variant of FAEST-128f using BLAKE3
+#[cfg(feature = "blake3-oracle")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FAEST128fBlake3Parameters;
+
+#[cfg(feature = "blake3-oracle")]
+impl FAESTParameters for FAEST128fBlake3Parameters {
+    type OWF = OWF128<BaseParamsBlake3_128>;
+    type Tau = <FAEST128fParameters as FAESTParameters>::Tau;
+
+    type N0 = <FAEST128fParameters as FAESTParameters>::N0;
+    type POWK0 = <FAEST128fParameters as FAESTParameters>::POWK0;
+    type N1 = <FAEST128fParameters as FAESTParameters>::N1;
+    type POWK1 = <FAEST128fParameters as FAESTParameters>::POWK1;
+    type SignatureSize = <FAEST128fParameters as FAESTParameters>::SignatureSize;
+}
+
+/// Experimental variant of FAEST-128 with smaller signatures
+///
+/// This parameter set is not part of the FAEST specification.
+#[cfg(feature = "experimental")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FAEST128usParameters;
+
+#[cfg(feature = "experimental")]
+impl FAESTParameters for FAEST128usParameters {
+    type OWF = OWF128;
+    type Tau = Tau128UltraSmall;
+
+    type N0 = U65536;
+    type POWK0 = Diff<U131072, U1>;
+    type N1 = U65536;
+    type POWK1 = Diff<U131072, U1>;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+// The whole point of this parameter set is to beat FAEST-128s.
+#[cfg(feature = "experimental")]
+const _: () = assert!(
+    <FAEST128usParameters as FAESTParameters>::SIGNATURE_SIZE
+        < <FAEST128sParameters as FAESTParameters>::SIGNATURE_SIZE
+);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "faest-192s"), allow(dead_code))]
+pub(crate) struct FAEST192sParameters;
+
+impl FAESTParameters for FAEST192sParameters {
+    type OWF = OWF192;
+    type Tau = Tau192Small;
+
+    type N0 = U4096;
+    type POWK0 = Diff<U8192, U1>;
+    type N1 = U4096;
+    type POWK1 = Diff<U8192, U1>;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "faest-192f"), allow(dead_code))]
+pub(crate) struct FAEST192fParameters;
+
+impl FAESTParameters for FAEST192fParameters {
+    type OWF = OWF192;
+    type Tau = Tau192Fast;
+
+    type N0 = U256;
+    type POWK0 = U511;
+    type N1 = U256;
+    type POWK1 = U511;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "faest-256s"), allow(dead_code))]
+pub(crate) struct FAEST256sParameters;
+
+impl FAESTParameters for FAEST256sParameters {
+    type OWF = OWF256;
+    type Tau = Tau256Small;
+
+    type N0 = U4096;
+    type POWK0 = Diff<U8192, U1>;
+    type N1 = U2048;
+    type POWK1 = Diff<U4096, U1>;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "faest-256f"), allow(dead_code))]
+pub(crate) struct FAEST256fParameters;
+
+impl FAESTParameters for FAEST256fParameters {
+    type OWF = OWF256;
+    type Tau = Tau256Fast;
+
+    type N0 = U256;
+    type POWK0 = U511;
+    type N1 = U256;
+    type POWK1 = U511;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "faest-em-128s"), allow(dead_code))]
+pub(crate) struct FAESTEM128sParameters;
+
+impl FAESTParameters for FAESTEM128sParameters {
+    type OWF = OWF128EM;
+    type Tau = Tau128Small;
+
+    type N0 = U4096;
+    type POWK0 = Diff<U8192, U1>;
+    type N1 = U2048;
+    type POWK1 = Diff<U4096, U1>;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "faest-em-128f"), allow(dead_code))]
+pub(crate) struct FAESTEM128fParameters;
+
+impl FAESTParameters for FAESTEM128fParameters {
+    type OWF = OWF128EM;
+    type Tau = Tau128Fast;
+
+    type N0 = U256;
+    type POWK0 = U511;
+    type N1 = U256;
+    type POWK1 = U511;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "faest-em-192s"), allow(dead_code))]
+pub(crate) struct FAESTEM192sParameters;
+
+impl FAESTParameters for FAESTEM192sParameters {
+    type OWF = OWF192EM;
+    type Tau = Tau192Small;
+
+    type N0 = U4096;
+    type POWK0 = Diff<U8192, U1>;
+    type N1 = U4096;
+    type POWK1 = Diff<U8192, U1>;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "faest-em-192f"), allow(dead_code))]
+pub(crate) struct FAESTEM192fParameters;
+
+impl FAESTParameters for FAESTEM192fParameters {
+    type OWF = OWF192EM;
+    type Tau = Tau192Fast;
+
+    type N0 = U256;
+    type POWK0 = U511;
+    type N1 = U256;
+    type POWK1 = U511;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "faest-em-256s"), allow(dead_code))]
+pub(crate) struct FAESTEM256sParameters;
+
+impl FAESTParameters for FAESTEM256sParameters {
+    type OWF = OWF256EM;
+    type Tau = Tau256Small;
+
+    type N0 = U4096;
+    type POWK0 = Diff<U8192, U1>;
+    type N1 = U2048;
+    type POWK1 = Diff<U4096, U1>;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "faest-em-256f"), allow(dead_code))]
+pub(crate) struct FAESTEM256fParameters;
+
+impl FAESTParameters for FAESTEM256fParameters {
+    type OWF = OWF256EM;
+    type Tau = Tau256Fast;
+
+    type N0 = U256;
+    type POWK0 = U511;
+    type N1 = U256;
+    type POWK1 = U511;
+    type SignatureSize = derived::SignatureSize<Self>;
+}
+
+// All parameter sets are consistent.
+const _: () = <FAEST128sParameters as FAESTParameters>::CHECK;
+const _: () = <FAEST128fParameters as FAESTParameters>::CHECK;
+#[cfg(feature = "blake3-oracle")]
+const _: () = <FAEST128sBlake3Parameters as FAESTParameters>::CHECK;
+#[cfg(feature = "blake3-oracle")]
+const _: () = <FAEST128fBlake3Parameters as FAESTParameters>::CHECK;
+#[cfg(feature = "experimental")]
+const _: () = <FAEST128usParameters as FAESTParameters>::CHECK;
+const _: () = <FAEST192sParameters as FAESTParameters>::CHECK;
+const _: () = <FAEST192fParameters as FAESTParameters>::CHECK;
+const _: () = <FAEST256sParameters as FAESTParameters>::CHECK;
+const _: () = <FAEST256fParameters as FAESTParameters>::CHECK;
+const _: () = <FAESTEM128sParameters as FAESTParameters>::CHECK;
+const _: () = <FAESTEM128fParameters as FAESTParameters>::CHECK;
+const _: () = <FAESTEM192sParameters as FAESTParameters>::CHECK;
+const _: () = <FAESTEM192fParameters as FAESTParameters>::CHECK;
+const _: () = <FAESTEM256sParameters as FAESTParameters>::CHECK;
+const _: () = <FAESTEM256fParameters as FAESTParameters>::CHECK;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use serde::Deserialize;
+
+    use crate::utils::test::{read_block_cipher_kats, read_test_data};
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct DataChalDec {
+        chal: Vec<u8>,
+        i: [usize; 1],
+        k0: [usize; 1],
+        res: Vec<u8>,
+    }
+
+    struct TauInconsistent;
+
+    impl TauParameters for TauInconsistent {
+        type Tau = U10;
+        type K0 = U12;
+        type K1 = U11;
+        type Tau0 = U7;
+        // Tau128Small uses 4, so k0 * tau0 + k1 * tau1 = 117 != 128
+        type Tau1 = U3;
+    }
+
+    struct TauMissingRepetition;
+
+    impl TauParameters for TauMissingRepetition {
+        type Tau = U11;
+        type K0 = U12;
+        type K1 = U11;
+        type Tau0 = U7;
+        type Tau1 = U3;
+    }
+
+    struct FAESTMissingRepetition;
+
+    impl FAESTParameters for FAESTMissingRepetition {
+        type OWF = OWF128;
+        type Tau = TauMissingRepetition;
+
+        type N0 = U4096;
+        type POWK0 = Diff<U8192, U1>;
+        type N1 = U2048;
+        type POWK1 = Diff<U4096, U1>;
+        type SignatureSize = U0;
+    }
+
+    struct FAESTInconsistent;
+
+    impl FAESTParameters for FAESTInconsistent {
+        type OWF = OWF128;
+        type Tau = TauInconsistent;
+
+        type N0 = U4096;
+        type POWK0 = Diff<U8192, U1>;
+        type N1 = U2048;
+        type POWK1 = Diff<U4096, U1>;
+        type SignatureSize = U0;
+    }
+
+    struct FAESTMismatchedN0;
+
+    impl FAESTParameters for FAESTMismatchedN0 {
+        type OWF = OWF128;
+        type Tau = Tau128Small;
+
+        type N0 = U2048;
+        type POWK0 = Diff<U4096, U1>;
+        type N1 = U2048;
+        type POWK1 = Diff<U4096, U1>;
+        type SignatureSize = U0;
+    }
+
+    struct FAESTWrongSignatureSize;
+
+    impl FAESTParameters for FAESTWrongSignatureSize {
+        type OWF = OWF128;
+        type Tau = Tau128Small;
+
+        type N0 = U4096;
+        type POWK0 = Diff<U8192, U1>;
+        type N1 = U2048;
+        type POWK1 = Diff<U4096, U1>;
+        type SignatureSize = U0;
+    }
+
+    #[test]
+    fn validate_parameters() {
+        assert_eq!(validate::<FAEST128fParameters>(), Ok(()));
+        assert_eq!(validate::<FAEST128sParameters>(), Ok(()));
+        assert_eq!(validate::<FAEST192fParameters>(), Ok(()));
+        assert_eq!(validate::<FAEST192sParameters>(), Ok(()));
+        assert_eq!(validate::<FAEST256fParameters>(), Ok(()));
+        assert_eq!(validate::<FAEST256sParameters>(), Ok(()));
+        assert_eq!(validate::<FAESTEM128fParameters>(), Ok(()));
+        assert_eq!(validate::<FAESTEM128sParameters>(), Ok(()));
+        assert_eq!(validate::<FAESTEM192fParameters>(), Ok(()));
+        assert_eq!(validate::<FAESTEM192sParameters>(), Ok(()));
+        assert_eq!(validate::<FAESTEM256fParameters>(), Ok(()));
+        assert_eq!(validate::<FAESTEM256sParameters>(), Ok(()));
+        #[cfg(feature = "blake3-oracle")]
+        {
+            assert_eq!(validate::<FAEST128fBlake3Parameters>(), Ok(()));
+            assert_eq!(validate::<FAEST128sBlake3Parameters>(), Ok(()));
+        }
+        #[cfg(feature = "experimental")]
+        assert_eq!(validate::<FAEST128usParameters>(), Ok(()));
+
+        assert_eq!(
+            validate::<FAESTInconsistent>(),
+            Err("k0 * tau0 + k1 * tau1 != lambda")
+        );
+        assert_eq!(
+            validate::<FAESTMissingRepetition>(),
+            Err("tau0 + tau1 != tau")
+        );
+        assert_eq!(validate::<FAESTMismatchedN0>(), Err("N0 != 2^k0"));
+        assert_eq!(
+            validate::<FAESTWrongSignatureSize>(),
+            Err("SignatureSize does not match the parameters")
+        );
+    }
+
+    #[test]
+    fn signature_sizes() {
+        // sizes as given in the specification
+        assert_eq!(FAEST128fParameters::SIGNATURE_SIZE, 6336);
+        assert_eq!(FAEST128sParameters::SIGNATURE_SIZE, 5006);
+        assert_eq!(FAEST192fParameters::SIGNATURE_SIZE, 16792);
+        assert_eq!(FAEST192sParameters::SIGNATURE_SIZE, 12744);
+        assert_eq!(FAEST256fParameters::SIGNATURE_SIZE, 28400);
+        assert_eq!(FAEST256sParameters::SIGNATURE_SIZE, 22100);
+        assert_eq!(FAESTEM128fParameters::SIGNATURE_SIZE, 5696);
+        assert_eq!(FAESTEM128sParameters::SIGNATURE_SIZE, 4566);
+        assert_eq!(FAESTEM192fParameters::SIGNATURE_SIZE, 13912);
+        assert_eq!(FAESTEM192sParameters::SIGNATURE_SIZE, 10824);
+        assert_eq!(FAESTEM256fParameters::SIGNATURE_SIZE, 26736);
+        assert_eq!(FAESTEM256sParameters::SIGNATURE_SIZE, 20956);
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn vole_output_lengths() {
+        use crate::constants::VOLE_REDUNDANCY_BITS;
+
+        assert_eq!(B::USIZE * 8, VOLE_REDUNDANCY_BITS);
+        // the VOLE hash outputs λ + B bits
+        assert_eq!(
+            BaseParams128::vole_output_length_bytes(),
+            (128 + VOLE_REDUNDANCY_BITS) / 8
+        );
+        assert_eq!(
+            BaseParams192::vole_output_length_bytes(),
+            (192 + VOLE_REDUNDANCY_BITS) / 8
+        );
+        assert_eq!(
+            BaseParams256::vole_output_length_bytes(),
+            (256 + VOLE_REDUNDANCY_BITS) / 8
+        );
+        assert_eq!(BaseParams128::vole_output_length_bytes(), 18);
+        assert_eq!(BaseParams192::vole_output_length_bytes(), 26);
+        assert_eq!(BaseParams256::vole_output_length_bytes(), 34);
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn verify_witness() {
+        fn check<O: OWFParameters>() {
+            let mut rng = rand::thread_rng();
+            let sk = O::keygen_with_rng(&mut rng);
+            let other_pk = O::keygen_with_rng(&mut rng).as_public_key();
+            let w = O::witness(&sk);
+            assert!(O::verify_witness(&sk.pk, &w));
+            assert!(!O::verify_witness(&other_pk, &w));
+
+            // key, key schedule or round states and the last byte
+            for index in [
+                0,
+                O::LAMBDABYTES::USIZE,
+                O::LBYTES::USIZE / 2,
+                O::LBYTES::USIZE - 1,
+            ] {
+                let mut modified = w.clone();
+                modified[index] ^= 1;
+                assert!(!O::verify_witness(&sk.pk, &modified));
+            }
+        }
+
+        check::<OWF128>();
+        check::<OWF192>();
+        check::<OWF256>();
+        check::<OWF128EM>();
+        check::<OWF192EM>();
+        check::<OWF256EM>();
+    }
+
+    #[test]
+    fn owf256em_rijndael_vectors() {
+        // The EM construction encrypts the secret key with the public input as
+        // Rijndael key, i.e., output = Rijndael-256_input(key) ⊕ key.
+        for kat in read_block_cipher_kats("rijndael256_ecb_kat.txt") {
+            let key = GenericArray::from_slice(&kat.plaintext);
+            let input = GenericArray::from_slice(&kat.key);
+            let mut output = GenericArray::default();
+            OWF256EM::evaluate_owf(key, input, &mut output);
+            assert!(zip(output, zip(&kat.ciphertext, key)).all(|(o, (c, k))| o == c ^ k));
+        }
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn circuit_structure() {
+        fn check<O: OWFParameters>(rounds: usize, witness_per_round_bytes: usize) {
+            assert_eq!(O::circuit_depth(), rounds);
+            assert_eq!(O::num_key_words() * 32, O::LAMBDA::USIZE);
+            assert_eq!(O::witness_per_round_bytes(), witness_per_round_bytes);
+        }
+
+        check::<OWF128>(10, 20);
+        check::<OWF192>(12, 34);
+        check::<OWF256>(14, 35);
+        check::<OWF128EM>(10, 16);
+        check::<OWF192EM>(12, 24);
+        check::<OWF256EM>(14, 32);
+    }
+
+    fn decoded_indices<T: TauParameters>(chal: &[u8]) -> Vec<usize> {
+        (0..T::Tau::USIZE)
+            .map(|i| {
+                T::decode_challenge_as_iter(chal, i)
+                    .enumerate()
+                    .map(|(j, bit)| usize::from(bit) << j)
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decoded_indices_in_range() {
+        // every challenge decodes to exactly k_i bits, so even the all-ones
+        // challenge selects an existing leaf
+        fn check<P: FAESTParameters>() {
+            let chal = vec![0xff; <P::OWF as OWFParameters>::LAMBDABYTES::USIZE];
+            for (i, index) in decoded_indices::<P::Tau>(&chal).into_iter().enumerate() {
+                if i < <P::Tau as TauParameters>::Tau0::USIZE {
+                    assert_eq!(index, P::N0::USIZE - 1);
+                } else {
+                    assert_eq!(index, P::N1::USIZE - 1);
+                }
+            }
+        }
+
+        check::<FAEST128sParameters>();
+        check::<FAEST128fParameters>();
+        check::<FAEST192sParameters>();
+        check::<FAEST256sParameters>();
+        check::<FAESTEM128sParameters>();
+        check::<FAESTEM256sParameters>();
+    }
+
+    #[test]
+    fn encode_challenge() {
+        fn check<T: TauParameters>() {
+            use rand::Rng;
+
+            let mut rng = rand::thread_rng();
+            for _ in 0..100 {
+                let indices: Vec<u16> = (0..T::Tau::USIZE)
+                    .map(|i| {
+                        let (_, size) = T::convert_index_and_size(i);
+                        rng.gen_range(0..1 << size)
+                    })
+                    .collect();
+                let chal = T::encode_challenge(&indices).unwrap();
+                assert_eq!(chal.len(), T::CHALLENGE_BYTES_NEEDED);
+                assert!(decoded_indices::<T>(&chal)
+                    .into_iter()
+                    .eq(indices.iter().map(|index| usize::from(*index))));
+            }
+
+            let mut indices = vec![0; T::Tau::USIZE];
+            assert!(T::encode_challenge(&indices[1..]).is_none());
+            indices[0] = 1 << T::K0::USIZE;
+            assert!(T::encode_challenge(&indices).is_none());
+            indices[0] = 0;
+            indices[T::Tau::USIZE - 1] = 1 << T::K1::USIZE;
+            assert!(T::encode_challenge(&indices).is_none());
+        }
+
+        check::<Tau128Small>();
+        check::<Tau128Fast>();
+        check::<Tau192Small>();
+        check::<Tau192Fast>();
+        check::<Tau256Small>();
+        check::<Tau256Fast>();
+    }
+
+    #[test]
+    fn decode_challenge_len() {
+        let chal = [0u8; 16];
+        for i in 0..<Tau128Small as TauParameters>::Tau::USIZE {
+            let iter = Tau128Small::decode_challenge_as_iter(&chal, i);
+            let expected = if i < <Tau128Small as TauParameters>::Tau0::USIZE {
+                <Tau128Small as TauParameters>::K0::USIZE
+            } else {
+                <Tau128Small as TauParameters>::K1::USIZE
+            };
+            assert_eq!(iter.size_hint(), (expected, Some(expected)));
+            assert_eq!(iter.len(), expected);
+        }
+    }
+
+    #[test]
+    fn chaldec() {
+        let database: Vec<DataChalDec> = read_test_data("decode_challenge.json");
+        for data in database {
+            if data.chal.len() == 16 {
+                if data.k0[0] == 12 {
+                    let res = Tau128Small::decode_challenge(&data.chal, data.i[0]);
+                    assert_eq!(res, data.res);
+                } else {
+                    let res = Tau128Fast::decode_challenge(&data.chal, data.i[0]);
+                    assert_eq!(res, data.res);
+                }
+            } else if data.chal.len() == 24 {
+                if data.k0[0] == 12 {
+                    let res = Tau192Small::decode_challenge(&data.chal, data.i[0]);
+                    assert_eq!(res, data.res);
+                } else {
+                    let res = Tau192Fast::decode_challenge(&data.chal, data.i[0]);
+                    assert_eq!(res, data.res);
+                }
+            } else if data.k0[0] == 12 {
+                let res = Tau256Small::decode_challenge(&data.chal, data.i[0]);
+                assert_eq!(res, data.res);
+            } else {
+                let res = Tau256Fast::decode_challenge(&data.chal, data.i[0]);
+                assert_eq!(res, data.res);
+            }
+        }
+    }
+
+    #[generic_tests::define]
+    mod owf_parameters {
+        use super::*;
+
+        use rand_core::RngCore;
+
+        #[test]
+        fn lambda<O: OWFParameters>() {
+            assert!(O::LAMBDA::USIZE == 128 || O::LAMBDA::USIZE == 192 || O::LAMBDA::USIZE == 256);
+            assert_eq!(O::LAMBDABYTES::USIZE * 8, O::LAMBDA::USIZE);
+        }
+
+        #[test]
+        fn pk_sk_size<O: OWFParameters>() {
+            assert_eq!(O::SK::USIZE, O::InputSize::USIZE + O::LAMBDABYTES::USIZE);
+            assert_eq!(O::PK::USIZE, O::InputSize::USIZE + O::InputSize::USIZE);
+        }
+
+        #[test]
+        fn owf_parameters<O: OWFParameters>() {
+            assert_eq!(O::LKE::USIZE % 8, 0);
+            assert_eq!(O::LKEBytes::USIZE * 8, O::LKE::USIZE);
+            assert_eq!(O::LENC::USIZE % 8, 0);
+            assert_eq!(O::L::USIZE % 8, 0);
+            assert_eq!(O::LBYTES::USIZE * 8, O::L::USIZE);
+        }
+
+        #[test]
+        fn derived<O: OWFParameters>() {
+            assert_eq!(derived::validate_owf::<O>(), Ok(()));
+        }
+
+        #[test]
+        fn witness_layout<O: OWFParameters>() {
+            assert_eq!(
+                O::WITNESS_KEY_SCHEDULE_BITS + O::WITNESS_ENCRYPTION_BITS + O::WITNESS_STATE_BITS,
+                O::L::USIZE
+            );
+            assert_eq!(
+                O::WITNESS_BITS_PER_ROUND * (O::R::USIZE - 1),
+                O::WITNESS_ENCRYPTION_BITS
+            );
+            if O::WITNESS_STATE_BITS == 0 {
+                // AES: one or two 128-bit blocks
+                assert_eq!(O::WITNESS_KEY_SCHEDULE_BITS, O::LKE::USIZE);
+                assert_eq!(
+                    O::WITNESS_BITS_PER_ROUND,
+                    if O::LAMBDA::USIZE == 128 { 128 } else { 256 }
+                );
+            } else {
+                // EM: the key is the state
+                assert_eq!(O::WITNESS_KEY_SCHEDULE_BITS, 0);
+                assert_eq!(O::WITNESS_STATE_BITS, O::LAMBDA::USIZE);
+                assert_eq!(O::WITNESS_BITS_PER_ROUND, O::LAMBDA::USIZE);
+            }
+        }
+
+        /// RNG counting the calls of [`RngCore::fill_bytes`]
+        #[cfg(feature = "_variant")]
+        struct CountingRng<R> {
+            rng: R,
+            fills: usize,
+        }
+
+        #[cfg(feature = "_variant")]
+        impl<R> RngCore for CountingRng<R>
+        where
+            R: RngCore,
+        {
+            fn next_u32(&mut self) -> u32 {
+                self.rng.next_u32()
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                self.rng.next_u64()
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.fills += 1;
+                self.rng.fill_bytes(dest);
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "_variant")]
+        #[test]
+        fn keygen_fast_rejection_rate<O: OWFParameters>() {
+            use rand::SeedableRng;
+
+            const KEYS: usize = 300;
+
+            let mut rng = CountingRng {
+                rng: rand_chacha::ChaCha8Rng::seed_from_u64(1234),
+                fills: 0,
+            };
+
+            // the standard key generation samples input and key at once per attempt
+            for _ in 0..KEYS {
+                O::keygen_with_rng(&mut rng);
+            }
+            let attempts_standard = rng.fills;
+
+            // the fast key generation samples the input once per key and the key
+            // once per attempt
+            rng.fills = 0;
+            for _ in 0..KEYS {
+                let sk = O::keygen_fast_with_rng(&mut rng);
+                assert!(O::extendwitness(&sk.owf_key, sk.pk.owf_input()).is_some());
+            }
+            let attempts_fast = rng.fills - KEYS;
+
+            // Both loops reject with the same probability, so the attempts
+            // per key are geometrically distributed with the same success
+            // probability p. p is at least 0.14 for all OWFs (about 7 attempts
+            // for AES-256), hence the average of 300 keys has a relative
+            // standard deviation of sqrt(1 - p) / sqrt(300) < 6% and the
+            // difference of both averages one of less than 8% of the mean.
+            // The tolerance of 25% is more than 3 standard deviations. The
+            // RNG is seeded, so the outcome is deterministic.
+            let avg_standard = attempts_standard as f64 / KEYS as f64;
+            let avg_fast = attempts_fast as f64 / KEYS as f64;
+            assert!(
+                (avg_standard - avg_fast).abs() < 0.25 * avg_standard,
+                "{avg_standard} vs {avg_fast}"
+            );
+        }
+
+        #[cfg(feature = "_variant")]
+        #[test]
+        fn find_zero_sbox_input<O: OWFParameters>() {
+            use rand::{RngCore, SeedableRng};
+
+            // about half or more of the candidates are rejected, but at least
+            // 14% are accepted, so both outcomes are covered
+            let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(4321);
+            let mut rejected = 0;
+            for _ in 0..200 {
+                let mut owf_key = GenericArray::default();
+                let mut owf_input = GenericArray::default();
+                rng.fill_bytes(&mut owf_key);
+                rng.fill_bytes(&mut owf_input);
+
+                let location = O::find_zero_sbox_input(&owf_key, &owf_input);
+                assert_eq!(
+                    location.is_none(),
+                    O::extendwitness(&owf_key, &owf_input).is_some()
+                );
+                if location.is_some() {
+                    rejected += 1;
+                }
+            }
+            assert!(rejected > 0 && rejected < 200);
+
+            let sk = O::keygen_with_rng(&mut rng);
+            let mut owf_input = sk.pk.owf_input().clone();
+            // zero input of the first S-box of the encryption
+            owf_input[0] = sk.owf_key[0];
+            assert_eq!(
+                O::find_zero_sbox_input(&sk.owf_key, &owf_input),
+                Some(KeyRejected::Encryption { block: 0, round: 1 })
+            );
+            if O::NST::USIZE == 0 {
+                // the key schedule of AES is applied to the key
+                let mut owf_key = sk.owf_key.clone();
+                owf_key[O::LAMBDABYTES::USIZE - 1] = 0;
+                assert_eq!(
+                    O::find_zero_sbox_input(&owf_key, sk.pk.owf_input()),
+                    Some(KeyRejected::KeySchedule {
+                        round: O::NK::USIZE / 4
+                    })
+                );
+            }
+        }
+
+        #[cfg(feature = "_variant")]
+        #[test]
+        fn keygen_rejection_rate<O: OWFParameters>() {
+            use rand::SeedableRng;
+
+            const KEYS: u32 = 300;
+
+            let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(5678);
+            let attempts: u32 = (0..KEYS)
+                .map(|_| O::keygen_with_rng_detailed(&mut rng).unwrap().1)
+                .sum();
+            let sk = O::keygen_with_rng(&mut rng);
+            let sboxes =
+                O::constraint_values(&O::witness(&sk), sk.pk.owf_input(), sk.pk.owf_output()).len();
+
+            // A candidate is accepted if none of the n S-box inputs is zero,
+            // i.e., with probability p = (255/256)^n. The number of attempts
+            // per key is geometrically distributed with mean 1/p and the
+            // average of 300 keys has a relative standard deviation of
+            // sqrt(1 - p) / sqrt(300) < 6%. The tolerance of 20% is more than 3
+            // standard deviations. The RNG is seeded, so the outcome is
+            // deterministic.
+            let expected = (256f64 / 255.).powi(sboxes as i32);
+            let average = f64::from(attempts) / f64::from(KEYS);
+            assert!(
+                (average - expected).abs() < 0.2 * expected,
+                "{average} vs {expected}"
+            );
+        }
+
+        #[cfg(feature = "_variant")]
+        #[test]
+        fn keygen_attempts<O: OWFParameters>() {
+            use rand::SeedableRng;
+
+            let mut rng = CountingRng {
+                rng: rand_chacha::ChaCha8Rng::seed_from_u64(1234),
+                fills: 0,
+            };
+            for _ in 0..20 {
+                rng.fills = 0;
+                let (sk, attempts) = O::keygen_with_rng_detailed(&mut rng).unwrap();
+                assert_eq!(attempts as usize, rng.fills);
+                assert!(O::extendwitness(&sk.owf_key, sk.pk.owf_input()).is_some());
+            }
+        }
+
+        #[cfg(feature = "_variant")]
+        #[test]
+        fn keygen_stuck_rng<O: OWFParameters>() {
+            // all-zero keys and inputs are always rejected; use a smaller
+            // bound than MAX_KEYGEN_ATTEMPTS to keep the test fast
+            let mut rng = CountingRng {
+                rng: rand::rngs::mock::StepRng::new(0, 0),
+                fills: 0,
+            };
+            assert!(keygen_bounded::<O>(&mut rng, 64).is_err());
+            assert_eq!(rng.fills, 64);
+        }
+
+        #[test]
+        fn evaluate_owf_multi<O: OWFParameters>() {
+            let mut rng = rand::thread_rng();
+            let mut key = GenericArray::<u8, O::LAMBDABYTES>::default();
+            rng.fill_bytes(&mut key);
+            let inputs: Vec<GenericArray<u8, O::InputSize>> = (0..32)
+                .map(|_| {
+                    let mut input = GenericArray::default();
+                    rng.fill_bytes(&mut input);
+                    input
+                })
+                .collect();
+
+            // also covers batches that do not fill all lanes
+            for n in [0, 1, 7, 8, 9, 32] {
+                let mut outputs = vec![GenericArray::default(); n];
+                O::evaluate_owf_multi(&key, &inputs[..n], &mut outputs);
+                for (input, output) in zip(&inputs[..n], &outputs) {
+                    assert_eq!(output, &O::evaluate(&key, input));
+                }
+            }
+        }
+
+        #[test]
+        fn evaluate_owf_ct<O: OWFParameters>() {
+            let mut rng = rand::thread_rng();
+            for _ in 0..16 {
+                let mut key = GenericArray::<u8, O::LAMBDABYTES>::default();
+                let mut input = GenericArray::<u8, O::InputSize>::default();
+                rng.fill_bytes(&mut key);
+                rng.fill_bytes(&mut input);
+
+                let mut output = GenericArray::<u8, O::InputSize>::default();
+                let mut output_ct = GenericArray::<u8, O::InputSize>::default();
+                O::evaluate_owf(&key, &input, &mut output);
+                O::evaluate_owf_ct(&key, &input, &mut output_ct);
+                assert_eq!(output, output_ct);
+            }
+        }
+
+        #[cfg(feature = "_variant")]
+        #[test]
+        fn constraint_values<O: OWFParameters>() {
+            let sk = O::keygen_with_rng(rand::thread_rng());
+            let w = O::witness(&sk);
+            let values = O::constraint_values(&w, sk.pk.owf_input(), sk.pk.owf_output());
+            assert!(!values.is_empty());
+            assert!(values
+                .iter()
+                .all(|(left, right)| *left * right == Field::<O>::ONE));
+        }
+
+        #[instantiate_tests(<OWF128>)]
+        mod owf_128 {}
+
+        #[instantiate_tests(<OWF192>)]
+        mod owf_192 {}
+
+        #[instantiate_tests(<OWF256>)]
+        mod owf_256 {}
+
+        #[instantiate_tests(<OWF128EM>)]
+        mod owf_em_128 {}
+
+        #[cfg(feature = "blake3-oracle")]
+        #[instantiate_tests(<OWF128<BaseParamsBlake3_128>>)]
+        mod owf_128_blake3 {}
+
+        #[instantiate_tests(<OWF192EM>)]
+        mod owf_em_192 {}
+
+        #[instantiate_tests(<OWF256EM>)]
+        mod owf_em_256 {}
+    }
+
+    #[generic_tests::define]
+    mod faest_parameters {
+        use super::*;
+
+        #[test]
+        fn tau_config<P: FAESTParameters>() {
+            assert_eq!(
+                <P::OWF as OWFParameters>::LAMBDA::USIZE,
+                <P::Tau as TauParameters>::K0::USIZE * <P::Tau as TauParameters>::Tau0::USIZE
+                    + <P::Tau as TauParameters>::K1::USIZE * <P::Tau as TauParameters>::Tau1::USIZE
+            );
+            assert_eq!(
+                <P::Tau as TauParameters>::CHALLENGE_BYTES_NEEDED,
+                <P::OWF as OWFParameters>::LAMBDABYTES::USIZE
+            );
+        }
+
+        #[cfg(feature = "_variant")]
+        #[test]
+        fn decommitment_sizes<P: FAESTParameters>() {
+            let tau = <P::Tau as TauParameters>::Tau::USIZE;
+            let lambda_bytes = <P::OWF as OWFParameters>::LAMBDABYTES::USIZE;
+
+            // τ0 * k0 + τ1 * k1 = λ seeds in total
+            assert_eq!(
+                decommitment_path_size::<P>(),
+                <P::OWF as OWFParameters>::LAMBDA::USIZE * lambda_bytes
+            );
+            assert_eq!(commitment_size::<P>(), tau * 2 * lambda_bytes);
+
+            let corrections = (tau - 1) * <P::OWF as OWFParameters>::LHATBYTES::USIZE;
+            let u_tilde =
+                <<P::OWF as OWFParameters>::BaseParams as BaseParameters>::vole_output_length_bytes(
+                );
+            let d = <P::OWF as OWFParameters>::LBYTES::USIZE;
+            let (a_t, chall3) = (lambda_bytes, lambda_bytes);
+            assert_eq!(
+                corrections
+                    + u_tilde
+                    + d
+                    + a_t
+                    + decommitment_path_size::<P>()
+                    + commitment_size::<P>()
+                    + chall3
+                    + IVSize::USIZE,
+                P::SIGNATURE_SIZE
+            );
+        }
+
+        #[instantiate_tests(<FAEST128fParameters>)]
+        mod faest_128f {}
+
+        #[instantiate_tests(<FAEST128sParameters>)]
+        mod faest_128s {}
+
+        #[instantiate_tests(<FAEST192fParameters>)]
+        mod faest_192f {}
+
+        #[instantiate_tests(<FAEST192sParameters>)]
+        mod faest_192s {}
+
+        #[instantiate_tests(<FAEST256fParameters>)]
+        mod faest_256f {}
+
+        #[instantiate_tests(<FAEST256sParameters>)]
+        mod faest_256s {}
+
+        #[instantiate_tests(<FAESTEM128fParameters>)]
+        mod faest_em_128f {}
+
+        #[instantiate_tests(<FAESTEM128sParameters>)]
+        mod faest_em_128s {}
+
+        #[instantiate_tests(<FAESTEM192fParameters>)]
+        mod faest_em_192f {}
+
+        #[instantiate_tests(<FAESTEM192sParameters>)]
+        mod faest_em_192s {}
+
+        #[instantiate_tests(<FAESTEM256fParameters>)]
+        mod faest_em_256f {}
+
+        #[instantiate_tests(<FAESTEM256sParameters>)]
+        mod faest_em_256s {}
+
+        #[cfg(feature = "blake3-oracle")]
+        #[instantiate_tests(<FAEST128fBlake3Parameters>)]
+        mod faest_128f_blake3 {}
+
+        #[cfg(feature = "blake3-oracle")]
+        #[instantiate_tests(<FAEST128sBlake3Parameters>)]
+        mod faest_128s_blake3 {}
+
+        #[cfg(feature = "experimental")]
+        #[instantiate_tests(<FAEST128usParameters>)]
+        mod faest_128us {}
+    }
+}
@@ -0,0 +1,72 @@
+//! Evaluation of polynomials over `GF(2^λ)`
+
+use crate::fields::BigGaloisField;
+
+/// Evaluate the polynomial with the given coefficients at `point`
+///
+/// The coefficients are given in increasing order of the degree, i.e.,
+/// `coefficients[i]` is the coefficient of `X^i`. The polynomial is evaluated
+/// with Horner's method.
+pub(crate) fn evaluate_polynomial<F>(coefficients: &[F], point: &F) -> F
+where
+    F: BigGaloisField,
+{
+    coefficients
+        .iter()
+        .rev()
+        .fold(F::ZERO, |acc, coefficient| acc * point + coefficient)
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod test {
+    use super::*;
+
+    use std::fmt::Debug;
+
+    use generic_array::typenum::Unsigned;
+    use rand::RngCore;
+
+    use crate::fields::{Field, GF128, GF192, GF256};
+
+    #[test]
+    fn evaluate_quadratic<F: BigGaloisField + Debug + Eq>() {
+        let mut rng = rand::thread_rng();
+        let mut sample = || {
+            let mut buffer = vec![0; <F as Field>::Length::USIZE];
+            rng.fill_bytes(&mut buffer);
+            F::from(buffer.as_slice())
+        };
+        let (c0, c1, c2, x) = (sample(), sample(), sample(), sample());
+
+        assert_eq!(
+            evaluate_polynomial(&[c0, c1, c2], &x),
+            c0 + c1 * x + c2 * x.square()
+        );
+        assert_eq!(evaluate_polynomial(&[c0, c1, c2], &F::ZERO), c0);
+        assert_eq!(evaluate_polynomial(&[c0, c1, c2], &F::ONE), c0 + c1 + c2);
+        assert_eq!(evaluate_polynomial(&[], &x), F::ZERO);
+    }
+
+    #[test]
+    fn evaluate_at_two<F: BigGaloisField + Debug + Eq>() {
+        // the coefficients are the bits of the result
+        let two = F::ONE.double();
+        let bits = <F as Field>::Length::USIZE * 8;
+
+        assert_eq!(evaluate_polynomial(&vec![F::ZERO; bits], &two), F::ZERO);
+        assert_eq!(
+            evaluate_polynomial(&vec![F::ONE; bits], &two),
+            F::from(vec![0xff; <F as Field>::Length::USIZE].as_slice())
+        );
+    }
+
+    #[instantiate_tests(<GF128>)]
+    mod gf128 {}
+
+    #[instantiate_tests(<GF192>)]
+    mod gf192 {}
+
+    #[instantiate_tests(<GF256>)]
+    mod gf256 {}
+}
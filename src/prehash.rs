@@ -0,0 +1,266 @@
+//! Signatures on prehashed messages
+//!
+//! By default, FAEST absorbs the message into the SHAKE-based random oracle of
+//! the proof. Some protocols instead require the message to be hashed with a
+//! standard hash function first, e.g., to match external tooling. The
+//! functions in this module sign the digest of the message, i.e., the message
+//! hash `µ` of the signature is computed on
+//!
+//! `OWF input || OWF output || 0x02 || len(name) || name of the hash || H(msg)`
+//!
+//! where the length is encoded as a single byte. This hash uses a separator
+//! distinct from the one of plain signatures. Hence, a signature on a
+//! prehashed message is never valid for any plain message, including the
+//! digest, or for a prehash with another hash function. The supported hash
+//! functions are SHA3-256 and SHA-256.
+//!
+//! Callers that already hold a 64-byte digest computed by a hash function of
+//! their choice can sign it with [`sign_digest_unsafe_prehash`]. As the hash
+//! function is unknown to this crate, it is not bound by the signature: the
+//! signer and the verifier have to agree on it out of band, and the security
+//! relies on its collision resistance. The digest is hashed into `µ` after
+//! the mode byte `0x03` with the same separator as above. Hence, these
+//! signatures are never valid for any plain message or for a prehash with a
+//! named hash function, and vice versa. Two applications that sign digests of
+//! different hash functions with the same key are not separated, though.
+//!
+//! ```
+//! # #[cfg(feature = "faest-128f")] {
+//! use faest::prehash::{sign_message_with_prehash, verify_with_prehash};
+//! use faest::{FAEST128fSignature, FAEST128fSigningKey, Keypair, KeypairGenerator};
+//! use sha3::Sha3_256;
+//!
+//! let mut rng = rand::thread_rng();
+//! let sk = FAEST128fSigningKey::generate(&mut rng);
+//! let msg = "some message".as_bytes();
+//! let signature: FAEST128fSignature = sign_message_with_prehash::<Sha3_256, _, _>(&sk, msg, &mut rng);
+//! verify_with_prehash::<Sha3_256, _, _>(&sk.verifying_key(), msg, &signature)
+//!     .expect("Verification failed");
+//! # }
+//! ```
+
+use rand_core::CryptoRngCore;
+use sha3::Digest;
+
+use crate::Error;
+use private::{SignPrehashed, VerifyPrehashed};
+
+/// The traits of this module can not be named outside of the crate. Hence,
+/// their methods are only callable by the functions of this module, which
+/// only pass the names of the [`PrehashDigest`] implementations.
+pub(crate) mod private {
+    use rand_core::CryptoRngCore;
+
+    use crate::Error;
+
+    pub trait Sealed {}
+
+    pub trait SignPrehashed<S> {
+        /// Sign `digest` computed with the hash function called `name`
+        ///
+        /// `name` is at most 255 bytes long.
+        fn sign_prehashed(
+            &self,
+            name: &'static [u8],
+            digest: &[u8],
+            rng: &mut dyn CryptoRngCore,
+        ) -> S;
+
+        /// Sign `digest` computed with a hash function chosen by the caller
+        fn sign_external_prehash(&self, digest: &[u8], rng: &mut dyn CryptoRngCore) -> S;
+    }
+
+    pub trait VerifyPrehashed<S> {
+        /// Verify a signature on `digest` computed with the hash function
+        /// called `name`
+        ///
+        /// `name` is at most 255 bytes long.
+        fn verify_prehashed(
+            &self,
+            name: &'static [u8],
+            digest: &[u8],
+            signature: &S,
+        ) -> Result<(), Error>;
+
+        /// Verify a signature on `digest` computed with a hash function chosen
+        /// by the caller
+        fn verify_external_prehash(&self, digest: &[u8], signature: &S) -> Result<(), Error>;
+    }
+}
+
+/// Signing keys that sign prehashed messages
+///
+/// This trait is sealed and implemented by the signing keys of all variants.
+/// Its methods are internal, so a prehashed message can only be signed with
+/// the name of a [`PrehashDigest`]:
+///
+/// ```compile_fail
+/// use faest::{FAEST128fSignature, FAEST128fSigningKey, KeypairGenerator};
+///
+/// let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+/// let signature: FAEST128fSignature =
+///     sk.sign_prehashed(&[0; 256], b"digest", &mut rand::thread_rng());
+/// ```
+pub trait PrehashSigner<S>: private::Sealed + SignPrehashed<S> {}
+
+/// Verification keys that verify signatures on prehashed messages
+///
+/// This trait is sealed and implemented by the verification keys of all
+/// variants.
+pub trait PrehashVerifier<S>: private::Sealed + VerifyPrehashed<S> {}
+
+/// Hash functions supported for prehashing
+///
+/// This trait is sealed and implemented for [`sha3::Sha3_256`] and
+/// [`sha2::Sha256`].
+pub trait PrehashDigest: Digest + private::Sealed {
+    /// Name of the hash function used for domain separation
+    ///
+    /// The name is at most 255 bytes long.
+    const NAME: &'static [u8];
+}
+
+impl private::Sealed for sha3::Sha3_256 {}
+
+impl PrehashDigest for sha3::Sha3_256 {
+    const NAME: &'static [u8] = b"SHA3-256";
+}
+
+impl private::Sealed for sha2::Sha256 {}
+
+impl PrehashDigest for sha2::Sha256 {
+    const NAME: &'static [u8] = b"SHA-256";
+}
+
+/// Sign the digest of `msg` under `H` with randomness from `rng`
+pub fn sign_message_with_prehash<H, K, S>(sk: &K, msg: &[u8], rng: &mut impl CryptoRngCore) -> S
+where
+    H: PrehashDigest,
+    K: PrehashSigner<S>,
+{
+    sk.sign_prehashed(H::NAME, &H::digest(msg), rng)
+}
+
+/// Verify a signature produced by [`sign_message_with_prehash`] with the same
+/// hash function `H`
+pub fn verify_with_prehash<H, V, S>(vk: &V, msg: &[u8], signature: &S) -> Result<(), Error>
+where
+    H: PrehashDigest,
+    V: PrehashVerifier<S>,
+{
+    vk.verify_prehashed(H::NAME, &H::digest(msg), signature)
+}
+
+/// Size of the digests accepted by [`sign_digest_unsafe_prehash`] (in bytes)
+pub const EXTERNAL_DIGEST_SIZE: usize = 64;
+
+/// Sign a `digest` computed by the caller with randomness from `rng`
+///
+/// The hash function is not bound by the signature. The signature is neither
+/// valid for the message nor for the digest as plain message, and it does not
+/// verify with [`verify_with_prehash`].
+pub fn sign_digest_unsafe_prehash<K, S>(
+    sk: &K,
+    digest: &[u8; EXTERNAL_DIGEST_SIZE],
+    rng: &mut impl CryptoRngCore,
+) -> S
+where
+    K: PrehashSigner<S>,
+{
+    sk.sign_external_prehash(digest, rng)
+}
+
+/// Verify a signature produced by [`sign_digest_unsafe_prehash`] on `digest`
+pub fn verify_digest_unsafe_prehash<V, S>(
+    vk: &V,
+    digest: &[u8; EXTERNAL_DIGEST_SIZE],
+    signature: &S,
+) -> Result<(), Error>
+where
+    V: PrehashVerifier<S>,
+{
+    vk.verify_external_prehash(digest, signature)
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod test {
+    use super::*;
+
+    use sha2::Sha256;
+    use sha3::{Sha3_256, Sha3_512};
+
+    #[cfg(feature = "faest-128f")]
+    use crate::{FAEST128fSignature, FAEST128fSigningKey};
+    #[cfg(feature = "faest-em-128s")]
+    use crate::{FAESTEM128sSignature, FAESTEM128sSigningKey};
+    use crate::{KeypairGenerator, Signer, Verifier};
+
+    const TEST_MESSAGE: &[u8] = "test message".as_bytes();
+
+    #[test]
+    fn sign_and_verify<K, S>()
+    where
+        K: KeypairGenerator + PrehashSigner<S> + Signer<S>,
+        K::VerifyingKey: PrehashVerifier<S> + Verifier<S>,
+    {
+        let mut rng = rand::thread_rng();
+        let sk = K::generate(&mut rng);
+        let vk = sk.verifying_key();
+
+        let signature = sign_message_with_prehash::<Sha3_256, _, S>(&sk, TEST_MESSAGE, &mut rng);
+        assert!(verify_with_prehash::<Sha3_256, _, _>(&vk, TEST_MESSAGE, &signature).is_ok());
+        assert!(verify_with_prehash::<Sha3_256, _, _>(&vk, b"other message", &signature).is_err());
+        assert!(verify_with_prehash::<Sha256, _, _>(&vk, TEST_MESSAGE, &signature).is_err());
+        assert!(vk.verify(TEST_MESSAGE, &signature).is_err());
+
+        let signature = sign_message_with_prehash::<Sha256, _, S>(&sk, TEST_MESSAGE, &mut rng);
+        assert!(verify_with_prehash::<Sha256, _, _>(&vk, TEST_MESSAGE, &signature).is_ok());
+        assert!(verify_with_prehash::<Sha3_256, _, _>(&vk, TEST_MESSAGE, &signature).is_err());
+
+        // a plain signature is not a signature on the prehashed message
+        let signature = sk.sign(TEST_MESSAGE);
+        assert!(verify_with_prehash::<Sha3_256, _, _>(&vk, TEST_MESSAGE, &signature).is_err());
+        let digest = Sha3_256::digest(TEST_MESSAGE);
+        let signature = sk.sign(&[b"FAEST-prehash".as_slice(), b"SHA3-256", &digest].concat());
+        assert!(verify_with_prehash::<Sha3_256, _, _>(&vk, TEST_MESSAGE, &signature).is_err());
+        let signature = sk.sign(&[[2, 8].as_slice(), b"SHA3-256", &digest].concat());
+        assert!(verify_with_prehash::<Sha3_256, _, _>(&vk, TEST_MESSAGE, &signature).is_err());
+    }
+
+    #[test]
+    fn external_digest<K, S>()
+    where
+        K: KeypairGenerator + PrehashSigner<S> + Signer<S>,
+        K::VerifyingKey: PrehashVerifier<S> + Verifier<S>,
+    {
+        let mut rng = rand::thread_rng();
+        let sk = K::generate(&mut rng);
+        let vk = sk.verifying_key();
+
+        let digest: [u8; EXTERNAL_DIGEST_SIZE] = Sha3_512::digest(TEST_MESSAGE).into();
+        let signature = sign_digest_unsafe_prehash::<_, S>(&sk, &digest, &mut rng);
+        assert!(verify_digest_unsafe_prehash(&vk, &digest, &signature).is_ok());
+        assert!(verify_digest_unsafe_prehash(&vk, &[0; EXTERNAL_DIGEST_SIZE], &signature).is_err());
+
+        // the signature is neither valid for the message nor for the digest
+        assert!(vk.verify(TEST_MESSAGE, &signature).is_err());
+        assert!(vk.verify(&digest, &signature).is_err());
+
+        // a plain signature on the digest is not an external prehash signature
+        let signature = sk.sign(&digest);
+        assert!(verify_digest_unsafe_prehash(&vk, &digest, &signature).is_err());
+        let signature = sk.sign(&[b"FAEST-external-prehash".as_slice(), &digest].concat());
+        assert!(verify_digest_unsafe_prehash(&vk, &digest, &signature).is_err());
+        let signature = sk.sign(&[[3].as_slice(), &digest].concat());
+        assert!(verify_digest_unsafe_prehash(&vk, &digest, &signature).is_err());
+    }
+
+    #[cfg(feature = "faest-128f")]
+    #[instantiate_tests(<FAEST128fSigningKey, FAEST128fSignature>)]
+    mod faest_128f {}
+
+    #[cfg(feature = "faest-em-128s")]
+    #[instantiate_tests(<FAESTEM128sSigningKey, FAESTEM128sSignature>)]
+    mod faest_em_128s {}
+}
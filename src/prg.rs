@@ -5,14 +5,19 @@ use generic_array::{
     typenum::{U16, U24, U32},
     ArrayLength, GenericArray,
 };
+#[cfg(all(feature = "blake3-oracle", feature = "zeroize"))]
+use zeroize::Zeroize;
 #[cfg(feature = "zeroize")]
 use zeroize::ZeroizeOnDrop;
 
-use crate::utils::Reader;
+use crate::{
+    aes_backend::{Aes128, Aes192, Aes256},
+    utils::Reader,
+};
 
-type Aes128Ctr128BE = ctr::Ctr128BE<aes::Aes128>;
-type Aes192Ctr128BE = ctr::Ctr128BE<aes::Aes192>;
-type Aes256Ctr128BE = ctr::Ctr128BE<aes::Aes256>;
+type Aes128Ctr128BE = ctr::Ctr128BE<Aes128>;
+type Aes192Ctr128BE = ctr::Ctr128BE<Aes192>;
+type Aes256Ctr128BE = ctr::Ctr128BE<Aes256>;
 
 /// Size of the IV
 pub type IVSize = U16;
@@ -88,6 +93,43 @@ impl Reader for PRG256 {
     }
 }
 
+/// PRG based on BLAKE3 in XOF mode
+///
+/// This is a non-standard extension for the 128 bit security level. The key
+/// and IV are absorbed in key derivation mode and the output is read from the
+/// XOF.
+#[cfg(feature = "blake3-oracle")]
+pub(crate) struct PRGBlake3(blake3::OutputReader);
+
+#[cfg(feature = "blake3-oracle")]
+impl PseudoRandomGenerator for PRGBlake3 {
+    type KeySize = U16;
+
+    fn new_prg(k: &GenericArray<u8, Self::KeySize>, iv: &IV) -> Self {
+        let mut hasher = blake3::Hasher::new_derive_key("faest-rs 2025-01-01 PRG");
+        hasher.update(k);
+        hasher.update(iv);
+        Self(hasher.finalize_xof())
+    }
+}
+
+#[cfg(all(feature = "blake3-oracle", feature = "zeroize"))]
+impl Drop for PRGBlake3 {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(all(feature = "blake3-oracle", feature = "zeroize"))]
+impl ZeroizeOnDrop for PRGBlake3 {}
+
+#[cfg(feature = "blake3-oracle")]
+impl Reader for PRGBlake3 {
+    fn read(&mut self, dst: &mut [u8]) {
+        self.0.fill(dst);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -203,4 +245,30 @@ mod test {
         prg.read(&mut res);
         assert_eq!(res, output);
     }
+
+    #[cfg(feature = "blake3-oracle")]
+    #[test]
+    fn test_prg_blake3() {
+        let key = GenericArray::from_array([0x42; 16]);
+        let iv = IV::from_array([0x13; 16]);
+
+        let mut res = [0; 240];
+        PRGBlake3::new_prg(&key, &iv).read(&mut res);
+
+        // output does not depend on the read granularity
+        let mut prg = PRGBlake3::new_prg(&key, &iv);
+        let mut res_chunked = [0; 240];
+        for chunk in res_chunked.chunks_mut(7) {
+            prg.read(chunk);
+        }
+        assert_eq!(res, res_chunked);
+
+        // output depends on key and IV
+        let mut res_iv = [0; 240];
+        PRGBlake3::new_prg(&key, &IV::from_array([0x14; 16])).read(&mut res_iv);
+        assert_ne!(res, res_iv);
+        let mut res_key = [0; 240];
+        PRGBlake3::new_prg(&GenericArray::from_array([0x43; 16]), &iv).read(&mut res_key);
+        assert_ne!(res, res_key);
+    }
 }
@@ -0,0 +1,92 @@
+//! Progress reporting for long-running signing and verification
+
+/// Phase of a signing or verification operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Phase {
+    /// Expansion of the secret key into the witness (signing)
+    WitnessExpansion,
+    /// Commitment to the VOLE correlations (signing)
+    Commitment,
+    /// Universal hashing of the VOLE correlations
+    Hashing,
+    /// Computation of the QuickSilver proof (signing)
+    Proof,
+    /// Opening of the vector commitments (signing)
+    Openings,
+    /// Reconstruction of the VOLE correlations (verification)
+    Reconstruction,
+    /// Recomputation of the QuickSilver proof (verification)
+    Recompute,
+}
+
+/// Start of each phase relative to the complete signing operation
+#[cfg(all(feature = "sign", feature = "_variant"))]
+const SIGNING: &[(Phase, f32)] = &[
+    (Phase::WitnessExpansion, 0.0),
+    (Phase::Commitment, 0.05),
+    (Phase::Hashing, 0.6),
+    (Phase::Proof, 0.7),
+    (Phase::Openings, 0.9),
+];
+
+/// Start of each phase relative to the complete verification operation
+#[cfg(feature = "_variant")]
+const VERIFICATION: &[(Phase, f32)] = &[
+    (Phase::Reconstruction, 0.0),
+    (Phase::Hashing, 0.6),
+    (Phase::Recompute, 0.75),
+];
+
+/// Reports the progress of an operation to an optional callback
+///
+/// The callback only receives the phase and the overall progress in `[0, 1]`,
+/// hence it can not influence the result of the operation.
+#[cfg(feature = "_variant")]
+pub(crate) struct Progress<'a> {
+    callback: Option<&'a mut dyn FnMut(Phase, f32)>,
+    phases: &'static [(Phase, f32)],
+}
+
+#[cfg(feature = "_variant")]
+impl<'a> Progress<'a> {
+    /// Do not report any progress
+    pub(crate) const fn none() -> Self {
+        Self {
+            callback: None,
+            phases: &[],
+        }
+    }
+
+    /// Report the progress of signing to `callback`
+    #[cfg(feature = "sign")]
+    pub(crate) fn signing(callback: &'a mut dyn FnMut(Phase, f32)) -> Self {
+        Self {
+            callback: Some(callback),
+            phases: SIGNING,
+        }
+    }
+
+    /// Report the progress of verification to `callback`
+    pub(crate) fn verification(callback: &'a mut dyn FnMut(Phase, f32)) -> Self {
+        Self {
+            callback: Some(callback),
+            phases: VERIFICATION,
+        }
+    }
+
+    /// Report that `done` out of `total` steps of `phase` are completed
+    pub(crate) fn report(&mut self, phase: Phase, done: usize, total: usize) {
+        let Some(callback) = self.callback.as_mut() else {
+            return;
+        };
+
+        let Some(idx) = self.phases.iter().position(|(p, _)| *p == phase) else {
+            debug_assert!(false, "phase {phase:?} is not part of this operation");
+            return;
+        };
+        let start = self.phases[idx].1;
+        let end = self.phases.get(idx + 1).map_or(1.0, |(_, end)| *end);
+        callback(phase, start + (end - start) * done as f32 / total as f32);
+    }
+}
@@ -1,3 +1,30 @@
+//! Random oracles `H_0` to `H_3` used by FAEST
+//!
+//! FAEST instantiates `H_i(x)` as `SHAKE(x || i)`, with SHAKE128 for the
+//! 128 bit security level and SHAKE256 otherwise. The states of the oracles
+//! can be cloned to fork a transcript after absorbing a common prefix, and
+//! reset to start over.
+//!
+//! ```
+//! use faest::random_oracles::Shake128Oracle;
+//!
+//! let mut prefix = Shake128Oracle::<2>::new();
+//! prefix.update(b"common prefix");
+//!
+//! let mut fork = prefix.clone();
+//! fork.update(b"suffix");
+//! let mut forked = [0; 32];
+//! fork.finalize_into(&mut forked);
+//!
+//! prefix.reset();
+//! prefix.update(b"common prefixsuffix");
+//! let mut digest = [0; 32];
+//! prefix.finalize_into(&mut digest);
+//! assert_eq!(forked, digest);
+//! ```
+
+use std::fmt;
+
 use sha3::{
     digest::{ExtendableOutput, Update, XofReader},
     Shake128, Shake128Reader, Shake256, Shake256Reader,
@@ -5,8 +32,20 @@ use sha3::{
 
 use crate::utils::Reader;
 
+/// Separator of the hasher returned by [`RandomOracle::keygen_init`]
+#[cfg(all(feature = "sign", feature = "parallel", feature = "_variant"))]
+pub(crate) const KEYGEN_SEP: u8 = 0xff;
+
+/// Separator of the hasher returned by [`RandomOracle::h1_domain_init`]
+#[cfg(feature = "_variant")]
+pub(crate) const H1_DOMAIN_SEP: u8 = 0xfe;
+
+/// Customization string absorbed by [`RandomOracle::keygen_init`]
+#[cfg(all(feature = "sign", feature = "parallel", feature = "_variant"))]
+const KEYGEN_DOMAIN: &[u8] = b"faest-rs batch key generation";
+
 pub(crate) trait RandomOracle {
-    type Hasher<const SEP: u8>: Hasher + Default;
+    type Hasher<const SEP: u8>: Hasher;
 
     /// Create hasher for `H0`
     fn h0_init() -> Self::Hasher<0> {
@@ -18,25 +57,81 @@ pub(crate) trait RandomOracle {
         Self::Hasher::default()
     }
 
+    /// Create hasher for `µ` of messages outside of the plain domain
+    ///
+    /// This hasher is not part of the FAEST specification. Its separator
+    /// differs from the one of `H1`, hence its inputs never collide with the
+    /// inputs of `µ` for plain messages.
+    #[cfg(feature = "_variant")]
+    fn h1_domain_init() -> Self::Hasher<H1_DOMAIN_SEP> {
+        Self::Hasher::default()
+    }
+
     /// Create hasher for `H2`
+    #[cfg(feature = "_variant")]
     fn h2_init() -> Self::Hasher<2> {
         Self::Hasher::default()
     }
 
     /// Create hasher for `H3`
+    #[cfg(all(feature = "sign", feature = "_variant"))]
     fn h3_init() -> Self::Hasher<3> {
         Self::Hasher::default()
     }
+
+    /// Create hasher to derive the RNGs for batch key generation
+    ///
+    /// This hasher is not part of the FAEST specification. It is separated
+    /// from `H0` to `H3` by its separator and absorbs a customization string
+    /// first.
+    #[cfg(all(feature = "sign", feature = "parallel", feature = "_variant"))]
+    fn keygen_init() -> Self::Hasher<KEYGEN_SEP> {
+        let mut hasher = Self::Hasher::default();
+        hasher.absorb_length_prefixed(KEYGEN_DOMAIN);
+        hasher
+    }
 }
 
 /// Interface for hashers associated ot the random oracles
-pub(crate) trait Hasher {
+///
+/// Hashers are cheap to clone: a clone taken after absorbing a common prefix
+/// continues independently of the original, so a transcript can be forked
+/// without re-absorbing the prefix.
+pub(crate) trait Hasher: Clone + Default {
     /// Digest reader
     type Reader: Reader;
 
     /// Hash additional bytes
     fn update(&mut self, data: &[u8]);
 
+    /// Hash additional bytes prefixed with their length
+    ///
+    /// The length is encoded as big-endian `u64`. Consecutive calls are hence
+    /// unambiguous, whereas `update(b"ab"); update(b"c")` and `update(b"a");
+    /// update(b"bc")` produce the same digest. Note that the hashes defined by
+    /// FAEST only absorb fixed-length inputs (and the message last) and thus use
+    /// [`Hasher::update`] to remain compatible with the specification.
+    #[cfg(all(feature = "sign", feature = "_variant"))]
+    fn absorb_length_prefixed(&mut self, data: &[u8]) {
+        self.update(&(data.len() as u64).to_be_bytes());
+        self.update(data);
+    }
+
+    /// Fork the hasher into `n` child hashers
+    ///
+    /// Child `i` is a clone of the current state that additionally absorbed
+    /// `i` encoded as big-endian `u64`, so the children continue
+    /// independently of each other and of `self`, which is left unchanged.
+    fn split(&self, n: usize) -> Vec<Self> {
+        (0..n as u64)
+            .map(|i| {
+                let mut child = self.clone();
+                child.update(&i.to_be_bytes());
+                child
+            })
+            .collect()
+    }
+
     /*
        /// Hash additional bytes obtained from an iterator
        fn update_from_iterator<I>(&mut self, it: I)
@@ -68,7 +163,7 @@ pub(crate) trait Hasher {
 pub(crate) struct RandomOracleShake128 {}
 
 /// Hasher based on `SHAKE128`
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct Hasher128<const SEP: u8> {
     hasher: Shake128,
 }
@@ -114,7 +209,7 @@ impl<const SEP: u8> Hasher for Hasher256<SEP> {
 pub(crate) struct RandomOracleShake256 {}
 
 /// Hasher based on SHAKE256
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub(crate) struct Hasher256<const SEP: u8> {
     hasher: Shake256,
 }
@@ -131,10 +226,398 @@ impl RandomOracle for RandomOracleShake256 {
     type Hasher<const SEP: u8> = Hasher256<SEP>;
 }
 
-#[cfg(test)]
+/// Random oracle based on BLAKE3 in XOF mode
+///
+/// This is a non-standard extension. Parameter sets using this random oracle
+/// are not compatible with the FAEST specification.
+#[cfg(feature = "blake3-oracle")]
+pub(crate) struct RandomOracleBlake3 {}
+
+/// Hasher based on BLAKE3
+#[cfg(feature = "blake3-oracle")]
+#[derive(Default, Clone)]
+pub(crate) struct HasherBlake3<const SEP: u8> {
+    hasher: blake3::Hasher,
+}
+
+#[cfg(feature = "blake3-oracle")]
+pub(crate) struct HasherBlake3Reader(blake3::OutputReader);
+
+#[cfg(all(feature = "blake3-oracle", feature = "zeroize"))]
+impl Drop for HasherBlake3Reader {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+#[cfg(feature = "blake3-oracle")]
+impl Reader for HasherBlake3Reader {
+    fn read(&mut self, dst: &mut [u8]) {
+        self.0.fill(dst);
+    }
+}
+
+#[cfg(feature = "blake3-oracle")]
+impl RandomOracle for RandomOracleBlake3 {
+    type Hasher<const SEP: u8> = HasherBlake3<SEP>;
+}
+
+#[cfg(feature = "blake3-oracle")]
+impl<const SEP: u8> Hasher for HasherBlake3<SEP> {
+    type Reader = HasherBlake3Reader;
+
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    fn finish(mut self) -> Self::Reader {
+        self.hasher.update(&[SEP]);
+        HasherBlake3Reader(self.hasher.finalize_xof())
+    }
+}
+
+macro_rules! define_oracle {
+    ($name:ident, $hasher:ident, $shake:literal) => {
+        #[doc = concat!("State of the random oracle `H_SEP` based on ", $shake)]
+        #[derive(Clone, Default)]
+        pub struct $name<const SEP: u8>($hasher<SEP>);
+
+        impl<const SEP: u8> $name<SEP> {
+            /// Create a new state that has not absorbed any input
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Absorb `data`
+            pub fn update(&mut self, data: &[u8]) {
+                self.0.update(data);
+            }
+
+            /// Discard all absorbed input
+            pub fn reset(&mut self) {
+                *self = Self::default();
+            }
+
+            /// Fork the state into `n` independent child states
+            ///
+            /// Child `i` additionally absorbs `i` encoded as big-endian `u64`.
+            /// The state itself is left unchanged.
+            pub fn split(&self, n: usize) -> Vec<Self> {
+                self.0.split(n).into_iter().map(Self).collect()
+            }
+
+            /// Finish absorbing and fill `dst` with the output of the oracle
+            pub fn finalize_into(self, dst: &mut [u8]) {
+                self.0.finish().read(dst);
+            }
+        }
+
+        impl<const SEP: u8> fmt::Debug for $name<SEP> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+    };
+}
+
+define_oracle!(Shake128Oracle, Hasher128, "SHAKE128");
+define_oracle!(Shake256Oracle, Hasher256, "SHAKE256");
+
+#[cfg(all(test, feature = "_variant"))]
 mod test {
     use super::*;
 
+    use generic_array::typenum::Unsigned;
+
+    use crate::fields::{BigGaloisField, Field, GF128, GF192, GF256};
+
+    fn digest<H: Hasher>(mut hasher: H, inputs: &[&[u8]], length_prefixed: bool) -> [u8; 32] {
+        for input in inputs {
+            if length_prefixed {
+                hasher.absorb_length_prefixed(input);
+            } else {
+                hasher.update(input);
+            }
+        }
+        let mut res = [0; 32];
+        hasher.finish().read(&mut res);
+        res
+    }
+
+    fn fork_after_absorb<H: Hasher>() {
+        let mut prefix = H::default();
+        prefix.update(b"common prefix");
+
+        let mut fork = prefix.clone();
+        fork.update(b"suffix 1");
+        prefix.update(b"suffix 2");
+
+        assert_eq!(
+            digest(fork, &[], false),
+            digest(H::default(), &[b"common prefix", b"suffix 1"], false)
+        );
+        assert_eq!(
+            digest(prefix, &[], false),
+            digest(H::default(), &[b"common prefix", b"suffix 2"], false)
+        );
+    }
+
+    #[test]
+    fn fork() {
+        fork_after_absorb::<<RandomOracleShake128 as RandomOracle>::Hasher<2>>();
+        fork_after_absorb::<<RandomOracleShake256 as RandomOracle>::Hasher<2>>();
+        #[cfg(feature = "blake3-oracle")]
+        fork_after_absorb::<<RandomOracleBlake3 as RandomOracle>::Hasher<2>>();
+    }
+
+    fn split_children<H: Hasher>() {
+        let mut parent = H::default();
+        parent.update(b"common prefix");
+        let expected_parent = digest(parent.clone(), &[], false);
+
+        let children = parent.split(4);
+        assert_eq!(children.len(), 4);
+        assert_eq!(digest(parent.clone(), &[], false), expected_parent);
+
+        let outputs: Vec<_> = children
+            .into_iter()
+            .map(|child| digest(child, &[], false))
+            .collect();
+        for (i, output) in outputs.iter().enumerate() {
+            assert_ne!(*output, expected_parent);
+            assert_eq!(
+                *output,
+                digest(parent.clone(), &[&(i as u64).to_be_bytes()], false)
+            );
+            for other in &outputs[i + 1..] {
+                assert_ne!(output, other);
+            }
+        }
+
+        assert!(parent.split(0).is_empty());
+    }
+
+    #[test]
+    fn split() {
+        split_children::<<RandomOracleShake128 as RandomOracle>::Hasher<2>>();
+        split_children::<<RandomOracleShake256 as RandomOracle>::Hasher<2>>();
+        #[cfg(feature = "blake3-oracle")]
+        split_children::<<RandomOracleBlake3 as RandomOracle>::Hasher<2>>();
+    }
+
+    #[test]
+    fn forked_digests() {
+        // H2("common prefix" || "suffix 1") with H_i(x) = SHAKE(x || i)
+        let expected_128 = [
+            0x0a, 0x4e, 0x50, 0x43, 0x19, 0xe8, 0x6e, 0x18, 0xde, 0x39, 0xc2, 0x5c, 0x4c, 0xcf,
+            0x57, 0x19, 0xa1, 0xab, 0xea, 0xb0, 0xf2, 0xda, 0x60, 0xf2, 0x5a, 0x70, 0xfe, 0x15,
+            0x22, 0xe9, 0x84, 0x00,
+        ];
+        let expected_256 = [
+            0x3e, 0x63, 0xcf, 0x43, 0x53, 0x6c, 0xcc, 0x11, 0x60, 0x33, 0x0d, 0x24, 0x2c, 0x02,
+            0x57, 0xf0, 0xb6, 0x72, 0xdf, 0x0c, 0xe0, 0xfc, 0x9d, 0x7a, 0x16, 0x15, 0xdc, 0x85,
+            0x89, 0xf6, 0x8e, 0xab,
+        ];
+
+        let mut prefix = RandomOracleShake128::h2_init();
+        prefix.update(b"common prefix");
+        assert_eq!(digest(prefix.clone(), &[b"suffix 1"], false), expected_128);
+        assert_ne!(digest(prefix, &[b"suffix 2"], false), expected_128);
+
+        let mut prefix = RandomOracleShake256::h2_init();
+        prefix.update(b"common prefix");
+        assert_eq!(digest(prefix.clone(), &[b"suffix 1"], false), expected_256);
+        assert_ne!(digest(prefix, &[b"suffix 2"], false), expected_256);
+    }
+
+    #[test]
+    fn public_oracles() {
+        let mut prefix = Shake128Oracle::<2>::new();
+        prefix.update(b"common prefix");
+        let mut fork = prefix.clone();
+        fork.update(b"suffix 1");
+        let mut output = [0; 32];
+        fork.finalize_into(&mut output);
+        assert_eq!(
+            output,
+            digest(
+                RandomOracleShake128::h2_init(),
+                &[b"common prefix", b"suffix 1"],
+                false
+            )
+        );
+
+        let mut prefix = Shake256Oracle::<2>::new();
+        prefix.update(b"common prefix");
+        let mut fork = prefix.clone();
+        fork.update(b"suffix 1");
+        let mut output = [0; 32];
+        fork.finalize_into(&mut output);
+        assert_eq!(
+            output,
+            digest(
+                RandomOracleShake256::h2_init(),
+                &[b"common prefix", b"suffix 1"],
+                false
+            )
+        );
+
+        // the separator is absorbed last
+        let mut output = [0; 32];
+        Shake128Oracle::<3>::new().finalize_into(&mut output);
+        assert_eq!(output, digest(RandomOracleShake128::h3_init(), &[], false));
+
+        let mut parent = Shake256Oracle::<2>::new();
+        parent.update(b"common prefix");
+        for (i, child) in parent.split(2).into_iter().enumerate() {
+            let mut output = [0; 32];
+            child.finalize_into(&mut output);
+            assert_eq!(
+                output,
+                digest(
+                    RandomOracleShake256::h2_init(),
+                    &[b"common prefix", &(i as u64).to_be_bytes()],
+                    false
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn reset() {
+        let mut oracle = Shake256Oracle::<0>::new();
+        oracle.update(b"discarded");
+        oracle.reset();
+        oracle.update(b"input");
+        let mut output = [0; 32];
+        oracle.finalize_into(&mut output);
+        assert_eq!(
+            output,
+            digest(RandomOracleShake256::h0_init(), &[b"input"], false)
+        );
+
+        let mut oracle = Shake128Oracle::<1>::new();
+        oracle.update(b"discarded");
+        oracle.reset();
+        let mut output = [0; 32];
+        oracle.finalize_into(&mut output);
+        assert_eq!(output, digest(RandomOracleShake128::h1_init(), &[], false));
+    }
+
+    fn squeeze_field_elements<RO: RandomOracle, F: BigGaloisField + PartialEq + std::fmt::Debug>() {
+        let reader = || {
+            let mut hasher = RO::h2_init();
+            hasher.update(b"input");
+            hasher.finish()
+        };
+
+        let mut bytes = vec![0; 3 * <F as Field>::Length::USIZE];
+        reader().read(&mut bytes);
+        let expected: Vec<_> = bytes
+            .chunks_exact(<F as Field>::Length::USIZE)
+            .map(F::from)
+            .collect();
+
+        let mut r = reader();
+        let elements: Vec<F> = (0..3).map(|_| r.squeeze_into_field()).collect();
+        assert_eq!(elements, expected);
+    }
+
+    #[test]
+    fn squeeze_into_field() {
+        squeeze_field_elements::<RandomOracleShake128, GF128>();
+        squeeze_field_elements::<RandomOracleShake128, GF192>();
+        squeeze_field_elements::<RandomOracleShake256, GF256>();
+        #[cfg(feature = "blake3-oracle")]
+        squeeze_field_elements::<RandomOracleBlake3, GF128>();
+    }
+
+    #[test]
+    fn length_prefixed_128() {
+        let ab_c: [&[u8]; 2] = [b"ab", b"c"];
+        let a_bc: [&[u8]; 2] = [b"a", b"bc"];
+        assert_eq!(
+            digest(RandomOracleShake128::h0_init(), &ab_c, false),
+            digest(RandomOracleShake128::h0_init(), &a_bc, false)
+        );
+        assert_ne!(
+            digest(RandomOracleShake128::h0_init(), &ab_c, true),
+            digest(RandomOracleShake128::h0_init(), &a_bc, true)
+        );
+    }
+
+    #[test]
+    fn length_prefixed_256() {
+        let ab_c: [&[u8]; 2] = [b"ab", b"c"];
+        let a_bc: [&[u8]; 2] = [b"a", b"bc"];
+        assert_eq!(
+            digest(RandomOracleShake256::h0_init(), &ab_c, false),
+            digest(RandomOracleShake256::h0_init(), &a_bc, false)
+        );
+        assert_ne!(
+            digest(RandomOracleShake256::h0_init(), &ab_c, true),
+            digest(RandomOracleShake256::h0_init(), &a_bc, true)
+        );
+    }
+
+    #[cfg(feature = "blake3-oracle")]
+    #[test]
+    fn blake3_domain_separation() {
+        let mut res = [[0; 32]; 4];
+        digest(RandomOracleBlake3::h0_init(), &[b"input"], false).clone_into(&mut res[0]);
+        digest(RandomOracleBlake3::h1_init(), &[b"input"], false).clone_into(&mut res[1]);
+        digest(RandomOracleBlake3::h2_init(), &[b"input"], false).clone_into(&mut res[2]);
+        digest(RandomOracleBlake3::h3_init(), &[b"input"], false).clone_into(&mut res[3]);
+        for i in 0..res.len() {
+            for j in i + 1..res.len() {
+                assert_ne!(res[i], res[j]);
+            }
+        }
+
+        // H_i(x) = BLAKE3(x || i)
+        let mut expected = [0; 32];
+        blake3::Hasher::new()
+            .update(b"input\x02")
+            .finalize_xof()
+            .fill(&mut expected);
+        assert_eq!(res[2], expected);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn keygen_domain_separation() {
+        let input: [&[u8]; 1] = [b"seed || index"];
+        let keygen = digest(RandomOracleShake128::keygen_init(), &input, false);
+        assert_ne!(
+            keygen,
+            digest(RandomOracleShake128::h0_init(), &input, false)
+        );
+
+        // the customization string is absorbed before the input
+        let mut hasher = <RandomOracleShake128 as RandomOracle>::Hasher::<0>::default();
+        hasher.absorb_length_prefixed(KEYGEN_DOMAIN);
+        assert_ne!(keygen, digest(hasher, &input, false));
+        assert_ne!(
+            digest(RandomOracleShake256::keygen_init(), &input, false),
+            digest(RandomOracleShake256::h0_init(), &input, false)
+        );
+    }
+
+    #[test]
+    fn length_prefix_encoding() {
+        let mut hasher = RandomOracleShake128::h1_init();
+        hasher.absorb_length_prefixed(b"abc");
+        let mut expected = RandomOracleShake128::h1_init();
+        expected.update(&[0, 0, 0, 0, 0, 0, 0, 3]);
+        expected.update(b"abc");
+
+        let mut res = [0; 32];
+        hasher.finish().read(&mut res);
+        let mut res_expected = [0; 32];
+        expected.finish().read(&mut res_expected);
+        assert_eq!(res, res_expected);
+    }
+
     #[test]
     fn test_h0_128() {
         let input = [
@@ -4,6 +4,16 @@
 //! All implementations are fully bitsliced and do not rely on any
 //! Look-Up Table (LUT).
 //!
+//! The module provides Rijndael with 128-bit (i.e., AES-128), 192-bit and
+//! 256-bit blocks and keys via the traits of the [cipher](aes::cipher) crate.
+//! Only encryption is supported.
+//!
+//! # Constant-time properties
+//!
+//! Key schedule and encryption are free of secret-dependent branches and
+//! memory accesses, independently of the target. Keys of a wrong length are
+//! rejected by [`KeyInit::new_from_slice`].
+//!
 //! # Author (original C code)
 //!
 //! Alexandre Adomnicai, Nanyang Technological University, Singapore
@@ -11,15 +21,19 @@
 //!
 //! Originally licensed MIT. Relicensed as Apache 2.0+MIT with permission.
 
+use std::array;
+
 use aes::{
     cipher::{
-        generic_array::typenum::{U24, U32},
-        BlockEncrypt, BlockSizeUser, KeyInit, KeySizeUser,
+        generic_array::typenum::{U1, U16, U24, U32},
+        inout::InOut,
+        BlockBackend, BlockCipher, BlockEncrypt, BlockSizeUser, KeyInit, KeySizeUser,
+        ParBlocksSizeUser,
     },
     Block,
 };
 use generic_array::{
-    typenum::{Unsigned, U12, U14, U2, U6, U8},
+    typenum::{Unsigned, U10, U12, U14, U2, U4, U6, U8},
     GenericArray,
 };
 #[cfg(feature = "zeroize")]
@@ -506,6 +520,12 @@ const M0: u32 = 0x55555555;
 const M1: u32 = 0x33333333;
 const M2: u32 = 0x0f0f0f0f;
 
+/// Read a little-endian `u32` from the first four bytes of `bytes`
+#[inline(always)]
+fn u32_from_le_bytes(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(array::from_fn(|i| bytes[i]))
+}
+
 /// Bitslice two 128-bit input blocks input0, input1 into a 256-bit internal state.
 pub(crate) fn bitslice(output: &mut [u32], input0: &[u8], input1: &[u8]) {
     debug_assert_eq!(output.len(), 8);
@@ -522,27 +542,27 @@ pub(crate) fn bitslice(output: &mut [u32], input0: &[u8], input1: &[u8]) {
 
     // Interleave the columns on input (note the order of input)
     //     b0 c1 c0 __ __ __ __ __ => c1 c0 b0 __ __ __ __ __
-    let mut t0 = u32::from_le_bytes(input0[0x00..0x04].try_into().unwrap());
-    let mut t2 = u32::from_le_bytes(input0[0x04..0x08].try_into().unwrap());
-    let mut t4 = u32::from_le_bytes(input0[0x08..0x0c].try_into().unwrap());
-    let mut t6 = u32::from_le_bytes(input0[0x0c..0x10].try_into().unwrap());
+    let mut t0 = u32_from_le_bytes(&input0[0x00..0x04]);
+    let mut t2 = u32_from_le_bytes(&input0[0x04..0x08]);
+    let mut t4 = u32_from_le_bytes(&input0[0x08..0x0c]);
+    let mut t6 = u32_from_le_bytes(&input0[0x0c..0x10]);
     let mut t1 = if input1.is_empty() {
         0
     } else {
-        u32::from_le_bytes(input1[0x00..0x04].try_into().unwrap())
+        u32_from_le_bytes(&input1[0x00..0x04])
     };
     let mut t3 = if input1.is_empty() {
         0
     } else {
-        u32::from_le_bytes(input1[0x04..0x08].try_into().unwrap())
+        u32_from_le_bytes(&input1[0x04..0x08])
     };
     let mut t5 = if input1.len() > 8 {
-        u32::from_le_bytes(input1[0x08..0x0c].try_into().unwrap())
+        u32_from_le_bytes(&input1[0x08..0x0c])
     } else {
         0
     };
     let mut t7 = if input1.len() > 8 {
-        u32::from_le_bytes(input1[0x0c..0x10].try_into().unwrap())
+        u32_from_le_bytes(&input1[0x0c..0x10])
     } else {
         0
     };
@@ -641,10 +661,11 @@ pub(crate) fn inv_bitslice(input: &[u32]) -> BatchBlocks {
     output
 }
 
+#[inline(always)]
 pub(crate) fn convert_from_batchblocks(input: BatchBlocks) -> impl Iterator<Item = [u8; 4]> {
     input
         .into_iter()
-        .flat_map(|input| (0..4).map(move |j| input[j * 4..(j + 1) * 4].try_into().unwrap()))
+        .flat_map(|input| (0..4).map(move |j| array::from_fn(|i| input[j * 4 + i])))
 }
 
 /// Copy 32-bytes within the provided slice to an 8-byte offset
@@ -682,8 +703,150 @@ const fn ske(r: usize, nst: usize, nk: usize) -> usize {
     4 * (r + 1) * nst / nk
 }
 
+/// AES-128 based on the fixsliced implementation
+///
+/// In contrast to [`aes::Aes128Enc`], which selects a backend at runtime, this
+/// implementation is always bitsliced.
+#[cfg_attr(feature = "zeroize", derive(ZeroizeOnDrop))]
+pub struct Rijndael128(Vec<u32>);
+
+impl KeySizeUser for Rijndael128 {
+    type KeySize = U16;
+}
+
+impl KeyInit for Rijndael128 {
+    fn new(key: &aes::cipher::Key<Self>) -> Self {
+        Self(rijndael_key_schedule::<U4, U4, U10>(key.as_slice(), ske(10, 4, 4)).0)
+    }
+}
+
+impl BlockSizeUser for Rijndael128 {
+    type BlockSize = U16;
+}
+
+impl BlockCipher for Rijndael128 {}
+
+impl BlockEncrypt for Rijndael128 {
+    fn encrypt_with_backend(&self, f: impl aes::cipher::BlockClosure<BlockSize = Self::BlockSize>) {
+        f.call(&mut Rijndael128Backend(self));
+    }
+}
+
+/// Backend of [`Rijndael128`] encrypting one block at a time
+struct Rijndael128Backend<'a>(&'a Rijndael128);
+
+impl BlockSizeUser for Rijndael128Backend<'_> {
+    type BlockSize = U16;
+}
+
+impl ParBlocksSizeUser for Rijndael128Backend<'_> {
+    type ParBlocksSize = U1;
+}
+
+impl BlockBackend for Rijndael128Backend<'_> {
+    fn proc_block(&mut self, mut block: InOut<'_, '_, Block>) {
+        let out = rijndael_encrypt::<U4, U10>(&self.0 .0, block.get_in().as_slice());
+        block.get_out().copy_from_slice(&out[0]);
+    }
+}
+
+/// AES-192 based on the fixsliced implementation
+///
+/// In contrast to [`aes::Aes192Enc`], which selects a backend at runtime, this
+/// implementation is always bitsliced.
+#[cfg_attr(feature = "zeroize", derive(ZeroizeOnDrop))]
+pub struct Aes192(Vec<u32>);
+
+impl KeySizeUser for Aes192 {
+    type KeySize = U24;
+}
+
+impl KeyInit for Aes192 {
+    fn new(key: &aes::cipher::Key<Self>) -> Self {
+        Self(rijndael_key_schedule::<U4, U6, U12>(key.as_slice(), ske(12, 4, 6)).0)
+    }
+}
+
+impl BlockSizeUser for Aes192 {
+    type BlockSize = U16;
+}
+
+impl BlockCipher for Aes192 {}
+
+impl BlockEncrypt for Aes192 {
+    fn encrypt_with_backend(&self, f: impl aes::cipher::BlockClosure<BlockSize = Self::BlockSize>) {
+        f.call(&mut Aes192Backend(self));
+    }
+}
+
+/// Backend of [`Aes192`] encrypting one block at a time
+struct Aes192Backend<'a>(&'a Aes192);
+
+impl BlockSizeUser for Aes192Backend<'_> {
+    type BlockSize = U16;
+}
+
+impl ParBlocksSizeUser for Aes192Backend<'_> {
+    type ParBlocksSize = U1;
+}
+
+impl BlockBackend for Aes192Backend<'_> {
+    fn proc_block(&mut self, mut block: InOut<'_, '_, Block>) {
+        let out = rijndael_encrypt::<U4, U12>(&self.0 .0, block.get_in().as_slice());
+        block.get_out().copy_from_slice(&out[0]);
+    }
+}
+
+/// AES-256 based on the fixsliced implementation
+///
+/// In contrast to [`aes::Aes256Enc`], which selects a backend at runtime, this
+/// implementation is always bitsliced.
 #[cfg_attr(feature = "zeroize", derive(ZeroizeOnDrop))]
-pub(crate) struct Rijndael192(Vec<u32>);
+pub struct Aes256(Vec<u32>);
+
+impl KeySizeUser for Aes256 {
+    type KeySize = U32;
+}
+
+impl KeyInit for Aes256 {
+    fn new(key: &aes::cipher::Key<Self>) -> Self {
+        Self(rijndael_key_schedule::<U4, U8, U14>(key.as_slice(), ske(14, 4, 8)).0)
+    }
+}
+
+impl BlockSizeUser for Aes256 {
+    type BlockSize = U16;
+}
+
+impl BlockCipher for Aes256 {}
+
+impl BlockEncrypt for Aes256 {
+    fn encrypt_with_backend(&self, f: impl aes::cipher::BlockClosure<BlockSize = Self::BlockSize>) {
+        f.call(&mut Aes256Backend(self));
+    }
+}
+
+/// Backend of [`Aes256`] encrypting one block at a time
+struct Aes256Backend<'a>(&'a Aes256);
+
+impl BlockSizeUser for Aes256Backend<'_> {
+    type BlockSize = U16;
+}
+
+impl ParBlocksSizeUser for Aes256Backend<'_> {
+    type ParBlocksSize = U1;
+}
+
+impl BlockBackend for Aes256Backend<'_> {
+    fn proc_block(&mut self, mut block: InOut<'_, '_, Block>) {
+        let out = rijndael_encrypt::<U4, U14>(&self.0 .0, block.get_in().as_slice());
+        block.get_out().copy_from_slice(&out[0]);
+    }
+}
+
+/// Rijndael with 192-bit blocks and keys based on the fixsliced implementation
+#[cfg_attr(feature = "zeroize", derive(ZeroizeOnDrop))]
+pub struct Rijndael192(Vec<u32>);
 
 impl KeySizeUser for Rijndael192 {
     type KeySize = U24;
@@ -699,27 +862,37 @@ impl BlockSizeUser for Rijndael192 {
     type BlockSize = U24;
 }
 
+impl BlockCipher for Rijndael192 {}
+
 impl BlockEncrypt for Rijndael192 {
-    fn encrypt_with_backend(
-        &self,
-        _f: impl aes::cipher::BlockClosure<BlockSize = Self::BlockSize>,
-    ) {
-        unimplemented!();
-    }
-
-    fn encrypt_block_b2b(
-        &self,
-        in_block: &aes::cipher::Block<Self>,
-        out_block: &mut aes::cipher::Block<Self>,
-    ) {
-        let out = rijndael_encrypt::<U6, U12>(&self.0, in_block.as_slice());
+    fn encrypt_with_backend(&self, f: impl aes::cipher::BlockClosure<BlockSize = Self::BlockSize>) {
+        f.call(&mut Rijndael192Backend(self));
+    }
+}
+
+/// Backend of [`Rijndael192`] encrypting one block at a time
+struct Rijndael192Backend<'a>(&'a Rijndael192);
+
+impl BlockSizeUser for Rijndael192Backend<'_> {
+    type BlockSize = U24;
+}
+
+impl ParBlocksSizeUser for Rijndael192Backend<'_> {
+    type ParBlocksSize = U1;
+}
+
+impl BlockBackend for Rijndael192Backend<'_> {
+    fn proc_block(&mut self, mut block: InOut<'_, '_, aes::cipher::Block<Self>>) {
+        let out = rijndael_encrypt::<U6, U12>(&self.0 .0, block.get_in().as_slice());
+        let out_block = block.get_out();
         out_block[..16].copy_from_slice(&out[0]);
         out_block[16..].copy_from_slice(&out[1][..8]);
     }
 }
 
+/// Rijndael with 256-bit blocks and keys based on the fixsliced implementation
 #[cfg_attr(feature = "zeroize", derive(ZeroizeOnDrop))]
-pub(crate) struct Rijndael256(Vec<u32>);
+pub struct Rijndael256(Vec<u32>);
 
 impl KeySizeUser for Rijndael256 {
     type KeySize = U32;
@@ -735,20 +908,29 @@ impl BlockSizeUser for Rijndael256 {
     type BlockSize = U32;
 }
 
+impl BlockCipher for Rijndael256 {}
+
 impl BlockEncrypt for Rijndael256 {
-    fn encrypt_with_backend(
-        &self,
-        _f: impl aes::cipher::BlockClosure<BlockSize = Self::BlockSize>,
-    ) {
-        unimplemented!();
-    }
-
-    fn encrypt_block_b2b(
-        &self,
-        in_block: &aes::cipher::Block<Self>,
-        out_block: &mut aes::cipher::Block<Self>,
-    ) {
-        let out = rijndael_encrypt::<U8, U14>(&self.0, in_block.as_slice());
+    fn encrypt_with_backend(&self, f: impl aes::cipher::BlockClosure<BlockSize = Self::BlockSize>) {
+        f.call(&mut Rijndael256Backend(self));
+    }
+}
+
+/// Backend of [`Rijndael256`] encrypting one block at a time
+struct Rijndael256Backend<'a>(&'a Rijndael256);
+
+impl BlockSizeUser for Rijndael256Backend<'_> {
+    type BlockSize = U32;
+}
+
+impl ParBlocksSizeUser for Rijndael256Backend<'_> {
+    type ParBlocksSize = U1;
+}
+
+impl BlockBackend for Rijndael256Backend<'_> {
+    fn proc_block(&mut self, mut block: InOut<'_, '_, aes::cipher::Block<Self>>) {
+        let out = rijndael_encrypt::<U8, U14>(&self.0 .0, block.get_in().as_slice());
+        let out_block = block.get_out();
         out_block[..16].copy_from_slice(&out[0]);
         out_block[16..].copy_from_slice(&out[1]);
     }
@@ -764,7 +946,7 @@ mod test {
     use generic_array::typenum::{U10, U12, U14, U4, U6, U8};
     use serde::Deserialize;
 
-    use crate::utils::test::read_test_data;
+    use crate::utils::test::{read_block_cipher_kats, read_test_data};
 
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "camelCase")]
@@ -829,6 +1011,112 @@ mod test {
         }
     }
 
+    #[test]
+    fn rijndael_cipher_test() {
+        fn encrypt<C: KeyInit + BlockEncrypt>(key: &[u8], text: &[u8]) -> Vec<u8> {
+            let mut block = aes::cipher::Block::<C>::clone_from_slice(text);
+            C::new_from_slice(key).unwrap().encrypt_block(&mut block);
+            block.to_vec()
+        }
+
+        let database: Vec<Rijndael> = read_test_data("rijndael_data.json");
+        for data in database.iter().filter(|data| data.bc == data.kc) {
+            let output = match data.bc {
+                4 => encrypt::<Rijndael128>(&data.key, &data.text),
+                6 => encrypt::<Rijndael192>(&data.key, &data.text),
+                8 => encrypt::<Rijndael256>(&data.key, &data.text),
+                _ => unreachable!(),
+            };
+            assert_eq!(output, data.output);
+        }
+    }
+
+    #[test]
+    fn invalid_key_size() {
+        for len in [0, 15, 17, 24, 32] {
+            assert!(Rijndael128::new_from_slice(&vec![0; len]).is_err());
+        }
+        for len in [0, 16, 23, 25, 32] {
+            assert!(Rijndael192::new_from_slice(&vec![0; len]).is_err());
+        }
+        for len in [0, 16, 24, 31, 33] {
+            assert!(Rijndael256::new_from_slice(&vec![0; len]).is_err());
+        }
+    }
+
+    /// Compare `C` to the implementation `E` of the `aes` crate
+    fn check_against_aes<C, E>()
+    where
+        C: KeyInit + BlockEncrypt + BlockSizeUser<BlockSize = U16>,
+        E: KeyInit<KeySize = C::KeySize> + BlockEncrypt + BlockSizeUser<BlockSize = U16>,
+    {
+        use rand::RngCore;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..16 {
+            let mut key = GenericArray::default();
+            let mut plaintext = GenericArray::default();
+            rng.fill_bytes(&mut key);
+            rng.fill_bytes(&mut plaintext);
+
+            let mut ciphertext = GenericArray::default();
+            let mut expected = GenericArray::default();
+            C::new(&key).encrypt_block_b2b(&plaintext, &mut ciphertext);
+            E::new(&key).encrypt_block_b2b(&plaintext, &mut expected);
+            assert_eq!(ciphertext, expected);
+
+            let mut blocks = [plaintext; 3];
+            blocks[1][0] ^= 1;
+            let mut expected_blocks = blocks;
+            C::new(&key).encrypt_blocks(&mut blocks);
+            E::new(&key).encrypt_blocks(&mut expected_blocks);
+            assert_eq!(blocks, expected_blocks);
+        }
+    }
+
+    #[test]
+    fn test_rijndael128() {
+        check_against_aes::<Rijndael128, aes::Aes128Enc>();
+    }
+
+    #[test]
+    fn test_aes192() {
+        check_against_aes::<Aes192, aes::Aes192Enc>();
+    }
+
+    #[test]
+    fn test_aes256() {
+        check_against_aes::<Aes256, aes::Aes256Enc>();
+    }
+
+    #[test]
+    fn aes_kat() {
+        // FIPS 197, Appendix C
+        fn encrypt<C: KeyInit + BlockEncrypt + BlockSizeUser<BlockSize = U16>>(
+            key: &[u8],
+        ) -> Vec<u8> {
+            let mut block = GenericArray::clone_from_slice(
+                &hex::decode("00112233445566778899aabbccddeeff").unwrap(),
+            );
+            C::new_from_slice(key).unwrap().encrypt_block(&mut block);
+            block.to_vec()
+        }
+
+        let key: Vec<u8> = (0..32).collect();
+        assert_eq!(
+            hex::encode(encrypt::<Rijndael128>(&key[..16])),
+            "69c4e0d86a7b0430d8cdb78070b4c55a"
+        );
+        assert_eq!(
+            hex::encode(encrypt::<Aes192>(&key[..24])),
+            "dda97ca4864cdfe06eaf70a0ec0d7191"
+        );
+        assert_eq!(
+            hex::encode(encrypt::<Aes256>(&key)),
+            "8ea2b7ca516745bfeafc49904b496089"
+        );
+    }
+
     #[test]
     fn test_rijndael192() {
         let mut key = GenericArray::default();
@@ -845,6 +1133,10 @@ mod test {
 
         rijndael.encrypt_block_b2b(&plaintext, &mut ciphertext);
         assert_eq!(ciphertext.as_slice(), &expected);
+
+        let mut blocks = [plaintext; 3];
+        rijndael.encrypt_blocks(&mut blocks);
+        assert!(blocks.iter().all(|block| block.as_slice() == expected));
     }
 
     #[test]
@@ -864,5 +1156,22 @@ mod test {
 
         rijndael.encrypt_block_b2b(&plaintext, &mut ciphertext);
         assert_eq!(ciphertext.as_slice(), &expected);
+
+        let mut blocks = [plaintext; 3];
+        rijndael.encrypt_blocks(&mut blocks);
+        assert!(blocks.iter().all(|block| block.as_slice() == expected));
+    }
+
+    #[test]
+    fn rijndael256_nist_vectors() {
+        let kats = read_block_cipher_kats("rijndael256_ecb_kat.txt");
+        for kat in &kats {
+            let rijndael = Rijndael256::new_from_slice(&kat.key).unwrap();
+            let mut block = GenericArray::clone_from_slice(&kat.plaintext);
+            rijndael.encrypt_block(&mut block);
+            assert_eq!(block.as_slice(), kat.ciphertext, "{kat:?}");
+        }
+        // catch truncated or mis-parsed files
+        assert!(kats.is_empty() || kats.len() == 512);
     }
 }
@@ -0,0 +1,308 @@
+//! Passphrase-based encryption of signing keys at rest
+//!
+//! A sealed key consists of a header followed by the encrypted key:
+//!
+//! | field       | size (in bytes) | description                             |
+//! |-------------|-----------------|-----------------------------------------|
+//! | magic       | 8               | `FAESTSK\0`                             |
+//! | version     | 1               | format version, currently 1             |
+//! | name length | 1               | length of the variant name              |
+//! | name        | variable        | name of the variant, e.g. `FAEST-128f`  |
+//! | KDF         | 1               | key derivation function, 1 for scrypt   |
+//! | log_n       | 1               | scrypt parameter log2(N)                |
+//! | r           | 4               | scrypt parameter r (big endian)         |
+//! | p           | 4               | scrypt parameter p (big endian)         |
+//! | salt        | 16              | salt for the KDF                        |
+//! | nonce       | 12              | nonce for ChaCha20-Poly1305             |
+//! | ciphertext  | variable        | encrypted signing key and tag           |
+//!
+//! The header is authenticated as associated data.
+
+use std::array;
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand_core::CryptoRngCore;
+use zeroize::Zeroizing;
+
+use crate::{Error, FaestVariant};
+
+const MAGIC: &[u8; 8] = b"FAESTSK\0";
+const VERSION: u8 = 1;
+const KDF_SCRYPT: u8 = 1;
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 12;
+
+/// Largest scrypt parameters accepted when unsealing (1 GiB of memory)
+const MAX_LOG_N: u8 = 20;
+const MAX_R: u32 = 8;
+const MAX_P: u32 = 16;
+
+/// Parameters of scrypt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct KdfParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            log_n: scrypt::Params::RECOMMENDED_LOG_N,
+            r: scrypt::Params::RECOMMENDED_R,
+            p: scrypt::Params::RECOMMENDED_P,
+        }
+    }
+}
+
+impl KdfParams {
+    fn derive_key(&self, passphrase: &[u8], salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, Error> {
+        if self.log_n > MAX_LOG_N || self.r > MAX_R || self.p > MAX_P {
+            return Err(Error::new());
+        }
+
+        let params =
+            scrypt::Params::new(self.log_n, self.r, self.p, 32).map_err(|_| Error::new())?;
+        let mut key = Zeroizing::new([0; 32]);
+        scrypt::scrypt(passphrase, salt, &params, key.as_mut()).map_err(|_| Error::new())?;
+        Ok(key)
+    }
+}
+
+fn header(variant: FaestVariant, params: &KdfParams, salt: &[u8], nonce: &[u8]) -> Vec<u8> {
+    let name = variant.name().as_bytes();
+    let mut header = Vec::with_capacity(MAGIC.len() + 12 + name.len() + SALT_SIZE + NONCE_SIZE);
+    header.extend_from_slice(MAGIC);
+    header.push(VERSION);
+    header.push(name.len() as u8);
+    header.extend_from_slice(name);
+    header.push(KDF_SCRYPT);
+    header.push(params.log_n);
+    header.extend_from_slice(&params.r.to_be_bytes());
+    header.extend_from_slice(&params.p.to_be_bytes());
+    header.extend_from_slice(salt);
+    header.extend_from_slice(nonce);
+    header
+}
+
+/// Encrypt an encoded signing key of `variant`
+pub(crate) fn seal(
+    variant: FaestVariant,
+    sk: &[u8],
+    passphrase: &[u8],
+    params: &KdfParams,
+    mut rng: impl CryptoRngCore,
+) -> Vec<u8> {
+    let mut salt = [0; SALT_SIZE];
+    rng.fill_bytes(&mut salt);
+    let mut nonce = [0; NONCE_SIZE];
+    rng.fill_bytes(&mut nonce);
+
+    // The parameters are either the default ones or have been parsed from a
+    // sealed key, hence they are valid.
+    #[allow(clippy::expect_used)]
+    let key = params
+        .derive_key(passphrase, &salt)
+        .expect("valid KDF parameters");
+    let mut sealed = header(variant, params, &salt, &nonce);
+    // Encryption only fails for messages exceeding the maximum length of
+    // ChaCha20Poly1305, which is far beyond the size of any signing key.
+    #[allow(clippy::expect_used)]
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key.as_ref()))
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: sk,
+                aad: &sealed,
+            },
+        )
+        .expect("encryption does not fail");
+    sealed.extend_from_slice(&ciphertext);
+    sealed
+}
+
+/// Decrypt a sealed signing key of `variant`
+///
+/// A wrong passphrase is reported with the same error as a malformed or
+/// tampered sealed key.
+pub(crate) fn unseal(
+    variant: FaestVariant,
+    passphrase: &[u8],
+    sealed: &[u8],
+) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let name = variant.name().as_bytes();
+    let header_len = MAGIC.len() + 12 + name.len() + SALT_SIZE + NONCE_SIZE;
+    if sealed.len() < header_len {
+        return Err(Error::new());
+    }
+
+    let (header_bytes, ciphertext) = sealed.split_at(header_len);
+    let (prefix, rest) = header_bytes.split_at(MAGIC.len() + 2 + name.len());
+    if prefix[..MAGIC.len()] != MAGIC[..]
+        || prefix[MAGIC.len()] != VERSION
+        || prefix[MAGIC.len() + 1] as usize != name.len()
+        || &prefix[MAGIC.len() + 2..] != name
+        || rest[0] != KDF_SCRYPT
+    {
+        return Err(Error::new());
+    }
+
+    let params = KdfParams {
+        log_n: rest[1],
+        r: u32::from_be_bytes(array::from_fn(|i| rest[2 + i])),
+        p: u32::from_be_bytes(array::from_fn(|i| rest[6 + i])),
+    };
+    let (salt, nonce) = rest[10..].split_at(SALT_SIZE);
+
+    let key = params.derive_key(passphrase, salt)?;
+    ChaCha20Poly1305::new(Key::from_slice(key.as_ref()))
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: header_bytes,
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| Error::new())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::RngCore;
+
+    /// Cheap parameters to keep the tests fast
+    const TEST_PARAMS: KdfParams = KdfParams {
+        log_n: 8,
+        r: 8,
+        p: 1,
+    };
+
+    const PASSPHRASE: &[u8] = b"correct horse battery staple";
+
+    fn random_key() -> Vec<u8> {
+        let mut sk = vec![0; 48];
+        rand::thread_rng().fill_bytes(&mut sk);
+        sk
+    }
+
+    fn seal_for_test(variant: FaestVariant, sk: &[u8]) -> Vec<u8> {
+        seal(variant, sk, PASSPHRASE, &TEST_PARAMS, rand::thread_rng())
+    }
+
+    #[test]
+    fn round_trip() {
+        for variant in FaestVariant::ALL {
+            let sk = random_key();
+            let sealed = seal_for_test(*variant, &sk);
+            assert_eq!(
+                unseal(*variant, PASSPHRASE, &sealed).unwrap().as_slice(),
+                sk.as_slice()
+            );
+        }
+    }
+
+    #[test]
+    fn fresh_salt_and_nonce() {
+        for variant in FaestVariant::ALL {
+            let sk = random_key();
+            assert_ne!(seal_for_test(*variant, &sk), seal_for_test(*variant, &sk));
+        }
+    }
+
+    #[test]
+    fn reject_wrong_passphrase_and_variant() {
+        for variant in FaestVariant::ALL {
+            let sk = random_key();
+            let sealed = seal_for_test(*variant, &sk);
+            assert!(unseal(*variant, b"wrong passphrase", &sealed).is_err());
+            for other in FaestVariant::ALL.iter().filter(|other| *other != variant) {
+                assert!(unseal(*other, PASSPHRASE, &sealed).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn reject_modifications() {
+        // every byte is checked, hence a single variant suffices
+        let Some(&variant) = FaestVariant::ALL.first() else {
+            return;
+        };
+        let sk = random_key();
+        let sealed = seal_for_test(variant, &sk);
+
+        for i in 0..sealed.len() {
+            let mut modified = sealed.clone();
+            modified[i] ^= 1;
+            assert!(
+                unseal(variant, PASSPHRASE, &modified).is_err(),
+                "modification of byte {i} not detected"
+            );
+        }
+        for len in [0, 10, sealed.len() - sk.len() - 16, sealed.len() - 1] {
+            assert!(unseal(variant, PASSPHRASE, &sealed[..len]).is_err());
+        }
+        assert!(unseal(variant, PASSPHRASE, &[sealed.as_slice(), &[0]].concat()).is_err());
+    }
+
+    #[test]
+    fn reject_expensive_parameters() {
+        for variant in FaestVariant::ALL {
+            let sealed = seal_for_test(*variant, &random_key());
+            let log_n_offset = MAGIC.len() + 2 + variant.name().len() + 1;
+            let mut modified = sealed.clone();
+            modified[log_n_offset] = MAX_LOG_N + 1;
+            assert!(unseal(*variant, PASSPHRASE, &modified).is_err());
+        }
+    }
+
+    #[cfg(feature = "faest-128f")]
+    #[test]
+    fn seal_signing_key() {
+        use crate::{FAEST128fSigningKey, KeypairGenerator};
+
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        // use the recommended scrypt parameters once
+        let sealed = sk.seal(PASSPHRASE, rand::thread_rng());
+        assert_eq!(
+            FAEST128fSigningKey::unseal(PASSPHRASE, &sealed).unwrap(),
+            sk
+        );
+        assert!(FAEST128fSigningKey::unseal(b"wrong passphrase", &sealed).is_err());
+    }
+
+    #[cfg(feature = "faest-128f")]
+    #[test]
+    fn reject_invalid_signing_key() {
+        use crate::FAEST128fSigningKey;
+
+        // the zero AES key produces zeros in the S-box inputs
+        let sk = [0; 32];
+        let sealed = seal_for_test(FaestVariant::FAEST128f, &sk);
+        assert!(unseal(FaestVariant::FAEST128f, PASSPHRASE, &sealed).is_ok());
+        assert!(FAEST128fSigningKey::unseal(PASSPHRASE, &sealed).is_err());
+    }
+
+    #[cfg(feature = "faest-128f")]
+    #[test]
+    fn fixture() {
+        use crate::{ByteEncoding, FAEST128fSigningKey};
+
+        let sealed = std::fs::read(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/data/sealed-faest-128f.bin"),
+        )
+        .expect("fixture is available");
+        let sk = FAEST128fSigningKey::unseal(PASSPHRASE, &sealed).expect("fixture unseals");
+        assert_eq!(
+            sk.to_bytes().as_slice(),
+            hex::decode("e93a48f9e93181b86a54a265469d3dad32a2da67c1cb85d788d3b256bf0912bb")
+                .unwrap()
+        );
+    }
+}
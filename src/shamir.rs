@@ -0,0 +1,453 @@
+//! Shamir's secret sharing of signing keys
+//!
+//! The OWF key is shared with a random polynomial over GF(2^λ). The share with
+//! index `i` is the evaluation of the polynomial at the field element encoded
+//! as `i`. Each share also contains the public key to check the reconstructed
+//! signing key. A share is encoded as follows:
+//!
+//! | field      | size (in bytes) | description                       |
+//! |------------|-----------------|-----------------------------------|
+//! | index      | 1               | index of the share, starting at 1 |
+//! | public key | variable        | encoded verification key          |
+//! | value      | λ / 8           | evaluation of the polynomial      |
+
+use std::fmt::{self, Debug};
+
+use generic_array::{typenum::Unsigned, GenericArray};
+use rand_core::CryptoRngCore;
+use subtle::{Choice, ConstantTimeEq};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{
+    fields::{BigGaloisField, Field},
+    internal_keys::{PublicKey, SecretKey},
+    parameter::{BaseParameters, OWFParameters},
+    ByteEncoding, Error,
+};
+
+type FieldOf<O> = <<O as OWFParameters>::BaseParams as BaseParameters>::Field;
+
+/// Share of the OWF key of a secret key
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub(crate) struct KeyShare<O>
+where
+    O: OWFParameters,
+{
+    index: u8,
+    value: GenericArray<u8, O::LAMBDABYTES>,
+    #[cfg_attr(feature = "zeroize", zeroize(skip))]
+    pk: PublicKey<O>,
+}
+
+impl<O> KeyShare<O>
+where
+    O: OWFParameters,
+{
+    /// Index of the share
+    pub(crate) fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Size of the encoded share (in bytes)
+    pub(crate) fn encoded_len() -> usize {
+        1 + O::PK::USIZE + O::LAMBDABYTES::USIZE
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(Self::encoded_len());
+        buf.push(self.index);
+        buf.extend_from_slice(&self.pk.to_bytes());
+        buf.extend_from_slice(&self.value);
+        buf
+    }
+}
+
+impl<O> TryFrom<&[u8]> for KeyShare<O>
+where
+    O: OWFParameters,
+{
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != Self::encoded_len() || bytes[0] == 0 {
+            return Err(Error::new());
+        }
+
+        let (pk, value) = bytes[1..].split_at(O::PK::USIZE);
+        Ok(Self {
+            index: bytes[0],
+            value: GenericArray::from_slice(value).clone(),
+            pk: PublicKey::try_from(pk)?,
+        })
+    }
+}
+
+impl<O> Clone for KeyShare<O>
+where
+    O: OWFParameters,
+{
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index,
+            value: self.value.clone(),
+            pk: self.pk.clone(),
+        }
+    }
+}
+
+impl<O> ConstantTimeEq for KeyShare<O>
+where
+    O: OWFParameters,
+{
+    fn ct_eq(&self, rhs: &Self) -> Choice {
+        // the public key is public
+        self.index.ct_eq(&rhs.index)
+            & self.value.as_slice().ct_eq(rhs.value.as_slice())
+            & Choice::from(u8::from(self.pk == rhs.pk))
+    }
+}
+
+impl<O> PartialEq for KeyShare<O>
+where
+    O: OWFParameters,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        self.ct_eq(rhs).into()
+    }
+}
+
+impl<O> Eq for KeyShare<O> where O: OWFParameters {}
+
+impl<O> Debug for KeyShare<O>
+where
+    O: OWFParameters,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyShare")
+            .field("index", &self.index)
+            .field("value", &"redacted")
+            .field("pk", &self.pk)
+            .finish()
+    }
+}
+
+/// Field element representing the index of a share
+fn field_from_index<F>(index: u8) -> F
+where
+    F: BigGaloisField,
+{
+    let mut bytes = GenericArray::<u8, F::Length>::default();
+    bytes[0] = index;
+    F::from(bytes.as_slice())
+}
+
+/// Invert a non-zero element by computing `x^(2^λ - 2)`
+///
+/// The exponent is `2 (2^(λ - 1) - 1)`, i.e., `λ - 1` one bits followed by a
+/// zero bit. The one bits are processed in chunks of 63 bits such that each
+/// chunk fits into the exponent of [`BigGaloisField::pow`].
+fn invert<F>(mut x: F) -> F
+where
+    F: BigGaloisField,
+{
+    const CHUNK: usize = 63;
+
+    let ones = F::Length::USIZE * 8 - 1;
+    let mut result = F::ONE;
+    for _ in 0..ones / CHUNK {
+        result *= x.pow((1 << CHUNK) - 1);
+        x = x.pow(1 << CHUNK);
+    }
+    result *= x.pow((1 << (ones % CHUNK)) - 1);
+    result.square()
+}
+
+/// Evaluate the polynomial interpolating `points` at `x`
+///
+/// The values of the points are given as bytes and only converted to field
+/// elements when needed, such that no copies of them are kept. Returns `None`
+/// if a value is not of the length of a field element.
+fn interpolate<F>(points: &[(F, &[u8])], x: F) -> Option<F>
+where
+    F: BigGaloisField,
+{
+    points
+        .iter()
+        .enumerate()
+        .map(|(j, (x_j, y_j))| {
+            let others = points
+                .iter()
+                .enumerate()
+                .filter(|(m, _)| *m != j)
+                .map(|(_, (x_m, _))| x_m);
+            let numerator: F = others.clone().map(|x_m| x - x_m).product();
+            let denominator: F = others.map(|x_m| *x_j - x_m).product();
+            F::from_bytes_checked(y_j).map(|y_j| y_j * numerator * invert(denominator))
+        })
+        .sum()
+}
+
+/// Split the OWF key of `sk` into `n` shares of which `threshold` are
+/// required to reconstruct it
+pub(crate) fn split_shares<O>(
+    sk: &SecretKey<O>,
+    n: usize,
+    threshold: usize,
+    mut rng: impl CryptoRngCore,
+) -> Result<Vec<KeyShare<O>>, Error>
+where
+    O: OWFParameters,
+{
+    if threshold == 0 || threshold > n || n > u8::MAX as usize {
+        return Err(Error::new());
+    }
+
+    // coefficients of the polynomial, the constant term is the OWF key
+    let mut coefficients = vec![sk.owf_key.clone()];
+    coefficients.resize_with(threshold, || {
+        let mut coefficient = GenericArray::default();
+        rng.fill_bytes(&mut coefficient);
+        coefficient
+    });
+
+    let shares = (1..=n as u8)
+        .map(|index| {
+            let x = field_from_index::<FieldOf<O>>(index);
+            #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+            let mut value = coefficients
+                .iter()
+                .rev()
+                .fold(FieldOf::<O>::ZERO, |acc, coefficient| {
+                    acc * x + FieldOf::<O>::from(coefficient.as_slice())
+                })
+                .as_bytes();
+            let share = KeyShare {
+                index,
+                value: GenericArray::from_slice(&value).clone(),
+                pk: sk.pk.clone(),
+            };
+            #[cfg(feature = "zeroize")]
+            value.as_mut_slice().zeroize();
+            share
+        })
+        .collect();
+
+    #[cfg(feature = "zeroize")]
+    coefficients
+        .iter_mut()
+        .for_each(|coefficient| coefficient.zeroize());
+    Ok(shares)
+}
+
+/// Reconstruct a secret key from its shares
+///
+/// Fails if the shares have duplicate indices, belong to different keys or do
+/// not reconstruct a key matching the public key, e.g., since there are fewer
+/// shares than the threshold.
+pub(crate) fn reconstruct_key<'a, O>(
+    shares: impl IntoIterator<Item = &'a KeyShare<O>>,
+) -> Result<SecretKey<O>, Error>
+where
+    O: OWFParameters + 'a,
+{
+    let shares: Vec<_> = shares.into_iter().collect();
+    let Some(first) = shares.first() else {
+        return Err(Error::new());
+    };
+    for (i, share) in shares.iter().enumerate() {
+        if share.pk != first.pk || shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(Error::new());
+        }
+    }
+
+    let points: Vec<_> = shares
+        .iter()
+        .map(|share| {
+            (
+                field_from_index::<FieldOf<O>>(share.index),
+                share.value.as_slice(),
+            )
+        })
+        .collect();
+    let owf_key = interpolate(&points, FieldOf::<O>::ZERO).ok_or_else(Error::new)?;
+
+    // the key is written directly into the encoded secret key to avoid
+    // further copies of it
+    let mut sk_bytes = GenericArray::<u8, O::SK>::default();
+    let (sk_input, sk_key) = sk_bytes.split_at_mut(O::InputSize::USIZE);
+    sk_input.copy_from_slice(first.pk.owf_input());
+    owf_key.to_bytes_into(sk_key);
+    let sk = SecretKey::<O>::try_from(sk_bytes.as_slice());
+    #[cfg(feature = "zeroize")]
+    sk_bytes.zeroize();
+    sk.and_then(|sk| {
+        if sk.pk == first.pk {
+            Ok(sk)
+        } else {
+            Err(Error::new())
+        }
+    })
+}
+
+#[cfg(test)]
+#[generic_tests::define]
+mod test {
+    use super::*;
+
+    use rand::RngCore;
+
+    use crate::{
+        fields::Square,
+        parameter::{OWF128, OWF128EM, OWF192, OWF192EM, OWF256, OWF256EM},
+    };
+
+    const N: usize = 5;
+    const THRESHOLD: usize = 3;
+
+    /// All subsets of `shares` with `k` elements
+    fn subsets<T>(shares: &[T], k: usize) -> impl Iterator<Item = Vec<&T>> {
+        (0u32..1 << shares.len())
+            .filter(move |mask| mask.count_ones() as usize == k)
+            .map(move |mask| {
+                shares
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| mask & (1 << i) != 0)
+                    .map(|(_, share)| share)
+                    .collect()
+            })
+    }
+
+    #[test]
+    fn inversion<O: OWFParameters>() {
+        let one = FieldOf::<O>::ONE.as_bytes();
+        for index in 1..=u8::MAX {
+            let x = field_from_index::<FieldOf<O>>(index);
+            assert_eq!((x * invert(x)).as_bytes(), one);
+        }
+
+        let mut bytes = GenericArray::<u8, O::LAMBDABYTES>::default();
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let x = FieldOf::<O>::from(bytes.as_slice());
+        assert_eq!((x * invert(x)).as_bytes(), one);
+
+        // compare with plain square-and-multiply of x^(2^λ - 2)
+        let mut power = x;
+        let mut expected = FieldOf::<O>::ONE;
+        for _ in 1..O::LAMBDABYTES::USIZE * 8 {
+            power = power.square();
+            expected *= power;
+        }
+        assert_eq!(invert(x).as_bytes(), expected.as_bytes());
+    }
+
+    #[test]
+    fn reconstruct_from_any_subset<O: OWFParameters>() {
+        let sk = O::keygen_with_rng(rand::thread_rng());
+        let shares = split_shares(&sk, N, THRESHOLD, rand::thread_rng()).unwrap();
+        assert_eq!(shares.len(), N);
+
+        for k in THRESHOLD..=N {
+            for subset in subsets(&shares, k) {
+                assert_eq!(reconstruct_key(subset).unwrap(), sk);
+            }
+        }
+        for subset in subsets(&shares, THRESHOLD - 1) {
+            assert!(reconstruct_key(subset).is_err());
+        }
+    }
+
+    #[test]
+    fn insufficient_shares_reveal_nothing<O: OWFParameters>() {
+        let sk = O::keygen_with_rng(rand::thread_rng());
+        let other_sk = O::keygen_with_rng(rand::thread_rng());
+        let shares = split_shares(&sk, N, THRESHOLD, rand::thread_rng()).unwrap();
+
+        // the shares are consistent with a polynomial for any other key
+        let mut points = vec![(FieldOf::<O>::ZERO, other_sk.owf_key.as_slice())];
+        points.extend(shares[..THRESHOLD - 1].iter().map(|share| {
+            (
+                field_from_index::<FieldOf<O>>(share.index),
+                share.value.as_slice(),
+            )
+        }));
+        let index = THRESHOLD as u8;
+        let value = interpolate(&points, field_from_index(index))
+            .unwrap()
+            .as_bytes();
+
+        let mut other_shares: Vec<_> = shares[..THRESHOLD - 1]
+            .iter()
+            .map(|share| KeyShare {
+                index: share.index,
+                value: share.value.clone(),
+                pk: other_sk.pk.clone(),
+            })
+            .collect();
+        other_shares.push(KeyShare {
+            index,
+            value: GenericArray::from_slice(&value).clone(),
+            pk: other_sk.pk.clone(),
+        });
+        assert_eq!(reconstruct_key(&other_shares).unwrap(), other_sk);
+    }
+
+    #[test]
+    fn reject_invalid<O: OWFParameters>() {
+        let sk = O::keygen_with_rng(rand::thread_rng());
+        assert!(split_shares(&sk, N, 0, rand::thread_rng()).is_err());
+        assert!(split_shares(&sk, N, N + 1, rand::thread_rng()).is_err());
+        assert!(split_shares(&sk, 256, 2, rand::thread_rng()).is_err());
+        assert!(reconstruct_key::<O>(&[]).is_err());
+
+        let shares = split_shares(&sk, N, THRESHOLD, rand::thread_rng()).unwrap();
+        // duplicate index
+        assert!(reconstruct_key([&shares[0], &shares[1], &shares[1]]).is_err());
+        // shares of different keys
+        let other_sk = O::keygen_with_rng(rand::thread_rng());
+        let other_shares = split_shares(&other_sk, N, THRESHOLD, rand::thread_rng()).unwrap();
+        assert!(reconstruct_key([&shares[0], &shares[1], &other_shares[2]]).is_err());
+        // modified share
+        let mut modified = shares[2].clone();
+        modified.value[0] ^= 1;
+        assert!(bool::from(shares[2].ct_eq(&shares[2].clone())));
+        assert!(!bool::from(modified.ct_eq(&shares[2])));
+        assert_ne!(modified, shares[2]);
+        assert!(reconstruct_key([&shares[0], &shares[1], &modified]).is_err());
+    }
+
+    #[test]
+    fn encoding<O: OWFParameters>() {
+        let sk = O::keygen_with_rng(rand::thread_rng());
+        let shares = split_shares(&sk, N, THRESHOLD, rand::thread_rng()).unwrap();
+        for share in &shares {
+            let encoded = share.to_vec();
+            assert_eq!(encoded.len(), KeyShare::<O>::encoded_len());
+            assert_eq!(&KeyShare::<O>::try_from(encoded.as_slice()).unwrap(), share);
+
+            assert!(KeyShare::<O>::try_from(&encoded[1..]).is_err());
+            let mut encoded = encoded;
+            encoded[0] = 0;
+            assert!(KeyShare::<O>::try_from(encoded.as_slice()).is_err());
+        }
+    }
+
+    #[instantiate_tests(<OWF128>)]
+    mod owf_128 {}
+
+    #[instantiate_tests(<OWF192>)]
+    mod owf_192 {}
+
+    #[instantiate_tests(<OWF256>)]
+    mod owf_256 {}
+
+    #[instantiate_tests(<OWF128EM>)]
+    mod owf_em_128 {}
+
+    #[instantiate_tests(<OWF192EM>)]
+    mod owf_em_192 {}
+
+    #[instantiate_tests(<OWF256EM>)]
+    mod owf_em_256 {}
+}
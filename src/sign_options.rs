@@ -0,0 +1,422 @@
+//! Options for signing
+//!
+//! [`SignOptions`] collects the optional inputs of a signing operation, i.e.,
+//! the source of the randomness `ρ`, a context string, a progress callback, a
+//! [`BufferPool`] for the scratch buffers and, with the `parallel` feature,
+//! whether the VOLE commitments are computed in parallel. Invalid combinations
+//! of options are reported as [`InvalidSignOptions`].
+//! The options are passed to `sign_with_options` of the signing keys. With the
+//! default options, the signature is identical to the one produced by
+//! [`Signer::sign`](crate::Signer::sign).
+//!
+//! If a non-empty context is set, the message hash `µ` of the signature is
+//! computed on
+//!
+//! `OWF input || OWF output || 0x01 || len(context) || context || msg`
+//!
+//! where the length is encoded as a single byte. This hash uses a separator
+//! distinct from the one of plain signatures. Hence, a signature with a
+//! context is never valid for any plain message or for another context, even
+//! if the plain message starts with an encoded context. Such signatures are
+//! verified with `verify_with_context` of the verification keys.
+//!
+//! ```
+//! # #[cfg(feature = "faest-128f")] {
+//! use faest::{FAEST128fSigningKey, Keypair, KeypairGenerator, SignOptions};
+//!
+//! let mut rng = rand::thread_rng();
+//! let sk = FAEST128fSigningKey::generate(&mut rng);
+//! let msg = "some message".as_bytes();
+//! let options = SignOptions::new().with_rng(&mut rng).context(b"example");
+//! let signature = sk.sign_with_options(msg, options).expect("valid options");
+//! sk.verifying_key()
+//!     .verify_with_context(msg, b"example", &signature)
+//!     .expect("Verification failed");
+//! # }
+//! ```
+
+#[cfg(feature = "sign")]
+use std::fmt;
+
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use generic_array::{ArrayLength, GenericArray};
+#[cfg(feature = "sign")]
+use rand_core::CryptoRngCore;
+
+#[cfg(feature = "_variant")]
+use crate::faest::Domain;
+#[cfg(feature = "sign")]
+use crate::Error;
+#[cfg(feature = "sign")]
+use crate::{BufferPool, Phase};
+
+/// Maximal length of a context string (in bytes)
+pub const MAX_CONTEXT_LENGTH: usize = u8::MAX as usize;
+
+/// Builder for the options of a signing operation
+///
+/// Per default, signing is deterministic, i.e., no additional randomness `ρ`
+/// is used, without a context, without reporting progress and with freshly
+/// allocated scratch buffers. With the `parallel` feature, the VOLE
+/// commitments are computed in parallel per default.
+#[cfg(feature = "sign")]
+#[derive(Default)]
+pub struct SignOptions<'a> {
+    deterministic: bool,
+    rng: Option<&'a mut dyn CryptoRngCore>,
+    context: &'a [u8],
+    progress: Option<&'a mut dyn FnMut(Phase, f32)>,
+    buffer_pool: Option<&'a BufferPool>,
+    sequential: bool,
+}
+
+#[cfg(feature = "sign")]
+impl<'a> SignOptions<'a> {
+    /// Default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sign deterministically, i.e., without additional randomness
+    ///
+    /// Signing fails if an RNG is also set with [`Self::with_rng`].
+    pub fn deterministic(mut self) -> Self {
+        self.deterministic = true;
+        self
+    }
+
+    /// Sample the additional randomness `ρ` from `rng`
+    ///
+    /// The randomness hedges against fault attacks. Signing fails if
+    /// [`Self::deterministic`] is also set.
+    pub fn with_rng(mut self, rng: &'a mut dyn CryptoRngCore) -> Self {
+        self.rng = Some(rng);
+        self
+    }
+
+    /// Bind the signature to `context`
+    ///
+    /// Contexts longer than [`MAX_CONTEXT_LENGTH`] bytes are rejected when
+    /// signing. The empty context is identical to not setting a context.
+    pub fn context(mut self, context: &'a [u8]) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Report the progress to `progress`
+    ///
+    /// See `sign_with_progress` of the signing keys for the reported values.
+    pub fn progress(mut self, progress: &'a mut dyn FnMut(Phase, f32)) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Take the large scratch buffers from `pool`
+    ///
+    /// The buffers are zeroized and returned to the pool after signing. The
+    /// signature does not depend on the pool.
+    pub fn buffer_pool(mut self, pool: &'a BufferPool) -> Self {
+        self.buffer_pool = Some(pool);
+        self
+    }
+
+    /// Compute the VOLE commitments in parallel if `parallel` is set
+    ///
+    /// Sequential signing avoids occupying the threads of the Rayon thread
+    /// pool, e.g., if signatures are already computed in parallel. The
+    /// signature does not depend on this option.
+    #[cfg(feature = "parallel")]
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.sequential = !parallel;
+        self
+    }
+
+    /// Whether the VOLE commitments are computed in parallel
+    pub(crate) fn is_parallel(&self) -> bool {
+        cfg!(feature = "parallel") && !self.sequential
+    }
+}
+
+#[cfg(all(feature = "sign", feature = "_variant"))]
+impl<'a> SignOptions<'a> {
+    /// Sample `ρ` if an RNG is set
+    ///
+    /// Fails if deterministic signing was requested as well.
+    pub(crate) fn rho<N: ArrayLength>(
+        &mut self,
+    ) -> Result<Option<GenericArray<u8, N>>, InvalidSignOptions> {
+        match (self.deterministic, self.rng.as_mut()) {
+            (true, Some(_)) => Err(InvalidSignOptions::ConflictingRandomness),
+            (_, rng) => Ok(rng.map(|rng| {
+                let mut rho = GenericArray::default();
+                rng.fill_bytes(&mut rho);
+                rho
+            })),
+        }
+    }
+
+    /// Take the progress callback
+    pub(crate) fn take_progress(&mut self) -> Option<&'a mut dyn FnMut(Phase, f32)> {
+        self.progress.take()
+    }
+
+    /// Pool of the scratch buffers, if set
+    pub(crate) fn pool(&self) -> Option<&'a BufferPool> {
+        self.buffer_pool
+    }
+
+    /// Domain of the message to sign
+    pub(crate) fn domain(&self) -> Result<Domain<'a>, InvalidSignOptions> {
+        context_domain(self.context).ok_or(InvalidSignOptions::ContextTooLong)
+    }
+}
+
+#[cfg(feature = "sign")]
+impl fmt::Debug for SignOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignOptions")
+            .field("deterministic", &self.deterministic)
+            .field("randomized", &self.rng.is_some())
+            .field("context", &self.context)
+            .field("progress", &self.progress.is_some())
+            .field("buffer_pool", &self.buffer_pool)
+            .field("parallel", &self.is_parallel())
+            .finish()
+    }
+}
+
+/// Reason for rejecting the [`SignOptions`] of a signing operation
+#[cfg(feature = "sign")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidSignOptions {
+    /// Both [`SignOptions::deterministic`] and [`SignOptions::with_rng`] were
+    /// set
+    ConflictingRandomness,
+    /// The context is longer than [`MAX_CONTEXT_LENGTH`] bytes
+    ContextTooLong,
+}
+
+#[cfg(feature = "sign")]
+impl fmt::Display for InvalidSignOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ConflictingRandomness => {
+                f.write_str("both deterministic and randomized signing requested")
+            }
+            Self::ContextTooLong => write!(f, "context is longer than {MAX_CONTEXT_LENGTH} bytes"),
+        }
+    }
+}
+
+#[cfg(feature = "sign")]
+impl std::error::Error for InvalidSignOptions {}
+
+#[cfg(feature = "sign")]
+impl From<InvalidSignOptions> for Error {
+    fn from(_: InvalidSignOptions) -> Self {
+        Error::new()
+    }
+}
+
+/// Domain of messages bound to `context`
+///
+/// Returns `None` if the context is longer than [`MAX_CONTEXT_LENGTH`] bytes.
+#[cfg(feature = "_variant")]
+pub(crate) fn context_domain(context: &[u8]) -> Option<Domain<'_>> {
+    match context.len() {
+        0 => Some(Domain::Plain),
+        1..=MAX_CONTEXT_LENGTH => Some(Domain::Context(context)),
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "faest-128f"))]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "randomized-signer")]
+    use rand::SeedableRng;
+
+    #[cfg(feature = "randomized-signer")]
+    use crate::RandomizedSigner;
+    use crate::{
+        FAEST128fSignature, FAEST128fSigningKey, Keypair, KeypairGenerator, Signer, Verifier,
+    };
+
+    const TEST_MESSAGE: &[u8] = "test message".as_bytes();
+
+    #[test]
+    fn default_options() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let signature: FAEST128fSignature = sk.sign(TEST_MESSAGE);
+        assert_eq!(
+            sk.sign_with_options(TEST_MESSAGE, SignOptions::new())
+                .unwrap(),
+            signature
+        );
+
+        assert_eq!(
+            sk.sign_with_options(TEST_MESSAGE, SignOptions::new().deterministic())
+                .unwrap(),
+            signature
+        );
+
+        // conflicting choices of the randomness are rejected
+        let mut rng = rand::thread_rng();
+        let options = SignOptions::new().with_rng(&mut rng).deterministic();
+        assert_eq!(
+            sk.sign_with_options(TEST_MESSAGE, options),
+            Err(InvalidSignOptions::ConflictingRandomness)
+        );
+        let options = SignOptions::new().deterministic().with_rng(&mut rng);
+        assert_eq!(
+            sk.sign_with_options(TEST_MESSAGE, options),
+            Err(InvalidSignOptions::ConflictingRandomness)
+        );
+    }
+
+    #[cfg(feature = "randomized-signer")]
+    #[test]
+    fn randomized() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(42);
+        let signature = sk
+            .sign_with_options(TEST_MESSAGE, SignOptions::new().with_rng(&mut rng))
+            .unwrap();
+        let expected: FAEST128fSignature = sk.sign_with_rng(
+            &mut rand_chacha::ChaCha8Rng::seed_from_u64(42),
+            TEST_MESSAGE,
+        );
+        assert_eq!(signature, expected);
+        assert_ne!(signature, sk.sign(TEST_MESSAGE));
+    }
+
+    #[test]
+    fn context() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let vk = sk.verifying_key();
+        let signature = sk
+            .sign_with_options(TEST_MESSAGE, SignOptions::new().context(b"context"))
+            .unwrap();
+        assert!(vk
+            .verify_with_context(TEST_MESSAGE, b"context", &signature)
+            .is_ok());
+        assert!(vk
+            .verify_with_context(TEST_MESSAGE, b"other context", &signature)
+            .is_err());
+        assert!(vk.verify(TEST_MESSAGE, &signature).is_err());
+
+        // the empty context is the plain message
+        let signature = sk
+            .sign_with_options(TEST_MESSAGE, SignOptions::new().context(b""))
+            .unwrap();
+        assert!(vk.verify(TEST_MESSAGE, &signature).is_ok());
+        assert!(vk
+            .verify_with_context(TEST_MESSAGE, b"", &signature)
+            .is_ok());
+
+        // a plain signature on the encoding of the context is not a signature
+        // with the context
+        let signature: FAEST128fSignature =
+            sk.sign(&[[1, 7].as_slice(), b"context", TEST_MESSAGE].concat());
+        assert!(vk
+            .verify_with_context(TEST_MESSAGE, b"context", &signature)
+            .is_err());
+
+        // too long contexts
+        let context = [0; MAX_CONTEXT_LENGTH + 1];
+        assert_eq!(
+            sk.sign_with_options(TEST_MESSAGE, SignOptions::new().context(&context)),
+            Err(InvalidSignOptions::ContextTooLong)
+        );
+        assert!(vk
+            .verify_with_context(TEST_MESSAGE, &context, &signature)
+            .is_err());
+        let context = [0; MAX_CONTEXT_LENGTH];
+        let signature = sk
+            .sign_with_options(TEST_MESSAGE, SignOptions::new().context(&context))
+            .unwrap();
+        assert!(vk
+            .verify_with_context(TEST_MESSAGE, &context, &signature)
+            .is_ok());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let signature: FAEST128fSignature = sk.sign(TEST_MESSAGE);
+        for parallel in [false, true] {
+            let options = SignOptions::new().context(b"context").parallel(parallel);
+            assert_eq!(options.is_parallel(), parallel);
+            let signature_with_context = sk.sign_with_options(TEST_MESSAGE, options).unwrap();
+            assert!(sk
+                .verifying_key()
+                .verify_with_context(TEST_MESSAGE, b"context", &signature_with_context)
+                .is_ok());
+
+            assert_eq!(
+                sk.sign_with_options(TEST_MESSAGE, SignOptions::new().parallel(parallel))
+                    .unwrap(),
+                signature
+            );
+        }
+        assert!(SignOptions::new().is_parallel());
+    }
+
+    #[test]
+    fn progress() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let mut records = Vec::new();
+        let mut progress = |phase, progress| records.push((phase, progress));
+        let signature = sk
+            .sign_with_options(TEST_MESSAGE, SignOptions::new().progress(&mut progress))
+            .unwrap();
+        assert_eq!(signature, sk.sign(TEST_MESSAGE));
+        assert_eq!(
+            records.first().map(|(phase, _)| *phase),
+            Some(Phase::WitnessExpansion)
+        );
+        assert_eq!(records.last(), Some(&(Phase::Openings, 1.0)));
+    }
+
+    #[test]
+    fn buffer_pool() {
+        let pool = BufferPool::new(1 << 20);
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let signature: FAEST128fSignature = sk.sign(TEST_MESSAGE);
+        for _ in 0..2 {
+            assert_eq!(
+                sk.sign_with_options(TEST_MESSAGE, SignOptions::new().buffer_pool(&pool))
+                    .unwrap(),
+                signature
+            );
+        }
+        let pooled_bytes = pool.pooled_bytes();
+        assert!(pooled_bytes > 0);
+
+        // the buffers are shared with other parameter sets
+        #[cfg(feature = "faest-em-128f")]
+        {
+            use crate::{FAESTEM128fSignature, FAESTEM128fSigningKey};
+
+            let sk = FAESTEM128fSigningKey::generate(rand::thread_rng());
+            let signature: FAESTEM128fSignature = sk.sign(TEST_MESSAGE);
+            assert_eq!(
+                sk.sign_with_options(TEST_MESSAGE, SignOptions::new().buffer_pool(&pool))
+                    .unwrap(),
+                signature
+            );
+            assert!(pool.pooled_bytes() >= pooled_bytes);
+        }
+
+        // the pool does not exceed its bound
+        let pool = BufferPool::new(0);
+        assert_eq!(
+            sk.sign_with_options(TEST_MESSAGE, SignOptions::new().buffer_pool(&pool))
+                .unwrap(),
+            signature
+        );
+        assert_eq!(pool.pooled_bytes(), 0);
+    }
+}
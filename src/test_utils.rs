@@ -0,0 +1,241 @@
+//! Utilities for conformance tests
+//!
+//! The official known answer tests of FAEST are generated with the
+//! deterministic random bit generator of the NIST PQC project, i.e., AES-256 in
+//! counter mode as specified in `rng.c` of the NIST reference code.
+//! [`NistDrbg`] implements this generator to reproduce the keys and
+//! signatures of the `.rsp` files.
+//!
+//! ```
+//! # #[cfg(feature = "faest-128f")] {
+//! use faest::{test_utils::NistDrbg, FAEST128fSigningKey, KeypairGenerator};
+//!
+//! let mut rng = NistDrbg::init(&[0; 48]);
+//! let sk = FAEST128fSigningKey::generate(&mut rng);
+//! # }
+//! ```
+//!
+//! For benchmarks and examples, [`keygen_with_seed`] and [`test_signature`]
+//! produce reproducible keys and signatures from a `u64` seed:
+//!
+//! ```
+//! # #[cfg(feature = "faest-128f")] {
+//! use faest::{test_utils, FAEST128fSignature, FAEST128fSigningKey, Keypair, Verifier};
+//!
+//! let sk: FAEST128fSigningKey = test_utils::keygen_with_seed(42);
+//! let signature: FAEST128fSignature =
+//!     test_utils::test_signature::<FAEST128fSigningKey, _>(42, b"message");
+//! assert!(sk.verifying_key().verify(b"message", &signature).is_ok());
+//! # }
+//! ```
+
+use aes::cipher::{generic_array::GenericArray, BlockEncrypt, KeyInit};
+use rand_core::{impls, CryptoRng, Error, RngCore};
+
+use crate::{aes_backend::Aes256, KeypairGenerator, Signer};
+
+/// Size of the seed (in bytes)
+pub const SEED_SIZE: usize = 48;
+
+/// The AES-256-CTR DRBG of the NIST PQC project
+///
+/// Every call to [`RngCore::fill_bytes`] corresponds to one call of
+/// `randombytes` of the reference code, i.e., the state is updated after each
+/// call. Hence, the output depends on the granularity of the requests.
+#[derive(Debug, Clone)]
+pub struct NistDrbg {
+    key: [u8; 32],
+    v: [u8; 16],
+}
+
+impl NistDrbg {
+    /// Instantiate the generator from a seed, i.e., `randombytes_init`
+    pub fn init(seed: &[u8; SEED_SIZE]) -> Self {
+        let mut drbg = Self {
+            key: [0; 32],
+            v: [0; 16],
+        };
+        drbg.update(Some(seed));
+        drbg
+    }
+
+    fn increment_v(&mut self) {
+        self.v = (u128::from_be_bytes(self.v).wrapping_add(1)).to_be_bytes();
+    }
+
+    fn next_block(&mut self, cipher: &Aes256) -> GenericArray<u8, aes::cipher::consts::U16> {
+        self.increment_v();
+        let mut block = GenericArray::from(self.v);
+        cipher.encrypt_block(&mut block);
+        block
+    }
+
+    /// `AES256_CTR_DRBG_Update`
+    fn update(&mut self, provided_data: Option<&[u8; SEED_SIZE]>) {
+        let cipher = Aes256::new(GenericArray::from_slice(&self.key));
+        let mut temp = [0; SEED_SIZE];
+        for chunk in temp.chunks_exact_mut(16) {
+            chunk.copy_from_slice(&self.next_block(&cipher));
+        }
+        if let Some(provided_data) = provided_data {
+            temp.iter_mut()
+                .zip(provided_data)
+                .for_each(|(t, d)| *t ^= d);
+        }
+        self.key.copy_from_slice(&temp[..32]);
+        self.v.copy_from_slice(&temp[32..]);
+    }
+}
+
+impl RngCore for NistDrbg {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let cipher = Aes256::new(GenericArray::from_slice(&self.key));
+        for chunk in dest.chunks_mut(16) {
+            let block = self.next_block(&cipher);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        self.update(None);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for NistDrbg {}
+
+/// Generate a key from a `u64` seed
+///
+/// The key is generated with [`NistDrbg`] seeded with the little-endian
+/// encoding of `seed`, hence the same seed always yields the same key. Only
+/// use this in tests, benchmarks and examples.
+pub fn keygen_with_seed<K>(seed: u64) -> K
+where
+    K: KeypairGenerator,
+{
+    let mut drbg_seed = [0; SEED_SIZE];
+    drbg_seed[..8].copy_from_slice(&seed.to_le_bytes());
+    K::generate(NistDrbg::init(&drbg_seed))
+}
+
+/// Sign `msg` with the key generated by [`keygen_with_seed`] from `seed`
+///
+/// The signature is deterministic, i.e., it only depends on `seed` and `msg`.
+pub fn test_signature<K, S>(seed: u64, msg: &[u8]) -> S
+where
+    K: KeypairGenerator + Signer<S>,
+{
+    keygen_with_seed::<K>(seed).sign(msg)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(any(feature = "faest-128f", feature = "faest-em-192s"))]
+    use std::{fs, path::Path};
+
+    use nist_pqc_seeded_rng::NistPqcAes256CtrRng;
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn matches_reference() {
+        let seed: [u8; SEED_SIZE] = core::array::from_fn(|i| i as u8);
+        let mut rng = NistDrbg::init(&seed);
+        let mut expected = NistPqcAes256CtrRng::from_seed(seed.into());
+
+        for len in [0, 1, 16, 17, 48, 100] {
+            let mut output = vec![0; len];
+            let mut expected_output = vec![0; len];
+            rng.fill_bytes(&mut output);
+            expected.fill_bytes(&mut expected_output);
+            assert_eq!(output, expected_output);
+        }
+        assert_eq!(rng.next_u64(), expected.next_u64());
+    }
+
+    /// Read `(seed, pk, sk)` of the first `n` entries of a `.rsp` file
+    #[cfg(any(feature = "faest-128f", feature = "faest-em-192s"))]
+    fn read_keys(rsp: &str, n: usize) -> Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+        let Ok(data) = fs::read_to_string(path.join(rsp))
+            .or_else(|_| fs::read_to_string(path.join(format!("reduced_{rsp}"))))
+        else {
+            println!("Test file {} is not available. Skipping test.", rsp);
+            return Vec::new();
+        };
+
+        let value = |line: &str| hex::decode(line.split_once(" = ").unwrap().1).unwrap();
+        let mut keys = Vec::new();
+        let (mut seed, mut pk) = (Vec::new(), Vec::new());
+        for line in data.lines() {
+            if line.starts_with("seed = ") {
+                seed = value(line);
+            } else if line.starts_with("pk = ") {
+                pk = value(line);
+            } else if line.starts_with("sk = ") {
+                keys.push((seed.clone(), pk.clone(), value(line)));
+                if keys.len() == n {
+                    break;
+                }
+            }
+        }
+        keys
+    }
+
+    #[cfg(feature = "faest-128f")]
+    #[test]
+    fn faest_128f_keys() {
+        use crate::{ByteEncoding, FAEST128fSigningKey, Keypair, KeypairGenerator};
+
+        for (seed, pk, sk) in read_keys("PQCsignKAT_faest_128f.rsp", 5) {
+            let mut rng = NistDrbg::init(seed.as_slice().try_into().unwrap());
+            let key = FAEST128fSigningKey::generate(&mut rng);
+            assert_eq!(key.to_vec(), sk);
+            assert_eq!(key.verifying_key().to_vec(), pk);
+        }
+    }
+
+    #[cfg(feature = "faest-128f")]
+    #[test]
+    fn seeded_keys_and_signatures() {
+        use crate::{FAEST128fSignature, FAEST128fSigningKey, Keypair, Verifier};
+
+        let sk: FAEST128fSigningKey = keygen_with_seed(1);
+        assert_eq!(sk, keygen_with_seed(1));
+        assert_ne!(sk, keygen_with_seed(2));
+
+        let signature: FAEST128fSignature = test_signature::<FAEST128fSigningKey, _>(1, b"message");
+        assert_eq!(
+            signature,
+            test_signature::<FAEST128fSigningKey, _>(1, b"message")
+        );
+        assert!(sk.verifying_key().verify(b"message", &signature).is_ok());
+        assert!(sk
+            .verifying_key()
+            .verify(b"other message", &signature)
+            .is_err());
+    }
+
+    #[cfg(feature = "faest-em-192s")]
+    #[test]
+    fn faest_em_192s_keys() {
+        use crate::{ByteEncoding, FAESTEM192sSigningKey, Keypair, KeypairGenerator};
+
+        for (seed, pk, sk) in read_keys("PQCsignKAT_faest_em_192s.rsp", 5) {
+            let mut rng = NistDrbg::init(seed.as_slice().try_into().unwrap());
+            let key = FAESTEM192sSigningKey::generate(&mut rng);
+            assert_eq!(key.to_vec(), sk);
+            assert_eq!(key.verifying_key().to_vec(), pk);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Asynchronous signing for Tokio-based servers
+//!
+//! Signing takes several milliseconds and would block the async runtime.
+//! [`ThreadPoolSigningService`] moves the signing operations to the blocking
+//! thread pool of Tokio via [`tokio::task::spawn_blocking`].
+//!
+//! ```
+//! # #[cfg(feature = "faest-128f")] {
+//! use faest::{FAEST128fSigningKey, FAEST128fSignature, KeypairGenerator};
+//! use faest::tokio_compat::{SigningService, ThreadPoolSigningService};
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+//! let service = ThreadPoolSigningService::<_, FAEST128fSignature>::new(sk);
+//! let signature = service.sign(b"some message").await.expect("Signing failed");
+//! # });
+//! # }
+//! ```
+
+use std::{future::Future, marker::PhantomData, sync::Arc};
+
+use signature::SignatureEncoding;
+
+use crate::{ByteEncoding, Error, Keypair, Signer};
+
+/// Service producing signatures asynchronously
+pub trait SigningService: Send + Sync {
+    /// Sign `msg` and return the encoded signature
+    fn sign(&self, msg: &[u8]) -> impl Future<Output = Result<Vec<u8>, Error>> + Send;
+
+    /// Return the encoded verification key
+    fn public_key(&self) -> impl Future<Output = Result<Vec<u8>, Error>> + Send;
+}
+
+/// Signing service running on the blocking thread pool of Tokio
+///
+/// `K` is the signing key and `S` the produced signature type.
+pub struct ThreadPoolSigningService<K, S> {
+    key: Arc<K>,
+    signature: PhantomData<fn() -> S>,
+}
+
+impl<K, S> ThreadPoolSigningService<K, S> {
+    /// Create a new service signing with `key`
+    pub fn new(key: K) -> Self {
+        Self {
+            key: Arc::new(key),
+            signature: PhantomData,
+        }
+    }
+}
+
+impl<K, S> Clone for ThreadPoolSigningService<K, S> {
+    fn clone(&self) -> Self {
+        Self {
+            key: Arc::clone(&self.key),
+            signature: PhantomData,
+        }
+    }
+}
+
+impl<K, S> std::fmt::Debug for ThreadPoolSigningService<K, S>
+where
+    K: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThreadPoolSigningService")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl<K, S> SigningService for ThreadPoolSigningService<K, S>
+where
+    K: Signer<S> + Keypair + Send + Sync + 'static,
+    K::VerifyingKey: ByteEncoding,
+    S: SignatureEncoding + Send + 'static,
+{
+    fn sign(&self, msg: &[u8]) -> impl Future<Output = Result<Vec<u8>, Error>> + Send {
+        let key = Arc::clone(&self.key);
+        let msg = msg.to_vec();
+        async move {
+            tokio::task::spawn_blocking(move || key.try_sign(&msg).map(|s| s.to_vec()))
+                .await
+                .map_err(|_| Error::new())?
+        }
+    }
+
+    fn public_key(&self) -> impl Future<Output = Result<Vec<u8>, Error>> + Send {
+        let verifying_key = self.key.verifying_key().to_vec();
+        async move { Ok(verifying_key) }
+    }
+}
+
+#[cfg(all(test, feature = "faest-128f"))]
+mod test {
+    use super::*;
+
+    use crate::{FAEST128fSignature, FAEST128fSigningKey, KeypairGenerator, Verifier};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sign_concurrently() {
+        const N: usize = 100;
+
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let vk = sk.verifying_key();
+        let service = ThreadPoolSigningService::<_, FAEST128fSignature>::new(sk);
+        assert_eq!(service.public_key().await.unwrap(), vk.to_vec());
+
+        let tasks: Vec<_> = (0..N)
+            .map(|i| {
+                let service = service.clone();
+                tokio::spawn(async move {
+                    let msg = format!("message {i}");
+                    let signature = service.sign(msg.as_bytes()).await;
+                    (msg, signature)
+                })
+            })
+            .collect();
+        for task in tasks {
+            let (msg, signature) = task.await.unwrap();
+            let signature = FAEST128fSignature::try_from(signature.unwrap().as_slice()).unwrap();
+            assert!(vk.verify(msg.as_bytes(), &signature).is_ok());
+        }
+    }
+}
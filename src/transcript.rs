@@ -0,0 +1,224 @@
+//! Transcripts of the random oracle calls for differential debugging
+//!
+//! With the `transcript` feature, signing and verification report every
+//! invocation of the random oracles `H1`, `H2` and `H3` in order. This helps
+//! to pin down where this implementation and another one diverge.
+//! [`TranscriptRecorder::record`] collects the entries of all operations on
+//! the current thread while running a closure:
+//!
+//! ```
+//! # #[cfg(feature = "faest-128f")] {
+//! use faest::{
+//!     transcript::TranscriptRecorder, FAEST128fSignature, FAEST128fSigningKey, KeypairGenerator,
+//!     Signer,
+//! };
+//!
+//! let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+//! let (_signature, transcript) =
+//!     TranscriptRecorder::record(|| -> FAEST128fSignature { sk.sign(b"message") });
+//! assert_eq!(
+//!     transcript.labels(),
+//!     ["mu", "iv", "hcom", "chall1", "hv", "chall2", "chall3"]
+//! );
+//! # }
+//! ```
+//!
+//! The output of `H3` is only recorded partially: the seed `r` of the GGM trees
+//! is omitted, as it reveals the witness together with the signature. As both
+//! `r` and `iv` are read from the same XOF, matching `iv`s also imply matching
+//! `r`s. Transcripts nevertheless contain values derived from the secret key
+//! and should only be recorded for test keys.
+
+use std::{cell::RefCell, fmt};
+
+/// A single invocation of a random oracle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptEntry {
+    /// The random oracle, i.e., `"H1"`, `"H2"` or `"H3"`
+    pub oracle: &'static str,
+    /// The value derived by the invocation, e.g., `"chall1"`
+    pub label: &'static str,
+    /// Number of bytes absorbed by the random oracle (without the domain
+    /// separator)
+    pub input_length: usize,
+    /// Output of the random oracle
+    pub output: Vec<u8>,
+}
+
+/// Recorded invocations of the random oracles in order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    entries: Vec<TranscriptEntry>,
+}
+
+impl Transcript {
+    /// All recorded entries
+    pub fn entries(&self) -> &[TranscriptEntry] {
+        &self.entries
+    }
+
+    /// The labels of the recorded entries
+    pub fn labels(&self) -> Vec<&'static str> {
+        self.entries.iter().map(|entry| entry.label).collect()
+    }
+
+    /// The outputs of all entries with `label`
+    pub fn outputs<'a>(&'a self, label: &'a str) -> impl Iterator<Item = &'a [u8]> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.label == label)
+            .map(|entry| entry.output.as_slice())
+    }
+}
+
+/// One line per entry: oracle, label, input length and hex-encoded output
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            write!(
+                f,
+                "{} {} ({} bytes): ",
+                entry.oracle, entry.label, entry.input_length
+            )?;
+            for byte in &entry.output {
+                write!(f, "{byte:02x}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Vec<TranscriptEntry>>> = const { RefCell::new(None) };
+}
+
+/// Restores the enclosing recording, also if the closure panics
+struct Guard(Option<Vec<TranscriptEntry>>);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        RECORDER.with(|recorder| *recorder.borrow_mut() = self.0.take());
+    }
+}
+
+/// Records the random oracle calls on the current thread
+#[derive(Debug, Clone, Copy)]
+pub struct TranscriptRecorder;
+
+impl TranscriptRecorder {
+    /// Run `f` and return its result together with the transcript of all
+    /// random oracle calls performed by `f`
+    ///
+    /// Recordings may be nested. The entries of the inner recording are not
+    /// part of the outer transcript.
+    pub fn record<T>(f: impl FnOnce() -> T) -> (T, Transcript) {
+        let outer = RECORDER.with(|recorder| recorder.borrow_mut().replace(Vec::new()));
+        let guard = Guard(outer);
+        let result = f();
+        let entries = RECORDER
+            .with(|recorder| recorder.borrow_mut().take())
+            .unwrap_or_default();
+        drop(guard);
+        (result, Transcript { entries })
+    }
+}
+
+/// Report an invocation of a random oracle to an active recording
+#[cfg(feature = "_variant")]
+pub(crate) fn record(
+    oracle: &'static str,
+    label: &'static str,
+    input_length: usize,
+    output: &[u8],
+) {
+    RECORDER.with(|recorder| {
+        if let Some(entries) = recorder.borrow_mut().as_mut() {
+            entries.push(TranscriptEntry {
+                oracle,
+                label,
+                input_length,
+                output: output.to_vec(),
+            });
+        }
+    });
+}
+
+#[cfg(all(test, feature = "faest-128f"))]
+mod test {
+    use super::*;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        FAEST128fSignature, FAEST128fSigningKey, Keypair, KeypairGenerator, SignOptions, Verifier,
+    };
+
+    const TEST_MESSAGE: &[u8] = "test message".as_bytes();
+
+    #[test]
+    fn sign_and_verify() {
+        let sk = FAEST128fSigningKey::generate(ChaCha8Rng::seed_from_u64(42));
+        let mut rng = ChaCha8Rng::seed_from_u64(1234);
+        let (signature, sign_transcript) = TranscriptRecorder::record(|| {
+            sk.sign_with_options(TEST_MESSAGE, SignOptions::new().with_rng(&mut rng))
+                .unwrap()
+        });
+        // H1 and H3 derive µ, r and iv; the VOLE commitment is hashed to
+        // h_com; the three challenges bind the commitment, the VOLE hash and
+        // the QuickSilver proof
+        assert_eq!(
+            sign_transcript.labels(),
+            ["mu", "iv", "hcom", "chall1", "hv", "chall2", "chall3"]
+        );
+
+        let vk = sk.verifying_key();
+        let (result, verify_transcript) =
+            TranscriptRecorder::record(|| vk.verify(TEST_MESSAGE, &signature));
+        assert!(result.is_ok());
+        assert_eq!(
+            verify_transcript.labels(),
+            ["mu", "hcom", "chall1", "hv", "chall2", "chall3"]
+        );
+
+        // the verifier recomputes the same values
+        for label in verify_transcript.labels() {
+            assert!(sign_transcript
+                .outputs(label)
+                .eq(verify_transcript.outputs(label)));
+        }
+        assert_eq!(
+            verify_transcript.entries()[0].input_length,
+            2 * 16 + TEST_MESSAGE.len()
+        );
+
+        // recording is deterministic for fixed ρ
+        let mut rng = ChaCha8Rng::seed_from_u64(1234);
+        let (_, transcript) = TranscriptRecorder::record(|| {
+            sk.sign_with_options(TEST_MESSAGE, SignOptions::new().with_rng(&mut rng))
+                .unwrap()
+        });
+        assert_eq!(transcript, sign_transcript);
+        assert_eq!(transcript.to_string().lines().count(), 7);
+    }
+
+    #[test]
+    fn nested_and_inactive() {
+        let sk = FAEST128fSigningKey::generate(ChaCha8Rng::seed_from_u64(42));
+        let vk = sk.verifying_key();
+        let signature: FAEST128fSignature = crate::Signer::sign(&sk, TEST_MESSAGE);
+
+        let ((_, inner), outer) = TranscriptRecorder::record(|| {
+            let inner = TranscriptRecorder::record(|| vk.verify(TEST_MESSAGE, &signature));
+            vk.verify(TEST_MESSAGE, &signature).unwrap();
+            inner
+        });
+        assert_eq!(inner.entries().len(), 6);
+        assert_eq!(outer.entries().len(), 6);
+
+        // nothing is recorded outside of a recording
+        vk.verify(TEST_MESSAGE, &signature).unwrap();
+        RECORDER.with(|recorder| assert!(recorder.borrow().is_none()));
+    }
+}
@@ -1,15 +1,101 @@
-use std::{array, iter::zip};
+//! ε-almost-universal hash families used by FAEST
+//!
+//! FAEST compresses the VOLE correlations and the QuickSilver constraints with
+//! two polynomial hash families over `GF(2^λ)`. Both are keyed by a seed that
+//! is derived from the transcript by the random oracle. The seed is split into
+//! field elements in order, i.e., the first `λ/8` bytes form the first element.
+//!
+//! ## VOLE hash
+//!
+//! The key consists of `r_0, r_1, r_2, r_3, s ∈ GF(2^λ)` and `t ∈ GF(2^64)`,
+//! i.e., `5λ/8 + 8` bytes. An input `x` of `ℓ̂` bits is split into `x_0 || x_1`
+//! where `x_1` consists of the last `λ + B` bits with `B` =
+//! [`VOLE_REDUNDANCY_BITS`](crate::constants::VOLE_REDUNDANCY_BITS). `x_0` is
+//! padded with zeros to a multiple of `λ` bits and interpreted as elements
+//! `y_1, …, y_n ∈ GF(2^λ)` as well as `z_1, …, z_m ∈ GF(2^64)`. The hash is
+//!
+//! ```text
+//! h_0 = Σ y_i · s^(n-i)        h_1 = Σ z_j · t^(m-j)
+//! h_2 = r_0 · h_0 + r_1 · h_1  h_3 = r_2 · h_0 + r_3 · h_1
+//! H(x) = (h_2 || h_3[..B]) ⊕ x_1
+//! ```
+//!
+//! and consists of `λ/8 + 2` bytes. This is an instance of the [`VoleHash128`],
+//! [`VoleHash192`] and [`VoleHash256`] types.
+//!
+//! ## ZK hash
+//!
+//! The key consists of `r_0, r_1, s ∈ GF(2^λ)` and `t ∈ GF(2^64)`, i.e.,
+//! `3λ/8 + 8` bytes. For inputs `v_1, …, v_n ∈ GF(2^λ)` and `x_1 ∈ GF(2^λ)`,
+//! the hash is
+//!
+//! ```text
+//! h_0 = Σ v_i · s^(n-i)        h_1 = Σ v_i · t^(n-i)
+//! H(v, x_1) = r_0 · h_0 + r_1 · h_1 + x_1
+//! ```
+//!
+//! where `t` is embedded into `GF(2^λ)`. This is an instance of the
+//! [`ZkHash128`], [`ZkHash192`] and [`ZkHash256`] types.
+//!
+//! Both hashes are linear in their input, i.e., `H(k, a ⊕ b) = H(k, a) ⊕ H(k,
+//! b)` for inputs of the same length. The test vectors used by this crate are
+//! available in `tests/data/volehash_*.json` and `tests/data/zkhash_*.json`.
+//!
+//! ```
+//! use faest::universal_hashing::{VoleHash128, ZkHash128};
+//!
+//! let hasher = VoleHash128::new_with_key(&[0x42; VoleHash128::KEY_SIZE]).expect("valid key");
+//! let h = hasher.hash(&[0x13; 234]).expect("input is long enough");
+//! assert_eq!(h.len(), VoleHash128::OUTPUT_SIZE);
+//!
+//! let mut hasher = ZkHash128::new_with_key(&[0x42; ZkHash128::KEY_SIZE]).expect("valid key");
+//! hasher.update(&[0x01; 16]);
+//! let h = hasher.finalize(&[0x00; 16]);
+//! assert_eq!(h.len(), 16);
+//! ```
+
+use std::{array, fmt, iter::zip};
 
 use generic_array::{
     typenum::{Prod, Quot, Sum, Unsigned, U16, U3, U5, U8},
     ArrayLength, GenericArray,
 };
-use itertools::{chain, izip};
+#[cfg(all(feature = "sign", feature = "_variant"))]
+use itertools::izip;
 
-use crate::fields::{BigGaloisField, Field, GF128, GF192, GF256, GF64};
+use crate::{
+    fields::{BigGaloisField, Field, GF128, GF192, GF256, GF64},
+    utils::Reader,
+    Error,
+};
+#[cfg(feature = "_variant")]
+use crate::{
+    parameter::{BaseParameters, OWFParameters},
+    poly::evaluate_polynomial,
+};
+
+/// Evaluate the mask of the QuickSilver proof at `alpha`
+///
+/// The last `λ` of the `ℓ + λ` VOLE values, i.e., the tags of the prover or
+/// the keys of the verifier, are the coefficients of a polynomial of degree
+/// `λ - 1`. FAEST evaluates it at `X` to obtain the masking input `x_1` of the
+/// ZK hash. As the evaluation is linear, the keys of the verifier evaluate to
+/// `v_s + u_s · Δ` for the tag `v_s` and the mask `u_s` of the prover.
+#[cfg(feature = "_variant")]
+pub(crate) fn evaluate_at<O>(
+    values: &GenericArray<<<O as OWFParameters>::BaseParams as BaseParameters>::Field, O::LAMBDAL>,
+    alpha: &<<O as OWFParameters>::BaseParams as BaseParameters>::Field,
+) -> <<O as OWFParameters>::BaseParams as BaseParameters>::Field
+where
+    O: OWFParameters,
+{
+    evaluate_polynomial(&values[O::L::USIZE..O::L::USIZE + O::LAMBDA::USIZE], alpha)
+}
 
+/// Additional bits `B` returned by VOLE hash, see
+/// [`VOLE_REDUNDANCY_BITS`](crate::constants::VOLE_REDUNDANCY_BITS)
 type BBits = U16;
-// Additional bytes returned by VOLE hash
+/// Additional bytes returned by VOLE hash
 pub(crate) type B = Quot<BBits, U8>;
 
 /// Interface to instantiate a VOLE hasher
@@ -22,11 +108,10 @@ where
     type Hasher: VoleHasherProcess<F, Self::OutputLength>;
 
     fn new_vole_hasher(sd: &GenericArray<u8, Self::SDLength>) -> Self::Hasher {
-        let r = array::from_fn(|i| F::from(&sd[i * F::Length::USIZE..(i + 1) * F::Length::USIZE]));
-        let s = F::from(&sd[4 * F::Length::USIZE..5 * F::Length::USIZE]);
-        let t = GF64::from(
-            &sd[5 * F::Length::USIZE..5 * F::Length::USIZE + <GF64 as Field>::Length::USIZE],
-        );
+        let mut sd = sd.as_slice();
+        let r = array::from_fn(|_| sd.squeeze_into_field());
+        let s = sd.squeeze_into_field();
+        let t = GF64::from(&sd[..<GF64 as Field>::Length::USIZE]);
 
         Self::Hasher::from_r_s_t(r, s, t)
     }
@@ -54,6 +139,16 @@ where
         self.process_split(x0, GenericArray::from_slice(x1))
     }
 
+    /// Hash all inputs, i.e., equivalent to calling
+    /// [`VoleHasherProcess::process`] on each of them
+    #[cfg(feature = "_variant")]
+    fn process_batch<'a>(
+        &self,
+        xs: impl IntoIterator<Item = &'a [u8]>,
+    ) -> Vec<GenericArray<u8, OutputLength>> {
+        xs.into_iter().map(|x| self.process(x)).collect()
+    }
+
     fn from_r_s_t(r: [F; 4], s: F, t: GF64) -> Self;
 }
 
@@ -96,26 +191,31 @@ where
         x0: &[u8],
         x1: &GenericArray<u8, <Self as VoleHasherInit<F>>::OutputLength>,
     ) -> GenericArray<u8, <Self as VoleHasherInit<F>>::OutputLength> {
-        let mut h0 = F::ZERO;
-        let mut h1 = GF64::ZERO;
+        let mut h = self.absorb(x0).finalize();
+        zip(h.iter_mut(), x1).for_each(|(h, x1)| *h ^= x1);
+        h
+    }
 
-        let iter = x0.chunks_exact(<F as Field>::Length::USIZE);
-        let remainder = iter.remainder();
-        iter.for_each(|data| self.process_block(&mut h0, &mut h1, data));
-        if !remainder.is_empty() {
-            self.process_unpadded_block(&mut h0, &mut h1, remainder);
+    #[cfg(feature = "_variant")]
+    fn process_batch<'a>(
+        &self,
+        xs: impl IntoIterator<Item = &'a [u8]>,
+    ) -> Vec<GenericArray<u8, <Self as VoleHasherInit<F>>::OutputLength>> {
+        let output_length = <Self as VoleHasherInit<F>>::OutputLength::USIZE;
+        let (hashers, x1s): (Vec<_>, Vec<_>) = xs
+            .into_iter()
+            .map(|x| {
+                debug_assert!(x.len() > output_length);
+                let (x0, x1) = x.split_at(x.len() - output_length);
+                (self.absorb(x0), x1)
+            })
+            .unzip();
+
+        let mut hs = StreamingVoleHasher::batch_finalize(hashers);
+        for (h, x1) in zip(hs.iter_mut(), x1s) {
+            zip(h.iter_mut(), x1).for_each(|(h, x1)| *h ^= x1);
         }
-
-        let h2 = self.r[0] * h0 + self.r[1] * h1;
-        let h3 = self.r[2] * h0 + self.r[3] * h1;
-
-        GenericArray::from_iter(
-            izip!(
-                chain(h2.as_bytes(), h3.as_bytes().into_iter().take(B::USIZE),),
-                x1
-            )
-            .map(|(x1, x2)| x1 ^ x2),
-        )
+        hs
     }
 
     fn from_r_s_t(r: [F; 4], s: F, t: GF64) -> Self {
@@ -127,6 +227,30 @@ impl<F> VoleHasher<F>
 where
     F: BigGaloisField,
 {
+    /// Instantiate a hasher that absorbs the input in chunks
+    pub(crate) fn streaming(&self) -> StreamingVoleHasher<F> {
+        StreamingVoleHasher {
+            hasher: self.clone(),
+            h0: F::ZERO,
+            h1: GF64::ZERO,
+            buffer: Vec::with_capacity(StreamingVoleHasher::<F>::BLOCK_SIZE),
+        }
+    }
+
+    /// Absorb `x`, padding the last chunk with zeros
+    fn absorb(&self, x: &[u8]) -> StreamingVoleHasher<F> {
+        let mut hasher = self.streaming();
+        let iter = x.chunks_exact(<F as Field>::Length::USIZE);
+        let remainder = iter.remainder();
+        iter.for_each(|chunk| hasher.update(GenericArray::from_slice(chunk)));
+        if !remainder.is_empty() {
+            let mut buf = GenericArray::default();
+            buf[..remainder.len()].copy_from_slice(remainder);
+            hasher.update(&buf);
+        }
+        hasher
+    }
+
     fn process_block(&self, h0: &mut F, h1: &mut GF64, data: &[u8]) {
         *h0 = *h0 * self.s + F::from(data);
         data.chunks_exact(<GF64 as Field>::Length::USIZE)
@@ -135,10 +259,109 @@ where
             });
     }
 
-    fn process_unpadded_block(&self, h0: &mut F, h1: &mut GF64, data: &[u8]) {
-        let mut buf = GenericArray::<u8, F::Length>::default();
-        buf[..data.len()].copy_from_slice(data);
-        self.process_block(h0, h1, &buf);
+    fn output<OutputLength>(&self, h0: F, h1: GF64) -> GenericArray<u8, OutputLength>
+    where
+        OutputLength: ArrayLength,
+    {
+        let h2 = self.r[0] * h0 + self.r[1] * h1;
+        let h3 = self.r[2] * h0 + self.r[3] * h1;
+
+        let mut ret = GenericArray::<u8, OutputLength>::default();
+        let (ret_h2, ret_h3) = ret.split_at_mut(F::Length::USIZE);
+        h2.to_bytes_into(ret_h2);
+        ret_h3.copy_from_slice(&h3.as_bytes()[..B::USIZE]);
+        ret
+    }
+}
+
+/// Streaming interface of the VOLE hasher
+///
+/// The input is absorbed in chunks of `λ` bits, which are buffered and
+/// processed in blocks of [`StreamingVoleHasher::BLOCK_SIZE`] chunks. The last
+/// chunk has to be padded with zeros. In contrast to
+/// [`VoleHasherProcess::process`], the hash is not combined with the last bytes
+/// of the input.
+#[derive(Debug, Clone)]
+pub(crate) struct StreamingVoleHasher<F>
+where
+    F: BigGaloisField,
+{
+    hasher: VoleHasher<F>,
+    h0: F,
+    h1: GF64,
+    buffer: Vec<GenericArray<u8, F::Length>>,
+}
+
+impl<F> StreamingVoleHasher<F>
+where
+    F: BigGaloisField,
+{
+    /// Number of chunks that are buffered before they are processed
+    pub(crate) const BLOCK_SIZE: usize = 64;
+
+    /// Absorb a chunk of the input
+    pub(crate) fn update(&mut self, chunk: &GenericArray<u8, F::Length>) {
+        self.buffer.push(chunk.clone());
+        if self.buffer.len() == Self::BLOCK_SIZE {
+            self.process_buffer();
+        }
+    }
+
+    fn process_buffer(&mut self) {
+        for chunk in self.buffer.drain(..) {
+            self.hasher
+                .process_block(&mut self.h0, &mut self.h1, &chunk);
+        }
+    }
+}
+
+impl<F> StreamingVoleHasher<F>
+where
+    F: BigGaloisField,
+    VoleHasher<F>: VoleHasherInit<F>,
+{
+    /// Process the buffered chunks and produce the hash
+    pub(crate) fn finalize(
+        mut self,
+    ) -> GenericArray<u8, <VoleHasher<F> as VoleHasherInit<F>>::OutputLength> {
+        self.process_buffer();
+        self.hasher.output(self.h0, self.h1)
+    }
+
+    /// Finalize multiple hashers at once
+    ///
+    /// The buffered chunks of all hashers are processed in an interleaved
+    /// fashion, i.e., the `i`-th chunk of every hasher is processed before the
+    /// `i+1`-th one. The hashers are independent, hence the field
+    /// multiplications of different hashers can be pipelined. The outputs are
+    /// identical to those of [`StreamingVoleHasher::finalize`].
+    #[cfg(feature = "_variant")]
+    pub(crate) fn batch_finalize(
+        mut hashers: Vec<Self>,
+    ) -> Vec<GenericArray<u8, <VoleHasher<F> as VoleHasherInit<F>>::OutputLength>> {
+        let max_len = hashers
+            .iter()
+            .map(|hasher| hasher.buffer.len())
+            .max()
+            .unwrap_or_default();
+        for idx in 0..max_len {
+            for Self {
+                hasher,
+                h0,
+                h1,
+                buffer,
+            } in hashers.iter_mut()
+            {
+                if let Some(chunk) = buffer.get(idx) {
+                    hasher.process_block(h0, h1, chunk);
+                }
+            }
+        }
+
+        hashers
+            .into_iter()
+            .map(|hasher| hasher.hasher.output(hasher.h0, hasher.h1))
+            .collect()
     }
 }
 
@@ -150,12 +373,11 @@ where
     type SDLength: ArrayLength;
 
     fn new_zk_hasher(sd: &GenericArray<u8, Self::SDLength>) -> ZKHasher<F> {
-        let r0 = F::from(&sd[..F::Length::USIZE]);
-        let r1 = F::from(&sd[F::Length::USIZE..2 * F::Length::USIZE]);
-        let s = F::from(&sd[2 * F::Length::USIZE..3 * F::Length::USIZE]);
-        let t = GF64::from(
-            &sd[3 * F::Length::USIZE..3 * F::Length::USIZE + <GF64 as Field>::Length::USIZE],
-        );
+        let mut sd = sd.as_slice();
+        let r0 = sd.squeeze_into_field();
+        let r1 = sd.squeeze_into_field();
+        let s = sd.squeeze_into_field();
+        let t = GF64::from(&sd[..<GF64 as Field>::Length::USIZE]);
 
         ZKHasher {
             h0: F::ZERO,
@@ -243,6 +465,7 @@ where
         Self { a_hasher, b_hasher }
     }
 
+    #[cfg(all(feature = "sign", feature = "_variant"))]
     pub(crate) fn process<I1, I2, I3, I4>(&mut self, s: I1, vs: I2, s_b: I3, v_s_b: I4)
     where
         I1: Iterator<Item = F>,
@@ -251,13 +474,20 @@ where
         I4: Iterator<Item = F>,
     {
         for (s_j, vs_j, s_b_j, v_s_b_j) in izip!(s, vs, s_b, v_s_b) {
-            let a0 = v_s_b_j * vs_j;
-            let a1 = (s_j + vs_j) * (s_b_j + v_s_b_j) + F::ONE + a0;
-            self.a_hasher.update(&a1);
-            self.b_hasher.update(&a0);
+            self.assert_mul((s_j, vs_j), (s_b_j, v_s_b_j), (F::ONE, F::ZERO));
         }
     }
 
+    /// Add the constraint `x_a * x_b = x_c` for committed values given as pairs
+    /// of value and VOLE tag
+    pub(crate) fn assert_mul(&mut self, a: (F, F), b: (F, F), c: (F, F)) {
+        let ((x_a, v_a), (x_b, v_b), (x_c, v_c)) = (a, b, c);
+        let a0 = v_a * v_b;
+        let a1 = (x_a + v_a) * (x_b + v_b) + x_c + v_c + a0;
+        self.a_hasher.update(&a1);
+        self.b_hasher.update(&a0);
+    }
+
     pub(crate) fn finalize(self, u: &F, v: &F) -> (F, F) {
         let a = self.a_hasher.finalize(u);
         let b = self.b_hasher.finalize(v);
@@ -270,7 +500,7 @@ where
     F: BigGaloisField,
 {
     b_hasher: ZKHasher<F>,
-    delta_squared: F,
+    delta: F,
 }
 
 impl<F> ZKVerifyHasher<F>
@@ -278,35 +508,131 @@ where
     F: BigGaloisField,
 {
     fn new(b_hasher: ZKHasher<F>, delta: F) -> Self {
-        Self {
-            b_hasher,
-            delta_squared: delta.square(),
-        }
+        Self { b_hasher, delta }
     }
 
+    #[cfg(feature = "_variant")]
     pub(crate) fn process<I1, I2>(&mut self, qs: I1, qs_b: I2)
     where
         I1: Iterator<Item = F>,
         I2: Iterator<Item = F>,
     {
         for (q, qb) in zip(qs, qs_b) {
-            let b = q * qb + self.delta_squared;
-            self.b_hasher.update(&b);
+            self.assert_mul(q, qb, self.delta);
         }
     }
 
+    /// Add the constraint `x_a * x_b = x_c` for committed values given by their
+    /// VOLE keys
+    pub(crate) fn assert_mul(&mut self, q_a: F, q_b: F, q_c: F) {
+        let b = q_a * q_b + q_c * self.delta;
+        self.b_hasher.update(&b);
+    }
+
     pub(crate) fn finalize(self, v: &F) -> F {
         self.b_hasher.finalize(v)
     }
 }
 
+macro_rules! define_hashes {
+    ($lambda:literal, $field:ident, $bytes:literal) => {
+        paste::paste! {
+            #[doc = "VOLE hash at security level " $lambda]
+            #[derive(Clone)]
+            pub struct [<VoleHash $lambda>](VoleHasher<$field>);
+
+            impl [<VoleHash $lambda>] {
+                /// Size of the key (in bytes)
+                pub const KEY_SIZE: usize = 5 * $bytes + 8;
+                /// Size of the hash (in bytes)
+                pub const OUTPUT_SIZE: usize = $bytes + 2;
+
+                /// Create a new hasher from `key`
+                ///
+                /// Fails if `key` does not consist of [`Self::KEY_SIZE`] bytes.
+                pub fn new_with_key(key: &[u8]) -> Result<Self, Error> {
+                    GenericArray::try_from_slice(key)
+                        .map(|key| Self(VoleHasher::<$field>::new_vole_hasher(key)))
+                        .map_err(|_| Error::new())
+                }
+
+                /// Hash `x`
+                ///
+                /// The last [`Self::OUTPUT_SIZE`] bytes of `x` are added to the
+                /// hash of the remaining bytes. Fails if `x` is not longer than
+                /// [`Self::OUTPUT_SIZE`] bytes.
+                pub fn hash(&self, x: &[u8]) -> Result<[u8; $bytes + 2], Error> {
+                    if x.len() <= Self::OUTPUT_SIZE {
+                        return Err(Error::new());
+                    }
+                    Ok(self.0.process(x).into())
+                }
+            }
+
+            impl fmt::Debug for [<VoleHash $lambda>] {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_tuple(stringify!([<VoleHash $lambda>]))
+                        .field(&"redacted")
+                        .finish()
+                }
+            }
+
+            #[doc = "ZK hash at security level " $lambda]
+            #[derive(Clone)]
+            pub struct [<ZkHash $lambda>](ZKHasher<$field>);
+
+            impl [<ZkHash $lambda>] {
+                /// Size of the key (in bytes)
+                pub const KEY_SIZE: usize = 3 * $bytes + 8;
+
+                /// Create a new hasher from `key`
+                ///
+                /// Fails if `key` does not consist of [`Self::KEY_SIZE`] bytes.
+                pub fn new_with_key(key: &[u8]) -> Result<Self, Error> {
+                    GenericArray::try_from_slice(key)
+                        .map(|key| Self(ZKHasher::<$field>::new_zk_hasher(key)))
+                        .map_err(|_| Error::new())
+                }
+
+                /// Absorb the field element `v`
+                pub fn update(&mut self, v: &[u8; $bytes]) {
+                    self.0.update(&$field::from(v.as_slice()));
+                }
+
+                /// Produce the hash and add `x1`
+                pub fn finalize(self, x1: &[u8; $bytes]) -> [u8; $bytes] {
+                    self.0
+                        .finalize(&$field::from(x1.as_slice()))
+                        .as_bytes()
+                        .into()
+                }
+            }
+
+            impl fmt::Debug for [<ZkHash $lambda>] {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_tuple(stringify!([<ZkHash $lambda>]))
+                        .field(&"redacted")
+                        .finish()
+                }
+            }
+        }
+    };
+}
+
+define_hashes!(128, GF128, 16);
+define_hashes!(192, GF192, 24);
+define_hashes!(256, GF256, 32);
+
 #[cfg(test)]
 mod test {
     use super::*;
 
     use generic_array::GenericArray;
+    use rand::RngCore;
     use serde::{de::DeserializeOwned, Deserialize};
 
+    #[cfg(feature = "_variant")]
+    use crate::utils::witness_bit_at;
     use crate::{
         fields::{GF128, GF192, GF256},
         utils::test::read_test_data,
@@ -368,6 +694,144 @@ mod test {
         }
     }
 
+    fn streaming<F>()
+    where
+        F: BigGaloisField,
+        VoleHasher<F>: VoleHasherInit<F, Hasher = VoleHasher<F>>,
+    {
+        let mut rng = rand::thread_rng();
+        let mut sd = GenericArray::<u8, <VoleHasher<F> as VoleHasherInit<F>>::SDLength>::default();
+        rng.fill_bytes(&mut sd);
+        let hasher = VoleHasher::<F>::new_vole_hasher(&sd);
+
+        for blocks in [0, 1, 63, 64, 65, 1000] {
+            let mut x = vec![0; blocks * <F as Field>::Length::USIZE];
+            rng.fill_bytes(&mut x);
+
+            let mut streaming = hasher.streaming();
+            for chunk in x.chunks_exact(<F as Field>::Length::USIZE) {
+                streaming.update(GenericArray::from_slice(chunk));
+            }
+
+            // process the blocks without buffering
+            let mut h0 = F::ZERO;
+            let mut h1 = GF64::ZERO;
+            for chunk in x.chunks_exact(<F as Field>::Length::USIZE) {
+                hasher.process_block(&mut h0, &mut h1, chunk);
+            }
+            let expected: GenericArray<u8, <VoleHasher<F> as VoleHasherInit<F>>::OutputLength> =
+                hasher.output(h0, h1);
+            assert_eq!(streaming.finalize(), expected, "{blocks} blocks");
+        }
+    }
+
+    #[cfg(feature = "_variant")]
+    fn batch<F>()
+    where
+        F: BigGaloisField,
+        VoleHasher<F>: VoleHasherInit<F, Hasher = VoleHasher<F>>,
+    {
+        let mut rng = rand::thread_rng();
+        let mut sd = GenericArray::<u8, <VoleHasher<F> as VoleHasherInit<F>>::SDLength>::default();
+        rng.fill_bytes(&mut sd);
+        let hasher = VoleHasher::<F>::new_vole_hasher(&sd);
+
+        // inputs of different lengths, including partial chunks
+        let xs: Vec<_> = (0..F::Length::USIZE * 8)
+            .map(|idx| {
+                let mut x = vec![0; 100 + 17 * idx];
+                rng.fill_bytes(&mut x);
+                x
+            })
+            .collect();
+        let expected: Vec<_> = xs.iter().map(|x| hasher.process(x)).collect();
+        assert_eq!(hasher.process_batch(xs.iter().map(Vec::as_slice)), expected);
+        assert!(hasher.process_batch([]).is_empty());
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn batch_volehash() {
+        batch::<GF128>();
+        batch::<GF192>();
+        batch::<GF256>();
+    }
+
+    #[test]
+    fn streaming_volehash() {
+        streaming::<GF128>();
+        streaming::<GF192>();
+        streaming::<GF256>();
+    }
+
+    /// The mask of the keys evaluates to the VOLE correlation of the evaluated mask
+    #[cfg(feature = "_variant")]
+    fn evaluate_mask<O: OWFParameters>() {
+        use generic_array::sequence::GenericSequence;
+
+        use crate::fields::Double;
+
+        type F<O> = <<O as OWFParameters>::BaseParams as BaseParameters>::Field;
+
+        let mut rng = rand::thread_rng();
+        let mut sample = || {
+            let mut buffer = vec![0; <F<O> as Field>::Length::USIZE];
+            rng.fill_bytes(&mut buffer);
+            F::<O>::from(buffer.as_slice())
+        };
+        let delta = sample();
+        let tags = GenericArray::<F<O>, O::LAMBDAL>::generate(|_| sample());
+        let mut bits = vec![0u8; O::LAMBDAL::USIZE.div_ceil(8)];
+        rand::thread_rng().fill_bytes(&mut bits);
+        let bit = |i: usize| witness_bit_at(&bits, i) == 1;
+        let keys = GenericArray::<F<O>, O::LAMBDAL>::generate(|i| {
+            if bit(i) {
+                tags[i] + delta
+            } else {
+                tags[i]
+            }
+        });
+
+        let mut mask = vec![0u8; O::LAMBDABYTES::USIZE];
+        for i in 0..O::LAMBDA::USIZE {
+            mask[i / 8] |= u8::from(bit(O::L::USIZE + i)) << (i % 8);
+        }
+        let u_s = F::<O>::from(mask.as_slice());
+
+        let x = F::<O>::ONE.double();
+        let v_s = evaluate_at::<O>(&tags, &x);
+        assert_eq!(evaluate_at::<O>(&keys, &x), v_s + u_s * delta);
+
+        // only the mask is evaluated
+        let mut mask_only = tags.clone();
+        mask_only[..O::L::USIZE].fill(F::<O>::ZERO);
+        assert_eq!(evaluate_at::<O>(&mask_only, &x), v_s);
+        assert_eq!(
+            evaluate_at::<O>(&tags, &F::<O>::ONE),
+            tags[O::L::USIZE..]
+                .iter()
+                .fold(F::<O>::ZERO, |acc, v| acc + v)
+        );
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn evaluate_mask_128() {
+        evaluate_mask::<crate::parameter::OWF128>();
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn evaluate_mask_192() {
+        evaluate_mask::<crate::parameter::OWF192>();
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn evaluate_mask_256_em() {
+        evaluate_mask::<crate::parameter::OWF256EM>();
+    }
+
     #[test]
     fn test_zkhash_128() {
         let database: Vec<ZKHashDatabaseEntry<GF128>> = read_test_data("zkhash_128.json");
@@ -415,4 +879,91 @@ mod test {
             assert_eq!(GF256::from(data.h.as_slice()), res);
         }
     }
+
+    macro_rules! public_api_tests {
+        ($lambda:literal, $bytes:literal) => {
+            paste::paste! {
+                #[test]
+                fn [<public_volehash_ $lambda>]() {
+                    let database: Vec<VoleHashDatabaseEntry> =
+                        read_test_data(concat!("volehash_", $lambda, ".json"));
+                    for data in database {
+                        let hasher = [<VoleHash $lambda>]::new_with_key(&data.sd).unwrap();
+                        assert_eq!(hasher.hash(&data.xs).unwrap().as_slice(), data.h);
+                    }
+
+                    let key = [0; [<VoleHash $lambda>]::KEY_SIZE];
+                    assert!([<VoleHash $lambda>]::new_with_key(&key[1..]).is_err());
+                    assert!([<VoleHash $lambda>]::new_with_key(&[0; [<VoleHash $lambda>]::KEY_SIZE + 1]).is_err());
+                    let hasher = [<VoleHash $lambda>]::new_with_key(&key).unwrap();
+                    assert!(hasher.hash(&[0; [<VoleHash $lambda>]::OUTPUT_SIZE]).is_err());
+                }
+
+                #[test]
+                fn [<public_zkhash_ $lambda>]() {
+                    let database: Vec<ZKHashDatabaseEntry<[<GF $lambda>]>> =
+                        read_test_data(concat!("zkhash_", $lambda, ".json"));
+                    for data in database {
+                        let mut hasher = [<ZkHash $lambda>]::new_with_key(&data.sd).unwrap();
+                        for v in &data.x0 {
+                            hasher.update(&v.as_bytes().into());
+                        }
+                        assert_eq!(hasher.finalize(&data.x1.as_bytes().into()).as_slice(), data.h);
+                    }
+
+                    let key = [0; [<ZkHash $lambda>]::KEY_SIZE];
+                    assert!([<ZkHash $lambda>]::new_with_key(&key[1..]).is_err());
+                    assert!([<ZkHash $lambda>]::new_with_key(&[0; [<ZkHash $lambda>]::KEY_SIZE + 1]).is_err());
+                }
+
+                #[test]
+                fn [<linearity_ $lambda>]() {
+                    let mut rng = rand::thread_rng();
+                    let xor = |a: &[u8], b: &[u8]| -> Vec<u8> { zip(a, b).map(|(a, b)| a ^ b).collect() };
+
+                    for _ in 0..16 {
+                        let mut key = [0; [<VoleHash $lambda>]::KEY_SIZE];
+                        rng.fill_bytes(&mut key);
+                        let hasher = [<VoleHash $lambda>]::new_with_key(&key).unwrap();
+
+                        let len = [<VoleHash $lambda>]::OUTPUT_SIZE + 1 + (rng.next_u32() % 512) as usize;
+                        let mut a = vec![0; len];
+                        let mut b = vec![0; len];
+                        rng.fill_bytes(&mut a);
+                        rng.fill_bytes(&mut b);
+
+                        // H(k, a ⊕ b) = H(k, a) ⊕ H(k, b) and H(k, 0) = 0
+                        assert_eq!(
+                            hasher.hash(&xor(&a, &b)).unwrap().as_slice(),
+                            xor(&hasher.hash(&a).unwrap(), &hasher.hash(&b).unwrap())
+                        );
+                        assert!(hasher.hash(&vec![0; len]).unwrap().iter().all(|x| *x == 0));
+
+                        let mut key = [0; [<ZkHash $lambda>]::KEY_SIZE];
+                        rng.fill_bytes(&mut key);
+                        let hasher = [<ZkHash $lambda>]::new_with_key(&key).unwrap();
+
+                        let n = 1 + (rng.next_u32() % 32) as usize;
+                        let mut a = vec![0; (n + 1) * $bytes];
+                        let mut b = vec![0; (n + 1) * $bytes];
+                        rng.fill_bytes(&mut a);
+                        rng.fill_bytes(&mut b);
+                        let hash = |x: &[u8]| {
+                            let mut hasher = hasher.clone();
+                            let (vs, x1) = x.split_at(n * $bytes);
+                            vs.chunks_exact($bytes)
+                                .for_each(|v| hasher.update(v.try_into().unwrap()));
+                            hasher.finalize(x1.try_into().unwrap())
+                        };
+                        assert_eq!(hash(&xor(&a, &b)).as_slice(), xor(&hash(&a), &hash(&b)));
+                        assert_eq!(hash(&vec![0; (n + 1) * $bytes]), [0; $bytes]);
+                    }
+                }
+            }
+        };
+    }
+
+    public_api_tests!(128, 16);
+    public_api_tests!(192, 24);
+    public_api_tests!(256, 32);
 }
@@ -1,12 +1,21 @@
-use std::{array, iter::zip};
+#[cfg(feature = "_variant")]
+use std::array;
+use std::{fmt, iter::zip};
 
-use generic_array::{typenum::Unsigned, ArrayLength, GenericArray};
+#[cfg(feature = "_variant")]
+use generic_array::typenum::Unsigned;
+use generic_array::{ArrayLength, GenericArray};
+#[cfg(feature = "_variant")]
 use itertools::iproduct;
+#[cfg(feature = "_variant")]
+use subtle::{Choice, ConditionallySelectable};
 
 use crate::{
-    fields::ByteCombine,
-    parameter::{BaseParameters, OWFParameters, TauParameters},
+    fields::BigGaloisField,
+    parameter::{BaseParameters, OWFParameters},
 };
+#[cfg(feature = "_variant")]
+use crate::{fields::ByteCombine, parameter::TauParameters};
 
 /// Reader interface for PRGs and random oracles
 pub(crate) trait Reader {
@@ -22,10 +31,32 @@ pub(crate) trait Reader {
         self.read(&mut dst);
         dst
     }
+
+    /// Read exactly `F::Length` bytes and interpret them as field element
+    fn squeeze_into_field<F>(&mut self) -> F
+    where
+        F: BigGaloisField,
+    {
+        let mut buf = GenericArray::<u8, F::Length>::default();
+        self.read(&mut buf);
+        F::from(buf.as_slice())
+    }
+}
+
+/// Read from the front of a byte slice
+///
+/// Panics if more bytes are requested than available.
+impl Reader for &[u8] {
+    fn read(&mut self, dst: &mut [u8]) {
+        let (head, tail) = self.split_at(dst.len());
+        dst.copy_from_slice(head);
+        *self = tail;
+    }
 }
 
 pub(crate) type Field<O> = <<O as OWFParameters>::BaseParams as BaseParameters>::Field;
 
+#[cfg(feature = "_variant")]
 pub(crate) fn transpose_and_into_field<O>(
     gv: &GenericArray<GenericArray<u8, O::LAMBDALBYTES>, O::LAMBDA>,
 ) -> Box<GenericArray<Field<O>, O::LAMBDAL>>
@@ -42,10 +73,10 @@ where
     )
 }
 
-#[allow(clippy::boxed_local)]
+#[cfg(feature = "_variant")]
 pub(crate) fn convert_gq<O, Tau>(
     d: &GenericArray<u8, O::LBYTES>,
-    mut gq: Box<GenericArray<GenericArray<u8, O::LAMBDALBYTES>, O::LAMBDA>>,
+    gq: &mut GenericArray<GenericArray<u8, O::LAMBDALBYTES>, O::LAMBDA>,
     chall3: &GenericArray<u8, O::LAMBDABYTES>,
 ) -> Box<GenericArray<Field<O>, O::LAMBDAL>>
 where
@@ -69,9 +100,10 @@ where
         }
     }
 
-    transpose_and_into_field::<O>(&gq)
+    transpose_and_into_field::<O>(gq)
 }
 
+#[cfg(feature = "_variant")]
 pub(crate) fn bit_combine_with_delta<O>(x: u8, delta: &Field<O>) -> Field<O>
 where
     O: OWFParameters,
@@ -80,21 +112,118 @@ where
     Field::<O>::byte_combine(&tmp)
 }
 
+/// XOR `src` into `dst`
+///
+/// Not inlined to share one vectorized loop across all parameter sets.
+#[inline(never)]
+pub(crate) fn xor_into(dst: &mut [u8], src: &[u8]) {
+    debug_assert_eq!(dst.len(), src.len());
+    zip(dst, src).for_each(|(d, s)| *d ^= s);
+}
+
+/// XOR `src` into `dst` if `choice` is `1` without branching on `choice`
+///
+/// Not inlined to share one vectorized loop across all parameter sets.
+#[cfg(feature = "_variant")]
+#[inline(never)]
+pub(crate) fn conditional_xor_into(dst: &mut [u8], src: &[u8], choice: Choice) {
+    debug_assert_eq!(dst.len(), src.len());
+    zip(dst, src).for_each(|(d, s)| *d ^= u8::conditional_select(&0, s, choice));
+}
+
 /// Check for `0` in buffers for key validity.
 ///
 /// This function does not need to be constant time. It may only return early
 /// during key generation. During witness extension it always returns `false`
 /// and iterates over all bytes of the buffer.
+#[inline(always)]
 pub(crate) fn contains_zeros(buf: &[u8]) -> bool {
     buf.contains(&0)
 }
 
+/// Bit `i` of the packed witness `w`
+///
+/// Bits are stored LSB first per byte. This is the convention of FAEST for
+/// all bit strings, e.g., also for the OWF output and the challenges.
+#[inline(always)]
+pub(crate) fn witness_bit_at(w: &[u8], i: usize) -> u8 {
+    (w[i / 8] >> (i % 8)) & 1
+}
+
+/// Debug representation of public byte strings as short hex prefix
+///
+/// Only the first [`HexPrefix::LENGTH`] bytes are printed, followed by `…` if
+/// the string is longer.
+pub(crate) struct HexPrefix<'a>(pub(crate) &'a [u8]);
+
+impl HexPrefix<'_> {
+    /// Number of printed bytes
+    pub(crate) const LENGTH: usize = 8;
+}
+
+impl fmt::Debug for HexPrefix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.iter().take(Self::LENGTH) {
+            write!(f, "{byte:02x}")?;
+        }
+        if self.0.len() > Self::LENGTH {
+            write!(f, "…")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test {
+    use super::*;
+
     use std::{fs::File, path::Path};
 
+    #[cfg(feature = "_variant")]
+    use rand::RngCore;
     use serde::de::DeserializeOwned;
 
+    #[test]
+    fn witness_bits() {
+        let w = [0b1000_0001, 0b0000_0110];
+        let bits: Vec<_> = (0..16).map(|i| witness_bit_at(&w, i)).collect();
+        assert_eq!(bits, [1, 0, 0, 0, 0, 0, 0, 1, 0, 1, 1, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn hex_prefix() {
+        assert_eq!(format!("{:?}", HexPrefix(&[])), "");
+        assert_eq!(format!("{:?}", HexPrefix(&[0x01, 0xab])), "01ab");
+        assert_eq!(
+            format!("{:?}", HexPrefix(&(0..8).collect::<Vec<u8>>())),
+            "0001020304050607"
+        );
+        assert_eq!(
+            format!("{:?}", HexPrefix(&(0..9).collect::<Vec<u8>>())),
+            "0001020304050607…"
+        );
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn conditional_xor() {
+        let mut rng = rand::thread_rng();
+        let mut a = [0u8; 34];
+        let mut b = [0u8; 34];
+        rng.fill_bytes(&mut a);
+        rng.fill_bytes(&mut b);
+
+        let mut expected = a;
+        xor_into(&mut expected, &b);
+        assert!(zip(expected, zip(a, b)).all(|(e, (a, b))| e == a ^ b));
+
+        for bit in [false, true] {
+            let mut dst = a;
+            conditional_xor_into(&mut dst, &b, Choice::from(bit as u8));
+            assert_eq!(dst, if bit { expected } else { a });
+        }
+    }
+
     pub(crate) fn read_test_data<T: DeserializeOwned>(path: &str) -> Vec<T> {
         File::open(
             Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -110,4 +239,48 @@ pub(crate) mod test {
         )
         .unwrap_or_else(|_| panic!("Failed to read JSON test data from {}", path))
     }
+
+    /// Known answer test of a block cipher
+    #[derive(Debug)]
+    pub(crate) struct BlockCipherKat {
+        pub(crate) key: Vec<u8>,
+        pub(crate) plaintext: Vec<u8>,
+        pub(crate) ciphertext: Vec<u8>,
+    }
+
+    /// Read known answer tests in the format of the Rijndael submission
+    ///
+    /// Fixed values are set once, e.g., `PT=` in `ecb_vk.txt`, and each `CT=`
+    /// line completes a test with the latest key and plaintext.
+    pub(crate) fn read_block_cipher_kats(path: &str) -> Vec<BlockCipherKat> {
+        let Ok(data) = std::fs::read_to_string(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/data")
+                .join(path),
+        ) else {
+            println!("Test file {} is not available. Skipping test.", path);
+            return Vec::new();
+        };
+
+        let mut key = Vec::new();
+        let mut plaintext = Vec::new();
+        let mut kats = Vec::new();
+        for line in data.lines().filter(|line| !line.starts_with('#')) {
+            let Some((name, value)) = line.split_once('=') else {
+                continue;
+            };
+            let decode = || hex::decode(value).expect("valid hex");
+            match name {
+                "KEY" => key = decode(),
+                "PT" => plaintext = decode(),
+                "CT" => kats.push(BlockCipherKat {
+                    key: key.clone(),
+                    plaintext: plaintext.clone(),
+                    ciphertext: decode(),
+                }),
+                _ => {}
+            }
+        }
+        kats
+    }
 }
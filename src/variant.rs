@@ -0,0 +1,739 @@
+//! Runtime selection of FAEST variants
+
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "_variant")]
+use generic_array::typenum::Unsigned;
+use paste::paste;
+
+use crate::Error;
+#[cfg(feature = "_variant")]
+use crate::{
+    parameter::{FAESTParameters, OWFParameters},
+    Verifier,
+};
+
+macro_rules! define_variants {
+    ($($(#[$meta:meta])* $param:ident => $name:literal, $id:literal, $by_length:literal;)*) => {
+        paste! {
+            /// Identifier of a FAEST variant
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #[non_exhaustive]
+            pub enum FaestVariant {
+                $(
+                    $(#[$meta])*
+                    #[doc = $name]
+                    $param,
+                )*
+            }
+
+            impl FaestVariant {
+                /// All variants enabled in this build
+                pub const ALL: &'static [Self] = &[$($(#[$meta])* Self::$param,)*];
+
+                /// Name of the variant
+                pub const fn name(self) -> &'static str {
+                    match self {
+                        $($(#[$meta])* Self::$param => $name,)*
+                    }
+                }
+
+                /// Stable numeric identifier of the variant
+                ///
+                /// The identifiers do not depend on the enabled features and
+                /// are used in encodings that need to identify the variant,
+                /// e.g., multi-signatures.
+                pub const fn id(self) -> u8 {
+                    match self {
+                        $($(#[$meta])* Self::$param => $id,)*
+                    }
+                }
+
+                /// Variant with the identifier `id`
+                ///
+                /// Returns `None` if the identifier is unknown or the variant
+                /// is not enabled in this build.
+                pub fn from_id(id: u8) -> Option<Self> {
+                    Self::ALL.iter().copied().find(|variant| variant.id() == id)
+                }
+
+                /// Size of the signatures (in bytes)
+                pub const fn signature_size(self) -> usize {
+                    match self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param => <crate::parameter::[<$param Parameters>] as FAESTParameters>::SIGNATURE_SIZE,
+                        )*
+                    }
+                }
+
+                /// Information about the parameter set of the variant
+                pub const fn parameter_info(self) -> ParameterInfo {
+                    match self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param => ParameterInfo {
+                                variant: self,
+                                security_level: <<crate::parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::LAMBDA::USIZE,
+                                signature_size: self.signature_size(),
+                                verification_key_size: <<crate::parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::PK::USIZE,
+                            },
+                        )*
+                    }
+                }
+
+                /// Whether the variant is identified by the length of its signatures
+                ///
+                /// This is the case for all variants except for those that
+                /// share the signature size with a standard variant.
+                pub const fn identified_by_length(self) -> bool {
+                    match self {
+                        $($(#[$meta])* Self::$param => $by_length,)*
+                    }
+                }
+
+                /// Maximal number of signatures per key, `None` if unlimited
+                ///
+                /// FAEST signing is stateless, hence keys do not need to be
+                /// rotated after a number of signatures.
+                pub fn max_sign_operations(self) -> Option<u64> {
+                    match self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param => <<crate::parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::max_sign_operations(),
+                        )*
+                    }
+                }
+
+                /// Whether signing requires a unique nonce from the caller
+                pub fn requires_unique_nonce(self) -> bool {
+                    match self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param => <<crate::parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::requires_unique_nonce(),
+                        )*
+                    }
+                }
+
+                /// Number of rounds of the AES or Rijndael circuit of the OWF
+                pub fn circuit_depth(self) -> usize {
+                    match self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param => <<crate::parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::circuit_depth(),
+                        )*
+                    }
+                }
+
+                /// Number of 32-bit words of the OWF key
+                pub fn num_key_words(self) -> usize {
+                    match self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param => <<crate::parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::num_key_words(),
+                        )*
+                    }
+                }
+
+                /// Size of the witness per round (in bytes, rounded down)
+                pub fn witness_per_round_bytes(self) -> usize {
+                    match self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param => <<crate::parameter::[<$param Parameters>] as FAESTParameters>::OWF as OWFParameters>::witness_per_round_bytes(),
+                        )*
+                    }
+                }
+
+                /// Estimate the cycles of signing and verification
+                ///
+                /// Returns `(sign_cycles, verify_cycles)` computed from the
+                /// number of AES blocks, SHAKE blocks and field
+                /// multiplications of the variant. This is a rough model for
+                /// planning, not a measurement.
+                pub fn estimate_cycles(self, costs: &CycleCosts) -> (u64, u64) {
+                    match self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param => <crate::parameter::[<$param Parameters>] as FAESTParameters>::estimate_cycles(costs),
+                        )*
+                    }
+                }
+
+                /// Parse a signature of this variant
+                pub fn parse_signature(self, signature: &[u8]) -> Result<ParsedSignature, Error> {
+                    match self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param => crate::[<$param Signature>]::try_from(signature)
+                                .map(|signature| ParsedSignature::$param(Box::new(signature))),
+                        )*
+                    }
+                }
+
+                /// Parse a raw encoded verification key of this variant
+                pub fn parse_verification_key(self, verification_key: &[u8]) -> Result<ParsedVerificationKey, Error> {
+                    match self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param => crate::[<$param VerificationKey>]::try_from(verification_key)
+                                .map(ParsedVerificationKey::$param),
+                        )*
+                    }
+                }
+            }
+
+            /// Verification key of any FAEST variant
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            #[non_exhaustive]
+            pub enum ParsedVerificationKey {
+                $(
+                    $(#[$meta])*
+                    #[doc = "Verification key for " $name]
+                    $param(crate::[<$param VerificationKey>]),
+                )*
+            }
+
+            impl ParsedVerificationKey {
+                /// Variant of the verification key
+                pub const fn variant(&self) -> FaestVariant {
+                    match *self {
+                        $($(#[$meta])* Self::$param(_) => FaestVariant::$param,)*
+                    }
+                }
+
+                /// Verify a signature of the same variant
+                ///
+                /// Fails if the signature belongs to another variant.
+                pub fn verify(&self, msg: &[u8], signature: &ParsedSignature) -> Result<(), Error> {
+                    match (self, signature) {
+                        $(
+                            $(#[$meta])*
+                            (Self::$param(verification_key), ParsedSignature::$param(signature)) => {
+                                verification_key.verify(msg, signature)
+                            }
+                        )*
+                        #[allow(unreachable_patterns)]
+                        _ => Err(Error::new()),
+                    }
+                }
+            }
+
+            impl AsRef<[u8]> for ParsedVerificationKey {
+                fn as_ref(&self) -> &[u8] {
+                    match *self {
+                        $($(#[$meta])* Self::$param(ref verification_key) => verification_key.as_bytes(),)*
+                    }
+                }
+            }
+
+            /// Signature of any FAEST variant
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            #[non_exhaustive]
+            pub enum ParsedSignature {
+                $(
+                    $(#[$meta])*
+                    #[doc = "Signature for " $name]
+                    $param(Box<crate::[<$param Signature>]>),
+                )*
+            }
+
+            impl ParsedSignature {
+                /// Variant of the signature
+                pub const fn variant(&self) -> FaestVariant {
+                    match *self {
+                        $($(#[$meta])* Self::$param(_) => FaestVariant::$param,)*
+                    }
+                }
+
+                /// Verify the signature with an encoded verification key of the same variant
+                pub fn verify(&self, msg: &[u8], verification_key: &[u8]) -> Result<(), Error> {
+                    match *self {
+                        $(
+                            $(#[$meta])*
+                            Self::$param(ref signature) => {
+                                crate::[<$param VerificationKey>]::try_from(verification_key)?
+                                    .verify(msg, signature)
+                            }
+                        )*
+                    }
+                }
+
+                /// Verify the signature with a tagged verification key
+                ///
+                /// The key is expected in the encoding of
+                /// [`ParsedVerificationKey::to_tagged_bytes`]. Fails if the
+                /// key is tagged with another variant, even if its raw
+                /// encoding has the size of the keys of this variant.
+                pub fn verify_tagged(&self, msg: &[u8], tagged_verification_key: &[u8]) -> Result<(), Error> {
+                    let (variant, verification_key) = split_tag(tagged_verification_key)?;
+                    if variant != self.variant() {
+                        return Err(Error::new());
+                    }
+                    self.verify(msg, verification_key)
+                }
+            }
+
+            impl AsRef<[u8]> for ParsedSignature {
+                fn as_ref(&self) -> &[u8] {
+                    match *self {
+                        $($(#[$meta])* Self::$param(ref signature) => signature.as_ref().as_ref(),)*
+                    }
+                }
+            }
+        }
+    };
+}
+
+define_variants! {
+    #[cfg(feature = "faest-128f")]
+    FAEST128f => "FAEST-128f", 1, true;
+    #[cfg(feature = "faest-128s")]
+    FAEST128s => "FAEST-128s", 2, true;
+    #[cfg(feature = "faest-192f")]
+    FAEST192f => "FAEST-192f", 3, true;
+    #[cfg(feature = "faest-192s")]
+    FAEST192s => "FAEST-192s", 4, true;
+    #[cfg(feature = "faest-256f")]
+    FAEST256f => "FAEST-256f", 5, true;
+    #[cfg(feature = "faest-256s")]
+    FAEST256s => "FAEST-256s", 6, true;
+    #[cfg(feature = "faest-em-128f")]
+    FAESTEM128f => "FAEST-EM-128f", 7, true;
+    #[cfg(feature = "faest-em-128s")]
+    FAESTEM128s => "FAEST-EM-128s", 8, true;
+    #[cfg(feature = "faest-em-192f")]
+    FAESTEM192f => "FAEST-EM-192f", 9, true;
+    #[cfg(feature = "faest-em-192s")]
+    FAESTEM192s => "FAEST-EM-192s", 10, true;
+    #[cfg(feature = "faest-em-256f")]
+    FAESTEM256f => "FAEST-EM-256f", 11, true;
+    #[cfg(feature = "faest-em-256s")]
+    FAESTEM256s => "FAEST-EM-256s", 12, true;
+    #[cfg(feature = "blake3-oracle")]
+    FAEST128fBlake3 => "FAEST-128f-BLAKE3", 13, false;
+    #[cfg(feature = "blake3-oracle")]
+    FAEST128sBlake3 => "FAEST-128s-BLAKE3", 14, false;
+    #[cfg(feature = "experimental")]
+    FAEST128us => "FAEST-128us", 15, true;
+}
+
+// Length-based detection requires pairwise distinct signature sizes.
+const _: () = {
+    let all = FaestVariant::ALL;
+    let mut i = 0;
+    while i < all.len() {
+        let mut j = i + 1;
+        while j < all.len() {
+            assert!(
+                !all[i].identified_by_length()
+                    || !all[j].identified_by_length()
+                    || all[i].signature_size() != all[j].signature_size(),
+                "two variants identified by length share the signature size"
+            );
+            j += 1;
+        }
+        i += 1;
+    }
+};
+
+impl ParsedVerificationKey {
+    /// Tagged encoding of the verification key
+    ///
+    /// The encoding is the identifier of the variant (see
+    /// [`FaestVariant::id`]) as a single byte followed by the raw encoding of
+    /// the key. In contrast to the raw encoding, it can not be mistaken for a
+    /// key of another variant of the same size, e.g., FAEST-192f and
+    /// FAEST-256f. The raw encoding remains the default as it is the one of the
+    /// FAEST specification.
+    pub fn to_tagged_bytes(&self) -> Vec<u8> {
+        tag(self.variant(), self.as_ref())
+    }
+
+    /// Parse a tagged verification key and return it together with its variant
+    ///
+    /// Fails if the variant is unknown or not enabled in this build, or if the
+    /// size of the key does not match the variant.
+    pub fn from_tagged_bytes(bytes: &[u8]) -> Result<(FaestVariant, Self), Error> {
+        let (variant, verification_key) = split_tag(bytes)?;
+        variant
+            .parse_verification_key(verification_key)
+            .map(|verification_key| (variant, verification_key))
+    }
+}
+
+/// Prefix the raw encoding of a verification key of `variant` with its identifier
+pub(crate) fn tag(variant: FaestVariant, verification_key: &[u8]) -> Vec<u8> {
+    let mut ret = Vec::with_capacity(1 + verification_key.len());
+    ret.push(variant.id());
+    ret.extend_from_slice(verification_key);
+    ret
+}
+
+/// Split a tagged verification key into the variant and the raw encoding
+pub(crate) fn split_tag(bytes: &[u8]) -> Result<(FaestVariant, &[u8]), Error> {
+    let (id, verification_key) = bytes.split_first().ok_or_else(Error::new)?;
+    let variant = FaestVariant::from_id(*id).ok_or_else(Error::new)?;
+    Ok((variant, verification_key))
+}
+
+/// Information about the parameter set of a FAEST variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParameterInfo {
+    /// The variant
+    pub variant: FaestVariant,
+    /// Security level `λ` (in bits)
+    pub security_level: usize,
+    /// Size of the signatures (in bytes)
+    pub signature_size: usize,
+    /// Size of the encoded verification keys (in bytes)
+    pub verification_key_size: usize,
+}
+
+/// Costs of the basic operations of FAEST (in cycles) on a target platform
+///
+/// See [`FaestVariant::estimate_cycles`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CycleCosts {
+    /// Encryption of one AES block
+    pub aes_block: u64,
+    /// One Keccak permutation, i.e., absorbing or squeezing one block of SHAKE
+    pub shake_block: u64,
+    /// Multiplication in `GF(2^λ)`
+    pub field_mul: u64,
+}
+
+impl fmt::Display for FaestVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FaestVariant {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|variant| variant.name() == s)
+            .ok_or_else(Error::new)
+    }
+}
+
+/// Parse a signature of an unknown variant
+///
+/// The variant is identified by the length of the signature. Variants that
+/// share their signature size with another variant (see
+/// [`FaestVariant::identified_by_length`]) are never returned. Use
+/// [`FaestVariant::parse_signature`] if the variant is known.
+pub fn parse_signature_any(signature: &[u8]) -> Result<(FaestVariant, ParsedSignature), Error> {
+    let variant = FaestVariant::ALL
+        .iter()
+        .copied()
+        .find(|variant| {
+            variant.identified_by_length() && variant.signature_size() == signature.len()
+        })
+        .ok_or_else(Error::new)?;
+    variant
+        .parse_signature(signature)
+        .map(|signature| (variant, signature))
+}
+
+/// Detect the parameter set of a signature from its length
+///
+/// Returns `None` if the length does not match any variant enabled in this
+/// build or if it matches more than one variant. In contrast to
+/// [`parse_signature_any`], variants that are not identified by the length of
+/// their signatures are taken into account, hence the standard FAEST-128
+/// variants are ambiguous if the `blake3-oracle` feature is enabled.
+pub fn detect_parameters(signature: &[u8]) -> Option<ParameterInfo> {
+    let mut candidates = FaestVariant::ALL
+        .iter()
+        .filter(|variant| variant.signature_size() == signature.len());
+    match (candidates.next(), candidates.next()) {
+        (Some(variant), None) => Some(variant.parameter_info()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn estimate_cycles() {
+        let costs = CycleCosts {
+            aes_block: 100,
+            shake_block: 1000,
+            field_mul: 50,
+        };
+        for variant in FaestVariant::ALL.iter().copied() {
+            let (sign, verify) = variant.estimate_cycles(&costs);
+            assert!(sign > 0, "{variant}");
+            assert!(verify > 0, "{variant}");
+            // the verifier does not expand the hidden leaves
+            assert!(verify < sign, "{variant}");
+        }
+
+        let zero = CycleCosts {
+            aes_block: 0,
+            shake_block: 0,
+            field_mul: 0,
+        };
+        let Some(variant) = FaestVariant::ALL.first() else {
+            return;
+        };
+        assert_eq!(variant.estimate_cycles(&zero), (0, 0));
+    }
+
+    #[test]
+    fn parse_any() {
+        for variant in FaestVariant::ALL
+            .iter()
+            .copied()
+            .filter(|variant| variant.identified_by_length())
+        {
+            let signature = vec![0x5a; variant.signature_size()];
+            let (parsed_variant, parsed) = parse_signature_any(&signature).unwrap();
+            assert_eq!(parsed_variant, variant);
+            assert_eq!(parsed.variant(), variant);
+            assert_eq!(parsed.as_ref(), signature.as_slice());
+
+            assert!(parse_signature_any(&signature[..signature.len() - 1]).is_err());
+            assert!(parse_signature_any(&vec![0x5a; variant.signature_size() + 1]).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_known_variant() {
+        for variant in FaestVariant::ALL.iter().copied() {
+            let signature = vec![0xa5; variant.signature_size()];
+            assert_eq!(
+                variant.parse_signature(&signature).unwrap().variant(),
+                variant
+            );
+            assert!(variant
+                .parse_signature(&signature[..signature.len() - 1])
+                .is_err());
+            assert!(variant
+                .parse_signature(&vec![0xa5; variant.signature_size() + 1])
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn detect_by_length() {
+        for variant in FaestVariant::ALL.iter().copied() {
+            let info = variant.parameter_info();
+            assert_eq!(info.variant, variant);
+            assert_eq!(info.signature_size, variant.signature_size());
+
+            let signature = vec![0; variant.signature_size()];
+            match detect_parameters(&signature) {
+                Some(detected) => assert_eq!(detected, info),
+                None => {
+                    assert!(FaestVariant::ALL.iter().any(|other| *other != variant
+                        && other.signature_size() == variant.signature_size()))
+                }
+            }
+        }
+
+        for len in FaestVariant::ALL
+            .iter()
+            .flat_map(|variant| [variant.signature_size() - 1, variant.signature_size() + 1])
+        {
+            if FaestVariant::ALL
+                .iter()
+                .all(|variant| variant.signature_size() != len)
+            {
+                assert_eq!(detect_parameters(&vec![0; len]), None);
+            }
+        }
+        assert_eq!(detect_parameters(&[]), None);
+    }
+
+    #[cfg(all(feature = "blake3-oracle", feature = "faest-128f"))]
+    #[test]
+    fn detect_ambiguous() {
+        let signature = vec![0; FaestVariant::FAEST128f.signature_size()];
+        assert_eq!(detect_parameters(&signature), None);
+        assert!(parse_signature_any(&signature).is_ok());
+    }
+
+    #[test]
+    fn names() {
+        for variant in FaestVariant::ALL {
+            assert_eq!(
+                variant.to_string().parse::<FaestVariant>().unwrap(),
+                *variant
+            );
+        }
+        assert!("FAEST-128".parse::<FaestVariant>().is_err());
+    }
+
+    #[test]
+    fn ids() {
+        for variant in FaestVariant::ALL {
+            assert_eq!(FaestVariant::from_id(variant.id()), Some(*variant));
+            assert_eq!(
+                FaestVariant::ALL
+                    .iter()
+                    .filter(|other| other.id() == variant.id())
+                    .count(),
+                1
+            );
+        }
+        assert_eq!(FaestVariant::from_id(0), None);
+        assert_eq!(FaestVariant::from_id(u8::MAX), None);
+    }
+
+    #[test]
+    fn key_reuse() {
+        for variant in FaestVariant::ALL {
+            assert_eq!(variant.max_sign_operations(), None);
+            assert!(!variant.requires_unique_nonce());
+        }
+    }
+
+    #[test]
+    fn circuit_structure() {
+        for variant in FaestVariant::ALL {
+            let security_level = variant.parameter_info().security_level;
+            assert_eq!(variant.num_key_words() * 32, security_level);
+            assert_eq!(variant.circuit_depth(), variant.num_key_words() + 6);
+        }
+    }
+
+    #[test]
+    fn tagged_verification_keys() {
+        for variant in FaestVariant::ALL.iter().copied() {
+            let raw = vec![0x3c; variant.parameter_info().verification_key_size];
+            let verification_key = variant.parse_verification_key(&raw).unwrap();
+            assert_eq!(verification_key.variant(), variant);
+            assert_eq!(verification_key.as_ref(), raw.as_slice());
+
+            let tagged = verification_key.to_tagged_bytes();
+            assert_eq!(tagged[0], variant.id());
+            assert_eq!(&tagged[1..], raw.as_slice());
+            assert_eq!(
+                ParsedVerificationKey::from_tagged_bytes(&tagged).unwrap(),
+                (variant, verification_key)
+            );
+
+            assert!(ParsedVerificationKey::from_tagged_bytes(&tagged[..tagged.len() - 1]).is_err());
+            assert!(
+                ParsedVerificationKey::from_tagged_bytes(&[tagged.as_slice(), &[0]].concat())
+                    .is_err()
+            );
+            // the raw encoding is not accepted as tagged encoding
+            assert!(ParsedVerificationKey::from_tagged_bytes(&raw).is_err());
+        }
+        assert!(ParsedVerificationKey::from_tagged_bytes(&[]).is_err());
+        assert!(ParsedVerificationKey::from_tagged_bytes(&[0; 33]).is_err());
+        assert!(ParsedVerificationKey::from_tagged_bytes(&[u8::MAX; 33]).is_err());
+    }
+
+    #[cfg(feature = "_variant")]
+    #[test]
+    fn tagged_ambiguous_sizes() {
+        let mut pairs = 0;
+        for variant in FaestVariant::ALL.iter().copied() {
+            let size = variant.parameter_info().verification_key_size;
+            for other in FaestVariant::ALL.iter().copied().filter(|other| {
+                *other != variant && other.parameter_info().verification_key_size == size
+            }) {
+                pairs += 1;
+
+                // the raw encoding is valid for both variants
+                let raw = vec![0xc3; size];
+                assert!(variant.parse_verification_key(&raw).is_ok());
+                assert!(other.parse_verification_key(&raw).is_ok());
+
+                // the tagged encoding only for the tagged variant
+                let tagged = variant
+                    .parse_verification_key(&raw)
+                    .unwrap()
+                    .to_tagged_bytes();
+                let (parsed_variant, parsed) =
+                    ParsedVerificationKey::from_tagged_bytes(&tagged).unwrap();
+                assert_eq!(parsed_variant, variant, "{variant} / {other}");
+                assert_ne!(parsed.variant(), other, "{variant} / {other}");
+
+                let signature = other
+                    .parse_signature(&vec![0; other.signature_size()])
+                    .unwrap();
+                assert!(
+                    signature.verify_tagged(b"message", &tagged).is_err(),
+                    "{variant} / {other}"
+                );
+            }
+        }
+
+        // ordered pairs of the specified variants: FAEST-128 and FAEST-EM-128
+        // (32 bytes, 4 * 3), FAEST-EM-192 (48 bytes, 2 * 1), FAEST-192,
+        // FAEST-256 and FAEST-EM-256 (64 bytes, 6 * 5)
+        #[cfg(feature = "full")]
+        assert!(pairs >= 12 + 2 + 30, "{pairs}");
+        #[cfg(not(feature = "full"))]
+        let _ = pairs;
+    }
+
+    #[cfg(all(feature = "faest-128f", feature = "faest-em-128f"))]
+    #[test]
+    fn verify_tagged() {
+        use crate::{
+            FAEST128fSignature, FAEST128fSigningKey, FAEST128fVerificationKey,
+            FAESTEM128fVerificationKey, Keypair, KeypairGenerator, Signer,
+        };
+
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let vk = sk.verifying_key();
+        let signature: FAEST128fSignature = sk.sign(b"message");
+        let (_, parsed) = parse_signature_any(signature.as_ref()).unwrap();
+
+        let tagged = vk.to_tagged_bytes();
+        assert_eq!(
+            FAEST128fVerificationKey::from_tagged_bytes(&tagged).unwrap(),
+            vk
+        );
+        assert!(parsed.verify_tagged(b"message", &tagged).is_ok());
+        assert!(parsed.verify_tagged(b"other message", &tagged).is_err());
+        assert!(parsed.verify_tagged(b"message", vk.as_bytes()).is_err());
+
+        // a FAEST-128f key relabeled as FAEST-EM-128f key of the same size
+        let mut relabeled = tagged.clone();
+        relabeled[0] = FaestVariant::FAESTEM128f.id();
+        assert!(FAEST128fVerificationKey::from_tagged_bytes(&relabeled).is_err());
+        assert!(FAESTEM128fVerificationKey::from_tagged_bytes(&relabeled).is_ok());
+        assert!(parsed.verify_tagged(b"message", &relabeled).is_err());
+        // while the raw encoding is accepted
+        assert!(parsed.verify(b"message", vk.as_bytes()).is_ok());
+
+        let (variant, parsed_vk) = ParsedVerificationKey::from_tagged_bytes(&tagged).unwrap();
+        assert_eq!(variant, FaestVariant::FAEST128f);
+        assert!(parsed_vk.verify(b"message", &parsed).is_ok());
+        let (_, relabeled_vk) = ParsedVerificationKey::from_tagged_bytes(&relabeled).unwrap();
+        assert!(relabeled_vk.verify(b"message", &parsed).is_err());
+    }
+
+    #[cfg(feature = "faest-128f")]
+    #[test]
+    fn verify_parsed() {
+        use crate::{
+            ByteEncoding, FAEST128fSignature, FAEST128fSigningKey, Keypair, KeypairGenerator,
+            Signer,
+        };
+
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let vk = sk.verifying_key().to_bytes();
+        let signature: FAEST128fSignature = sk.sign(b"message");
+
+        let (variant, parsed) = parse_signature_any(signature.as_ref()).unwrap();
+        assert_eq!(variant, FaestVariant::FAEST128f);
+        assert!(parsed.verify(b"message", &vk).is_ok());
+        assert!(parsed.verify(b"other message", &vk).is_err());
+        assert!(parsed.verify(b"message", &vk[1..]).is_err());
+    }
+}
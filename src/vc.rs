@@ -51,6 +51,60 @@ pub trait VectorCommitment {
         GenericArray<u8, Self::LambdaBytesTimes2>,
         Vec<GenericArray<u8, Self::LambdaBytes>>,
     );
+
+    /// Reconstruct the commitment without storing the tree
+    ///
+    /// Computes the same commitment as [`VectorCommitment::reconstruct`], but
+    /// passes the seeds of the leaves to `f` in the order of their indices
+    /// instead of returning them. For the hidden leaf, `f` receives `None`.
+    /// Only the keys on the path to the current leaf are kept in memory.
+    fn reconstruct_streaming(
+        pdecom: &[u8],
+        b: &[u8],
+        iv: &IV,
+        f: impl FnMut(usize, Option<&GenericArray<u8, Self::LambdaBytes>>),
+    ) -> GenericArray<u8, Self::LambdaBytesTimes2>;
+
+    /// Check that opened leaves are consistent with a commitment
+    ///
+    /// `coms` are the commitments to all leaves as included in the
+    /// decommitment and `openings` are pairs of leaf index and leaf key. As the
+    /// commitment hashes all leaf commitments, `coms` is required in full, but
+    /// only the opened leaves are recomputed. Returns `false` if an index is out
+    /// of range or an opened leaf does not match its commitment.
+    ///
+    /// This only checks the consistency of individual leaves for interactive
+    /// use of the vector commitment and does not replace the verification of
+    /// signatures.
+    fn verify_leaves(
+        com: &GenericArray<u8, Self::LambdaBytesTimes2>,
+        iv: &IV,
+        coms: &[GenericArray<u8, Self::LambdaBytesTimes2>],
+        openings: &[(usize, &GenericArray<u8, Self::LambdaBytes>)],
+    ) -> bool;
+
+    /// Check that exactly the leaves `leaf_indices` are opened consistently
+    /// with a commitment
+    ///
+    /// In addition to [`VectorCommitment::verify_leaves`], every index of
+    /// `leaf_indices` has to be opened exactly once and no other leaf may be
+    /// opened. Returns `false` if an index is out of range or repeated.
+    fn verify_all_leaves(
+        com: &GenericArray<u8, Self::LambdaBytesTimes2>,
+        iv: &IV,
+        coms: &[GenericArray<u8, Self::LambdaBytesTimes2>],
+        leaf_indices: &[usize],
+        openings: &[(usize, &GenericArray<u8, Self::LambdaBytes>)],
+    ) -> bool {
+        let mut expected = leaf_indices.to_vec();
+        expected.sort_unstable();
+        let mut opened: Vec<_> = openings.iter().map(|(j, _)| *j).collect();
+        opened.sort_unstable();
+
+        expected.windows(2).all(|w| w[0] != w[1])
+            && opened == expected
+            && Self::verify_leaves(com, iv, coms, openings)
+    }
 }
 
 pub(crate) struct VC<PRG, R>(PhantomData<PRG>, PhantomData<R>)
@@ -98,12 +152,7 @@ where
         let mut sd = vec![GenericArray::default(); n];
         let mut com = vec![GenericArray::default(); n];
         for j in 0..n {
-            let mut h0_hasher = R::h0_init();
-            h0_hasher.update(&k[n - 1 + j]);
-            h0_hasher.update(iv);
-            let mut reader = h0_hasher.finish();
-            reader.read(&mut sd[j]);
-            reader.read(&mut com[j]);
+            (sd[j], com[j]) = Self::commit_leaf(&k[n - 1 + j], iv);
             h1_hasher.update(&com[j]);
         }
         //step 6
@@ -162,12 +211,8 @@ where
         //step 11
         for j in 0..(1 << d) {
             if j != a {
-                let mut h0_hasher = R::h0_init();
-                h0_hasher.update(&k[(1 << d) - 1 + j]);
-                h0_hasher.update(iv);
-                let mut reader = h0_hasher.finish();
-                reader.read(&mut sd[j]);
-                let com_j: GenericArray<_, Self::LambdaBytesTimes2> = reader.read_into();
+                let com_j;
+                (sd[j], com_j) = Self::commit_leaf(&k[(1 << d) - 1 + j], iv);
                 h1_hasher.update(&com_j);
             } else {
                 h1_hasher.update(&pdecom[pdecom.len() - 2 * Self::LambdaBytes::USIZE..]);
@@ -175,6 +220,121 @@ where
         }
         (h1_hasher.finish().read_into(), sd)
     }
+
+    fn reconstruct_streaming(
+        pdecom: &[u8],
+        b: &[u8],
+        iv: &IV,
+        mut f: impl FnMut(usize, Option<&GenericArray<u8, Self::LambdaBytes>>),
+    ) -> GenericArray<u8, Self::LambdaBytesTimes2> {
+        let d = b.len();
+        // index of the hidden leaf
+        let a = b
+            .iter()
+            .enumerate()
+            .fold(0, |a, (i, b_i)| a | (usize::from(*b_i) << i));
+
+        // the keys in `pdecom` are the roots of the subtrees covering all
+        // leaves except for the hidden one; sort them by their first leaf
+        let mut subtrees: Vec<_> = (1..=d)
+            .map(|i| {
+                let first_leaf = ((a >> (d - i)) ^ 1) << (d - i);
+                let key = &pdecom[(i - 1) * Self::LambdaBytes::USIZE..i * Self::LambdaBytes::USIZE];
+                (first_leaf, d - i, GenericArray::from_slice(key))
+            })
+            .collect();
+        subtrees.sort_unstable_by_key(|(first_leaf, _, _)| *first_leaf);
+
+        let com_a = &pdecom[pdecom.len() - 2 * Self::LambdaBytes::USIZE..];
+        let mut h1_hasher = R::h1_init();
+        let mut leaf = 0;
+        for (first_leaf, height, key) in subtrees {
+            if leaf == a {
+                f(a, None);
+                h1_hasher.update(com_a);
+                leaf += 1;
+            }
+            debug_assert_eq!(leaf, first_leaf);
+            Self::expand_subtree(key, height, iv, &mut |sd, com| {
+                f(leaf, Some(sd));
+                h1_hasher.update(com);
+                leaf += 1;
+            });
+        }
+        if leaf == a {
+            f(a, None);
+            h1_hasher.update(com_a);
+        }
+        h1_hasher.finish().read_into()
+    }
+
+    fn verify_leaves(
+        com: &GenericArray<u8, Self::LambdaBytesTimes2>,
+        iv: &IV,
+        coms: &[GenericArray<u8, Self::LambdaBytesTimes2>],
+        openings: &[(usize, &GenericArray<u8, Self::LambdaBytes>)],
+    ) -> bool {
+        let mut h1_hasher = R::h1_init();
+        coms.iter().for_each(|com_j| h1_hasher.update(com_j));
+        let h: GenericArray<u8, Self::LambdaBytesTimes2> = h1_hasher.finish().read_into();
+
+        h == *com
+            && openings.iter().all(|(j, k_j)| {
+                coms.get(*j)
+                    .is_some_and(|com_j| Self::commit_leaf(k_j, iv).1 == *com_j)
+            })
+    }
+}
+
+impl<PRG, R> VC<PRG, R>
+where
+    PRG: PseudoRandomGenerator,
+    PRG::KeySize: Add<PRG::KeySize>,
+    <PRG::KeySize as Add<PRG::KeySize>>::Output: ArrayLength,
+    R: RandomOracle,
+{
+    /// Derive the seed and the commitment of a leaf from its key
+    #[allow(clippy::type_complexity)]
+    fn commit_leaf(
+        k: &GenericArray<u8, PRG::KeySize>,
+        iv: &IV,
+    ) -> (
+        GenericArray<u8, PRG::KeySize>,
+        GenericArray<u8, Sum<PRG::KeySize, PRG::KeySize>>,
+    ) {
+        let mut h0_hasher = R::h0_init();
+        h0_hasher.update(k);
+        h0_hasher.update(iv);
+        let mut reader = h0_hasher.finish();
+        let mut sd = GenericArray::default();
+        reader.read(&mut sd);
+        (sd, reader.read_into())
+    }
+
+    /// Pass seeds and commitments of all leaves below `k` to `f`, in order
+    #[allow(clippy::type_complexity)]
+    fn expand_subtree(
+        k: &GenericArray<u8, PRG::KeySize>,
+        height: usize,
+        iv: &IV,
+        f: &mut impl FnMut(
+            &GenericArray<u8, PRG::KeySize>,
+            &GenericArray<u8, Sum<PRG::KeySize, PRG::KeySize>>,
+        ),
+    ) {
+        if height == 0 {
+            let (sd, com) = Self::commit_leaf(k, iv);
+            f(&sd, &com);
+        } else {
+            let mut prg = PRG::new_prg(k, iv);
+            let mut left = GenericArray::default();
+            let mut right = GenericArray::default();
+            prg.read(&mut left);
+            prg.read(&mut right);
+            Self::expand_subtree(&left, height - 1, iv, f);
+            Self::expand_subtree(&right, height - 1, iv, f);
+        }
+    }
 }
 
 //reconstruct is tested in the integration_test_vc test_commitment_and_decomitment() function.
@@ -438,4 +598,154 @@ mod test {
             }
         }
     }
+
+    fn verify_leaves_for<VC: VectorCommitment>() {
+        const N: usize = 8;
+
+        let mut r = GenericArray::default();
+        r.iter_mut().enumerate().for_each(|(i, x)| *x = i as u8);
+        let iv = IV::default();
+        let (com, (k, coms), _) = VC::commit(&r, &iv, N);
+        let leaf = |j: usize| &k[N - 1 + j];
+
+        // all leaves, a subset and no leaves
+        let all: Vec<_> = (0..N).map(|j| (j, leaf(j))).collect();
+        assert!(VC::verify_leaves(&com, &iv, &coms, &all));
+        assert!(VC::verify_leaves(
+            &com,
+            &iv,
+            &coms,
+            &[(1, leaf(1)), (6, leaf(6))]
+        ));
+        assert!(VC::verify_leaves(&com, &iv, &coms, &[]));
+
+        // a single corrupted leaf
+        let mut corrupted = leaf(3).clone();
+        corrupted[0] ^= 1;
+        assert!(!VC::verify_leaves(
+            &com,
+            &iv,
+            &coms,
+            &[(1, leaf(1)), (3, &corrupted)]
+        ));
+        // leaf opened at the wrong index
+        assert!(!VC::verify_leaves(&com, &iv, &coms, &[(2, leaf(3))]));
+
+        // a corrupted leaf commitment
+        let mut modified_coms = coms.clone();
+        modified_coms[5][0] ^= 1;
+        assert!(!VC::verify_leaves(
+            &com,
+            &iv,
+            &modified_coms,
+            &[(1, leaf(1))]
+        ));
+
+        // index out of range
+        assert!(!VC::verify_leaves(&com, &iv, &coms, &[(N, leaf(0))]));
+        assert!(!VC::verify_leaves(
+            &com,
+            &iv,
+            &coms[..N - 1],
+            &[(0, leaf(0))]
+        ));
+    }
+
+    fn verify_all_leaves_for<VC: VectorCommitment>() {
+        const N: usize = 8;
+
+        let mut r = GenericArray::default();
+        r.iter_mut().enumerate().for_each(|(i, x)| *x = 2 * i as u8);
+        let iv = IV::default();
+        let (com, (k, coms), _) = VC::commit(&r, &iv, N);
+        let leaf = |j: usize| &k[N - 1 + j];
+
+        let openings = [(5, leaf(5)), (2, leaf(2))];
+        assert!(VC::verify_all_leaves(&com, &iv, &coms, &[2, 5], &openings));
+        assert!(VC::verify_all_leaves(&com, &iv, &coms, &[], &[]));
+
+        // an expected leaf is missing
+        assert!(!VC::verify_all_leaves(
+            &com,
+            &iv,
+            &coms,
+            &[2, 5, 7],
+            &openings
+        ));
+        // an unexpected leaf is opened
+        assert!(!VC::verify_all_leaves(&com, &iv, &coms, &[2], &openings));
+        // a repeated index
+        assert!(!VC::verify_all_leaves(
+            &com,
+            &iv,
+            &coms,
+            &[2, 2, 5],
+            &[(2, leaf(2)), (2, leaf(2)), (5, leaf(5))]
+        ));
+        // index out of range
+        assert!(!VC::verify_all_leaves(
+            &com,
+            &iv,
+            &coms,
+            &[N],
+            &[(N, leaf(0))]
+        ));
+        // a leaf that does not match its commitment
+        assert!(!VC::verify_all_leaves(
+            &com,
+            &iv,
+            &coms,
+            &[2, 5],
+            &[(5, leaf(2)), (2, leaf(5))]
+        ));
+    }
+
+    fn reconstruct_streaming_for<VC: VectorCommitment>() {
+        const D: usize = 4;
+
+        let mut r = GenericArray::default();
+        r.iter_mut().enumerate().for_each(|(i, x)| *x = 3 * i as u8);
+        let iv = IV::default();
+        let (_, decom, _) = VC::commit(&r, &iv, 1 << D);
+
+        for a in 0..1 << D {
+            let b: GenericArray<u8, U4> =
+                GenericArray::from_iter((0..D).map(|i| (a >> i) as u8 & 1));
+            let (cop, com_a) = VC::open::<U31, U4, U16>(&decom, &b);
+            let pdecom = [cop.concat().as_slice(), com_a].concat();
+
+            let (expected_com, expected_sd) = VC::reconstruct(&pdecom, &b, &iv);
+            let mut indices = Vec::new();
+            let com = VC::reconstruct_streaming(&pdecom, &b, &iv, |j, sd| {
+                indices.push(j);
+                match sd {
+                    Some(sd) => assert_eq!(*sd, expected_sd[j]),
+                    None => assert_eq!(j, a),
+                }
+            });
+            assert_eq!(com, expected_com);
+            assert_eq!(indices, (0..1 << D).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn reconstruct_streaming() {
+        reconstruct_streaming_for::<VC<PRG128, RandomOracleShake128>>();
+        reconstruct_streaming_for::<VC<PRG192, RandomOracleShake256>>();
+        reconstruct_streaming_for::<VC<PRG256, RandomOracleShake256>>();
+    }
+
+    #[test]
+    fn verify_leaves() {
+        verify_leaves_for::<VC<PRG128, RandomOracleShake128>>();
+        verify_leaves_for::<VC<PRG192, RandomOracleShake256>>();
+        verify_leaves_for::<VC<PRG256, RandomOracleShake256>>();
+    }
+
+    #[test]
+    fn verify_all_leaves() {
+        verify_all_leaves_for::<VC<PRG128, RandomOracleShake128>>();
+        verify_all_leaves_for::<VC<PRG192, RandomOracleShake256>>();
+        verify_all_leaves_for::<VC<PRG256, RandomOracleShake256>>();
+    }
 }
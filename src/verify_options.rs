@@ -0,0 +1,225 @@
+//! Options for verification
+//!
+//! [`VerifyOptions`] collects the optional inputs of a verification, i.e., the
+//! context the signature is bound to, a progress callback, whether the VOLE
+//! correlations are reconstructed with a small memory footprint and a
+//! [`BufferPool`] for the scratch buffers. The options are passed to
+//! `verify_with_options` and `verify_vectored_with_options` of the
+//! verification keys. With the default options, the result is identical to
+//! the one of [`Verifier::verify`](crate::Verifier::verify).
+//!
+//! Services verifying many signatures can reuse the buffers of the
+//! reconstructed VOLE correlations across verifications by passing the same
+//! pool to all of them:
+//!
+//! ```
+//! # #[cfg(all(feature = "sign", feature = "faest-128f"))] {
+//! use faest::{BufferPool, FAEST128fSigningKey, Keypair, KeypairGenerator, Signer, VerifyOptions};
+//!
+//! let pool = BufferPool::new(1 << 20);
+//! let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+//! let vk = sk.verifying_key();
+//! for msg in [b"first message", b"other message"] {
+//!     let signature = sk.sign(msg);
+//!     vk.verify_with_options(msg, &signature, VerifyOptions::new().buffer_pool(&pool))
+//!         .expect("Verification failed");
+//! }
+//! assert!(pool.pooled_bytes() > 0);
+//! # }
+//! ```
+
+use std::fmt;
+
+#[cfg(feature = "_variant")]
+use crate::{faest::Domain, sign_options::context_domain, Error};
+use crate::{BufferPool, Phase};
+
+/// Builder for the options of a verification
+///
+/// Per default, signatures are verified without a context, without reporting
+/// progress, with the GGM trees stored during the reconstruction and with
+/// freshly allocated scratch buffers.
+#[derive(Default)]
+pub struct VerifyOptions<'a> {
+    context: &'a [u8],
+    progress: Option<&'a mut dyn FnMut(Phase, f32)>,
+    low_memory: bool,
+    buffer_pool: Option<&'a BufferPool>,
+}
+
+impl<'a> VerifyOptions<'a> {
+    /// Default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify signatures bound to `context`
+    ///
+    /// Contexts longer than [`MAX_CONTEXT_LENGTH`](crate::MAX_CONTEXT_LENGTH)
+    /// bytes are never valid. The empty context is identical to not setting a
+    /// context.
+    pub fn context(mut self, context: &'a [u8]) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Report the progress to `progress`
+    ///
+    /// See `verify_with_progress` of the verification keys for the reported
+    /// values.
+    pub fn progress(mut self, progress: &'a mut dyn FnMut(Phase, f32)) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Reconstruct the VOLE correlations without storing the GGM trees
+    ///
+    /// See `verify_low_memory` of the verification keys. The result does not
+    /// depend on this option.
+    pub fn low_memory(mut self) -> Self {
+        self.low_memory = true;
+        self
+    }
+
+    /// Take the large scratch buffers from `pool`
+    ///
+    /// The buffers are zeroized and returned to the pool after verification.
+    /// The result does not depend on the pool.
+    pub fn buffer_pool(mut self, pool: &'a BufferPool) -> Self {
+        self.buffer_pool = Some(pool);
+        self
+    }
+}
+
+#[cfg(feature = "_variant")]
+impl<'a> VerifyOptions<'a> {
+    /// Take the progress callback
+    pub(crate) fn take_progress(&mut self) -> Option<&'a mut dyn FnMut(Phase, f32)> {
+        self.progress.take()
+    }
+
+    /// Pool of the scratch buffers, if set
+    pub(crate) fn pool(&self) -> Option<&'a BufferPool> {
+        self.buffer_pool
+    }
+
+    /// Whether the VOLE correlations are reconstructed with a small memory
+    /// footprint
+    pub(crate) fn is_low_memory(&self) -> bool {
+        self.low_memory
+    }
+
+    /// Domain of the verified message
+    ///
+    /// Fails if the context is too long.
+    pub(crate) fn domain(&self) -> Result<Domain<'a>, Error> {
+        context_domain(self.context).ok_or_else(Error::new)
+    }
+}
+
+impl fmt::Debug for VerifyOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerifyOptions")
+            .field("context", &self.context)
+            .field("progress", &self.progress.is_some())
+            .field("low_memory", &self.low_memory)
+            .field("buffer_pool", &self.buffer_pool)
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "sign", feature = "faest-128f"))]
+mod test {
+    use super::*;
+
+    use crate::{
+        FAEST128fSignature, FAEST128fSigningKey, Keypair, KeypairGenerator, SignOptions, Signer,
+    };
+
+    const TEST_MESSAGE: &[u8] = "test message".as_bytes();
+
+    #[test]
+    fn default_options() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let vk = sk.verifying_key();
+        let signature: FAEST128fSignature = sk.sign(TEST_MESSAGE);
+        assert!(vk
+            .verify_with_options(TEST_MESSAGE, &signature, VerifyOptions::new())
+            .is_ok());
+        assert!(vk
+            .verify_with_options(b"other message", &signature, VerifyOptions::new())
+            .is_err());
+        assert!(vk
+            .verify_vectored_with_options(&[b"test ", b"message"], &signature, VerifyOptions::new())
+            .is_ok());
+    }
+
+    #[test]
+    fn context() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let vk = sk.verifying_key();
+        let signature = sk
+            .sign_with_options(TEST_MESSAGE, SignOptions::new().context(b"context"))
+            .unwrap();
+        let options = VerifyOptions::new().context(b"context");
+        assert!(vk
+            .verify_with_options(TEST_MESSAGE, &signature, options)
+            .is_ok());
+        let options = VerifyOptions::new().context(b"other context");
+        assert!(vk
+            .verify_with_options(TEST_MESSAGE, &signature, options)
+            .is_err());
+        assert!(vk
+            .verify_with_options(TEST_MESSAGE, &signature, VerifyOptions::new())
+            .is_err());
+
+        let context = [0; crate::MAX_CONTEXT_LENGTH + 1];
+        let options = VerifyOptions::new().context(&context);
+        assert!(vk
+            .verify_with_options(TEST_MESSAGE, &signature, options)
+            .is_err());
+    }
+
+    #[test]
+    fn buffer_pool() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let vk = sk.verifying_key();
+        let signature: FAEST128fSignature = sk.sign(TEST_MESSAGE);
+        let pool = BufferPool::new(1 << 20);
+        for low_memory in [false, true] {
+            for msg in [TEST_MESSAGE, b"other message"] {
+                let options = VerifyOptions::new().buffer_pool(&pool);
+                let options = if low_memory {
+                    options.low_memory()
+                } else {
+                    options
+                };
+                assert_eq!(
+                    vk.verify_with_options(msg, &signature, options).is_ok(),
+                    msg == TEST_MESSAGE
+                );
+                assert!(pool.pooled_bytes() > 0);
+            }
+        }
+    }
+
+    #[test]
+    fn progress() {
+        let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+        let vk = sk.verifying_key();
+        let signature: FAEST128fSignature = sk.sign(TEST_MESSAGE);
+        let mut reported = Vec::new();
+        let mut callback = |phase, progress| reported.push((phase, progress));
+        let options = VerifyOptions::new().progress(&mut callback);
+        assert!(vk
+            .verify_with_options(TEST_MESSAGE, &signature, options)
+            .is_ok());
+
+        let mut expected = Vec::new();
+        vk.verify_with_progress(TEST_MESSAGE, &signature, &mut |phase, progress| {
+            expected.push((phase, progress))
+        })
+        .unwrap();
+        assert_eq!(reported, expected);
+    }
+}
@@ -1,60 +1,532 @@
+//! VOLE correlations from vector commitments
+//!
+//! This module exposes the VOLE-in-the-head commitment of FAEST without the
+//! signature layer. The prover derives `τ` GGM trees from a root seed and
+//! converts the leaves of each tree into a VOLE correlation of `ℓ̂` bytes. The
+//! correlations of all repetitions are aligned with the first one via the
+//! corrections `c`. The hash of the vector commitments binds the prover to the
+//! trees.
+//!
+//! After receiving the challenge `Δ`, the prover opens all but one leaf of each
+//! tree. From the opening, the verifier reconstructs the keys `q`. Denoting by
+//! `Δ_j` the `j`-th bit of `Δ`, the `j`-th rows of `V` and `Q` satisfy
+//! `q_j = v_j + Δ_j·u`.
+//!
+//! The correlations are available for the `λ` and `τ` of the FAEST parameter
+//! sets, e.g., [`Vole128f`] for FAEST-128f and FAEST-EM-128f. The length `ℓ̂`
+//! is chosen by the caller.
+//!
+//! ```
+//! use faest::{
+//!     prg::IV,
+//!     vole::{self, Vole128f},
+//! };
+//! use generic_array::{typenum::U64, GenericArray};
+//!
+//! let seed = GenericArray::from_array([0x42; 16]);
+//! let iv = IV::default();
+//! let commitment = vole::commit::<Vole128f, U64>(&seed, &iv);
+//!
+//! let delta = GenericArray::from_array([0x13; 16]);
+//! let opening = vole::open(&commitment, &delta);
+//! let (hcom, q) =
+//!     vole::reconstruct::<Vole128f, U64>(&delta, &opening, commitment.corrections(), &iv, false)
+//!         .expect("valid opening");
+//! assert_eq!(&hcom, commitment.hash());
+//!
+//! for (j, (q_j, v_j)) in q.iter().zip(commitment.v().iter()).enumerate() {
+//!     let delta_j = (delta[j / 8] >> (j % 8)) & 1;
+//!     for (q, (v, u)) in q_j.iter().zip(v_j.iter().zip(commitment.u().iter())) {
+//!         assert_eq!(*q, v ^ (delta_j * u));
+//!     }
+//! }
+//! ```
+
 use std::{
     iter::zip,
     marker::PhantomData,
-    ops::{Index, IndexMut},
+    ops::{Index, IndexMut, Range},
 };
 
-use generic_array::{typenum::Unsigned, ArrayLength, GenericArray};
+use generic_array::{
+    typenum::{Unsigned, U11, U128, U16, U192, U22, U24, U256, U32, U48, U64},
+    ArrayLength, GenericArray,
+};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 use crate::{
-    parameter::TauParameters,
+    buffer_pool::{BufferPool, ScratchBuffer},
+    parameter::{
+        BaseParameters, FAEST128fParameters, FAEST128sParameters, FAEST192fParameters,
+        FAEST192sParameters, FAEST256fParameters, FAEST256sParameters, FAESTParameters,
+        OWFParameters, TauParameters,
+    },
     prg::{PseudoRandomGenerator, IV},
     random_oracles::{Hasher, RandomOracle},
-    utils::Reader,
+    utils::{xor_into, Reader},
     vc::VectorCommitment,
+    Error,
 };
 
+mod private {
+    pub trait Sealed {}
+}
+
+/// Interface of the VOLE commitments
+///
+/// This trait is sealed and implemented for the `λ` and `τ` of all FAEST
+/// parameter sets.
+pub trait VoleParameters: private::Sealed + Sized {
+    /// Security parameter `λ` (in bits)
+    type Lambda: ArrayLength;
+    /// Security parameter `λ` (in bytes)
+    type LambdaBytes: ArrayLength;
+    /// Size of the hash of the vector commitments (in bytes)
+    type LambdaBytesTimes2: ArrayLength;
+    /// Number of repetitions `τ`
+    type Tau: ArrayLength;
+
+    /// Commit to VOLE correlations of `LH` bytes, see [commit]
+    fn commit<LH>(seed: &Seed<Self>, iv: &IV) -> VoleCommitment<Self, LH>
+    where
+        LH: ArrayLength;
+
+    /// Open the commitment for `delta`, see [open]
+    fn open<LH>(commitment: &VoleCommitment<Self, LH>, delta: &Delta<Self>) -> Vec<u8>
+    where
+        LH: ArrayLength;
+
+    /// Reconstruct the keys of the VOLE correlations, see [reconstruct]
+    fn reconstruct<LH>(
+        delta: &Delta<Self>,
+        opening: &[u8],
+        corrections: &[u8],
+        iv: &IV,
+        low_memory: bool,
+    ) -> Result<(CommitmentHash<Self>, Rows<Self, LH>), Error>
+    where
+        LH: ArrayLength;
+
+    /// Expand the GGM tree of the first repetition and return its commitment
+    #[cfg(feature = "bench-internals")]
+    #[doc(hidden)]
+    fn expand_ggm(seed: &Seed<Self>, iv: &IV) -> CommitmentHash<Self>;
+}
+
+/// Root seed of the VOLE commitment
+pub type Seed<V> = GenericArray<u8, <V as VoleParameters>::LambdaBytes>;
+/// Challenge `Δ`
+pub type Delta<V> = GenericArray<u8, <V as VoleParameters>::LambdaBytes>;
+/// Hash of the vector commitments
+pub type CommitmentHash<V> = GenericArray<u8, <V as VoleParameters>::LambdaBytesTimes2>;
+/// The `λ` rows of `V` or `Q`, each consisting of `LH` bytes
+pub type Rows<V, LH> = Box<GenericArray<GenericArray<u8, LH>, <V as VoleParameters>::Lambda>>;
+
+type Decommitment<V> = Box<
+    GenericArray<
+        (
+            Vec<GenericArray<u8, <V as VoleParameters>::LambdaBytes>>,
+            Vec<GenericArray<u8, <V as VoleParameters>::LambdaBytesTimes2>>,
+        ),
+        <V as VoleParameters>::Tau,
+    >,
+>;
+
+/// Commitment of the prover to VOLE correlations of `LH` bytes
+pub struct VoleCommitment<V, LH>
+where
+    V: VoleParameters,
+    LH: ArrayLength,
+{
+    hcom: CommitmentHash<V>,
+    decom: Decommitment<V>,
+    u: Box<GenericArray<u8, LH>>,
+    v: Rows<V, LH>,
+    c: Vec<u8>,
+}
+
+impl<V, LH> VoleCommitment<V, LH>
+where
+    V: VoleParameters,
+    LH: ArrayLength,
+{
+    /// Hash of the vector commitments
+    pub fn hash(&self) -> &CommitmentHash<V> {
+        &self.hcom
+    }
+
+    /// The random vector `u`
+    pub fn u(&self) -> &GenericArray<u8, LH> {
+        &self.u
+    }
+
+    /// The `λ` rows of `V`
+    pub fn v(&self) -> &GenericArray<GenericArray<u8, LH>, V::Lambda> {
+        &self.v
+    }
+
+    /// Corrections `c_1, ..., c_{τ-1}` of `LH` bytes each
+    pub fn corrections(&self) -> &[u8] {
+        &self.c
+    }
+}
+
+impl<V, LH> std::fmt::Debug for VoleCommitment<V, LH>
+where
+    V: VoleParameters,
+    LH: ArrayLength,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VoleCommitment")
+            .field("hcom", &self.hcom.as_slice())
+            .field("c", &self.c)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<V, LH> Drop for VoleCommitment<V, LH>
+where
+    V: VoleParameters,
+    LH: ArrayLength,
+{
+    fn drop(&mut self) {
+        self.u.zeroize();
+        self.v.iter_mut().for_each(|v| v.zeroize());
+        self.decom.iter_mut().for_each(|(keys, coms)| {
+            keys.iter_mut().for_each(|k| k.zeroize());
+            coms.iter_mut().for_each(|c| c.zeroize());
+        });
+    }
+}
+
+/// Commit to VOLE correlations of `LH` bytes derived from `seed`
+pub fn commit<V, LH>(seed: &Seed<V>, iv: &IV) -> VoleCommitment<V, LH>
+where
+    V: VoleParameters,
+    LH: ArrayLength,
+{
+    V::commit(seed, iv)
+}
+
+/// Open the commitment for the challenge `delta`
+///
+/// The opening consists of the decommitments to all but one leaf of each tree,
+/// where the hidden leaves are determined by `delta`.
+pub fn open<V, LH>(commitment: &VoleCommitment<V, LH>, delta: &Delta<V>) -> Vec<u8>
+where
+    V: VoleParameters,
+    LH: ArrayLength,
+{
+    V::open(commitment, delta)
+}
+
+/// Reconstruct the hash of the vector commitments and the keys `Q` from the
+/// `opening` for `delta`
+///
+/// The keys are corrected with the `corrections` of the prover, i.e., they
+/// form VOLE correlations with the `u` and `V` of the prover. The caller has to
+/// compare the hash with the one of the prover. If `low_memory` is set, the
+/// GGM trees are not stored while reconstructing the keys. The output is the
+/// same in both cases.
+///
+/// Fails if `opening` or `corrections` have an invalid size.
+pub fn reconstruct<V, LH>(
+    delta: &Delta<V>,
+    opening: &[u8],
+    corrections: &[u8],
+    iv: &IV,
+    low_memory: bool,
+) -> Result<(CommitmentHash<V>, Rows<V, LH>), Error>
+where
+    V: VoleParameters,
+    LH: ArrayLength,
+{
+    V::reconstruct(delta, opening, corrections, iv, low_memory)
+}
+
+type VC<P> = <<<P as FAESTParameters>::OWF as OWFParameters>::BaseParams as BaseParameters>::VC;
+type LambdaBytes<P> = <VC<P> as VectorCommitment>::LambdaBytes;
+type LambdaBytesTimes2<P> = <VC<P> as VectorCommitment>::LambdaBytesTimes2;
+
+/// Open the trees of the parameter set `P` for `delta`
+fn open_trees<P>(
+    decom: &[(
+        Vec<GenericArray<u8, LambdaBytes<P>>>,
+        Vec<GenericArray<u8, LambdaBytesTimes2<P>>>,
+    )],
+    delta: &[u8],
+) -> Vec<u8>
+where
+    P: FAESTParameters,
+{
+    let mut opening = Vec::new();
+    for (i, decom_i) in decom.iter().enumerate() {
+        let b = P::Tau::decode_challenge(delta, i);
+        let (keys, com) = if i < <P::Tau as TauParameters>::Tau0::USIZE {
+            VC::<P>::open::<P::POWK0, <P::Tau as TauParameters>::K0, P::N0>(
+                decom_i,
+                GenericArray::from_slice(&b),
+            )
+        } else {
+            VC::<P>::open::<P::POWK1, <P::Tau as TauParameters>::K1, P::N1>(
+                decom_i,
+                GenericArray::from_slice(&b),
+            )
+        };
+        keys.into_iter()
+            .chain([com])
+            .for_each(|x| opening.extend_from_slice(x));
+    }
+    opening
+}
+
+/// Reconstruct and correct the keys of the parameter set `P`
 #[allow(clippy::type_complexity)]
-pub fn convert_to_vole<'a, PRG, LH>(
+fn reconstruct_keys<P, LH>(
+    delta: &[u8],
+    opening: &[u8],
+    corrections: &[u8],
+    iv: &IV,
+    low_memory: bool,
+) -> Result<
+    (
+        GenericArray<u8, LambdaBytesTimes2<P>>,
+        Box<GenericArray<GenericArray<u8, LH>, <VC<P> as VectorCommitment>::Lambda>>,
+    ),
+    Error,
+>
+where
+    P: FAESTParameters,
+    LH: ArrayLength,
+{
+    let lambda_bytes = LambdaBytes::<P>::USIZE;
+    let tau = <P::Tau as TauParameters>::Tau::USIZE;
+    if opening.len() != lambda_bytes * 8 * lambda_bytes + tau * 2 * lambda_bytes
+        || corrections.len() != (tau - 1) * LH::USIZE
+    {
+        return Err(Error::new());
+    }
+
+    let (hcom, mut q) = if low_memory {
+        volereconstruct_low_memory::<VC<P>, P::Tau, LH>(delta, opening, iv)
+    } else {
+        volereconstruct::<VC<P>, P::Tau, LH>(delta, opening, iv)
+    };
+    for (i, c_i) in corrections.chunks_exact(LH::USIZE).enumerate() {
+        let (index, size) = P::Tau::convert_index_and_size(i + 1);
+        for (q_j, delta_j) in zip(
+            &mut q[index..index + size],
+            P::Tau::decode_challenge_as_iter(delta, i + 1),
+        ) {
+            if delta_j == 1 {
+                zip(q_j.iter_mut(), c_i).for_each(|(q, c)| *q ^= c);
+            }
+        }
+    }
+    Ok((hcom, q))
+}
+
+macro_rules! define_vole {
+    ($name:ident, $params:ty, $lambda:ty, $lambda_bytes:ty, $lambda_bytes_times2:ty, $tau:ty, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $name;
+
+        impl private::Sealed for $name {}
+
+        impl VoleParameters for $name {
+            type Lambda = $lambda;
+            type LambdaBytes = $lambda_bytes;
+            type LambdaBytesTimes2 = $lambda_bytes_times2;
+            type Tau = $tau;
+
+            fn commit<LH>(seed: &Seed<Self>, iv: &IV) -> VoleCommitment<Self, LH>
+            where
+                LH: ArrayLength,
+            {
+                let mut c = vec![0; LH::USIZE * (<$tau>::USIZE - 1)];
+                let (hcom, decom, u, v) = volecommit::<
+                    VC<$params>,
+                    <$params as FAESTParameters>::Tau,
+                    LH,
+                >(VoleCommitmentCRef::new(&mut c), seed, iv);
+                VoleCommitment {
+                    hcom,
+                    decom,
+                    u,
+                    v,
+                    c,
+                }
+            }
+
+            fn open<LH>(commitment: &VoleCommitment<Self, LH>, delta: &Delta<Self>) -> Vec<u8>
+            where
+                LH: ArrayLength,
+            {
+                open_trees::<$params>(&commitment.decom, delta)
+            }
+
+            fn reconstruct<LH>(
+                delta: &Delta<Self>,
+                opening: &[u8],
+                corrections: &[u8],
+                iv: &IV,
+                low_memory: bool,
+            ) -> Result<(CommitmentHash<Self>, Rows<Self, LH>), Error>
+            where
+                LH: ArrayLength,
+            {
+                reconstruct_keys::<$params, LH>(delta, opening, corrections, iv, low_memory)
+            }
+
+            #[cfg(feature = "bench-internals")]
+            fn expand_ggm(seed: &Seed<Self>, iv: &IV) -> CommitmentHash<Self> {
+                VC::<$params>::commit(seed, iv, <$params as FAESTParameters>::N0::USIZE).0
+            }
+        }
+    };
+}
+
+define_vole!(
+    Vole128s,
+    FAEST128sParameters,
+    U128,
+    U16,
+    U32,
+    U11,
+    "VOLE commitments of FAEST-128s and FAEST-EM-128s"
+);
+define_vole!(
+    Vole128f,
+    FAEST128fParameters,
+    U128,
+    U16,
+    U32,
+    U16,
+    "VOLE commitments of FAEST-128f and FAEST-EM-128f"
+);
+define_vole!(
+    Vole192s,
+    FAEST192sParameters,
+    U192,
+    U24,
+    U48,
+    U16,
+    "VOLE commitments of FAEST-192s and FAEST-EM-192s"
+);
+define_vole!(
+    Vole192f,
+    FAEST192fParameters,
+    U192,
+    U24,
+    U48,
+    U24,
+    "VOLE commitments of FAEST-192f and FAEST-EM-192f"
+);
+define_vole!(
+    Vole256s,
+    FAEST256sParameters,
+    U256,
+    U32,
+    U64,
+    U22,
+    "VOLE commitments of FAEST-256s and FAEST-EM-256s"
+);
+define_vole!(
+    Vole256f,
+    FAEST256fParameters,
+    U256,
+    U32,
+    U64,
+    U32,
+    "VOLE commitments of FAEST-256f and FAEST-EM-256f"
+);
+
+/// Convert the expansions of the leaves to VOLE correlations written to `v`
+/// using `r` as scratch space
+///
+/// `r` needs to hold at least `2 * n` chunks of `LH` bytes for the `n` leaves.
+/// Its initial content is ignored, so `r` can be reused across repetitions.
+fn convert_to_vole<'a, PRG, LH>(
     v: &mut [GenericArray<u8, LH>],
     sd_0: Option<&GenericArray<u8, PRG::KeySize>>,
     sd: impl ExactSizeIterator<Item = &'a GenericArray<u8, PRG::KeySize>>,
     iv: &IV,
+    r: &mut [u8],
 ) -> GenericArray<u8, LH>
 where
     PRG: PseudoRandomGenerator,
     LH: ArrayLength,
 {
     // these parameters are known upfront!
+    let mut sd = sd;
     let n = sd.len() + 1;
     let d = 64 - (n.leading_zeros() as usize) - 1;
-    let mut r = vec![0; LH::USIZE * n * 2];
+    let r = &mut r[..LH::USIZE * n * 2];
+    // the PRGs add their output to the buffer
+    r[..LH::USIZE * n].fill(0);
+    expand_leaves::<PRG>(&mut r[..LH::USIZE * n], LH::USIZE, sd_0, &mut sd, iv);
+
+    for (j, item) in v.iter_mut().enumerate() {
+        accumulate_vole_row(item, r, n, j);
+    }
+    GenericArray::from_slice(&r[(d % 2) * n * LH::USIZE..((d % 2) * n + 1) * LH::USIZE]).clone()
+}
+
+/// Write the expansions of `sd_0` and the seeds of `sd` to consecutive chunks
+/// of `len` bytes of `r`
+///
+/// Only generic over the PRG such that it is instantiated once per security
+/// level instead of once per parameter set and iterator.
+fn expand_leaves<PRG>(
+    r: &mut [u8],
+    len: usize,
+    sd_0: Option<&GenericArray<u8, PRG::KeySize>>,
+    sd: &mut dyn Iterator<Item = &GenericArray<u8, PRG::KeySize>>,
+    iv: &IV,
+) where
+    PRG: PseudoRandomGenerator,
+{
     if let Some(sd0) = sd_0 {
-        PRG::new_prg(sd0, iv).read(&mut r[0..LH::USIZE]);
+        PRG::new_prg(sd0, iv).read(&mut r[..len]);
     }
-    for (ri, sdi) in zip(r[LH::USIZE..].chunks_exact_mut(LH::USIZE), sd) {
+    for (ri, sdi) in zip(r[len..].chunks_exact_mut(len), sd) {
         PRG::new_prg(sdi, iv).read(ri);
     }
+}
 
-    // FIXME
-    for (j, item) in v.iter_mut().enumerate() {
-        let j_offset = (j % 2) * n;
-        let j1_offset = ((j + 1) % 2) * n;
-        for i in 0..n / (1 << (j + 1)) {
-            let j_offset = j_offset + 2 * i;
-            let j1_offset = j1_offset + i;
-            for k in 0..LH::USIZE {
-                item[k] ^= r[(j_offset + 1) * LH::USIZE + k];
-                r[j1_offset * LH::USIZE + k] =
-                    r[j_offset * LH::USIZE + k] ^ r[(j_offset + 1) * LH::USIZE + k];
-            }
+/// Compute the `j`-th row of the VOLE correlations from the expansions in `r`
+///
+/// `r` consists of two halves of `n` chunks of `row.len()` bytes each. The
+/// pairwise sums of the chunks of one half are written to the other one for
+/// the next row. This loop only depends on runtime lengths and is hence not
+/// generic to avoid instantiating it for every parameter set.
+#[inline(never)]
+fn accumulate_vole_row(row: &mut [u8], r: &mut [u8], n: usize, j: usize) {
+    let len = row.len();
+    let (first, second) = r.split_at_mut(n * len);
+    let (src, dst) = if j % 2 == 0 {
+        (&*first, second)
+    } else {
+        (&*second, first)
+    };
+    for (pair, sum) in
+        zip(src.chunks_exact(2 * len), dst.chunks_exact_mut(len)).take(n / (1 << (j + 1)))
+    {
+        let (left, right) = pair.split_at(len);
+        for (((row, sum), left), right) in zip(zip(zip(row.iter_mut(), sum), left), right) {
+            *row ^= right;
+            *sum = left ^ right;
         }
     }
-    GenericArray::from_slice(&r[(d % 2) * n * LH::USIZE..((d % 2) * n + 1) * LH::USIZE]).clone()
 }
 
 /// Reference to storage area in signature for all `c`s.
-pub struct VoleCommitmentCRef<'a, LH>(&'a mut [u8], PhantomData<LH>);
+pub(crate) struct VoleCommitmentCRef<'a, LH>(&'a mut [u8], PhantomData<LH>);
 
 impl<LH> Index<usize> for VoleCommitmentCRef<'_, LH>
 where
@@ -86,8 +558,8 @@ where
 }
 
 #[allow(clippy::type_complexity)]
-pub fn volecommit<VC, Tau, LH>(
-    mut c: VoleCommitmentCRef<LH>,
+pub(crate) fn volecommit<VC, Tau, LH>(
+    c: VoleCommitmentCRef<LH>,
     r: &GenericArray<u8, VC::LambdaBytes>,
     iv: &IV,
 ) -> (
@@ -105,6 +577,50 @@ pub fn volecommit<VC, Tau, LH>(
     Box<GenericArray<u8, LH>>,
     Box<GenericArray<GenericArray<u8, LH>, VC::Lambda>>,
 )
+where
+    Tau: TauParameters,
+    VC: VectorCommitment,
+    LH: ArrayLength,
+{
+    let mut v = GenericArray::default_boxed();
+    let (hcom, decom, u) =
+        volecommit_with_progress::<VC, Tau, LH>(c, r, iv, &mut v, None, true, |_| {});
+    (hcom, decom, u, v)
+}
+
+/// Variant of [`volecommit`] writing `V` to `v` and calling `on_repetition`
+/// with the number of completed repetitions
+///
+/// The scratch space of the conversion of the leaves to VOLE correlations is
+/// shared by all repetitions and taken from `pool` if given. With the
+/// `parallel` feature and if `parallel` is set, the repetitions are computed
+/// in parallel with one scratch buffer per task. The seeds of the repetitions
+/// are still drawn in order, so the result does not depend on the scheduling
+/// of the tasks, and `on_repetition` is called once all repetitions are done.
+/// Otherwise, `parallel` is ignored.
+#[allow(clippy::type_complexity)]
+pub(crate) fn volecommit_with_progress<VC, Tau, LH>(
+    mut c: VoleCommitmentCRef<LH>,
+    r: &GenericArray<u8, VC::LambdaBytes>,
+    iv: &IV,
+    v: &mut GenericArray<GenericArray<u8, LH>, VC::Lambda>,
+    pool: Option<&BufferPool>,
+    parallel: bool,
+    mut on_repetition: impl FnMut(usize),
+) -> (
+    GenericArray<u8, VC::LambdaBytesTimes2>,
+    //Here decom can have two diferent length, depending on if it's a i < t0 or > 0 so we use vectors
+    Box<
+        GenericArray<
+            (
+                Vec<GenericArray<u8, VC::LambdaBytes>>,
+                Vec<GenericArray<u8, VC::LambdaBytesTimes2>>,
+            ),
+            Tau::Tau,
+        >,
+    >,
+    Box<GenericArray<u8, LH>>,
+)
 where
     Tau: TauParameters,
     VC: VectorCommitment,
@@ -113,41 +629,142 @@ where
     let mut prg = VC::PRG::new_prg(r, iv);
     let mut decom = GenericArray::default_boxed();
     let mut u0 = GenericArray::<u8, LH>::default_boxed();
-    let mut v = GenericArray::default_boxed();
+    let scratch_len = 2 * LH::USIZE * (1 << Tau::K0::USIZE.max(Tau::K1::USIZE));
 
     let mut hasher = VC::RO::h1_init();
-    for i in 0..Tau::Tau::USIZE {
-        let b = usize::from(i < Tau::Tau0::USIZE);
-        let k = b * Tau::K0::USIZE + (1 - b) * Tau::K1::USIZE;
-        let mut r_i = GenericArray::default();
-        prg.read(&mut r_i);
-        let (com_i, decom_i, sd_i) = VC::commit(&r_i, iv, 1 << k);
+    let mut finish_repetition = |i: usize, (com_i, decom_i, ui): Repetition<VC, LH>| {
         decom[i] = decom_i;
         hasher.update(&com_i);
-        let ui = convert_to_vole::<VC::PRG, _>(
-            {
-                let (index, size) = Tau::convert_index_and_size(i);
-                &mut v[index..index + size]
-            },
-            Some(&sd_i[0]),
-            sd_i.iter().skip(1),
-            iv,
-        );
-
         if i == 0 {
             *u0 = ui;
         } else {
-            for (c, (u0, ui)) in zip(c[i - 1].iter_mut(), zip(u0.iter(), ui.into_iter())) {
-                *c = u0 ^ ui;
+            let c_i = &mut c[i - 1];
+            c_i.copy_from_slice(&u0);
+            xor_into(c_i, &ui);
+        }
+        on_repetition(i + 1);
+    };
+
+    if parallel && cfg!(feature = "parallel") {
+        #[cfg(feature = "parallel")]
+        {
+            let r_is: Vec<GenericArray<u8, VC::LambdaBytes>> = (0..Tau::Tau::USIZE)
+                .map(|_| {
+                    let mut r_i = GenericArray::default();
+                    prg.read(&mut r_i);
+                    r_i
+                })
+                .collect();
+            let mut v_is = Vec::with_capacity(Tau::Tau::USIZE);
+            let mut rest = v.as_mut_slice();
+            for i in 0..Tau::Tau::USIZE {
+                let (_, size) = Tau::convert_index_and_size(i);
+                let (v_i, tail) = rest.split_at_mut(size);
+                v_is.push(v_i);
+                rest = tail;
+            }
+
+            let repetitions: Vec<_> = r_is
+                .par_iter()
+                .zip(v_is)
+                .enumerate()
+                .map_init(
+                    || ScratchBuffer::new(scratch_len, pool),
+                    |scratch, (i, (r_i, v_i))| {
+                        commit_repetition::<VC, Tau, LH>(i, r_i, iv, v_i, scratch)
+                    },
+                )
+                .collect();
+            for (i, repetition) in repetitions.into_iter().enumerate() {
+                finish_repetition(i, repetition);
             }
         }
+    } else {
+        let mut scratch = ScratchBuffer::new(scratch_len, pool);
+        for i in 0..Tau::Tau::USIZE {
+            let mut r_i = GenericArray::default();
+            prg.read(&mut r_i);
+            let (index, size) = Tau::convert_index_and_size(i);
+            let repetition = commit_repetition::<VC, Tau, LH>(
+                i,
+                &r_i,
+                iv,
+                &mut v[index..index + size],
+                &mut scratch,
+            );
+            finish_repetition(i, repetition);
+        }
     }
 
-    (hasher.finish().read_into(), decom, u0, v)
+    (hasher.finish().read_into(), decom, u0)
+}
+
+/// Commitment, decommitment and `u` of a single repetition
+type Repetition<VC, LH> = (
+    GenericArray<u8, <VC as VectorCommitment>::LambdaBytesTimes2>,
+    (
+        Vec<GenericArray<u8, <VC as VectorCommitment>::LambdaBytes>>,
+        Vec<GenericArray<u8, <VC as VectorCommitment>::LambdaBytesTimes2>>,
+    ),
+    GenericArray<u8, LH>,
+);
+
+/// Commit to the GGM tree of the `i`-th repetition seeded by `r_i` and write
+/// the VOLE correlations of its leaves to `v`
+fn commit_repetition<VC, Tau, LH>(
+    i: usize,
+    r_i: &GenericArray<u8, VC::LambdaBytes>,
+    iv: &IV,
+    v: &mut [GenericArray<u8, LH>],
+    scratch: &mut [u8],
+) -> Repetition<VC, LH>
+where
+    Tau: TauParameters,
+    VC: VectorCommitment,
+    LH: ArrayLength,
+{
+    let b = usize::from(i < Tau::Tau0::USIZE);
+    let k = b * Tau::K0::USIZE + (1 - b) * Tau::K1::USIZE;
+    let (com_i, decom_i, sd_i) = VC::commit(r_i, iv, 1 << k);
+    let ui = convert_to_vole::<VC::PRG, _>(v, Some(&sd_i[0]), sd_i.iter().skip(1), iv, scratch);
+    (com_i, decom_i, ui)
+}
+
+/// Partial decommitment of the `i`-th repetition
+fn partial_decommitment<VC, Tau>(pdecom: &[u8], i: usize) -> &[u8]
+where
+    Tau: TauParameters,
+    VC: VectorCommitment,
+{
+    &pdecom[partial_decommitment_range(
+        i,
+        Tau::Tau0::USIZE,
+        Tau::K0::USIZE,
+        Tau::K1::USIZE,
+        VC::LambdaBytes::USIZE,
+    )]
+}
+
+/// Range of the partial decommitment of the `i`-th repetition, i.e., `k`
+/// keys and the commitment of the hidden leaf
+fn partial_decommitment_range(
+    i: usize,
+    tau0: usize,
+    k0: usize,
+    k1: usize,
+    lambda_bytes: usize,
+) -> Range<usize> {
+    let (keys, k) = if i < tau0 {
+        (k0 * i, k0)
+    } else {
+        (k0 * tau0 + (i - tau0) * k1, k1)
+    };
+    let start = keys * lambda_bytes + i * 2 * lambda_bytes;
+    start..start + k * lambda_bytes + 2 * lambda_bytes
 }
 
 #[allow(clippy::type_complexity)]
-pub fn volereconstruct<VC, Tau, LH>(
+pub(crate) fn volereconstruct<VC, Tau, LH>(
     chal: &[u8],
     pdecom: &[u8],
     iv: &IV,
@@ -155,27 +772,43 @@ pub fn volereconstruct<VC, Tau, LH>(
     GenericArray<u8, VC::LambdaBytesTimes2>,
     Box<GenericArray<GenericArray<u8, LH>, VC::Lambda>>,
 )
+where
+    Tau: TauParameters,
+    VC: VectorCommitment,
+    LH: ArrayLength,
+{
+    let mut q = GenericArray::default_boxed();
+    let hcom = volereconstruct_with_progress::<VC, Tau, LH>(chal, pdecom, iv, &mut q, None, |_| {});
+    (hcom, q)
+}
+
+/// Variant of [`volereconstruct`] writing `Q` to `q` and calling
+/// `on_repetition` with the number of completed repetitions
+///
+/// `q` needs to be zero-initialized. The scratch space of the conversion of
+/// the leaves to VOLE correlations is shared by all repetitions and taken from
+/// `pool` if given.
+pub(crate) fn volereconstruct_with_progress<VC, Tau, LH>(
+    chal: &[u8],
+    pdecom: &[u8],
+    iv: &IV,
+    q: &mut GenericArray<GenericArray<u8, LH>, VC::Lambda>,
+    pool: Option<&BufferPool>,
+    mut on_repetition: impl FnMut(usize),
+) -> GenericArray<u8, VC::LambdaBytesTimes2>
 where
     Tau: TauParameters,
     VC: VectorCommitment,
     LH: ArrayLength,
 {
     let mut hasher = VC::RO::h1_init();
-    let q = Box::from_iter((0..Tau::Tau::USIZE).flat_map(|i| {
+    let mut scratch = ScratchBuffer::new(
+        2 * LH::USIZE * (1 << Tau::K0::USIZE.max(Tau::K1::USIZE)),
+        pool,
+    );
+    for i in 0..Tau::Tau::USIZE {
         let delta_p = Tau::decode_challenge(chal, i);
-        let pdecom = if i < Tau::Tau0::USIZE {
-            let start =
-                Tau::K0::USIZE * i * VC::LambdaBytes::USIZE + i * 2 * VC::LambdaBytes::USIZE;
-            &pdecom[start
-                ..start + Tau::K0::USIZE * VC::LambdaBytes::USIZE + 2 * VC::LambdaBytes::USIZE]
-        } else {
-            let start = (Tau::K0::USIZE * Tau::Tau0::USIZE
-                + (i - Tau::Tau0::USIZE) * Tau::K1::USIZE)
-                * VC::LambdaBytes::USIZE
-                + i * 2 * VC::LambdaBytes::USIZE;
-            &pdecom[start
-                ..start + Tau::K1::USIZE * VC::LambdaBytes::USIZE + 2 * VC::LambdaBytes::USIZE]
-        };
+        let pdecom = partial_decommitment::<VC, Tau>(pdecom, i);
         let (com_i, s_i) = VC::reconstruct(pdecom, &delta_p, iv);
         hasher.update(&com_i);
 
@@ -184,22 +817,98 @@ where
             .enumerate()
             .fold(0, |a, (j, d)| a ^ (usize::from(d) << j));
 
-        let k = if i < Tau::Tau0::USIZE {
-            Tau::K0::USIZE
-        } else {
-            Tau::K1::USIZE
-        };
-
-        let mut buf = vec![GenericArray::default(); k];
+        let (index, size) = Tau::convert_index_and_size(i);
         convert_to_vole::<VC::PRG, _>(
-            buf.as_mut_slice(),
+            &mut q[index..index + size],
             None,
-            (1..(1 << k)).map(|j| &s_i[j ^ delta]),
+            (1..(1 << size)).map(|j| &s_i[j ^ delta]),
             iv,
+            &mut scratch,
         );
-        buf.into_iter()
-    }));
-    (hasher.finish().read_into(), q)
+        on_repetition(i + 1);
+    }
+    hasher.finish().read_into()
+}
+
+/// Variant of [`volereconstruct`] with a small memory footprint
+///
+/// Instead of storing the GGM trees, the seeds of the leaves are derived on
+/// the fly and their PRG expansions are accumulated directly into the VOLE
+/// correlations. The peak memory usage is hence independent of the number of
+/// leaves. The outputs are identical to those of [`volereconstruct`].
+#[allow(clippy::type_complexity)]
+pub(crate) fn volereconstruct_low_memory<VC, Tau, LH>(
+    chal: &[u8],
+    pdecom: &[u8],
+    iv: &IV,
+) -> (
+    GenericArray<u8, VC::LambdaBytesTimes2>,
+    Box<GenericArray<GenericArray<u8, LH>, VC::Lambda>>,
+)
+where
+    Tau: TauParameters,
+    VC: VectorCommitment,
+    LH: ArrayLength,
+{
+    let mut q = GenericArray::default_boxed();
+    let hcom =
+        volereconstruct_low_memory_with_progress::<VC, Tau, LH>(chal, pdecom, iv, &mut q, |_| {});
+    (hcom, q)
+}
+
+/// Variant of [`volereconstruct_low_memory`] writing `Q` to `q` and calling
+/// `on_repetition` with the number of completed repetitions
+///
+/// `q` needs to be zero-initialized.
+pub(crate) fn volereconstruct_low_memory_with_progress<VC, Tau, LH>(
+    chal: &[u8],
+    pdecom: &[u8],
+    iv: &IV,
+    q: &mut GenericArray<GenericArray<u8, LH>, VC::Lambda>,
+    mut on_repetition: impl FnMut(usize),
+) -> GenericArray<u8, VC::LambdaBytesTimes2>
+where
+    Tau: TauParameters,
+    VC: VectorCommitment,
+    LH: ArrayLength,
+{
+    let mut hasher = VC::RO::h1_init();
+    let mut r = GenericArray::<u8, LH>::default();
+    for i in 0..Tau::Tau::USIZE {
+        let delta_p = Tau::decode_challenge(chal, i);
+        let delta: usize = delta_p
+            .iter()
+            .enumerate()
+            .fold(0, |a, (j, d)| a ^ (usize::from(*d) << j));
+        let (index, size) = Tau::convert_index_and_size(i);
+        let q_i = &mut q[index..index + size];
+
+        // `convert_to_vole` is applied to the seeds permuted by `delta`: the
+        // `j`-th output accumulates the expansions of all leaves whose
+        // permuted index has bit `j` set
+        let com_i = VC::reconstruct_streaming(
+            partial_decommitment::<VC, Tau>(pdecom, i),
+            &delta_p,
+            iv,
+            |leaf, sd| {
+                let Some(sd) = sd else {
+                    return;
+                };
+                let l = leaf ^ delta;
+                // the PRGs add their output to the buffer
+                r.fill(0);
+                VC::PRG::new_prg(sd, iv).read(&mut r);
+                for (j, q_ij) in q_i.iter_mut().enumerate() {
+                    if (l >> j) & 1 == 1 {
+                        xor_into(q_ij, &r);
+                    }
+                }
+            },
+        );
+        hasher.update(&com_i);
+        on_repetition(i + 1);
+    }
+    hasher.finish().read_into()
 }
 
 #[cfg(test)]
@@ -207,6 +916,7 @@ mod test {
     use super::*;
 
     use generic_array::{sequence::GenericSequence, GenericArray};
+    use rand::RngCore;
     use serde::Deserialize;
 
     use crate::{
@@ -216,7 +926,7 @@ mod test {
             FAESTEM128sParameters, FAESTEM192fParameters, FAESTEM192sParameters,
             FAESTEM256fParameters, FAESTEM256sParameters, FAESTParameters, OWFParameters,
         },
-        utils::test::read_test_data,
+        utils::{test::read_test_data, witness_bit_at},
     };
 
     type VC<P> = <<<P as FAESTParameters>::OWF as OWFParameters>::BaseParams as BaseParameters>::VC;
@@ -497,4 +1207,119 @@ mod test {
             }
         }
     }
+
+    fn compare_low_memory<P: FAESTParameters>(data: &DataVoleReconstruct) {
+        let pdecom = data
+            .pdec
+            .iter()
+            .zip(&data.com)
+            .flat_map(|(x, y)| [x.concat(), y.to_vec()].concat())
+            .collect::<Vec<u8>>();
+        let res = volereconstruct::<VC<P>, Tau<P>, LH<P>>(&data.chal, &pdecom, &IV::default());
+        let res_low_memory =
+            volereconstruct_low_memory::<VC<P>, Tau<P>, LH<P>>(&data.chal, &pdecom, &IV::default());
+        assert_eq!(res_low_memory.0, *GenericArray::from_slice(&data.hcom));
+        assert_eq!(res_low_memory, res);
+    }
+
+    #[test]
+    fn volereconstruct_low_memory_test() {
+        let database: Vec<DataVoleReconstruct> = read_test_data("DataVoleReconstruct.json");
+        for data in database {
+            match (data.chal.len(), data.q[0].len() == 8) {
+                (16, true) => compare_low_memory::<FAEST128fParameters>(&data),
+                (16, false) => compare_low_memory::<FAEST128sParameters>(&data),
+                (24, true) => compare_low_memory::<FAEST192fParameters>(&data),
+                (24, false) => compare_low_memory::<FAEST192sParameters>(&data),
+                (_, true) => compare_low_memory::<FAEST256fParameters>(&data),
+                (_, false) => compare_low_memory::<FAEST256sParameters>(&data),
+            }
+        }
+    }
+
+    fn vole_correlation<V: VoleParameters>() {
+        type LH = generic_array::typenum::U100;
+
+        let mut rng = rand::thread_rng();
+        let mut seed = Seed::<V>::default();
+        let mut iv = IV::default();
+        rng.fill_bytes(&mut seed);
+        rng.fill_bytes(&mut iv);
+        let commitment = commit::<V, LH>(&seed, &iv);
+        assert_eq!(
+            commitment.corrections().len(),
+            LH::USIZE * (V::Tau::USIZE - 1)
+        );
+
+        for _ in 0..4 {
+            let mut delta = Delta::<V>::default();
+            rng.fill_bytes(&mut delta);
+            let opening = open(&commitment, &delta);
+
+            for low_memory in [false, true] {
+                let (hcom, q) = reconstruct::<V, LH>(
+                    &delta,
+                    &opening,
+                    commitment.corrections(),
+                    &iv,
+                    low_memory,
+                )
+                .unwrap();
+                assert_eq!(&hcom, commitment.hash());
+
+                // q_j = v_j + Δ_j * u
+                for (j, (q_j, v_j)) in zip(q.iter(), commitment.v()).enumerate() {
+                    let delta_j = witness_bit_at(&delta, j);
+                    let expected: GenericArray<u8, LH> = zip(v_j, commitment.u())
+                        .map(|(v, u)| v ^ (delta_j * u))
+                        .collect();
+                    assert_eq!(q_j, &expected, "row {j}");
+                }
+            }
+
+            assert!(reconstruct::<V, LH>(
+                &delta,
+                &opening[1..],
+                commitment.corrections(),
+                &iv,
+                false
+            )
+            .is_err());
+            assert!(reconstruct::<V, LH>(&delta, &opening, &[], &iv, false).is_err());
+        }
+    }
+
+    #[test]
+    fn vole_correlation_test() {
+        vole_correlation::<Vole128s>();
+        vole_correlation::<Vole128f>();
+        vole_correlation::<Vole192s>();
+        vole_correlation::<Vole192f>();
+        vole_correlation::<Vole256s>();
+        vole_correlation::<Vole256f>();
+    }
+
+    fn vole_parameters<V, P>()
+    where
+        V: VoleParameters,
+        P: FAESTParameters,
+    {
+        assert_eq!(V::Lambda::USIZE, <P::OWF as OWFParameters>::LAMBDA::USIZE);
+        assert_eq!(
+            V::LambdaBytes::USIZE,
+            <P::OWF as OWFParameters>::LAMBDABYTES::USIZE
+        );
+        assert_eq!(V::LambdaBytesTimes2::USIZE, 2 * V::LambdaBytes::USIZE);
+        assert_eq!(V::Tau::USIZE, <P::Tau as TauParameters>::Tau::USIZE);
+    }
+
+    #[test]
+    fn vole_parameters_test() {
+        vole_parameters::<Vole128s, FAEST128sParameters>();
+        vole_parameters::<Vole128f, FAEST128fParameters>();
+        vole_parameters::<Vole192s, FAEST192sParameters>();
+        vole_parameters::<Vole192f, FAEST192fParameters>();
+        vole_parameters::<Vole256s, FAEST256sParameters>();
+        vole_parameters::<Vole256f, FAEST256fParameters>();
+    }
 }
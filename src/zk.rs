@@ -0,0 +1,558 @@
+//! QuickSilver-style zero-knowledge proofs for multiplicative constraints
+//!
+//! This module exposes the proof system FAEST uses to show that the committed
+//! witness satisfies the constraints of the AES and EM circuits. The prover
+//! holds committed values together with their VOLE tags, and the verifier holds
+//! the corresponding VOLE keys. For a global key `Δ`, a bit `x` with tag `v` has
+//! key `q = v + x·Δ`. Both sides combine committed bits into wires via linear
+//! operations and assert constraints of the form `a · b = c`. All constraints
+//! are then compressed with the ZK hash into the pair `(a~, b~)`, which is
+//! masked using an additional committed random field element.
+//!
+//! The proofs are designed for VOLE-in-the-head protocols, i.e., the
+//! verifier learns `Δ` and the keys only after the prover has sent `(a~, b~)`.
+//! Generating the VOLE correlations is not part of this module.
+//!
+//! The types are available for the security levels 128, 192 and 256. The
+//! challenge, i.e., the key of the ZK hash, consists of `3λ/8 + 8` bytes.
+//!
+//! ```
+//! use faest::zk::{Prover128, ProverWire128, Verifier128, VerifierWire128};
+//! use rand::RngCore;
+//!
+//! /// Multiplication in GF(2^8) with the AES polynomial
+//! fn gf256_mul(mut x: u8, mut y: u8) -> u8 {
+//!     let mut z = 0;
+//!     while y != 0 {
+//!         if y & 1 == 1 {
+//!             z ^= x;
+//!         }
+//!         x = (x << 1) ^ if x & 0x80 != 0 { 0x1b } else { 0 };
+//!         y >>= 1;
+//!     }
+//!     z
+//! }
+//!
+//! let mut rng = rand::thread_rng();
+//! let mut delta = [0u8; 16];
+//! rng.fill_bytes(&mut delta);
+//!
+//! // VOLE correlation for a bit: the prover obtains the tag, the verifier the key
+//! let mut commit = |bit: bool| {
+//!     let mut tag = [0u8; 16];
+//!     rng.fill_bytes(&mut tag);
+//!     let mut key = tag;
+//!     if bit {
+//!         key.iter_mut().zip(&delta).for_each(|(k, d)| *k ^= d);
+//!     }
+//!     (ProverWire128::from_bit(bit, &tag), VerifierWire128::from_key(&key))
+//! };
+//! let mut commit_byte = |byte: u8| {
+//!     let (p, v): (Vec<_>, Vec<_>) = (0..8).map(|i| commit((byte >> i) & 1 == 1)).unzip();
+//!     (
+//!         ProverWire128::byte_combine(&p.try_into().unwrap()),
+//!         VerifierWire128::byte_combine(&v.try_into().unwrap()),
+//!     )
+//! };
+//!
+//! // prove knowledge of x and y with x * y = z over GF(2^8)
+//! let (x, y) = (0x57, 0x83);
+//! let (x_p, x_v) = commit_byte(x);
+//! let (y_p, y_v) = commit_byte(y);
+//! let (z_p, z_v) = commit_byte(gf256_mul(x, y));
+//! let (mask_p, mask_v): (Vec<_>, Vec<_>) = (0..128).map(|i| commit(i % 3 == 0)).unzip();
+//!
+//! let mut challenge = [0u8; 56];
+//! rng.fill_bytes(&mut challenge);
+//!
+//! let mut prover = Prover128::new(&challenge);
+//! prover.assert_mul(&x_p, &y_p, &z_p);
+//! let (a_t, b_t) = prover.finalize(&ProverWire128::combine(&mask_p));
+//!
+//! let mut verifier = Verifier128::new(&challenge, &delta);
+//! verifier.assert_mul(&x_v, &y_v, &z_v);
+//! assert!(verifier
+//!     .verify(&VerifierWire128::combine(&mask_v), &a_t, &b_t)
+//!     .is_ok());
+//! ```
+
+use std::{fmt, ops::Add};
+
+use generic_array::GenericArray;
+
+use crate::{
+    fields::{BigGaloisField, Field, GF128, GF192, GF256},
+    universal_hashing::{ZKHasher, ZKHasherInit, ZKProofHasher, ZKVerifyHasher},
+    Error,
+};
+
+/// Committed value together with its VOLE tag
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ProverWire<F> {
+    value: F,
+    tag: F,
+}
+
+impl<F> ProverWire<F>
+where
+    F: BigGaloisField,
+{
+    fn from_bit(bit: bool, tag: &[u8]) -> Self {
+        Self {
+            value: if bit { F::ONE } else { F::ZERO },
+            tag: F::from(tag),
+        }
+    }
+
+    fn byte_combine(bits: &[Self; 8]) -> Self {
+        Self {
+            value: F::byte_combine(&bits.map(|bit| bit.value)),
+            tag: F::byte_combine(&bits.map(|bit| bit.tag)),
+        }
+    }
+
+    fn combine(bits: &[Self]) -> Self {
+        bits.iter().rev().fold(
+            Self {
+                value: F::ZERO,
+                tag: F::ZERO,
+            },
+            |sum, bit| Self {
+                value: sum.value.double() + bit.value,
+                tag: sum.tag.double() + bit.tag,
+            },
+        )
+    }
+}
+
+impl<F> Add for ProverWire<F>
+where
+    F: BigGaloisField,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            value: self.value + rhs.value,
+            tag: self.tag + rhs.tag,
+        }
+    }
+}
+
+/// VOLE key of a committed value
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct VerifierWire<F> {
+    key: F,
+}
+
+impl<F> VerifierWire<F>
+where
+    F: BigGaloisField,
+{
+    fn from_key(key: &[u8]) -> Self {
+        Self { key: F::from(key) }
+    }
+
+    fn byte_combine(bits: &[Self; 8]) -> Self {
+        Self {
+            key: F::byte_combine(&bits.map(|bit| bit.key)),
+        }
+    }
+
+    fn combine(bits: &[Self]) -> Self {
+        Self {
+            key: bits
+                .iter()
+                .rev()
+                .fold(F::ZERO, |sum, bit| sum.double() + bit.key),
+        }
+    }
+}
+
+impl<F> Add for VerifierWire<F>
+where
+    F: BigGaloisField,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            key: self.key + rhs.key,
+        }
+    }
+}
+
+/// Prover accumulating constraints over committed values
+pub(crate) struct Prover<F>
+where
+    F: BigGaloisField,
+{
+    zk_hasher: ZKProofHasher<F>,
+}
+
+impl<F> Prover<F>
+where
+    F: BigGaloisField,
+    ZKHasher<F>: ZKHasherInit<F>,
+{
+    fn new(challenge: &[u8]) -> Self {
+        Self {
+            zk_hasher: ZKHasher::<F>::new_zk_proof_hasher(GenericArray::from_slice(challenge)),
+        }
+    }
+
+    const fn one() -> ProverWire<F> {
+        ProverWire {
+            value: F::ONE,
+            tag: F::ZERO,
+        }
+    }
+
+    fn assert_mul(&mut self, a: &ProverWire<F>, b: &ProverWire<F>, c: &ProverWire<F>) {
+        self.zk_hasher
+            .assert_mul((a.value, a.tag), (b.value, b.tag), (c.value, c.tag));
+    }
+
+    fn finalize(self, mask: &ProverWire<F>) -> (F, F) {
+        self.zk_hasher.finalize(&mask.value, &mask.tag)
+    }
+}
+
+/// Verifier accumulating constraints over VOLE keys
+pub(crate) struct Verifier<F>
+where
+    F: BigGaloisField,
+{
+    zk_hasher: ZKVerifyHasher<F>,
+    delta: F,
+}
+
+impl<F> Verifier<F>
+where
+    F: BigGaloisField,
+    ZKHasher<F>: ZKHasherInit<F>,
+{
+    fn new(challenge: &[u8], delta: &[u8]) -> Self {
+        let delta = F::from(delta);
+        Self {
+            zk_hasher: ZKHasher::<F>::new_zk_verify_hasher(
+                GenericArray::from_slice(challenge),
+                delta,
+            ),
+            delta,
+        }
+    }
+
+    const fn one(&self) -> VerifierWire<F> {
+        VerifierWire { key: self.delta }
+    }
+
+    fn assert_mul(&mut self, a: &VerifierWire<F>, b: &VerifierWire<F>, c: &VerifierWire<F>) {
+        self.zk_hasher.assert_mul(a.key, b.key, c.key);
+    }
+
+    fn verify(self, mask: &VerifierWire<F>, a_t: &[u8], b_t: &[u8]) -> Result<(), Error> {
+        let b_t_p = self.zk_hasher.finalize(&mask.key) + F::from(a_t) * self.delta;
+        if b_t_p.as_bytes().as_slice() == b_t {
+            Ok(())
+        } else {
+            Err(Error::new())
+        }
+    }
+}
+
+macro_rules! define_zk {
+    ($lambda:literal, $field:ident, $bytes:literal, $chall:literal) => {
+        paste::paste! {
+            #[doc = "Committed value together with its VOLE tag at security level " $lambda]
+            #[derive(Clone, Copy)]
+            pub struct [<ProverWire $lambda>](ProverWire<$field>);
+
+            impl [<ProverWire $lambda>] {
+                /// Create a wire from a committed bit and its tag
+                pub fn from_bit(bit: bool, tag: &[u8; $bytes]) -> Self {
+                    Self(ProverWire::from_bit(bit, tag))
+                }
+
+                /// Combine eight committed bits into an element of GF(2^8)
+                ///
+                /// The bits are given starting with the least significant one and
+                /// GF(2^8) is defined by the AES polynomial.
+                pub fn byte_combine(bits: &[Self; 8]) -> Self {
+                    Self(ProverWire::byte_combine(&bits.map(|bit| bit.0)))
+                }
+
+                /// Combine committed bits into a field element
+                ///
+                /// The `i`-th bit is the coefficient of `X^i`. In particular,
+                #[doc = "combining " $lambda " random bits yields the mask for"]
+                #[doc = "[`" [<Prover $lambda>] "::finalize`]."]
+                pub fn combine(bits: &[Self]) -> Self {
+                    Self(ProverWire::combine(&bits.iter().map(|bit| bit.0).collect::<Vec<_>>()))
+                }
+            }
+
+            impl Add for [<ProverWire $lambda>] {
+                type Output = Self;
+
+                fn add(self, rhs: Self) -> Self {
+                    Self(self.0 + rhs.0)
+                }
+            }
+
+            impl fmt::Debug for [<ProverWire $lambda>] {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_tuple(stringify!([<ProverWire $lambda>]))
+                        .field(&"redacted")
+                        .finish()
+                }
+            }
+
+            #[doc = "VOLE key of a committed value at security level " $lambda]
+            #[derive(Debug, Clone, Copy)]
+            pub struct [<VerifierWire $lambda>](VerifierWire<$field>);
+
+            impl [<VerifierWire $lambda>] {
+                /// Create a wire from the key of a committed bit
+                pub fn from_key(key: &[u8; $bytes]) -> Self {
+                    Self(VerifierWire::from_key(key))
+                }
+
+                /// Combine the keys of eight committed bits into an element of GF(2^8)
+                ///
+                #[doc = "See [`" [<ProverWire $lambda>] "::byte_combine`]."]
+                pub fn byte_combine(bits: &[Self; 8]) -> Self {
+                    Self(VerifierWire::byte_combine(&bits.map(|bit| bit.0)))
+                }
+
+                /// Combine the keys of committed bits into a field element
+                ///
+                #[doc = "See [`" [<ProverWire $lambda>] "::combine`]."]
+                pub fn combine(bits: &[Self]) -> Self {
+                    Self(VerifierWire::combine(&bits.iter().map(|bit| bit.0).collect::<Vec<_>>()))
+                }
+            }
+
+            impl Add for [<VerifierWire $lambda>] {
+                type Output = Self;
+
+                fn add(self, rhs: Self) -> Self {
+                    Self(self.0 + rhs.0)
+                }
+            }
+
+            #[doc = "Prover for constraints over committed values at security level " $lambda]
+            pub struct [<Prover $lambda>](Prover<$field>);
+
+            impl [<Prover $lambda>] {
+                /// Create a new prover for the given challenge
+                pub fn new(challenge: &[u8; $chall]) -> Self {
+                    Self(Prover::new(challenge))
+                }
+
+                /// Wire holding the constant one
+                pub const fn one(&self) -> [<ProverWire $lambda>] {
+                    [<ProverWire $lambda>](Prover::one())
+                }
+
+                /// Assert that `a * b = c`
+                pub fn assert_mul(
+                    &mut self,
+                    a: &[<ProverWire $lambda>],
+                    b: &[<ProverWire $lambda>],
+                    c: &[<ProverWire $lambda>],
+                ) {
+                    self.0.assert_mul(&a.0, &b.0, &c.0);
+                }
+
+                /// Produce the proof `(a~, b~)` using the committed random `mask`
+                pub fn finalize(self, mask: &[<ProverWire $lambda>]) -> ([u8; $bytes], [u8; $bytes]) {
+                    let (a_t, b_t) = self.0.finalize(&mask.0);
+                    (a_t.as_bytes().into(), b_t.as_bytes().into())
+                }
+            }
+
+            impl fmt::Debug for [<Prover $lambda>] {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_tuple(stringify!([<Prover $lambda>]))
+                        .field(&"redacted")
+                        .finish()
+                }
+            }
+
+            #[doc = "Verifier for constraints over committed values at security level " $lambda]
+            pub struct [<Verifier $lambda>](Verifier<$field>);
+
+            impl [<Verifier $lambda>] {
+                /// Create a new verifier for the given challenge and global key `Δ`
+                pub fn new(challenge: &[u8; $chall], delta: &[u8; $bytes]) -> Self {
+                    Self(Verifier::new(challenge, delta))
+                }
+
+                /// Wire holding the constant one
+                pub const fn one(&self) -> [<VerifierWire $lambda>] {
+                    [<VerifierWire $lambda>](self.0.one())
+                }
+
+                /// Assert that `a * b = c`
+                pub fn assert_mul(
+                    &mut self,
+                    a: &[<VerifierWire $lambda>],
+                    b: &[<VerifierWire $lambda>],
+                    c: &[<VerifierWire $lambda>],
+                ) {
+                    self.0.assert_mul(&a.0, &b.0, &c.0);
+                }
+
+                /// Verify the proof `(a~, b~)` using the keys of the committed random `mask`
+                pub fn verify(
+                    self,
+                    mask: &[<VerifierWire $lambda>],
+                    a_t: &[u8; $bytes],
+                    b_t: &[u8; $bytes],
+                ) -> Result<(), Error> {
+                    self.0.verify(&mask.0, a_t, b_t)
+                }
+            }
+
+            impl fmt::Debug for [<Verifier $lambda>] {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_tuple(stringify!([<Verifier $lambda>])).finish()
+                }
+            }
+        }
+    };
+}
+
+define_zk!(128, GF128, 16, 56);
+define_zk!(192, GF192, 24, 80);
+define_zk!(256, GF256, 32, 104);
+
+#[cfg(test)]
+#[generic_tests::define]
+mod test {
+    use super::*;
+
+    use std::array;
+
+    use generic_array::typenum::Unsigned;
+    use rand::{Rng, RngCore};
+
+    /// Commit to `bit` using a random tag
+    fn commit<F>(bit: bool, delta: &F) -> (ProverWire<F>, VerifierWire<F>)
+    where
+        F: BigGaloisField,
+    {
+        let mut tag = vec![0u8; F::Length::USIZE];
+        rand::thread_rng().fill_bytes(&mut tag);
+        let prover = ProverWire::from_bit(bit, &tag);
+        let verifier = VerifierWire {
+            key: prover.tag + if bit { *delta } else { F::ZERO },
+        };
+        (prover, verifier)
+    }
+
+    fn commit_byte<F>(byte: u8, delta: &F) -> (ProverWire<F>, VerifierWire<F>)
+    where
+        F: BigGaloisField,
+    {
+        let bits: [_; 8] = array::from_fn(|i| commit((byte >> i) & 1 == 1, delta));
+        (
+            ProverWire::byte_combine(&bits.map(|(p, _)| p)),
+            VerifierWire::byte_combine(&bits.map(|(_, v)| v)),
+        )
+    }
+
+    /// Prove that `x_i * y_i = z_i` for all `i` over GF(2^8)
+    fn prove_and_verify<F>(statements: &[(u8, u8, u8)]) -> Result<(), Error>
+    where
+        F: BigGaloisField,
+        ZKHasher<F>: ZKHasherInit<F>,
+    {
+        let mut rng = rand::thread_rng();
+        let delta = F::from(
+            (0..F::Length::USIZE)
+                .map(|_| rng.gen())
+                .collect::<Vec<u8>>()
+                .as_slice(),
+        );
+        let challenge: Vec<u8> = (0..<ZKHasher<F> as ZKHasherInit<F>>::SDLength::USIZE)
+            .map(|_| rng.gen())
+            .collect();
+
+        let mut prover = Prover::<F>::new(&challenge);
+        let mut verifier = Verifier::<F>::new(&challenge, &delta.as_bytes());
+        for &(x, y, z) in statements {
+            let (x_p, x_v) = commit_byte(x, &delta);
+            let (y_p, y_v) = commit_byte(y, &delta);
+            let (z_p, z_v) = commit_byte(z, &delta);
+            prover.assert_mul(&x_p, &y_p, &z_p);
+            verifier.assert_mul(&x_v, &y_v, &z_v);
+            // (x + 1) * y = z + y
+            let (one_p, one_v) = (Prover::<F>::one(), verifier.one());
+            prover.assert_mul(&(x_p + one_p), &y_p, &(z_p + y_p));
+            verifier.assert_mul(&(x_v + one_v), &y_v, &(z_v + y_v));
+        }
+
+        let (mask_p, mask_v): (Vec<_>, Vec<_>) = (0..F::Length::USIZE * 8)
+            .map(|_| commit(rng.gen(), &delta))
+            .unzip();
+        let (a_t, b_t) = prover.finalize(&ProverWire::combine(&mask_p));
+        verifier.verify(
+            &VerifierWire::combine(&mask_v),
+            &a_t.as_bytes(),
+            &b_t.as_bytes(),
+        )
+    }
+
+    /// Multiplication in GF(2^8) with the AES polynomial
+    fn gf256_mul(mut x: u8, mut y: u8) -> u8 {
+        let mut z = 0;
+        while y != 0 {
+            if y & 1 == 1 {
+                z ^= x;
+            }
+            x = (x << 1) ^ if x & 0x80 != 0 { 0x1b } else { 0 };
+            y >>= 1;
+        }
+        z
+    }
+
+    fn random_statements(len: usize) -> Vec<(u8, u8, u8)> {
+        let mut rng = rand::thread_rng();
+        (0..len)
+            .map(|_| {
+                let (x, y) = (rng.gen(), rng.gen());
+                (x, y, gf256_mul(x, y))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_statements<F: BigGaloisField>()
+    where
+        ZKHasher<F>: ZKHasherInit<F>,
+    {
+        assert!(prove_and_verify::<F>(&random_statements(32)).is_ok());
+    }
+
+    #[test]
+    fn invalid_statement<F: BigGaloisField>()
+    where
+        ZKHasher<F>: ZKHasherInit<F>,
+    {
+        let mut statements = random_statements(32);
+        let idx = rand::thread_rng().gen_range(0..statements.len());
+        statements[idx].2 ^= 1 << rand::thread_rng().gen_range(0..8);
+        assert!(prove_and_verify::<F>(&statements).is_err());
+    }
+
+    #[instantiate_tests(<GF128>)]
+    mod gf128 {}
+
+    #[instantiate_tests(<GF192>)]
+    mod gf192 {}
+
+    #[instantiate_tests(<GF256>)]
+    mod gf256 {}
+}
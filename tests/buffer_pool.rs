@@ -0,0 +1,124 @@
+//! Check the memory usage of concurrent signing and of verification with a
+//! shared buffer pool
+//!
+//! The test installs a counting global allocator and hence lives in its own
+//! test binary.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use faest::{
+    BufferPool, FAEST128fSignature, FAEST128fSigningKey, FAEST128fVerificationKey, Keypair,
+    KeypairGenerator, SignOptions, Signer, VerifyOptions,
+};
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static TOTAL: AtomicUsize = AtomicUsize::new(0);
+
+/// Allocator keeping track of the live and of the total allocated bytes
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            LIVE.fetch_add(layout.size(), Ordering::Relaxed);
+            TOTAL.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const THREADS: usize = 4;
+const SIGNATURES_PER_THREAD: usize = 2;
+const MAX_POOLED_BYTES: usize = 1 << 20;
+/// Allowance for allocations outside of the pool that outlive a round, e.g.,
+/// lazily initialized state of the standard library
+const SLACK: usize = 16 << 10;
+
+fn sign_concurrently(sk: &FAEST128fSigningKey, pool: &BufferPool) {
+    thread::scope(|scope| {
+        for i in 0..THREADS {
+            scope.spawn(move || {
+                for j in 0..SIGNATURES_PER_THREAD {
+                    let msg = [i as u8, j as u8];
+                    let _: FAEST128fSignature = sk
+                        .sign_with_options(&msg, SignOptions::new().buffer_pool(pool))
+                        .expect("valid options");
+                }
+            });
+        }
+    });
+    // all scratch buffers are returned to the pool
+    assert!(pool.pooled_bytes() > 0);
+}
+
+/// Bytes allocated by signing once with `options`
+fn allocated_bytes(sk: &FAEST128fSigningKey, options: SignOptions<'_>) -> usize {
+    let total = TOTAL.load(Ordering::Relaxed);
+    let _: FAEST128fSignature = sk.sign_with_options(b"message", options).unwrap();
+    TOTAL.load(Ordering::Relaxed) - total
+}
+
+/// Bytes allocated by verifying `signature` once with `options`
+fn allocated_bytes_verify(
+    vk: &FAEST128fVerificationKey,
+    signature: &FAEST128fSignature,
+    options: VerifyOptions<'_>,
+) -> usize {
+    let total = TOTAL.load(Ordering::Relaxed);
+    vk.verify_with_options(b"message", signature, options)
+        .unwrap();
+    TOTAL.load(Ordering::Relaxed) - total
+}
+
+#[test]
+fn stable_memory_usage() {
+    let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+    let pool = BufferPool::new(MAX_POOLED_BYTES);
+
+    // the first round fills the pool
+    sign_concurrently(&sk, &pool);
+    let live = LIVE.load(Ordering::Relaxed);
+    for _ in 0..3 {
+        sign_concurrently(&sk, &pool);
+        assert!(pool.pooled_bytes() <= MAX_POOLED_BYTES);
+        assert!(LIVE.load(Ordering::Relaxed) <= live + MAX_POOLED_BYTES + SLACK);
+    }
+
+    // the buffers are released with the pool
+    let pooled_bytes = pool.pooled_bytes();
+    let live = LIVE.load(Ordering::Relaxed);
+    drop(pool);
+    assert!(LIVE.load(Ordering::Relaxed) <= live - pooled_bytes);
+
+    // the pooled buffers are not allocated anew
+    let pool = BufferPool::new(MAX_POOLED_BYTES);
+    allocated_bytes(&sk, SignOptions::new().buffer_pool(&pool));
+    assert!(pool.pooled_bytes() > 0);
+    let with_pool = allocated_bytes(&sk, SignOptions::new().buffer_pool(&pool));
+    let without_pool = allocated_bytes(&sk, SignOptions::new());
+    assert!(with_pool + pool.pooled_bytes() <= without_pool);
+
+    // the same holds for verification
+    let vk = sk.verifying_key();
+    let signature = sk.sign(b"message");
+    let pool = BufferPool::new(MAX_POOLED_BYTES);
+    allocated_bytes_verify(&vk, &signature, VerifyOptions::new().buffer_pool(&pool));
+    assert!(pool.pooled_bytes() > 0);
+    let with_pool =
+        allocated_bytes_verify(&vk, &signature, VerifyOptions::new().buffer_pool(&pool));
+    let without_pool = allocated_bytes_verify(&vk, &signature, VerifyOptions::new());
+    assert!(with_pool + pool.pooled_bytes() <= without_pool);
+}
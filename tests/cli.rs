@@ -0,0 +1,85 @@
+//! Drive the command line tool through key generation, signing, and
+//! verification
+
+#![cfg(feature = "cli")]
+
+use std::{fs, path::Path};
+
+use assert_cmd::Command;
+
+fn faest_cli(directory: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("faest-cli").expect("binary is built");
+    cmd.current_dir(directory);
+    cmd
+}
+
+fn sign_and_verify(variant: &str) {
+    let directory = tempfile::tempdir().expect("temporary directory");
+    let directory = directory.path();
+    fs::write(directory.join("file"), b"the message").expect("write file");
+
+    faest_cli(directory)
+        .args(["keygen", "--variant", variant])
+        .args(["--out-sk", "sk.pem", "--out-pk", "pk.pem"])
+        .assert()
+        .success();
+    faest_cli(directory)
+        .args([
+            "sign", "--sk", "sk.pem", "--in", "file", "--out", "file.sig",
+        ])
+        .assert()
+        .success();
+    faest_cli(directory)
+        .args([
+            "verify", "--pk", "pk.pem", "--in", "file", "--sig", "file.sig",
+        ])
+        .assert()
+        .success();
+
+    // verification failures
+    fs::write(directory.join("file"), b"another message").expect("write file");
+    faest_cli(directory)
+        .args([
+            "verify", "--pk", "pk.pem", "--in", "file", "--sig", "file.sig",
+        ])
+        .assert()
+        .code(1);
+
+    // usage errors
+    faest_cli(directory)
+        .args([
+            "verify", "--pk", "sk.pem", "--in", "file", "--sig", "file.sig",
+        ])
+        .assert()
+        .code(2);
+    faest_cli(directory)
+        .args([
+            "verify", "--pk", "pk.pem", "--in", "missing", "--sig", "file.sig",
+        ])
+        .assert()
+        .code(2);
+    faest_cli(directory)
+        .args(["verify", "--pk", "pk.pem", "--in", "file", "--sig", "file"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn faest_128f() {
+    sign_and_verify("faest_128f");
+}
+
+#[test]
+fn faest_em_128s() {
+    sign_and_verify("FAEST-EM-128s");
+}
+
+#[test]
+fn unknown_variant() {
+    let directory = tempfile::tempdir().expect("temporary directory");
+    faest_cli(directory.path())
+        .args(["keygen", "--variant", "faest_512s"])
+        .args(["--out-sk", "sk.pem", "--out-pk", "pk.pem"])
+        .assert()
+        .code(2);
+}
@@ -0,0 +1,67 @@
+//! Check that signing releases all allocations, also if it unwinds
+//!
+//! The test installs a tracking global allocator and hence lives in its own
+//! test binary.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicIsize, Ordering},
+};
+
+use faest::{FAEST128fSignature, FAEST128fSigningKey, KeypairGenerator, Phase, Signer};
+
+/// Currently allocated bytes of all threads
+///
+/// With the `parallel` feature, signing allocates and frees on the threads of
+/// the rayon pool, hence the counter can not be thread-local.
+static ALLOCATED: AtomicIsize = AtomicIsize::new(0);
+
+/// Allocator keeping track of the currently allocated bytes
+struct TrackingAllocator;
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATED.fetch_add(layout.size() as isize, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size() as isize, Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+#[test]
+fn no_leak_on_panic_in_prove() {
+    let sk = FAEST128fSigningKey::generate(rand::thread_rng());
+    // the unwinder allocates some state on first use
+    let _ = panic::catch_unwind(|| panic::resume_unwind(Box::new("warm up")));
+    // the first signature starts the rayon pool and fills the buffer pools
+    let _: Box<FAEST128fSignature> = sk.sign(b"message");
+
+    let allocated = ALLOCATED.load(Ordering::SeqCst);
+    let signature: Box<FAEST128fSignature> = sk.sign(b"message");
+    drop(signature);
+    assert_eq!(ALLOCATED.load(Ordering::SeqCst), allocated);
+
+    // abort signing once the witness, the VOLE commitment and the hashes are
+    // computed, i.e., right before the proof
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        sk.sign_with_progress(b"message", &mut |phase, _| {
+            if phase == Phase::Proof {
+                // bypass the panic hook which may allocate while printing the backtrace
+                panic::resume_unwind(Box::new("fault injected in prove"));
+            }
+        })
+    }));
+    assert!(result.is_err());
+    drop(result);
+    assert_eq!(ALLOCATED.load(Ordering::SeqCst), allocated);
+}
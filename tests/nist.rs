@@ -1,13 +1,18 @@
+#[cfg(feature = "randomized-signer")]
+use std::fmt::Debug;
 use std::{
-    fmt::Debug,
     fs::File,
     io::{BufRead, BufReader},
     path::Path,
 };
 
+#[cfg(feature = "randomized-signer")]
 use nist_pqc_seeded_rng::NistPqcAes256CtrRng;
+#[cfg(feature = "randomized-signer")]
 use rand_core::SeedableRng;
-use signature::{RandomizedSigner, SignatureEncoding, Verifier};
+#[cfg(feature = "randomized-signer")]
+use signature::RandomizedSigner;
+use signature::{SignatureEncoding, Verifier};
 
 use faest::*;
 
@@ -99,6 +104,33 @@ fn read_kats(kats: &str) -> Vec<TestVector> {
     ret
 }
 
+type VerifyFn<V, S> = fn(&V, &[u8], &S) -> Result<(), Error>;
+
+/// Verify the signatures of the KATs without generating keys or signing
+fn verify_nist<VK, S>(test_data: &str, verify_low_memory: VerifyFn<VK, S>)
+where
+    VK: Verifier<S> + for<'a> TryFrom<&'a [u8], Error = Error>,
+    S: SignatureEncoding + for<'a> TryFrom<&'a [u8], Error = Error>,
+{
+    for TestVector {
+        message, pk, sm, ..
+    } in read_kats(test_data)
+    {
+        let vk = VK::try_from(&pk).unwrap();
+        let signature = S::try_from(&sm[message.len()..]).unwrap();
+        assert_eq!(sm[..message.len()], message);
+        assert!(vk.verify(&message, &signature).is_ok());
+
+        assert!(verify_low_memory(&vk, &message, &signature).is_ok());
+        let mut other_message = message.clone();
+        other_message.push(0);
+        assert!(vk.verify(&other_message, &signature).is_err());
+        assert!(verify_low_memory(&vk, &other_message, &signature).is_err());
+    }
+}
+
+/// Regenerate the keys and signatures of the KATs
+#[cfg(feature = "randomized-signer")]
 fn test_nist<KP, S>(test_data: &str)
 where
     KP: KeypairGenerator
@@ -132,62 +164,134 @@ where
     }
 }
 
+#[cfg(feature = "faest-128s")]
 #[test]
 fn faest_128s() {
+    verify_nist::<FAEST128sVerificationKey, FAEST128sSignature>(
+        "PQCsignKAT_faest_128s.rsp",
+        FAEST128sVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAEST128sSigningKey, FAEST128sSignature>("PQCsignKAT_faest_128s.rsp");
 }
 
+#[cfg(feature = "faest-128f")]
 #[test]
 fn faest_128f() {
+    verify_nist::<FAEST128fVerificationKey, FAEST128fSignature>(
+        "PQCsignKAT_faest_128f.rsp",
+        FAEST128fVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAEST128fSigningKey, FAEST128fSignature>("PQCsignKAT_faest_128f.rsp");
 }
 
+#[cfg(feature = "faest-192s")]
 #[test]
 fn faest_192s() {
+    verify_nist::<FAEST192sVerificationKey, FAEST192sSignature>(
+        "PQCsignKAT_faest_192s.rsp",
+        FAEST192sVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAEST192sSigningKey, FAEST192sSignature>("PQCsignKAT_faest_192s.rsp");
 }
 
+#[cfg(feature = "faest-192f")]
 #[test]
 fn faest_192f() {
+    verify_nist::<FAEST192fVerificationKey, FAEST192fSignature>(
+        "PQCsignKAT_faest_192f.rsp",
+        FAEST192fVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAEST192fSigningKey, FAEST192fSignature>("PQCsignKAT_faest_192f.rsp");
 }
 
+#[cfg(feature = "faest-256s")]
 #[test]
 fn faest_256s() {
+    verify_nist::<FAEST256sVerificationKey, FAEST256sSignature>(
+        "PQCsignKAT_faest_256s.rsp",
+        FAEST256sVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAEST256sSigningKey, FAEST256sSignature>("PQCsignKAT_faest_256s.rsp");
 }
 
+#[cfg(feature = "faest-256f")]
 #[test]
 fn faest_256f() {
+    verify_nist::<FAEST256fVerificationKey, FAEST256fSignature>(
+        "PQCsignKAT_faest_256f.rsp",
+        FAEST256fVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAEST256fSigningKey, FAEST256fSignature>("PQCsignKAT_faest_256f.rsp");
 }
 
+#[cfg(feature = "faest-em-128s")]
 #[test]
 fn faest_em_128s() {
+    verify_nist::<FAESTEM128sVerificationKey, FAESTEM128sSignature>(
+        "PQCsignKAT_faest_em_128s.rsp",
+        FAESTEM128sVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAESTEM128sSigningKey, FAESTEM128sSignature>("PQCsignKAT_faest_em_128s.rsp");
 }
 
+#[cfg(feature = "faest-em-128f")]
 #[test]
 fn faest_em_128f() {
+    verify_nist::<FAESTEM128fVerificationKey, FAESTEM128fSignature>(
+        "PQCsignKAT_faest_em_128f.rsp",
+        FAESTEM128fVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAESTEM128fSigningKey, FAESTEM128fSignature>("PQCsignKAT_faest_em_128f.rsp");
 }
 
+#[cfg(feature = "faest-em-192s")]
 #[test]
 fn faest_em_192s() {
+    verify_nist::<FAESTEM192sVerificationKey, FAESTEM192sSignature>(
+        "PQCsignKAT_faest_em_192s.rsp",
+        FAESTEM192sVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAESTEM192sSigningKey, FAESTEM192sSignature>("PQCsignKAT_faest_em_192s.rsp");
 }
 
+#[cfg(feature = "faest-em-192f")]
 #[test]
 fn faest_em_192f() {
+    verify_nist::<FAESTEM192fVerificationKey, FAESTEM192fSignature>(
+        "PQCsignKAT_faest_em_192f.rsp",
+        FAESTEM192fVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAESTEM192fSigningKey, FAESTEM192fSignature>("PQCsignKAT_faest_em_192f.rsp");
 }
 
+#[cfg(feature = "faest-em-256s")]
 #[test]
 fn faest_em_256s() {
+    verify_nist::<FAESTEM256sVerificationKey, FAESTEM256sSignature>(
+        "PQCsignKAT_faest_em_256s.rsp",
+        FAESTEM256sVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAESTEM256sSigningKey, FAESTEM256sSignature>("PQCsignKAT_faest_em_256s.rsp");
 }
 
+#[cfg(feature = "faest-em-256f")]
 #[test]
 fn faest_em_256f() {
+    verify_nist::<FAESTEM256fVerificationKey, FAESTEM256fSignature>(
+        "PQCsignKAT_faest_em_256f.rsp",
+        FAESTEM256fVerificationKey::verify_low_memory,
+    );
+    #[cfg(feature = "randomized-signer")]
     test_nist::<FAESTEM256fSigningKey, FAESTEM256fSignature>("PQCsignKAT_faest_em_256f.rsp");
 }
@@ -0,0 +1,60 @@
+//! Check the speedup of signing with parallel VOLE commitments
+//!
+//! FAEST-256f has `τ = 32` repetitions which are committed to in parallel
+//! with the `parallel` feature. The test compares the wall-clock time of
+//! signing on a single-threaded pool with the global pool. It is ignored by
+//! default as timing depends on the machine; run it with `--ignored`.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use faest::{
+    signature::{Signer, Verifier},
+    FAEST256fSignature, FAEST256fSigningKey, KeypairGenerator,
+};
+
+const RUNS: usize = 5;
+const MSG: &[u8] = b"parallel signing";
+
+/// Minimal speedup on machines with at least 4 cores
+const MIN_SPEEDUP: f64 = 1.5;
+
+/// Sign `MSG` `RUNS` times and return the fastest run
+fn time_sign(sk: &FAEST256fSigningKey) -> (Duration, FAEST256fSignature) {
+    let mut signature = sk.sign(MSG);
+    let mut fastest = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        signature = sk.sign(MSG);
+        fastest = fastest.min(start.elapsed());
+    }
+    (fastest, signature)
+}
+
+#[test]
+#[ignore = "timing depends on the machine"]
+fn faest_256f_parallel_speedup() {
+    let sk = FAEST256fSigningKey::generate(rand::thread_rng());
+
+    let (sequential, sequential_signature) = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .expect("thread pool")
+        .install(|| time_sign(&sk));
+    let (parallel, parallel_signature) = time_sign(&sk);
+
+    // signing is deterministic and independent of the scheduling
+    assert_eq!(sequential_signature, parallel_signature);
+    assert!(sk.verify(MSG, &parallel_signature).is_ok());
+
+    let cores = thread::available_parallelism().map_or(1, |cores| cores.get());
+    let speedup = sequential.as_secs_f64() / parallel.as_secs_f64();
+    if cores >= 4 {
+        assert!(
+            speedup >= MIN_SPEEDUP,
+            "speedup of {speedup:.2} on {cores} cores (sequential {sequential:?}, parallel {parallel:?})"
+        );
+    }
+}